@@ -0,0 +1,137 @@
+//! Buffer pool benchmarks
+//!
+//! Serializes a million messages with a fresh buffer per message and with a
+//! `BufferPool`, and reports the heap allocations of each strategy next to
+//! the criterion timings.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use fbe::buffer::WriteBuffer;
+use fbe::pool::BufferPool;
+use fbe::sender::Serialize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// System allocator counting allocations
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const MESSAGES: usize = 1_000_000;
+
+struct Tick {
+    id: i64,
+    symbol: &'static str,
+    price: f64,
+}
+
+/// Small message payloads: a bare symbol and a 1 KiB note
+const SYMBOLS: [(&str, &str); 2] = [("26_bytes", "EURUSD"), ("1044_bytes", NOTE)];
+
+const NOTE: &str = concat!(
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+    "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+);
+
+impl Serialize for Tick {
+    fn serialize(&self, buffer: &mut WriteBuffer) -> usize {
+        buffer.write_i64(0, self.id);
+        buffer.write_string(8, self.symbol);
+        buffer.write_f64(12 + self.symbol.len(), self.price);
+        buffer.size()
+    }
+
+    fn size_hint(&self) -> usize {
+        20 + self.symbol.len()
+    }
+}
+
+fn tick(id: usize, symbol: &'static str) -> Tick {
+    Tick {
+        id: id as i64,
+        symbol,
+        price: 1.0842,
+    }
+}
+
+fn send_fresh(symbol: &'static str, count: usize) -> usize {
+    let mut total = 0;
+    for id in 0..count {
+        let value = tick(id, symbol);
+        let mut buffer = WriteBuffer::growable();
+        buffer.reserve(value.size_hint());
+        value.serialize(&mut buffer);
+        total += black_box(buffer.data()).len();
+    }
+    total
+}
+
+fn send_pooled(pool: &BufferPool, symbol: &'static str, count: usize) -> usize {
+    let mut total = 0;
+    for id in 0..count {
+        let buffer = pool.serialize(&tick(id, symbol));
+        total += black_box(buffer.data()).len();
+    }
+    total
+}
+
+fn count_allocations(f: impl FnOnce() -> usize) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn bench_buffer_pool(c: &mut Criterion) {
+    let pool = BufferPool::new();
+    for (name, symbol) in SYMBOLS {
+        println!(
+            "allocations for {} {} messages: fresh buffer {}, pooled buffer {}",
+            MESSAGES,
+            name,
+            count_allocations(|| send_fresh(symbol, MESSAGES)),
+            count_allocations(|| send_pooled(&pool, symbol, MESSAGES)),
+        );
+
+        let mut group = c.benchmark_group(format!("send_1m_{}", name));
+        group.sample_size(10);
+        group.bench_function("fresh_buffer", |b| b.iter(|| send_fresh(symbol, MESSAGES)));
+        group.bench_function("buffer_pool", |b| b.iter(|| send_pooled(&pool, symbol, MESSAGES)));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_buffer_pool);
+criterion_main!(benches);
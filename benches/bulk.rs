@@ -0,0 +1,64 @@
+//! Bulk primitive encoding benchmarks
+//!
+//! Compares the bulk vector/array paths with the element-by-element loops
+//! they replace.
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use std::hint::black_box;
+
+const COUNTS: [usize; 2] = [64, 16384];
+
+fn write_loop_i32(buffer: &mut WriteBuffer, values: &[i32]) {
+    for (i, &value) in values.iter().enumerate() {
+        buffer.write_i32(i * 4, value);
+    }
+}
+
+fn read_loop_f64(buffer: &ReadBuffer, count: usize) -> Vec<f64> {
+    let mut values = Vec::with_capacity(count);
+    for i in 0..count {
+        values.push(buffer.read_f64(i * 8));
+    }
+    values
+}
+
+fn bench_write_i32(c: &mut Criterion) {
+    let mut group = c.benchmark_group("write_array_i32");
+    for count in COUNTS {
+        let values: Vec<i32> = (0..count as i32).collect();
+        let mut buffer = WriteBuffer::new();
+        buffer.allocate(count * 4);
+        group.bench_with_input(BenchmarkId::new("loop", count), &values, |b, values| {
+            b.iter(|| write_loop_i32(&mut buffer, black_box(values)))
+        });
+        group.bench_with_input(BenchmarkId::new("bulk", count), &values, |b, values| {
+            b.iter(|| buffer.write_array(0, black_box(values)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_read_f64(c: &mut Criterion) {
+    let mut group = c.benchmark_group("read_array_f64");
+    for count in COUNTS {
+        let values: Vec<f64> = (0..count).map(|i| i as f64 * 0.5).collect();
+        let mut writer = WriteBuffer::new();
+        writer.allocate(count * 8);
+        writer.write_array(0, &values);
+        let reader = ReadBuffer::borrowed(writer.data());
+        group.bench_with_input(BenchmarkId::new("loop", count), &count, |b, &count| {
+            b.iter(|| read_loop_f64(black_box(&reader), count))
+        });
+        group.bench_with_input(BenchmarkId::new("bulk", count), &count, |b, &count| {
+            b.iter(|| black_box(&reader).read_array::<f64>(0, count))
+        });
+        group.bench_with_input(BenchmarkId::new("view", count), &count, |b, &count| {
+            b.iter(|| black_box(&reader).view_array::<f64>(0, count).map(<[f64]>::len))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_write_i32, bench_read_f64);
+criterion_main!(benches);
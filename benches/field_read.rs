@@ -0,0 +1,42 @@
+//! Field model read benchmarks
+//!
+//! Reads the same fields from messages padded to different total sizes; the
+//! per-field cost should not depend on the message size.
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use fbe::buffer::WriteBuffer;
+use fbe::field_model::{FieldModelI64, FieldModelString};
+use fbe::field_model_collections::FieldModelVectorI32;
+use std::hint::black_box;
+
+/// Message with an i64, a string and a 16-element vector followed by padding
+fn message(size: usize) -> Vec<u8> {
+    let mut buffer = WriteBuffer::new();
+    buffer.allocate(32);
+    buffer.write_i64(0, 0x0FBE);
+    buffer.write_string(8, "EURUSD");
+    buffer.write_vector_i32(20, &(0..16).collect::<Vec<_>>());
+    let padding = size.saturating_sub(buffer.size());
+    buffer.allocate(padding);
+    buffer.data()[..buffer.size()].to_vec()
+}
+
+fn bench_field_read(c: &mut Criterion) {
+    let mut group = c.benchmark_group("field_read");
+    for size in [1 << 10, 1 << 16, 1 << 22] {
+        let data = message(size);
+        group.bench_with_input(BenchmarkId::new("i64", size), &data, |b, data| {
+            b.iter(|| FieldModelI64::new(black_box(data), 0).get())
+        });
+        group.bench_with_input(BenchmarkId::new("string", size), &data, |b, data| {
+            b.iter(|| FieldModelString::new(black_box(data), 8).get())
+        });
+        group.bench_with_input(BenchmarkId::new("vector_i32", size), &data, |b, data| {
+            b.iter(|| FieldModelVectorI32::new(black_box(data), 20).get())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_field_read);
+criterion_main!(benches);
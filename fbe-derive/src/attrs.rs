@@ -0,0 +1,128 @@
+//! `#[fbe(...)]` attribute parsing
+
+use syn::{Attribute, Expr, Ident, LitInt, Result, Type};
+
+/// Struct level attributes
+#[derive(Default)]
+pub struct StructAttrs {
+    pub id: Option<LitInt>,
+}
+
+/// Field level attributes
+#[derive(Default)]
+pub struct FieldAttrs {
+    pub key: bool,
+    pub base: bool,
+    pub default: Option<Expr>,
+    /// FBE `char` (1-byte Latin-1) instead of `wchar`
+    pub latin1: bool,
+}
+
+/// Enum variant attributes
+#[derive(Default)]
+pub struct VariantAttrs {
+    pub default: bool,
+}
+
+/// Flags attributes
+#[derive(Default)]
+pub struct FlagsAttrs {
+    pub flags: Vec<(Ident, Expr)>,
+}
+
+fn fbe_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("fbe"))
+}
+
+pub fn struct_attrs(attrs: &[Attribute]) -> Result<StructAttrs> {
+    let mut result = StructAttrs::default();
+    for attr in fbe_attrs(attrs) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("id") {
+                result.id = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported fbe struct attribute"))
+            }
+        })?;
+    }
+    Ok(result)
+}
+
+pub fn field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs> {
+    let mut result = FieldAttrs::default();
+    for attr in fbe_attrs(attrs) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("key") {
+                result.key = true;
+                Ok(())
+            } else if meta.path.is_ident("base") {
+                result.base = true;
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                result.default = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("char") {
+                result.latin1 = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported fbe field attribute"))
+            }
+        })?;
+    }
+    Ok(result)
+}
+
+pub fn variant_attrs(attrs: &[Attribute]) -> Result<VariantAttrs> {
+    let mut result = VariantAttrs::default();
+    for attr in fbe_attrs(attrs) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("default") {
+                result.default = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported fbe variant attribute"))
+            }
+        })?;
+    }
+    Ok(result)
+}
+
+pub fn flags_attrs(attrs: &[Attribute]) -> Result<FlagsAttrs> {
+    let mut result = FlagsAttrs::default();
+    for attr in fbe_attrs(attrs) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("flags") {
+                meta.parse_nested_meta(|flag| {
+                    let name = flag.path.require_ident()?.clone();
+                    let value = flag.value()?.parse()?;
+                    result.flags.push((name, value));
+                    Ok(())
+                })
+            } else {
+                Err(meta.error("unsupported fbe flags attribute"))
+            }
+        })?;
+    }
+    Ok(result)
+}
+
+/// Get the integer type from `#[repr(..)]`, if any
+pub fn repr_type(attrs: &[Attribute]) -> Result<Option<Type>> {
+    let mut result = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            } else if let Some(ident) = meta.path.get_ident()
+                && ident != "C"
+                && ident != "transparent"
+            {
+                result = Some(Type::Verbatim(quote::quote!(#ident)));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(result)
+}
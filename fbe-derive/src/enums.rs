@@ -0,0 +1,158 @@
+//! `#[derive(FbeEnum)]`
+
+use crate::attrs;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Ident, Result};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            input,
+            "FbeEnum can only be derived for enums",
+        ));
+    };
+    if data.variants.is_empty() {
+        return Err(Error::new_spanned(
+            input,
+            "FbeEnum requires at least one variant",
+        ));
+    }
+
+    let mut variants = Vec::new();
+    let mut fallback = None;
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "FbeEnum variants must not have fields",
+            ));
+        }
+        if attrs::variant_attrs(&variant.attrs)?.default {
+            if fallback.is_some() {
+                return Err(Error::new_spanned(
+                    variant,
+                    "only one variant can be #[fbe(default)]",
+                ));
+            }
+            fallback = Some(&variant.ident);
+        }
+        variants.push(&variant.ident);
+    }
+    let fallback = fallback.unwrap_or(variants[0]);
+
+    let name = &input.ident;
+    let repr = attrs::repr_type(&input.attrs)?.unwrap_or_else(|| syn::parse_quote!(i32));
+    let arbitrary = expand_arbitrary(name, &variants);
+
+    Ok(quote! {
+        impl ::core::convert::From<&#name> for #repr {
+            fn from(value: &#name) -> Self {
+                match value {
+                    #(#name::#variants => #name::#variants as #repr,)*
+                }
+            }
+        }
+
+        impl ::core::convert::From<#name> for #repr {
+            fn from(value: #name) -> Self {
+                <#repr>::from(&value)
+            }
+        }
+
+        impl ::core::convert::TryFrom<#repr> for #name {
+            type Error = #repr;
+
+            fn try_from(value: #repr) -> ::core::result::Result<Self, #repr> {
+                #(
+                    if value == #name::#variants as #repr {
+                        return Ok(#name::#variants);
+                    }
+                )*
+                Err(value)
+            }
+        }
+
+        impl ::fbe::final_model::FinalValue for #name {
+            fn final_size(&self) -> usize {
+                ::core::mem::size_of::<#repr>()
+            }
+
+            fn write_final(&self, buffer: &mut ::fbe::buffer::WriteBuffer, offset: usize) -> usize {
+                ::fbe::final_model::FinalValue::write_final(&<#repr>::from(self), buffer, offset)
+            }
+
+            fn read_final(buffer: &::fbe::buffer::ReadBuffer, offset: usize) -> (Self, usize) {
+                let (value, size) = <#repr as ::fbe::final_model::FinalValue>::read_final(buffer, offset);
+                (<#name>::try_from(value).unwrap_or(#name::#fallback), size)
+            }
+
+            fn read_final_size(_buffer: &::fbe::buffer::ReadBuffer, _offset: usize) -> usize {
+                ::core::mem::size_of::<#repr>()
+            }
+
+            fn try_read_final(
+                buffer: &::fbe::buffer::ReadBuffer,
+                offset: usize,
+                budget: &mut ::fbe::limits::DecodeBudget,
+            ) -> ::core::result::Result<(Self, usize), ::fbe::limits::DecodeError> {
+                let (value, size) = <#repr as ::fbe::final_model::FinalValue>::try_read_final(buffer, offset, budget)?;
+                Ok((<#name>::try_from(value).unwrap_or(#name::#fallback), size))
+            }
+        }
+
+        impl ::fbe::field_model::FieldValue for #name {
+            const FBE_SIZE: usize = ::core::mem::size_of::<#repr>();
+
+            fn fbe_extra(&self) -> usize {
+                0
+            }
+
+            fn write_field(&self, buffer: &mut ::fbe::buffer::WriteBuffer, base: usize, offset: usize) {
+                ::fbe::field_model::FieldValue::write_field(&<#repr>::from(self), buffer, base, offset);
+            }
+
+            fn read_field(buffer: &::fbe::buffer::ReadBuffer, base: usize, offset: usize) -> Self {
+                let value = <#repr as ::fbe::field_model::FieldValue>::read_field(buffer, base, offset);
+                <#name>::try_from(value).unwrap_or(#name::#fallback)
+            }
+
+            fn try_read_field(
+                buffer: &::fbe::buffer::ReadBuffer,
+                base: usize,
+                offset: usize,
+                budget: &mut ::fbe::limits::DecodeBudget,
+            ) -> ::core::result::Result<Self, ::fbe::limits::DecodeError> {
+                let value = <#repr as ::fbe::field_model::FieldValue>::try_read_field(buffer, base, offset, budget)?;
+                Ok(<#name>::try_from(value).unwrap_or(#name::#fallback))
+            }
+        }
+
+        #arbitrary
+    })
+}
+
+/// `Arbitrary` picking any declared variant
+fn expand_arbitrary(name: &Ident, variants: &[&Ident]) -> TokenStream {
+    if !cfg!(feature = "proptest") {
+        return TokenStream::new();
+    }
+
+    let count = variants.len();
+    let indexes = 0..count;
+    quote! {
+        impl ::fbe::proptest::Arbitrary for #name {
+            type Parameters = ();
+            type Strategy = ::fbe::proptest::BoxedStrategy<Self>;
+
+            fn arbitrary_with(_args: ()) -> Self::Strategy {
+                ::fbe::proptest::Strategy::boxed(::fbe::proptest::Strategy::prop_map(0..#count, |index| {
+                    match index {
+                        #(#indexes => #name::#variants,)*
+                        _ => unreachable!(),
+                    }
+                }))
+            }
+        }
+    }
+}
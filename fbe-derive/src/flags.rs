@@ -0,0 +1,195 @@
+//! `#[derive(FbeFlags)]`
+
+use crate::attrs;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Ident, Result, Type};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let repr = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => return Err(newtype_error(input)),
+        },
+        _ => return Err(newtype_error(input)),
+    };
+
+    let name = &input.ident;
+    let (flags, values): (Vec<_>, Vec<_>) =
+        attrs::flags_attrs(&input.attrs)?.flags.into_iter().unzip();
+    let arbitrary = expand_arbitrary(name, repr);
+
+    Ok(quote! {
+        impl #name {
+            #(pub const #flags: Self = Self(#values);)*
+
+            /// Create empty flags
+            pub const fn empty() -> Self {
+                Self(0)
+            }
+
+            /// Create flags from raw bits
+            pub const fn from_bits(bits: #repr) -> Self {
+                Self(bits)
+            }
+
+            /// Get raw bits
+            pub const fn bits(&self) -> #repr {
+                self.0
+            }
+
+            /// Check if no flags are set
+            pub const fn is_empty(&self) -> bool {
+                self.0 == 0
+            }
+
+            /// Check if all given flags are set
+            pub const fn contains(&self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+
+            /// Set given flags
+            pub fn insert(&mut self, other: Self) {
+                self.0 |= other.0;
+            }
+
+            /// Clear given flags
+            pub fn remove(&mut self, other: Self) {
+                self.0 &= !other.0;
+            }
+        }
+
+        impl ::core::ops::BitOr for #name {
+            type Output = Self;
+
+            fn bitor(self, other: Self) -> Self {
+                Self(self.0 | other.0)
+            }
+        }
+
+        impl ::core::ops::BitAnd for #name {
+            type Output = Self;
+
+            fn bitand(self, other: Self) -> Self {
+                Self(self.0 & other.0)
+            }
+        }
+
+        impl ::core::ops::BitXor for #name {
+            type Output = Self;
+
+            fn bitxor(self, other: Self) -> Self {
+                Self(self.0 ^ other.0)
+            }
+        }
+
+        impl ::core::ops::Not for #name {
+            type Output = Self;
+
+            fn not(self) -> Self {
+                Self(!self.0)
+            }
+        }
+
+        impl ::core::ops::BitOrAssign for #name {
+            fn bitor_assign(&mut self, other: Self) {
+                self.0 |= other.0;
+            }
+        }
+
+        impl ::core::ops::BitAndAssign for #name {
+            fn bitand_assign(&mut self, other: Self) {
+                self.0 &= other.0;
+            }
+        }
+
+        impl ::core::ops::BitXorAssign for #name {
+            fn bitxor_assign(&mut self, other: Self) {
+                self.0 ^= other.0;
+            }
+        }
+
+        impl ::fbe::final_model::FinalValue for #name {
+            fn final_size(&self) -> usize {
+                ::core::mem::size_of::<#repr>()
+            }
+
+            fn write_final(&self, buffer: &mut ::fbe::buffer::WriteBuffer, offset: usize) -> usize {
+                ::fbe::final_model::FinalValue::write_final(&self.0, buffer, offset)
+            }
+
+            fn read_final(buffer: &::fbe::buffer::ReadBuffer, offset: usize) -> (Self, usize) {
+                let (bits, size) = <#repr as ::fbe::final_model::FinalValue>::read_final(buffer, offset);
+                (Self(bits), size)
+            }
+
+            fn read_final_size(_buffer: &::fbe::buffer::ReadBuffer, _offset: usize) -> usize {
+                ::core::mem::size_of::<#repr>()
+            }
+
+            fn try_read_final(
+                buffer: &::fbe::buffer::ReadBuffer,
+                offset: usize,
+                budget: &mut ::fbe::limits::DecodeBudget,
+            ) -> ::core::result::Result<(Self, usize), ::fbe::limits::DecodeError> {
+                let (bits, size) = <#repr as ::fbe::final_model::FinalValue>::try_read_final(buffer, offset, budget)?;
+                Ok((Self(bits), size))
+            }
+        }
+
+        impl ::fbe::field_model::FieldValue for #name {
+            const FBE_SIZE: usize = ::core::mem::size_of::<#repr>();
+
+            fn fbe_extra(&self) -> usize {
+                0
+            }
+
+            fn write_field(&self, buffer: &mut ::fbe::buffer::WriteBuffer, base: usize, offset: usize) {
+                ::fbe::field_model::FieldValue::write_field(&self.0, buffer, base, offset);
+            }
+
+            fn read_field(buffer: &::fbe::buffer::ReadBuffer, base: usize, offset: usize) -> Self {
+                Self(<#repr as ::fbe::field_model::FieldValue>::read_field(buffer, base, offset))
+            }
+
+            fn try_read_field(
+                buffer: &::fbe::buffer::ReadBuffer,
+                base: usize,
+                offset: usize,
+                budget: &mut ::fbe::limits::DecodeBudget,
+            ) -> ::core::result::Result<Self, ::fbe::limits::DecodeError> {
+                <#repr as ::fbe::field_model::FieldValue>::try_read_field(buffer, base, offset, budget).map(Self)
+            }
+        }
+
+        #arbitrary
+    })
+}
+
+/// `Arbitrary` from any raw bits, including undeclared ones
+fn expand_arbitrary(name: &Ident, repr: &Type) -> TokenStream {
+    if !cfg!(feature = "proptest") {
+        return TokenStream::new();
+    }
+
+    quote! {
+        impl ::fbe::proptest::Arbitrary for #name {
+            type Parameters = ();
+            type Strategy = ::fbe::proptest::BoxedStrategy<Self>;
+
+            fn arbitrary_with(_args: ()) -> Self::Strategy {
+                ::fbe::proptest::Strategy::boxed(::fbe::proptest::Strategy::prop_map(
+                    ::fbe::proptest::any::<#repr>(),
+                    Self,
+                ))
+            }
+        }
+    }
+}
+
+fn newtype_error(input: &DeriveInput) -> Error {
+    Error::new_spanned(
+        input,
+        "FbeFlags requires a newtype struct, e.g. `struct Flags(u32);`",
+    )
+}
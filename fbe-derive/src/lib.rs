@@ -0,0 +1,69 @@
+//! Derive macros for Fast Binary Encoding (FBE)
+//!
+//! Alternative to fbec code generation for types defined in Rust first.
+//! Generated code targets the `fbe` runtime crate (FinalModel inline and
+//! pointer-based Model layouts).
+
+mod attrs;
+mod enums;
+mod flags;
+mod structs;
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, parse_macro_input};
+
+/// Derive FBE serialization for a struct
+///
+/// Generates `serialize`/`try_serialize`/`deserialize`, the `Serialize`/`Deserialize`,
+/// `FinalValue`, `FieldValue` and `FieldStruct` trait impls and
+/// `{Name}FinalModel`/`{Name}Model`/`{Name}SizedModel` types (`{Name}Model`
+/// is the pointer-based FBE Model layout, `{Name}SizedModel` the FinalModel
+/// behind a 4-byte size header).
+///
+/// Attributes:
+/// - `#[fbe(id = 1)]` on the struct: FBE type id
+/// - `#[fbe(key)]` on fields: key fields (`key()`, `PartialEq`, `Eq`, `Hash`)
+/// - `#[fbe(default = expr)]` on fields: schema default (`Default`)
+/// - `#[fbe(base)]` on the first field: base struct (`AsRef`/`AsMut`)
+/// - `#[fbe(char)]` on `char` fields: 1-byte Latin-1 FBE `char` instead of `wchar`
+///   (`try_serialize` fails outside Latin-1, `serialize` writes `?`)
+///
+/// With the `fbe/proptest` feature it also implements `Arbitrary`, which
+/// needs `Debug` on the struct and `Arbitrary` on every field type.
+#[proc_macro_derive(FbeStruct, attributes(fbe))]
+pub fn derive_fbe_struct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    structs::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive FBE serialization for a fieldless enum
+///
+/// The underlying type is taken from `#[repr(..)]` (`i32` by default).
+/// Unknown values decode to the variant marked `#[fbe(default)]`, or the
+/// first variant.
+///
+/// With the `fbe/proptest` feature it also implements `Arbitrary` over the
+/// declared variants (the enum must implement `Debug`).
+#[proc_macro_derive(FbeEnum, attributes(fbe))]
+pub fn derive_fbe_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    enums::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive FBE flags for a newtype over an integer
+///
+/// Flag values are declared with `#[fbe(flags(READ = 0x01, WRITE = 0x02))]`.
+///
+/// With the `fbe/proptest` feature it also implements `Arbitrary` over any
+/// raw bits (the newtype must implement `Debug`).
+#[proc_macro_derive(FbeFlags, attributes(fbe))]
+pub fn derive_fbe_flags(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    flags::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
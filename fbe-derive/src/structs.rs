@@ -0,0 +1,762 @@
+//! `#[derive(FbeStruct)]`
+
+use crate::attrs::{self, FieldAttrs};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Error, Expr, Fields, Ident, Lit, Member, Result, Type};
+
+struct Field {
+    member: Member,
+    local: Ident,
+    ty: Type,
+    attrs: FieldAttrs,
+}
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            input,
+            "FbeStruct can only be derived for structs",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "FbeStruct does not support generic structs",
+        ));
+    }
+
+    let struct_attrs = attrs::struct_attrs(&input.attrs)?;
+    let mut fields = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        let attrs = attrs::field_attrs(&field.attrs)?;
+        if attrs.base && index != 0 {
+            return Err(Error::new_spanned(
+                field,
+                "base struct must be the first field",
+            ));
+        }
+        if attrs.latin1 && !is_char(&field.ty) {
+            return Err(Error::new_spanned(
+                &field.ty,
+                "#[fbe(char)] requires a `char` field",
+            ));
+        }
+        fields.push(Field {
+            member,
+            local: format_ident!("field_{}", index),
+            ty: field.ty.clone(),
+            attrs,
+        });
+    }
+
+    let name = &input.ident;
+    let fbe_type = match &struct_attrs.id {
+        Some(id) => quote!(#id),
+        None => quote!(0),
+    };
+
+    let final_value = expand_final_value(name, &data.fields, &fields);
+    let field_value = expand_field_value(name, &fbe_type, &data.fields, &fields);
+    let models = expand_models(input);
+    let keys = expand_keys(name, &fields);
+    let default = expand_default(name, &data.fields, &fields);
+    let base = expand_base(name, &fields);
+    let arbitrary = expand_arbitrary(name, &data.fields, &fields);
+
+    Ok(quote! {
+        #final_value
+        #field_value
+
+        impl #name {
+            /// FBE type id
+            pub const FBE_TYPE: usize = #fbe_type;
+
+            /// Serialize struct (FinalModel layout) at the buffer offset and return serialized size
+            pub fn serialize(&self, buffer: &mut ::fbe::buffer::WriteBuffer) -> usize {
+                buffer.reserve(buffer.offset() + ::fbe::final_model::FinalValue::final_size(self));
+                let size = ::fbe::final_model::FinalValue::write_final(self, buffer, 0);
+                buffer.set_size(buffer.offset() + size);
+                size
+            }
+
+            /// Serialize struct (FinalModel layout), failing on `#[fbe(char)]` fields outside Latin-1
+            pub fn try_serialize(
+                &self,
+                buffer: &mut ::fbe::buffer::WriteBuffer,
+            ) -> ::core::result::Result<usize, ::fbe::chars::CharError> {
+                ::fbe::final_model::FinalValue::check_chars(self)?;
+                Ok(self.serialize(buffer))
+            }
+
+            /// Deserialize struct (FinalModel layout)
+            pub fn deserialize(buffer: &::fbe::buffer::ReadBuffer) -> Self {
+                <Self as ::fbe::final_model::FinalValue>::read_final(buffer, 0).0
+            }
+
+            /// Deserialize struct (FinalModel layout) from untrusted input within decode limits
+            pub fn try_deserialize(
+                buffer: &::fbe::buffer::ReadBuffer,
+                limits: &::fbe::limits::DecodeLimits,
+            ) -> ::core::result::Result<Self, ::fbe::limits::DecodeError> {
+                let mut budget = ::fbe::limits::DecodeBudget::for_message(*limits, buffer.size())?;
+                <Self as ::fbe::final_model::FinalValue>::try_read_final(buffer, 0, &mut budget)
+                    .map(|(value, _)| value)
+            }
+        }
+
+        impl ::fbe::sender::Serialize for #name {
+            const FBE_TYPE: usize = #fbe_type;
+
+            fn serialize(&self, buffer: &mut ::fbe::buffer::WriteBuffer) -> usize {
+                #name::serialize(self, buffer)
+            }
+
+            fn size_hint(&self) -> usize {
+                ::fbe::final_model::FinalValue::final_size(self)
+            }
+        }
+
+        impl ::fbe::receiver::Deserialize for #name {
+            const FBE_TYPE: usize = #fbe_type;
+
+            fn deserialize(buffer: &::fbe::buffer::ReadBuffer) -> Self {
+                #name::deserialize(buffer)
+            }
+
+            fn try_deserialize(
+                buffer: &::fbe::buffer::ReadBuffer,
+                limits: &::fbe::limits::DecodeLimits,
+            ) -> ::core::result::Result<Self, ::fbe::limits::DecodeError> {
+                #name::try_deserialize(buffer, limits)
+            }
+        }
+
+        #models
+        #keys
+        #default
+        #base
+        #arbitrary
+    })
+}
+
+fn is_char(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident("char"))
+}
+
+/// FinalModel codec of the field: its `FinalValue` impl or the FBE `char` functions
+fn final_codec(field: &Field) -> TokenStream {
+    let ty = &field.ty;
+    if field.attrs.latin1 {
+        quote!(::fbe::chars::latin1)
+    } else {
+        quote!(<#ty as ::fbe::final_model::FinalValue>)
+    }
+}
+
+/// Model codec of the field: its `FieldValue` impl or the FBE `char` functions
+fn field_codec(field: &Field) -> TokenStream {
+    let ty = &field.ty;
+    if field.attrs.latin1 {
+        quote!(::fbe::chars::latin1)
+    } else {
+        quote!(<#ty as ::fbe::field_model::FieldValue>)
+    }
+}
+
+fn construct(shape: &Fields, fields: &[Field], values: &[TokenStream]) -> TokenStream {
+    match shape {
+        Fields::Named(_) => {
+            let members = fields.iter().map(|field| &field.member);
+            quote!(Self { #(#members: #values),* })
+        }
+        Fields::Unnamed(_) => quote!(Self(#(#values),*)),
+        Fields::Unit => quote!(Self),
+    }
+}
+
+fn expand_final_value(name: &Ident, shape: &Fields, fields: &[Field]) -> TokenStream {
+    if fields.is_empty() {
+        return quote! {
+            impl ::fbe::final_model::FinalValue for #name {
+                fn final_size(&self) -> usize {
+                    0
+                }
+
+                fn write_final(&self, _buffer: &mut ::fbe::buffer::WriteBuffer, _offset: usize) -> usize {
+                    0
+                }
+
+                fn read_final(_buffer: &::fbe::buffer::ReadBuffer, _offset: usize) -> (Self, usize) {
+                    (Self, 0)
+                }
+
+                fn read_final_size(_buffer: &::fbe::buffer::ReadBuffer, _offset: usize) -> usize {
+                    0
+                }
+
+                fn try_read_final(
+                    _buffer: &::fbe::buffer::ReadBuffer,
+                    _offset: usize,
+                    _budget: &mut ::fbe::limits::DecodeBudget,
+                ) -> ::core::result::Result<(Self, usize), ::fbe::limits::DecodeError> {
+                    Ok((Self, 0))
+                }
+            }
+        };
+    }
+
+    let members: Vec<_> = fields.iter().map(|field| &field.member).collect();
+    let locals: Vec<_> = fields.iter().map(|field| &field.local).collect();
+    let codecs: Vec<_> = fields.iter().map(final_codec).collect();
+    let value = construct(
+        shape,
+        fields,
+        &locals
+            .iter()
+            .map(|local| quote!(#local))
+            .collect::<Vec<_>>(),
+    );
+
+    quote! {
+        impl ::fbe::final_model::FinalValue for #name {
+            fn final_size(&self) -> usize {
+                #(#codecs::final_size(&self.#members))+*
+            }
+
+            fn write_final(&self, buffer: &mut ::fbe::buffer::WriteBuffer, offset: usize) -> usize {
+                let mut size = 0;
+                #(size += #codecs::write_final(&self.#members, buffer, offset + size);)*
+                size
+            }
+
+            fn read_final(buffer: &::fbe::buffer::ReadBuffer, offset: usize) -> (Self, usize) {
+                let mut size = 0;
+                #(
+                    let (#locals, field_size) = #codecs::read_final(buffer, offset + size);
+                    size += field_size;
+                )*
+                (#value, size)
+            }
+
+            fn read_final_size(buffer: &::fbe::buffer::ReadBuffer, offset: usize) -> usize {
+                let mut size = 0;
+                #(size += #codecs::read_final_size(buffer, offset + size);)*
+                size
+            }
+
+            fn try_read_final(
+                buffer: &::fbe::buffer::ReadBuffer,
+                offset: usize,
+                budget: &mut ::fbe::limits::DecodeBudget,
+            ) -> ::core::result::Result<(Self, usize), ::fbe::limits::DecodeError> {
+                budget.enter()?;
+                let mut size = 0;
+                #(
+                    let (#locals, field_size) = #codecs::try_read_final(buffer, offset + size, budget)?;
+                    size += field_size;
+                )*
+                budget.leave();
+                Ok((#value, size))
+            }
+
+            fn write_vectored<'a>(&'a self, writer: &mut ::fbe::vectored::VectoredWriter<'a>) {
+                #(#codecs::write_vectored(&self.#members, writer);)*
+            }
+
+            fn check_chars(&self) -> ::core::result::Result<(), ::fbe::chars::CharError> {
+                #(#codecs::check_chars(&self.#members)?;)*
+                Ok(())
+            }
+        }
+    }
+}
+
+/// `FieldValue`/`FieldStruct` (pointer-based Model layout)
+///
+/// Field slots follow the struct body header in declaration order; a base
+/// struct contributes its own field slots inline.
+fn expand_field_value(name: &Ident, fbe_type: &TokenStream, shape: &Fields, fields: &[Field]) -> TokenStream {
+    let field_value = quote! {
+        impl ::fbe::field_model::FieldValue for #name {
+            const FBE_SIZE: usize = 4;
+
+            fn fbe_extra(&self) -> usize {
+                ::fbe::field_model::struct_extra(self)
+            }
+
+            fn write_field(&self, buffer: &mut ::fbe::buffer::WriteBuffer, base: usize, offset: usize) {
+                ::fbe::field_model::write_struct(self, buffer, base, offset);
+            }
+
+            fn read_field(buffer: &::fbe::buffer::ReadBuffer, base: usize, offset: usize) -> Self {
+                ::fbe::field_model::read_struct(buffer, base, offset)
+            }
+
+            fn try_read_field(
+                buffer: &::fbe::buffer::ReadBuffer,
+                base: usize,
+                offset: usize,
+                budget: &mut ::fbe::limits::DecodeBudget,
+            ) -> ::core::result::Result<Self, ::fbe::limits::DecodeError> {
+                ::fbe::field_model::try_read_struct(buffer, base, offset, budget)
+            }
+        }
+    };
+
+    if fields.is_empty() {
+        return quote! {
+            #field_value
+
+            impl ::fbe::field_model::FieldStruct for #name {
+                const FBE_TYPE: usize = #fbe_type;
+                const FIELDS_SIZE: usize = 0;
+
+                fn fields_extra(&self) -> usize {
+                    0
+                }
+
+                fn write_fields(&self, _buffer: &mut ::fbe::buffer::WriteBuffer, _body: usize, _offset: usize) {}
+
+                fn read_fields(_buffer: &::fbe::buffer::ReadBuffer, _body: usize, _offset: usize) -> Self {
+                    Self
+                }
+
+                fn try_read_fields(
+                    _buffer: &::fbe::buffer::ReadBuffer,
+                    _body: usize,
+                    _offset: usize,
+                    _budget: &mut ::fbe::limits::DecodeBudget,
+                ) -> ::core::result::Result<Self, ::fbe::limits::DecodeError> {
+                    Ok(Self)
+                }
+            }
+        };
+    }
+
+    let locals: Vec<_> = fields.iter().map(|field| &field.local).collect();
+    let sizes: Vec<_> = fields
+        .iter()
+        .map(|field| {
+            let ty = &field.ty;
+            if field.attrs.base {
+                quote!(<#ty as ::fbe::field_model::FieldStruct>::FIELDS_SIZE)
+            } else {
+                let codec = field_codec(field);
+                quote!(#codec::FBE_SIZE)
+            }
+        })
+        .collect();
+    let slots: Vec<_> = (0..fields.len())
+        .map(|index| {
+            let before = &sizes[..index];
+            quote!(offset #(+ #before)*)
+        })
+        .collect();
+    let mut extras = Vec::new();
+    let mut writes = Vec::new();
+    let mut reads = Vec::new();
+    let mut try_reads = Vec::new();
+    for (field, slot) in fields.iter().zip(&slots) {
+        let member = &field.member;
+        let ty = &field.ty;
+        if field.attrs.base {
+            let codec = quote!(<#ty as ::fbe::field_model::FieldStruct>);
+            extras.push(quote!(#codec::fields_extra(&self.#member)));
+            writes.push(quote!(#codec::write_fields(&self.#member, buffer, body, #slot)));
+            reads.push(quote!(#codec::read_fields(buffer, body, #slot)));
+            try_reads.push(quote!(#codec::try_read_fields(buffer, body, #slot, budget)?));
+        } else {
+            let codec = field_codec(field);
+            extras.push(quote!(#codec::fbe_extra(&self.#member)));
+            writes.push(quote!(#codec::write_field(&self.#member, buffer, body, #slot)));
+            reads.push(quote!(#codec::read_field(buffer, body, #slot)));
+            try_reads.push(quote!(#codec::try_read_field(buffer, body, #slot, budget)?));
+        }
+    }
+    let value = construct(
+        shape,
+        fields,
+        &locals
+            .iter()
+            .map(|local| quote!(#local))
+            .collect::<Vec<_>>(),
+    );
+
+    quote! {
+        #field_value
+
+        impl ::fbe::field_model::FieldStruct for #name {
+            const FBE_TYPE: usize = #fbe_type;
+            const FIELDS_SIZE: usize = #(#sizes)+*;
+
+            fn fields_extra(&self) -> usize {
+                #(#extras)+*
+            }
+
+            fn write_fields(&self, buffer: &mut ::fbe::buffer::WriteBuffer, body: usize, offset: usize) {
+                #(#writes;)*
+            }
+
+            fn read_fields(buffer: &::fbe::buffer::ReadBuffer, body: usize, offset: usize) -> Self {
+                #(let #locals = #reads;)*
+                #value
+            }
+
+            fn try_read_fields(
+                buffer: &::fbe::buffer::ReadBuffer,
+                body: usize,
+                offset: usize,
+                budget: &mut ::fbe::limits::DecodeBudget,
+            ) -> ::core::result::Result<Self, ::fbe::limits::DecodeError> {
+                #(let #locals = #try_reads;)*
+                Ok(#value)
+            }
+        }
+    }
+}
+
+/// `Arbitrary` from one strategy per field, nested in pairs to allow any field count
+fn expand_arbitrary(name: &Ident, shape: &Fields, fields: &[Field]) -> TokenStream {
+    if !cfg!(feature = "proptest") {
+        return TokenStream::new();
+    }
+
+    let strategy = fields.iter().rev().fold(quote!(::fbe::proptest::Just(())), |rest, field| {
+        let ty = &field.ty;
+        if field.attrs.latin1 {
+            quote!((::fbe::proptest::Strategy::prop_map(::fbe::proptest::any::<u8>(), char::from), #rest))
+        } else {
+            quote!((::fbe::proptest::any::<#ty>(), #rest))
+        }
+    });
+    let pattern = fields.iter().rev().fold(quote!(()), |rest, field| {
+        let local = &field.local;
+        quote!((#local, #rest))
+    });
+    let value = construct(
+        shape,
+        fields,
+        &fields
+            .iter()
+            .map(|field| {
+                let local = &field.local;
+                quote!(#local)
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    quote! {
+        impl ::fbe::proptest::Arbitrary for #name {
+            type Parameters = ();
+            type Strategy = ::fbe::proptest::BoxedStrategy<Self>;
+
+            fn arbitrary_with(_args: ()) -> Self::Strategy {
+                let strategy = #strategy;
+                ::fbe::proptest::Strategy::boxed(::fbe::proptest::Strategy::prop_map(strategy, |#pattern| #value))
+            }
+        }
+    }
+}
+
+fn expand_models(input: &DeriveInput) -> TokenStream {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let model = format_ident!("{}SizedModel", name);
+    let model_mut = format_ident!("{}SizedModelMut", name);
+    let pointer_model = format_ident!("{}Model", name);
+    let pointer_model_mut = format_ident!("{}ModelMut", name);
+    let final_model = format_ident!("{}FinalModel", name);
+    let final_model_mut = format_ident!("{}FinalModelMut", name);
+    let model_doc = format!(
+        "{} size-prefixed FinalModel (4-byte size header + inline data, not the pointer-based FBE Model)",
+        name
+    );
+    let pointer_model_doc = format!("{} Model (4-byte size, 4-byte pointer to the struct body)", name);
+    let final_model_doc = format!("{} FinalModel (inline format)", name);
+
+    quote! {
+        #[doc = #pointer_model_doc]
+        #vis struct #pointer_model<'a> {
+            buffer: &'a [u8],
+            offset: usize,
+        }
+
+        impl<'a> #pointer_model<'a> {
+            pub fn new(buffer: &'a [u8], offset: usize) -> Self {
+                Self { buffer, offset }
+            }
+
+            pub fn get(&self) -> #name {
+                ::fbe::field_model::read_model(self.buffer, self.offset)
+            }
+
+            /// Get struct from untrusted input within decode limits
+            pub fn try_get(
+                &self,
+                limits: &::fbe::limits::DecodeLimits,
+            ) -> ::core::result::Result<#name, ::fbe::limits::DecodeError> {
+                ::fbe::field_model::try_read_model(self.buffer, self.offset, limits)
+            }
+        }
+
+        impl ::fbe::field_model::FieldModel for #pointer_model<'_> {
+            fn offset(&self) -> usize {
+                self.offset
+            }
+            fn set_offset(&mut self, offset: usize) {
+                self.offset = offset;
+            }
+            fn size(&self) -> usize {
+                ::fbe::field_model::model_size(self.buffer, self.offset)
+            }
+            /// Verify sizes, pointers and the struct type id
+            fn verify(&self) -> bool {
+                ::fbe::field_model::verify_model::<#name>(self.buffer, self.offset)
+            }
+        }
+
+        #[doc = #pointer_model_doc]
+        #vis struct #pointer_model_mut<'a> {
+            buffer: &'a mut ::fbe::buffer::WriteBuffer,
+            offset: usize,
+        }
+
+        impl<'a> #pointer_model_mut<'a> {
+            pub fn new(buffer: &'a mut ::fbe::buffer::WriteBuffer, offset: usize) -> Self {
+                Self { buffer, offset }
+            }
+
+            /// Write struct, replacing anything after the offset, return serialized size
+            pub fn set(&mut self, value: &#name) -> usize {
+                ::fbe::field_model::write_model(value, self.buffer, self.offset)
+            }
+        }
+
+        #[doc = #model_doc]
+        #vis struct #model<'a> {
+            buffer: &'a [u8],
+            offset: usize,
+        }
+
+        impl<'a> #model<'a> {
+            pub fn new(buffer: &'a [u8], offset: usize) -> Self {
+                Self { buffer, offset }
+            }
+
+            /// Verify size header
+            pub fn verify(&self) -> bool {
+                let size = ::fbe::final_model::FinalModel::size(self);
+                size >= 4 && self.offset + size <= self.buffer.len()
+            }
+
+            pub fn get(&self) -> #name {
+                let buffer = ::fbe::buffer::ReadBuffer::borrowed(self.buffer);
+                <#name as ::fbe::final_model::FinalValue>::read_final(&buffer, self.offset + 4).0
+            }
+
+            /// Get struct from untrusted input within decode limits
+            pub fn try_get(
+                &self,
+                limits: &::fbe::limits::DecodeLimits,
+            ) -> ::core::result::Result<#name, ::fbe::limits::DecodeError> {
+                let buffer = ::fbe::buffer::ReadBuffer::borrowed(self.buffer);
+                let size = buffer.try_read_u32(self.offset)? as usize;
+                let mut budget = ::fbe::limits::DecodeBudget::for_message(*limits, size)?;
+                buffer.check_bounds(self.offset, size)?;
+                <#name as ::fbe::final_model::FinalValue>::try_read_final(&buffer, self.offset + 4, &mut budget)
+                    .map(|(value, _)| value)
+            }
+        }
+
+        impl ::fbe::final_model::FinalModel for #model<'_> {
+            fn offset(&self) -> usize {
+                self.offset
+            }
+            fn set_offset(&mut self, offset: usize) {
+                self.offset = offset;
+            }
+            /// Size from the header, 0 if the header is out of bounds
+            fn size(&self) -> usize {
+                ::fbe::field_model::model_size(self.buffer, self.offset)
+            }
+        }
+
+        #[doc = #model_doc]
+        #vis struct #model_mut<'a> {
+            buffer: &'a mut ::fbe::buffer::WriteBuffer,
+            offset: usize,
+        }
+
+        impl<'a> #model_mut<'a> {
+            pub fn new(buffer: &'a mut ::fbe::buffer::WriteBuffer, offset: usize) -> Self {
+                Self { buffer, offset }
+            }
+
+            /// Write size header and struct, return serialized size
+            pub fn set(&mut self, value: &#name) -> usize {
+                let size = 4 + ::fbe::final_model::FinalValue::final_size(value);
+                let end = self.buffer.offset() + self.offset + size;
+                if end > self.buffer.size() {
+                    self.buffer.resize(end);
+                }
+                self.buffer.write_u32(self.offset, size as u32);
+                ::fbe::final_model::FinalValue::write_final(value, self.buffer, self.offset + 4);
+                size
+            }
+        }
+
+        #[doc = #final_model_doc]
+        #vis struct #final_model<'a> {
+            buffer: &'a [u8],
+            offset: usize,
+        }
+
+        impl<'a> #final_model<'a> {
+            pub fn new(buffer: &'a [u8], offset: usize) -> Self {
+                Self { buffer, offset }
+            }
+
+            pub fn get(&self) -> #name {
+                let buffer = ::fbe::buffer::ReadBuffer::borrowed(self.buffer);
+                <#name as ::fbe::final_model::FinalValue>::read_final(&buffer, self.offset).0
+            }
+
+            /// Get struct from untrusted input within decode limits
+            pub fn try_get(
+                &self,
+                limits: &::fbe::limits::DecodeLimits,
+            ) -> ::core::result::Result<#name, ::fbe::limits::DecodeError> {
+                let buffer = ::fbe::buffer::ReadBuffer::borrowed(self.buffer);
+                let mut budget = ::fbe::limits::DecodeBudget::for_message(*limits, self.buffer.len())?;
+                <#name as ::fbe::final_model::FinalValue>::try_read_final(&buffer, self.offset, &mut budget)
+                    .map(|(value, _)| value)
+            }
+        }
+
+        impl ::fbe::final_model::FinalModel for #final_model<'_> {
+            fn offset(&self) -> usize {
+                self.offset
+            }
+            fn set_offset(&mut self, offset: usize) {
+                self.offset = offset;
+            }
+            fn size(&self) -> usize {
+                let buffer = ::fbe::buffer::ReadBuffer::borrowed(self.buffer);
+                <#name as ::fbe::final_model::FinalValue>::read_final_size(&buffer, self.offset)
+            }
+        }
+
+        #[doc = #final_model_doc]
+        #vis struct #final_model_mut<'a> {
+            buffer: &'a mut ::fbe::buffer::WriteBuffer,
+            offset: usize,
+        }
+
+        impl<'a> #final_model_mut<'a> {
+            pub fn new(buffer: &'a mut ::fbe::buffer::WriteBuffer, offset: usize) -> Self {
+                Self { buffer, offset }
+            }
+
+            /// Write struct, return serialized size
+            pub fn set(&mut self, value: &#name) -> usize {
+                let size = ::fbe::final_model::FinalValue::final_size(value);
+                let end = self.buffer.offset() + self.offset + size;
+                if end > self.buffer.size() {
+                    self.buffer.resize(end);
+                }
+                ::fbe::final_model::FinalValue::write_final(value, self.buffer, self.offset)
+            }
+        }
+    }
+}
+
+fn expand_keys(name: &Ident, fields: &[Field]) -> TokenStream {
+    let keys: Vec<_> = fields.iter().filter(|field| field.attrs.key).collect();
+    if keys.is_empty() {
+        return TokenStream::new();
+    }
+
+    let members: Vec<_> = keys.iter().map(|field| &field.member).collect();
+    let types: Vec<_> = keys.iter().map(|field| &field.ty).collect();
+    let (key_type, key_value) = if keys.len() == 1 {
+        (quote!(&#(#types)*), quote!(&self.#(#members)*))
+    } else {
+        (quote!((#(&#types),*)), quote!((#(&self.#members),*)))
+    };
+
+    quote! {
+        impl #name {
+            /// Get struct key
+            pub fn key(&self) -> #key_type {
+                #key_value
+            }
+        }
+
+        impl ::core::cmp::PartialEq for #name {
+            fn eq(&self, other: &Self) -> bool {
+                self.key() == other.key()
+            }
+        }
+
+        impl ::core::cmp::Eq for #name {}
+
+        impl ::core::hash::Hash for #name {
+            fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                self.key().hash(state);
+            }
+        }
+    }
+}
+
+fn expand_default(name: &Ident, shape: &Fields, fields: &[Field]) -> TokenStream {
+    if fields.iter().all(|field| field.attrs.default.is_none()) {
+        return TokenStream::new();
+    }
+
+    let values: Vec<_> = fields
+        .iter()
+        .map(|field| match &field.attrs.default {
+            Some(Expr::Lit(expr)) if matches!(expr.lit, Lit::Str(_)) => {
+                quote!(::core::convert::From::from(#expr))
+            }
+            Some(expr) => quote!(#expr),
+            None => quote!(::core::default::Default::default()),
+        })
+        .collect();
+    let value = construct(shape, fields, &values);
+
+    quote! {
+        impl ::core::default::Default for #name {
+            fn default() -> Self {
+                #value
+            }
+        }
+    }
+}
+
+fn expand_base(name: &Ident, fields: &[Field]) -> TokenStream {
+    let Some(field) = fields.first().filter(|field| field.attrs.base) else {
+        return TokenStream::new();
+    };
+    let member = &field.member;
+    let ty = &field.ty;
+
+    quote! {
+        impl ::core::convert::AsRef<#ty> for #name {
+            fn as_ref(&self) -> &#ty {
+                &self.#member
+            }
+        }
+
+        impl ::core::convert::AsMut<#ty> for #name {
+            fn as_mut(&mut self) -> &mut #ty {
+                &mut self.#member
+            }
+        }
+    }
+}
@@ -0,0 +1,6 @@
+fn main() {
+    fbec::Config::new()
+        .include("schemas")
+        .compile(&["e2e.fbe"])
+        .unwrap();
+}
@@ -0,0 +1,5 @@
+//! fbec output for `schemas/`, compiled against the `fbe` runtime
+
+pub mod proto {
+    include!(concat!(env!("OUT_DIR"), "/mod.rs"));
+}
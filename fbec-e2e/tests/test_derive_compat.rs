@@ -0,0 +1,170 @@
+//! fbec generated structs against `#[derive(FbeStruct)]` mirrors of the same schema
+#![cfg(feature = "derive")]
+
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::{FbeEnum, FbeStruct};
+use fbec_e2e::proto::blob::Blob;
+use fbec_e2e::proto::fill::{Fill, FillModelMut};
+use fbec_e2e::proto::kind::Kind;
+use fbec_e2e::proto::trade::Trade;
+
+mod derived {
+    use super::FbeEnum;
+    use super::FbeStruct;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, FbeEnum)]
+    #[repr(i32)]
+    pub enum Kind {
+        Small = 1,
+        Large,
+    }
+
+    #[derive(Debug, Clone, PartialEq, FbeStruct)]
+    #[fbe(id = 1)]
+    pub struct Trade {
+        pub id: i32,
+        pub symbol: String,
+        pub price: f64,
+    }
+
+    #[derive(Debug, Clone, PartialEq, FbeStruct)]
+    pub struct Blob {
+        pub data: Vec<u8>,
+        pub owner: Option<fbe::Uuid>,
+        pub prices: Vec<fbe::Decimal>,
+        pub created: fbe::Timestamp,
+        #[fbe(char)]
+        pub initial: char,
+        pub letter: char,
+        pub note: Option<String>,
+        pub fills: Vec<i64>,
+        pub name: String,
+        pub chunks: Option<Vec<Vec<u8>>>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, FbeStruct)]
+    pub struct Fill {
+        pub trade: Trade,
+        pub kind: Kind,
+        pub kinds: Vec<Kind>,
+        pub blob: Option<Blob>,
+        pub legs: Vec<Trade>,
+    }
+}
+
+impl From<&Kind> for derived::Kind {
+    fn from(value: &Kind) -> Self {
+        match value {
+            Kind::Small => Self::Small,
+            Kind::Large => Self::Large,
+        }
+    }
+}
+
+impl From<&Trade> for derived::Trade {
+    fn from(value: &Trade) -> Self {
+        Self { id: value.id, symbol: value.symbol.clone(), price: value.price }
+    }
+}
+
+impl From<&Blob> for derived::Blob {
+    fn from(value: &Blob) -> Self {
+        Self {
+            data: value.data.clone(),
+            owner: value.owner,
+            prices: value.prices.clone(),
+            created: value.created,
+            initial: value.initial,
+            letter: value.letter,
+            note: value.note.clone(),
+            fills: value.fills.clone(),
+            name: value.name.clone(),
+            chunks: value.chunks.clone(),
+        }
+    }
+}
+
+impl From<&Fill> for derived::Fill {
+    fn from(value: &Fill) -> Self {
+        Self {
+            trade: (&value.trade).into(),
+            kind: (&value.kind).into(),
+            kinds: value.kinds.iter().map(Into::into).collect(),
+            blob: value.blob.as_ref().map(Into::into),
+            legs: value.legs.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+fn fill() -> Fill {
+    Fill {
+        trade: Trade { id: 7, symbol: "EURUSD".to_string(), price: 1.0875 },
+        kind: Kind::Small,
+        kinds: vec![Kind::Large, Kind::Small],
+        blob: Some(Blob {
+            data: b"Binary\x00\xFF".to_vec(),
+            owner: Some("123e4567-e89b-12d3-a456-426655440000".parse().unwrap()),
+            prices: vec!["1.50".parse().unwrap()],
+            created: fbe::Timestamp::new(1729526400000000000),
+            initial: 'é',
+            letter: 'Ω',
+            note: Some("partial fill".to_string()),
+            fills: vec![100, -250],
+            ..Default::default()
+        }),
+        legs: vec![Trade { id: 8, ..Default::default() }],
+    }
+}
+
+/// FinalModel and Model bytes from fbec and the derive must match
+fn assert_compatible(value: &Fill) {
+    let mirror = derived::Fill::from(value);
+
+    let mut buffer = WriteBuffer::new();
+    let mut expected = WriteBuffer::new();
+    assert_eq!(value.try_serialize(&mut buffer), mirror.try_serialize(&mut expected));
+    assert_eq!(buffer.data(), expected.data());
+
+    let mut buffer = WriteBuffer::new();
+    value.serialize(&mut buffer);
+    let mut expected = WriteBuffer::new();
+    mirror.serialize(&mut expected);
+    assert_eq!(buffer.data(), expected.data());
+
+    let reader = ReadBuffer::from(buffer.data().to_vec());
+    assert_eq!(derived::Fill::deserialize(&reader), derived::Fill::from(&Fill::deserialize(&reader)));
+
+    let mut buffer = WriteBuffer::new();
+    FillModelMut::new(&mut buffer, 0).set(value);
+    let mut expected = WriteBuffer::new();
+    derived::FillModelMut::new(&mut expected, 0).set(&mirror);
+    assert_eq!(buffer.data(), expected.data());
+}
+
+#[test]
+fn test_derive_models_compatible() {
+    assert_compatible(&fill());
+    assert_compatible(&Fill::default());
+
+    // `char` fields outside Latin-1 fail `try_serialize` in both; `serialize` writes `?`
+    let mut value = fill();
+    value.blob.as_mut().unwrap().initial = 'Ω';
+    let mut buffer = WriteBuffer::new();
+    let error = Err(fbe::chars::CharError::NotLatin1('Ω'));
+    assert_eq!(derived::Fill::from(&value).try_serialize(&mut buffer), error);
+    assert_eq!(value.try_serialize(&mut buffer), error);
+    assert_compatible(&value);
+}
+
+#[cfg(feature = "proptest")]
+mod props {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_derive_arbitrary_compatible(fill: Fill) {
+            assert_compatible(&fill);
+        }
+    }
+}
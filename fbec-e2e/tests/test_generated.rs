@@ -0,0 +1,282 @@
+//! Round-trip tests for fbec generated code
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::field_model::FieldModel;
+use fbe::final_model::FinalModel;
+use fbe::limits::{DecodeError, DecodeLimits};
+use fbe::transport::StreamSender;
+use fbe::vectored::VectoredWriter;
+use fbe::{MessageLog, Sender};
+use std::cell::RefCell;
+use fbec_e2e::proto::blob::Blob;
+use fbec_e2e::proto::fill::{Fill, FillFinalModel, FillFinalModelMut, FillModel, FillModelMut};
+use fbec_e2e::proto::kind::Kind;
+use fbec_e2e::proto::trade::{Trade, TradeModel, TradeModelMut};
+
+fn blob() -> Blob {
+    Blob {
+        data: b"Binary\x00\xFF".to_vec(),
+        owner: Some("123e4567-e89b-12d3-a456-426655440000".parse().unwrap()),
+        prices: vec!["1.50".parse().unwrap(), "-123456.789".parse().unwrap()],
+        created: fbe::Timestamp::new(1729526400000000000),
+        initial: 'é',
+        letter: 'Ω',
+        note: Some("partial fill".to_string()),
+        fills: vec![100, -250],
+        chunks: Some(vec![b"FBE".to_vec(), Vec::new()]),
+        ..Default::default()
+    }
+}
+
+fn fill() -> Fill {
+    Fill {
+        trade: Trade { id: 7, symbol: "EURUSD".to_string(), price: 1.0875 },
+        kind: Kind::Small,
+        kinds: vec![Kind::Large, Kind::Small],
+        blob: Some(blob()),
+        legs: vec![Trade { id: 8, ..Default::default() }],
+    }
+}
+
+/// Serialize into a fixed-size (not growable) buffer
+fn serialize<T: fbe::Serialize>(value: &T) -> Vec<u8> {
+    let mut buffer = WriteBuffer::new();
+    let size = value.serialize(&mut buffer);
+    assert_eq!(size, value.size_hint());
+    assert_eq!(buffer.data().len(), size);
+    buffer.data().to_vec()
+}
+
+#[test]
+fn test_generated_roundtrip() {
+    for value in [blob(), Blob::default()] {
+        let data = serialize(&value);
+        let reader = ReadBuffer::from(data);
+        assert_eq!(Blob::deserialize(&reader), value);
+        assert_eq!(Blob::try_deserialize(&reader, &DecodeLimits::default()), Ok(value));
+    }
+
+    let trade = Trade { id: 7, symbol: "EURUSD".to_string(), price: 1.0875 };
+    let reader = ReadBuffer::from(serialize(&trade));
+    assert_eq!(Trade::deserialize(&reader), trade);
+    assert_eq!(Kind::Large as i32, 2);
+}
+
+#[test]
+fn test_generated_enum_and_struct_fields() {
+    assert_eq!(Fill::default().kind, Kind::Large);
+    for value in [fill(), Fill::default()] {
+        let data = serialize(&value);
+        let reader = ReadBuffer::from(data.clone());
+        assert_eq!(Fill::deserialize(&reader), value);
+        assert_eq!(Fill::try_deserialize(&reader, &DecodeLimits::default()), Ok(value));
+        for len in 0..data.len() {
+            let reader = ReadBuffer::from(data[..len].to_vec());
+            assert!(Fill::try_deserialize(&reader, &DecodeLimits::default()).is_err(), "{} bytes", len);
+        }
+    }
+
+    // Nested structs are inline, enums are their underlying `int32`
+    let value = fill();
+    let data = serialize(&value);
+    let trade = serialize(&value.trade);
+    assert_eq!(&data[..trade.len()], trade.as_slice());
+    assert_eq!(&data[trade.len()..trade.len() + 4], 1i32.to_le_bytes());
+
+    // Unknown enum values read as the first variant
+    let mut data = data;
+    data[trade.len()..trade.len() + 4].copy_from_slice(&9i32.to_le_bytes());
+    let reader = ReadBuffer::from(data);
+    assert_eq!(Fill::try_deserialize(&reader, &DecodeLimits::default()).unwrap().kind, Kind::Small);
+
+    // Nesting counts against the depth limit
+    let limits = DecodeLimits { max_depth: 1, ..Default::default() };
+    let reader = ReadBuffer::from(serialize(&value));
+    assert_eq!(Fill::try_deserialize(&reader, &limits), Err(DecodeError::TooDeep { limit: 1 }));
+
+    // `char` fields of nested structs are checked too
+    let value = Fill { blob: Some(Blob { initial: 'Ω', ..Default::default() }), ..fill() };
+    let mut buffer = WriteBuffer::new();
+    assert_eq!(value.try_serialize(&mut buffer), Err(fbe::chars::CharError::NotLatin1('Ω')));
+}
+
+#[test]
+fn test_generated_models() {
+    for value in [fill(), Fill::default()] {
+        let mut buffer = WriteBuffer::new();
+        let size = FillModelMut::new(&mut buffer, 0).set(&value);
+        let data = buffer.data().to_vec();
+        assert_eq!(size, data.len());
+
+        let model = FillModel::new(&data, 0);
+        assert!(model.verify());
+        assert_eq!(model.size(), size);
+        assert_eq!(model.get(), value);
+        assert_eq!(model.try_get(&DecodeLimits::default()), Ok(value.clone()));
+        for len in 0..data.len() {
+            assert!(FillModel::new(&data[..len], 0).try_get(&DecodeLimits::default()).is_err(), "{} bytes", len);
+        }
+
+        let mut buffer = WriteBuffer::new();
+        let size = FillFinalModelMut::new(&mut buffer, 0).set(&value);
+        assert_eq!(buffer.data(), serialize(&value));
+        let model = FillFinalModel::new(buffer.data(), 0);
+        assert_eq!(model.size(), size);
+        assert_eq!(model.try_get(&DecodeLimits::default()), Ok(value));
+    }
+
+    // Model layout: size, pointer to the body, body size and type id, then field slots
+    let trade = Trade { id: 7, symbol: "EUR".to_string(), price: 1.5 };
+    let mut buffer = WriteBuffer::new();
+    TradeModelMut::new(&mut buffer, 0).set(&trade);
+    let mut expected = Vec::new();
+    expected.extend_from_slice(&39u32.to_le_bytes());
+    expected.extend_from_slice(&8u32.to_le_bytes());
+    expected.extend_from_slice(&24u32.to_le_bytes());
+    expected.extend_from_slice(&1u32.to_le_bytes());
+    expected.extend_from_slice(&7i32.to_le_bytes());
+    expected.extend_from_slice(&24u32.to_le_bytes());
+    expected.extend_from_slice(&1.5f64.to_le_bytes());
+    expected.extend_from_slice(&3u32.to_le_bytes());
+    expected.extend_from_slice(b"EUR");
+    assert_eq!(buffer.data(), expected.as_slice());
+    assert!(TradeModel::new(buffer.data(), 0).verify());
+    assert!(!FillModel::new(buffer.data(), 0).verify());
+}
+
+#[test]
+fn test_generated_try_deserialize_truncated() {
+    let data = serialize(&blob());
+    for len in 0..data.len() {
+        let reader = ReadBuffer::from(data[..len].to_vec());
+        assert!(Blob::try_deserialize(&reader, &DecodeLimits::default()).is_err(), "{} bytes", len);
+    }
+
+    let limits = DecodeLimits { max_collection_len: 1, ..Default::default() };
+    assert!(matches!(
+        Blob::try_deserialize(&ReadBuffer::from(data), &limits),
+        Err(DecodeError::CollectionTooLong { .. })
+    ));
+}
+
+#[test]
+fn test_generated_char_errors() {
+    // `char` fields are Latin-1: `try_serialize` rejects others, `serialize` writes `?`
+    let value = Blob { initial: 'Ω', ..Default::default() };
+    let mut buffer = WriteBuffer::new();
+    assert_eq!(value.try_serialize(&mut buffer), Err(fbe::chars::CharError::NotLatin1('Ω')));
+    let reader = ReadBuffer::from(serialize(&value));
+    assert_eq!(Blob::deserialize(&reader).initial, '?');
+
+    let mut buffer = WriteBuffer::new();
+    assert_eq!(blob().try_serialize(&mut buffer), Ok(blob().size_hint()));
+    assert_eq!(buffer.data(), serialize(&blob()));
+
+    // `wchar` fields holding a surrogate are rejected by `try_deserialize`
+    let value = Blob { letter: 'Ω', ..Default::default() };
+    let mut data = serialize(&value);
+    let pos = data.windows(4).position(|w| w == 0x3A9u32.to_le_bytes()).unwrap();
+    data[pos..pos + 4].copy_from_slice(&0xD800u32.to_le_bytes());
+    let reader = ReadBuffer::from(data);
+    assert_eq!(
+        Blob::try_deserialize(&reader, &DecodeLimits::default()),
+        Err(DecodeError::InvalidChar(fbe::chars::CharError::InvalidWChar(0xD800)))
+    );
+}
+
+#[derive(Default)]
+struct RecordingSender {
+    buffer: WriteBuffer,
+    records: RefCell<Vec<MessageLog>>,
+}
+
+impl Sender for RecordingSender {
+    fn is_logging(&self) -> bool {
+        true
+    }
+
+    fn set_logging(&mut self, _enable: bool) {}
+
+    fn send_buffer(&mut self) -> &mut WriteBuffer {
+        &mut self.buffer
+    }
+
+    fn on_send(&mut self, data: &[u8]) -> usize {
+        data.len()
+    }
+
+    fn on_send_message(&self, record: &MessageLog) {
+        self.records.borrow_mut().push(record.clone());
+    }
+}
+
+#[test]
+fn test_generated_type_id() {
+    let mut sender = RecordingSender::default();
+    sender.send(&Trade { id: 7, symbol: "EURUSD".to_string(), price: 1.0875 });
+    sender.send(&blob());
+
+    // `struct Trade(1)` keeps its id, `struct Blob` has none
+    let records = sender.records.borrow();
+    assert_eq!(records[0].type_id, 1);
+    assert_eq!(records[1].type_id, 0);
+    assert_eq!(<Trade as fbe::Deserialize>::FBE_TYPE, 1);
+}
+
+#[test]
+fn test_generated_vectored() {
+    let value = Fill {
+        blob: Some(Blob { data: (0..1 << 20).map(|i| i as u8).collect(), ..blob() }),
+        ..fill()
+    };
+    let expected = serialize(&value);
+
+    // The megabyte `bytes` field is referenced, not copied
+    let mut writer = VectoredWriter::new();
+    writer.append(&value);
+    let data = &value.blob.as_ref().unwrap().data;
+    assert_eq!(writer.payloads(), 1);
+    assert!(writer.io_slices().iter().any(|slice| slice.as_ptr() == data.as_ptr() && slice.len() == data.len()));
+    assert_eq!(writer.to_vec(), expected);
+
+    let mut vectored = StreamSender::new(Vec::new());
+    let mut copied = StreamSender::new(Vec::new());
+    assert_eq!(vectored.send_vectored(&value).unwrap(), copied.send(&value).unwrap());
+    assert_eq!(vectored.get_ref(), copied.get_ref());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_generated_json() {
+    let value = blob();
+    let json = value.to_json();
+    assert_eq!(Blob::from_json(&json).unwrap(), value);
+    assert!(json.contains("\"prices\":[\"1.50\",\"-123456.789\"]"), "{}", json);
+    assert!(json.contains("\"chunks\":[\"RkJF\",\"\"]"), "{}", json);
+
+    let value = Blob { chunks: None, ..blob() };
+    let json = value.to_json();
+    assert_eq!(Blob::from_json(&json).unwrap(), value);
+    assert!(json.contains("\"chunks\":null"), "{}", json);
+
+    let value = fill();
+    let json = value.to_json();
+    assert_eq!(Fill::from_json(&json).unwrap(), value);
+    assert!(json.contains("\"kinds\":[2,1]"), "{}", json);
+}
+
+#[cfg(feature = "proptest")]
+mod props {
+    use super::*;
+    use fbe::proptest::{assert_message_roundtrip, assert_roundtrip};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_generated_arbitrary_roundtrip(blob: Blob, trade: Trade, fill: Fill) {
+            assert_message_roundtrip(&blob);
+            assert_message_roundtrip(&trade);
+            assert_message_roundtrip(&fill);
+            assert_roundtrip(&fill);
+        }
+    }
+}
@@ -0,0 +1,1400 @@
+//! Fast Binary Encoding compiler for Rust
+//!
+//! Generates Rust code from .fbe schema files. Usable from `build.rs`:
+//!
+//! ```no_run
+//! fbec::Config::new()
+//!     .include("schemas")
+//!     .compile(&["proto.fbe"])
+//!     .unwrap();
+//! ```
+//!
+//! and then from the crate:
+//!
+//! ```ignore
+//! mod proto {
+//!     include!(concat!(env!("OUT_DIR"), "/mod.rs"));
+//! }
+//! ```
+
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Struct field declaration (`type[?][[]] name`)
+static FIELD_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\w+)(\?)?(\[\])?\s+(\w+)").unwrap());
+
+/// Code generation configuration
+#[derive(Debug, Clone)]
+pub struct Config {
+    includes: Vec<PathBuf>,
+    out_dir: Option<PathBuf>,
+    runtime: String,
+    rerun_if_changed: bool,
+    path_attributes: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Config {
+    /// Create a new configuration for `build.rs` usage
+    ///
+    /// Output goes to `OUT_DIR`, generated code refers to the `fbe` crate,
+    /// `cargo:rerun-if-changed` is emitted for every schema and import, and
+    /// `mod.rs` uses `#[path]` attributes so it can be `include!`d.
+    pub fn new() -> Self {
+        Self {
+            includes: Vec::new(),
+            out_dir: None,
+            runtime: "fbe".to_string(),
+            rerun_if_changed: true,
+            path_attributes: true,
+        }
+    }
+
+    /// Add a directory to search for schemas and imports
+    pub fn include<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.includes.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set output directory (defaults to `OUT_DIR`)
+    pub fn out_dir<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.out_dir = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set path of the FBE runtime crate used by generated code (defaults to `fbe`)
+    pub fn runtime_path(&mut self, path: &str) -> &mut Self {
+        self.runtime = path.to_string();
+        self
+    }
+
+    /// Enable/Disable `cargo:rerun-if-changed` output
+    pub fn rerun_if_changed(&mut self, enable: bool) -> &mut Self {
+        self.rerun_if_changed = enable;
+        self
+    }
+
+    /// Enable/Disable `#[path]` attributes in the generated `mod.rs`
+    pub fn path_attributes(&mut self, enable: bool) -> &mut Self {
+        self.path_attributes = enable;
+        self
+    }
+
+    /// Compile schemas and their imports
+    pub fn compile<P: AsRef<Path>>(&self, schemas: &[P]) -> Result<(), String> {
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => out_dir.clone(),
+            None => std::env::var_os("OUT_DIR")
+                .map(PathBuf::from)
+                .ok_or("OUT_DIR is not set, use Config::out_dir()")?,
+        };
+        let output_dir = out_dir
+            .to_str()
+            .ok_or_else(|| format!("Invalid output directory: {}", out_dir.display()))?;
+
+        fs::create_dir_all(output_dir)
+            .map_err(|e| format!("Failed to create output directory: {}", e))?;
+
+        let mut generator = Generator::new(&self.runtime);
+        let mut visited = HashSet::new();
+        for schema in schemas {
+            let path = self.resolve(schema.as_ref(), None)?;
+            self.load(&path, &mut generator, &mut visited)?;
+        }
+        generator.generate_code(output_dir, self.path_attributes)?;
+
+        Ok(())
+    }
+
+    /// Parse a schema and, recursively, its imports
+    fn load(
+        &self,
+        path: &Path,
+        generator: &mut Generator,
+        visited: &mut HashSet<PathBuf>,
+    ) -> Result<(), String> {
+        let key = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        if !visited.insert(key) {
+            return Ok(());
+        }
+
+        if self.rerun_if_changed {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+        for import in parse_imports(&content) {
+            let import_path = self
+                .resolve(Path::new(&format!("{}.fbe", import)), path.parent())
+                .map_err(|e| format!("{} (imported from {})", e, path.display()))?;
+            self.load(&import_path, generator, visited)?;
+        }
+
+        generator.parse(&content)
+    }
+
+    /// Resolve a schema path against the importing directory and include paths
+    fn resolve(&self, path: &Path, base: Option<&Path>) -> Result<PathBuf, String> {
+        if path.is_absolute() || (base.is_none() && path.exists()) {
+            return Ok(path.to_path_buf());
+        }
+
+        base.into_iter()
+            .chain(self.includes.iter().map(PathBuf::as_path))
+            .map(|dir| dir.join(path))
+            .find(|candidate| candidate.exists())
+            .ok_or_else(|| format!("Schema not found: {}", path.display()))
+    }
+}
+
+/// Get imported package names (`import proto;`)
+fn parse_imports(content: &str) -> Vec<String> {
+    let re = Regex::new(r"(?m)^\s*import\s+([\w.]+)\s*;").unwrap();
+    re.captures_iter(content)
+        .map(|cap| cap[1].to_string())
+        .collect()
+}
+
+struct Generator {
+    runtime: String,
+    enums: Vec<EnumDef>,
+    flags: Vec<FlagsDef>,
+    structs: Vec<StructDef>,
+}
+
+#[derive(Debug, Clone)]
+struct EnumDef {
+    name: String,
+    base_type: String,
+    values: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+struct FlagsDef {
+    name: String,
+    base_type: String,
+    values: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+struct StructDef {
+    name: String,
+    /// FBE type id from `struct Name(id)`
+    id: Option<usize>,
+    fields: Vec<FieldDef>,
+}
+
+#[derive(Debug, Clone)]
+struct FieldDef {
+    name: String,
+    fbe_type: String,
+    is_optional: bool,
+    is_array: bool,
+    default: Option<String>,
+}
+
+impl Generator {
+    fn new(runtime: &str) -> Self {
+        Self {
+            runtime: runtime.to_string(),
+            enums: Vec::new(),
+            flags: Vec::new(),
+            structs: Vec::new(),
+        }
+    }
+
+    fn parse(&mut self, content: &str) -> Result<(), String> {
+        self.parse_enums(content)?;
+        self.parse_flags(content)?;
+        self.parse_structs(content)?;
+        Ok(())
+    }
+
+    fn parse_enums(&mut self, content: &str) -> Result<(), String> {
+        let re = Regex::new(r"enum\s+(\w+)\s*:\s*(\w+)\s*\{([^}]+)\}").unwrap();
+        
+        for cap in re.captures_iter(content) {
+            let name = cap[1].to_string();
+            let base_type = cap[2].to_string();
+            let body = &cap[3];
+
+            // Values without an explicit `= value` are left empty and
+            // continue from the previous one, as in upstream FBE
+            let mut values = Vec::new();
+            for line in body.lines() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with("//") {
+                    continue;
+                }
+
+                if let Some((key, val)) = line.split_once('=') {
+                    let key = key.trim().to_string();
+                    let val = val.trim().trim_end_matches(';').trim().to_string();
+                    values.push((key, val));
+                } else {
+                    let key = line.trim_end_matches(';').trim().to_string();
+                    if !key.is_empty() {
+                        values.push((key, String::new()));
+                    }
+                }
+            }
+
+            self.enums.push(EnumDef { name, base_type, values });
+        }
+
+        Ok(())
+    }
+
+    fn parse_flags(&mut self, content: &str) -> Result<(), String> {
+        let re = Regex::new(r"flags\s+(\w+)\s*:\s*(\w+)\s*\{([^}]+)\}").unwrap();
+        
+        for cap in re.captures_iter(content) {
+            let name = cap[1].to_string();
+            let base_type = cap[2].to_string();
+            let body = &cap[3];
+
+            let mut values = Vec::new();
+            for line in body.lines() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with("//") {
+                    continue;
+                }
+
+                if let Some((key, val)) = line.split_once('=') {
+                    let key = key.trim().to_string();
+                    let val = val.trim().trim_end_matches(';').trim().to_string();
+                    values.push((key, val));
+                }
+            }
+
+            self.flags.push(FlagsDef { name, base_type, values });
+        }
+
+        Ok(())
+    }
+
+    fn parse_structs(&mut self, content: &str) -> Result<(), String> {
+        let re = Regex::new(r"struct\s+(\w+)(?:\((\d+)\))?\s*\{([^}]+)\}").unwrap();
+        
+        for cap in re.captures_iter(content) {
+            let struct_name = cap[1].to_string();
+            let id = match cap.get(2) {
+                Some(id) => Some(
+                    id.as_str()
+                        .parse()
+                        .map_err(|e| format!("{}: invalid type id '{}': {}", struct_name, id.as_str(), e))?,
+                ),
+                None => None,
+            };
+            let body = &cap[3];
+
+            let mut fields = Vec::new();
+
+            for line in body.lines() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with("//") || line.starts_with("[") {
+                    continue;
+                }
+
+                if let Some(cap) = FIELD_RE.captures(line) {
+                    let fbe_type = cap[1].to_string();
+                    let is_optional = cap.get(2).is_some();
+                    let is_array = cap.get(3).is_some();
+                    let name = cap[4].to_string();
+                    let default = line
+                        .split_once('=')
+                        .map(|(_, val)| val.trim().trim_end_matches(';').trim().to_string());
+
+                    let field = FieldDef {
+                        name,
+                        fbe_type,
+                        is_optional,
+                        is_array,
+                        default,
+                    };
+                    check_field(&struct_name, &field)?;
+                    fields.push(field);
+                }
+            }
+
+            self.structs.push(StructDef { name: struct_name, id, fields });
+        }
+
+        Ok(())
+    }
+
+    /// Reject fields of unknown types or of flags types, which have no Rust type
+    fn check_types(&self) -> Result<(), String> {
+        for struct_def in &self.structs {
+            for field in &struct_def.fields {
+                let fbe_type = field.fbe_type.as_str();
+                if BUILTIN_TYPES.contains(&fbe_type)
+                    || self.enums.iter().any(|enum_def| enum_def.name == fbe_type)
+                    || self.structs.iter().any(|other| other.name == fbe_type)
+                {
+                    continue;
+                }
+                let reason = if self.flags.iter().any(|flags_def| flags_def.name == fbe_type) {
+                    "flags fields are not supported"
+                } else {
+                    "unknown type"
+                };
+                return Err(format!("{}.{}: field type '{}': {}", struct_def.name, field.name, fbe_type, reason));
+            }
+        }
+        Ok(())
+    }
+
+    fn generate_code(&self, output_dir: &str, path_attributes: bool) -> Result<(), String> {
+        self.check_types()?;
+
+        for enum_def in &self.enums {
+            self.generate_enum(enum_def, output_dir)?;
+        }
+
+        for flags_def in &self.flags {
+            self.generate_flags(flags_def, output_dir)?;
+        }
+
+        for struct_def in &self.structs {
+            self.generate_struct(struct_def, output_dir)?;
+        }
+
+        self.generate_mod_file(output_dir, path_attributes)?;
+        Ok(())
+    }
+
+    fn generate_enum(&self, enum_def: &EnumDef, output_dir: &str) -> Result<(), String> {
+        let file_name = format!("{}/{}.rs", output_dir, to_snake_case(&enum_def.name));
+        let rust_type = map_fbe_type(&enum_def.base_type);
+        if enum_def.values.is_empty() {
+            return Err(format!("{}: enum has no values", enum_def.name));
+        }
+
+        let variants: Vec<String> = enum_def
+            .values
+            .iter()
+            .map(|(key, _)| to_pascal_case(&escape_keyword(key)))
+            .collect();
+
+        let mut code = format!("//! {} enum\n\n", enum_def.name);
+        code.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq)]\n");
+        code.push_str(&format!("#[repr({})]\n", rust_type));
+        code.push_str(&format!("pub enum {} {{\n", enum_def.name));
+        
+        for (variant, (_, val)) in variants.iter().zip(&enum_def.values) {
+            if val.is_empty() {
+                code.push_str(&format!("    {},\n", variant));
+            } else if val.starts_with('\'') {
+                code.push_str(&format!("    {} = b{} as {},\n", variant, val, rust_type));
+            } else {
+                code.push_str(&format!("    {} = {},\n", variant, val));
+            }
+        }
+        code.push_str("}\n\n");
+
+        code.push_str(&format!("impl Default for {} {{\n", enum_def.name));
+        code.push_str("    fn default() -> Self {\n");
+        code.push_str(&format!("        Self::{}\n", variants[0]));
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+
+        code.push_str(&format!("impl TryFrom<{}> for {} {{\n", rust_type, enum_def.name));
+        code.push_str(&format!("    type Error = {};\n\n", rust_type));
+        code.push_str(&format!("    fn try_from(value: {}) -> Result<Self, {}> {{\n", rust_type, rust_type));
+        code.push_str("        match value {\n");
+        for variant in &variants {
+            code.push_str(&format!("            v if v == Self::{} as {} => Ok(Self::{}),\n", variant, rust_type, variant));
+        }
+        code.push_str("            _ => Err(value),\n");
+        code.push_str("        }\n");
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+
+        // Inline underlying value; unknown values read as the first variant, as `#[derive(FbeEnum)]` does
+        code.push_str(&format!("impl {}::final_model::FinalValue for {} {{\n", self.runtime, enum_def.name));
+        code.push_str("    fn final_size(&self) -> usize {\n");
+        code.push_str(&format!("        std::mem::size_of::<{}>()\n", rust_type));
+        code.push_str("    }\n\n");
+        code.push_str(&format!("    fn write_final(&self, buffer: &mut {}::buffer::WriteBuffer, offset: usize) -> usize {{\n", self.runtime));
+        code.push_str(&format!("        {}::final_model::FinalValue::write_final(&(*self as {}), buffer, offset)\n", self.runtime, rust_type));
+        code.push_str("    }\n\n");
+        code.push_str(&format!("    fn read_final(buffer: &{}::buffer::ReadBuffer, offset: usize) -> (Self, usize) {{\n", self.runtime));
+        code.push_str(&format!("        let (value, size) = <{} as {}::final_model::FinalValue>::read_final(buffer, offset);\n", rust_type, self.runtime));
+        code.push_str("        (Self::try_from(value).unwrap_or_default(), size)\n");
+        code.push_str("    }\n\n");
+        code.push_str(&format!("    fn read_final_size(_buffer: &{}::buffer::ReadBuffer, _offset: usize) -> usize {{\n", self.runtime));
+        code.push_str(&format!("        std::mem::size_of::<{}>()\n", rust_type));
+        code.push_str("    }\n\n");
+        code.push_str(&format!(
+            "    fn try_read_final(buffer: &{}::buffer::ReadBuffer, offset: usize, budget: &mut {}::limits::DecodeBudget) -> Result<(Self, usize), {}::limits::DecodeError> {{\n",
+            self.runtime, self.runtime, self.runtime
+        ));
+        code.push_str(&format!("        let (value, size) = <{} as {}::final_model::FinalValue>::try_read_final(buffer, offset, budget)?;\n", rust_type, self.runtime));
+        code.push_str("        Ok((Self::try_from(value).unwrap_or_default(), size))\n");
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+
+        // Model layout slot holds the underlying value as well
+        code.push_str(&format!("impl {}::field_model::FieldValue for {} {{\n", self.runtime, enum_def.name));
+        code.push_str(&format!("    const FBE_SIZE: usize = std::mem::size_of::<{}>();\n\n", rust_type));
+        code.push_str("    fn fbe_extra(&self) -> usize {\n");
+        code.push_str("        0\n");
+        code.push_str("    }\n\n");
+        code.push_str(&format!("    fn write_field(&self, buffer: &mut {}::buffer::WriteBuffer, base: usize, offset: usize) {{\n", self.runtime));
+        code.push_str(&format!("        {}::field_model::FieldValue::write_field(&(*self as {}), buffer, base, offset);\n", self.runtime, rust_type));
+        code.push_str("    }\n\n");
+        code.push_str(&format!("    fn read_field(buffer: &{}::buffer::ReadBuffer, base: usize, offset: usize) -> Self {{\n", self.runtime));
+        code.push_str(&format!("        let value = <{} as {}::field_model::FieldValue>::read_field(buffer, base, offset);\n", rust_type, self.runtime));
+        code.push_str("        Self::try_from(value).unwrap_or_default()\n");
+        code.push_str("    }\n\n");
+        code.push_str(&format!(
+            "    fn try_read_field(buffer: &{}::buffer::ReadBuffer, base: usize, offset: usize, budget: &mut {}::limits::DecodeBudget) -> Result<Self, {}::limits::DecodeError> {{\n",
+            self.runtime, self.runtime, self.runtime
+        ));
+        code.push_str(&format!("        let value = <{} as {}::field_model::FieldValue>::try_read_field(buffer, base, offset, budget)?;\n", rust_type, self.runtime));
+        code.push_str("        Ok(Self::try_from(value).unwrap_or_default())\n");
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+
+        // FBE JSON represents enums by their underlying value
+        code.push_str("#[cfg(feature = \"json\")]\n");
+        code.push_str(&format!("impl serde::Serialize for {} {{\n", enum_def.name));
+        code.push_str("    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {\n");
+        code.push_str(&format!("        serde::Serialize::serialize(&(*self as {}), serializer)\n", rust_type));
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+
+        code.push_str("#[cfg(feature = \"json\")]\n");
+        code.push_str(&format!("impl<'de> serde::Deserialize<'de> for {} {{\n", enum_def.name));
+        code.push_str("    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {\n");
+        code.push_str(&format!("        let value = <{} as serde::Deserialize>::deserialize(deserializer)?;\n", rust_type));
+        code.push_str(&format!(
+            "        Self::try_from(value).map_err(|value| serde::de::Error::custom(format!(\"invalid {} value: {{}}\", value)))\n",
+            enum_def.name
+        ));
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+
+        // Any declared variant, under the including crate's `proptest` feature
+        let values: Vec<String> = variants.iter().map(|variant| format!("Self::{}", variant)).collect();
+        code.push_str("#[cfg(feature = \"proptest\")]\n");
+        code.push_str(&format!("impl {}::proptest::Arbitrary for {} {{\n", self.runtime, enum_def.name));
+        code.push_str("    type Parameters = ();\n");
+        code.push_str(&format!("    type Strategy = {}::proptest::BoxedStrategy<Self>;\n\n", self.runtime));
+        code.push_str("    fn arbitrary_with(_args: ()) -> Self::Strategy {\n");
+        code.push_str(&format!("        use {}::proptest::Strategy;\n", self.runtime));
+        code.push_str(&format!("        let values = [{}];\n", values.join(", ")));
+        code.push_str("        (0..values.len()).prop_map(move |index| values[index]).boxed()\n");
+        code.push_str("    }\n");
+        code.push_str("}\n");
+
+        fs::write(&file_name, code)
+            .map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
+        Ok(())
+    }
+
+    fn generate_flags(&self, flags_def: &FlagsDef, output_dir: &str) -> Result<(), String> {
+        let file_name = format!("{}/{}.rs", output_dir, to_snake_case(&flags_def.name));
+        let rust_type = map_fbe_type(&flags_def.base_type);
+
+        let mut code = format!("//! {} flags\n\n", flags_def.name);
+        for (key, val) in &flags_def.values {
+            let const_name = to_screaming_snake_case(&escape_keyword(key));
+            code.push_str(&format!("pub const {}: {} = {};\n", const_name, rust_type, val));
+        }
+
+        fs::write(&file_name, code)
+            .map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
+        Ok(())
+    }
+
+    fn generate_struct(&self, struct_def: &StructDef, output_dir: &str) -> Result<(), String> {
+        let file_name = format!("{}/{}.rs", output_dir, to_snake_case(&struct_def.name));
+
+        let mut code = format!("//! {} struct\n\n", struct_def.name);
+        code.push_str(&format!("use {}::buffer::{{WriteBuffer, ReadBuffer}};\n", self.runtime));
+        let mut imports: Vec<&str> = struct_def
+            .fields
+            .iter()
+            .map(|field| field.fbe_type.as_str())
+            .filter(|fbe_type| !BUILTIN_TYPES.contains(fbe_type) && *fbe_type != struct_def.name)
+            .collect();
+        imports.sort_unstable();
+        imports.dedup();
+        for name in imports {
+            code.push_str(&format!("use super::{}::{};\n", to_snake_case(name), name));
+        }
+        code.push('\n');
+        let has_defaults = struct_def.fields.iter().any(|field| field.default.is_some());
+        if has_defaults {
+            code.push_str("#[derive(Debug, Clone, PartialEq)]\n");
+        } else {
+            code.push_str("#[derive(Debug, Clone, PartialEq, Default)]\n");
+        }
+        // JSON support is compiled in by the including crate's `json` feature
+        code.push_str("#[cfg_attr(feature = \"json\", derive(serde::Serialize, serde::Deserialize))]\n");
+        code.push_str(&format!("pub struct {} {{\n", struct_def.name));
+        
+        for field in &struct_def.fields {
+            let rust_type = self.map_field_type(field);
+            if let Some(adapter) = self.json_adapter(field) {
+                code.push_str(&format!("    #[cfg_attr(feature = \"json\", serde(with = \"{}\"))]\n", adapter));
+            }
+            code.push_str(&format!("    pub {}: {},\n", field.name, rust_type));
+        }
+        code.push_str("}\n\n");
+
+        // Schema default values
+        if has_defaults {
+            code.push_str(&format!("impl Default for {} {{\n", struct_def.name));
+            code.push_str("    fn default() -> Self {\n");
+            code.push_str("        Self {\n");
+            for field in &struct_def.fields {
+                let value = match &field.default {
+                    Some(default) => self.default_value(field, default),
+                    None => "Default::default()".to_string(),
+                };
+                code.push_str(&format!("            {}: {},\n", field.name, value));
+            }
+            code.push_str("        }\n");
+            code.push_str("    }\n");
+            code.push_str("}\n\n");
+        }
+
+        // Add serialization methods
+        code.push_str(&format!("impl {} {{\n", struct_def.name));
+        
+        // Serialize method
+        code.push_str("    /// Serialized size, reserved up front by `serialize`\n");
+        code.push_str("    pub fn size_hint(&self) -> usize {\n");
+        code.push_str(&format!("        {}\n", self.generate_size_hint(struct_def)));
+        code.push_str("    }\n\n");
+
+        code.push_str("    /// Serialize, writing `?` for `char` fields outside Latin-1\n");
+        code.push_str("    pub fn serialize(&self, buffer: &mut WriteBuffer) -> usize {\n");
+        code.push_str("        buffer.reserve(buffer.offset() + self.size_hint());\n");
+        code.push_str(&format!("        let size = {}::final_model::FinalValue::write_final(self, buffer, 0);\n", self.runtime));
+        code.push_str("        buffer.set_size(buffer.offset() + size);\n");
+        code.push_str("        size\n");
+        code.push_str("    }\n\n");
+
+        // Fallible serialize rejecting `char` fields outside Latin-1
+        code.push_str("    /// Serialize, failing on `char` fields outside Latin-1\n");
+        code.push_str(&format!(
+            "    pub fn try_serialize(&self, buffer: &mut WriteBuffer) -> Result<usize, {}::chars::CharError> {{\n",
+            self.runtime
+        ));
+        code.push_str("        self.check_chars()?;\n");
+        code.push_str("        Ok(self.serialize(buffer))\n");
+        code.push_str("    }\n\n");
+
+        code.push_str("    /// Check that `char` fields, including those of nested structs, are Latin-1\n");
+        code.push_str(&format!(
+            "    pub fn check_chars(&self) -> Result<(), {}::chars::CharError> {{\n",
+            self.runtime
+        ));
+        for field in &struct_def.fields {
+            code.push_str(&self.generate_check_chars_field(field));
+        }
+        code.push_str("        Ok(())\n");
+        code.push_str("    }\n\n");
+
+        // Deserialize method
+        code.push_str("    pub fn deserialize(buffer: &ReadBuffer) -> Self {\n");
+        code.push_str(&format!("        {}::final_model::FinalValue::read_final(buffer, 0).0\n", self.runtime));
+        code.push_str("    }\n\n");
+
+        // Bounded deserialize for untrusted input
+        code.push_str("    /// Deserialize from untrusted input within decode limits\n");
+        code.push_str(&format!(
+            "    pub fn try_deserialize(buffer: &ReadBuffer, limits: &{}::limits::DecodeLimits) -> Result<Self, {}::limits::DecodeError> {{\n",
+            self.runtime, self.runtime
+        ));
+        code.push_str(&format!("        let mut budget = {}::limits::DecodeBudget::for_message(*limits, buffer.size())?;\n", self.runtime));
+        code.push_str(&format!("        Ok({}::final_model::FinalValue::try_read_final(buffer, 0, &mut budget)?.0)\n", self.runtime));
+        code.push_str("    }\n\n");
+
+        // Add JSON serialization methods
+        code.push_str("    /// Convert struct to FBE JSON string\n");
+        code.push_str("    #[cfg(feature = \"json\")]\n");
+        code.push_str("    pub fn to_json(&self) -> String {\n");
+        code.push_str(&format!("        {}::json::to_json(self).expect(\"FBE struct is always representable as JSON\")\n", self.runtime));
+        code.push_str("    }\n\n");
+
+        code.push_str("    /// Create struct from FBE JSON string\n");
+        code.push_str("    #[cfg(feature = \"json\")]\n");
+        code.push_str(&format!("    pub fn from_json(json: &str) -> {}::json::Result<Self> {{\n", self.runtime));
+        code.push_str(&format!("        {}::json::from_json(json)\n", self.runtime));
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+
+        // Inline FinalModel layout, also used for nested struct fields
+        code.push_str(&format!("impl {}::final_model::FinalValue for {} {{\n", self.runtime, struct_def.name));
+        code.push_str("    fn final_size(&self) -> usize {\n");
+        code.push_str("        self.size_hint()\n");
+        code.push_str("    }\n\n");
+        code.push_str("    fn write_final(&self, buffer: &mut WriteBuffer, offset: usize) -> usize {\n");
+        code.push_str("        let start = offset;\n");
+        code.push_str("        let mut offset = offset;\n");
+        for field in &struct_def.fields {
+            code.push_str(&self.generate_serialize_field(field));
+        }
+        code.push_str("        offset - start\n");
+        code.push_str("    }\n\n");
+        code.push_str("    fn read_final(buffer: &ReadBuffer, offset: usize) -> (Self, usize) {\n");
+        code.push_str("        let start = offset;\n");
+        code.push_str("        let mut offset = offset;\n");
+        code.push_str("        let value = Self {\n");
+        for field in &struct_def.fields {
+            code.push_str(&self.generate_deserialize_field(field));
+        }
+        code.push_str("        };\n");
+        code.push_str("        (value, offset - start)\n");
+        code.push_str("    }\n\n");
+        code.push_str("    fn read_final_size(buffer: &ReadBuffer, offset: usize) -> usize {\n");
+        code.push_str("        let start = offset;\n");
+        code.push_str("        let mut offset = offset;\n");
+        for field in &struct_def.fields {
+            code.push_str(&self.generate_read_size_field(field));
+        }
+        code.push_str("        offset - start\n");
+        code.push_str("    }\n\n");
+        code.push_str(&format!(
+            "    fn try_read_final(buffer: &ReadBuffer, offset: usize, budget: &mut {}::limits::DecodeBudget) -> Result<(Self, usize), {}::limits::DecodeError> {{\n",
+            self.runtime, self.runtime
+        ));
+        code.push_str("        budget.enter()?;\n");
+        code.push_str("        let start = offset;\n");
+        code.push_str("        let mut offset = offset;\n");
+        code.push_str("        let value = Self {\n");
+        for field in &struct_def.fields {
+            code.push_str(&self.generate_try_deserialize_field(field));
+        }
+        code.push_str("        };\n");
+        code.push_str("        budget.leave();\n");
+        code.push_str("        Ok((value, offset - start))\n");
+        code.push_str("    }\n\n");
+        code.push_str(&format!(
+            "    fn write_vectored<'a>(&'a self, writer: &mut {}::vectored::VectoredWriter<'a>) {{\n",
+            self.runtime
+        ));
+        for field in &struct_def.fields {
+            code.push_str(&self.generate_write_vectored_field(field));
+        }
+        code.push_str("    }\n\n");
+        code.push_str(&format!("    fn check_chars(&self) -> Result<(), {}::chars::CharError> {{\n", self.runtime));
+        code.push_str("        Self::check_chars(self)\n");
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+
+        code.push_str(&self.generate_field_value(struct_def));
+        code.push_str(&self.generate_models(&struct_def.name));
+
+        // Add Display trait for logging
+        code.push_str("#[cfg(feature = \"json\")]\n");
+        code.push_str(&format!("impl std::fmt::Display for {} {{\n", struct_def.name));
+        code.push_str("    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {\n");
+        code.push_str(&format!("        write!(f, \"{}(", struct_def.name));
+        for (i, field) in struct_def.fields.iter().enumerate() {
+            if i > 0 {
+                code.push_str(", ");
+            }
+            code.push_str(&format!("{}={{:?}}", field.name));
+        }
+        code.push_str(")\"\n");
+        for field in &struct_def.fields {
+            code.push_str(&format!("            , self.{}\n", field.name));
+        }
+        code.push_str("        )\n");
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+
+        // Add Sender/Receiver traits with JSON log rendering
+        code.push_str(&format!("impl {}::sender::Serialize for {} {{\n", self.runtime, struct_def.name));
+        if let Some(id) = struct_def.id {
+            code.push_str(&format!("    const FBE_TYPE: usize = {};\n\n", id));
+        }
+        code.push_str("    fn serialize(&self, buffer: &mut WriteBuffer) -> usize {\n");
+        code.push_str(&format!("        {}::serialize(self, buffer)\n", struct_def.name));
+        code.push_str("    }\n\n");
+        code.push_str("    fn size_hint(&self) -> usize {\n");
+        code.push_str(&format!("        {}::size_hint(self)\n", struct_def.name));
+        code.push_str("    }\n\n");
+        code.push_str("    #[cfg(feature = \"json\")]\n");
+        code.push_str("    fn log_json(&self) -> Option<String> {\n");
+        code.push_str("        Some(self.to_json())\n");
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+
+        code.push_str(&format!("impl {}::receiver::Deserialize for {} {{\n", self.runtime, struct_def.name));
+        if let Some(id) = struct_def.id {
+            code.push_str(&format!("    const FBE_TYPE: usize = {};\n\n", id));
+        }
+        code.push_str("    fn deserialize(buffer: &ReadBuffer) -> Self {\n");
+        code.push_str(&format!("        {}::deserialize(buffer)\n", struct_def.name));
+        code.push_str("    }\n\n");
+        code.push_str(&format!(
+            "    fn try_deserialize(buffer: &ReadBuffer, limits: &{}::limits::DecodeLimits) -> Result<Self, {}::limits::DecodeError> {{\n",
+            self.runtime, self.runtime
+        ));
+        code.push_str(&format!("        {}::try_deserialize(buffer, limits)\n", struct_def.name));
+        code.push_str("    }\n\n");
+        code.push_str("    #[cfg(feature = \"json\")]\n");
+        code.push_str("    fn log_json(&self) -> Option<String> {\n");
+        code.push_str("        Some(self.to_json())\n");
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+
+        // Property-based testing support is compiled in by the including crate's `proptest` feature
+        let strategy = struct_def.fields.iter().rev().fold(
+            format!("{}::proptest::Just(())", self.runtime),
+            |rest, field| format!("({}, {})", self.arbitrary_strategy(field), rest),
+        );
+        let pattern = struct_def
+            .fields
+            .iter()
+            .rev()
+            .fold("()".to_string(), |rest, field| format!("({}, {})", field.name, rest));
+        let names: Vec<&str> = struct_def.fields.iter().map(|field| field.name.as_str()).collect();
+        code.push_str("#[cfg(feature = \"proptest\")]\n");
+        code.push_str(&format!("impl {}::proptest::Arbitrary for {} {{\n", self.runtime, struct_def.name));
+        code.push_str("    type Parameters = ();\n");
+        code.push_str(&format!("    type Strategy = {}::proptest::BoxedStrategy<Self>;\n\n", self.runtime));
+        code.push_str("    fn arbitrary_with(_args: ()) -> Self::Strategy {\n");
+        code.push_str(&format!("        use {}::proptest::Strategy;\n", self.runtime));
+        code.push_str(&format!("        let strategy = {};\n", strategy));
+        code.push_str(&format!("        strategy.prop_map(|{}| Self {{ {} }}).boxed()\n", pattern, names.join(", ")));
+        code.push_str("    }\n");
+        code.push_str("}\n");
+
+        fs::write(&file_name, code)
+            .map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
+        Ok(())
+    }
+
+    /// `FieldValue`/`FieldStruct` impls for the pointer-based Model layout, also used for nested struct fields
+    fn generate_field_value(&self, struct_def: &StructDef) -> String {
+        let runtime = &self.runtime;
+        let name = &struct_def.name;
+        let mut code = format!("impl {}::field_model::FieldValue for {} {{\n", runtime, name);
+        code.push_str("    const FBE_SIZE: usize = 4;\n\n");
+        code.push_str("    fn fbe_extra(&self) -> usize {\n");
+        code.push_str(&format!("        {}::field_model::struct_extra(self)\n", runtime));
+        code.push_str("    }\n\n");
+        code.push_str("    fn write_field(&self, buffer: &mut WriteBuffer, base: usize, offset: usize) {\n");
+        code.push_str(&format!("        {}::field_model::write_struct(self, buffer, base, offset);\n", runtime));
+        code.push_str("    }\n\n");
+        code.push_str("    fn read_field(buffer: &ReadBuffer, base: usize, offset: usize) -> Self {\n");
+        code.push_str(&format!("        {}::field_model::read_struct(buffer, base, offset)\n", runtime));
+        code.push_str("    }\n\n");
+        code.push_str(&format!(
+            "    fn try_read_field(buffer: &ReadBuffer, base: usize, offset: usize, budget: &mut {}::limits::DecodeBudget) -> Result<Self, {}::limits::DecodeError> {{\n",
+            runtime, runtime
+        ));
+        code.push_str(&format!("        {}::field_model::try_read_struct(buffer, base, offset, budget)\n", runtime));
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+
+        // Field slots follow the struct body header in schema order
+        let mut slots = Vec::new();
+        let mut fields_size = 0;
+        for field in &struct_def.fields {
+            slots.push(if fields_size == 0 { "offset".to_string() } else { format!("offset + {}", fields_size) });
+            fields_size += self.get_slot_size(field);
+        }
+        let extras: Vec<String> = struct_def
+            .fields
+            .iter()
+            .filter(|field| self.has_extra(field))
+            .map(|field| format!("{}::field_model::FieldValue::fbe_extra(&self.{})", runtime, field.name))
+            .collect();
+        let prefix = if struct_def.fields.is_empty() { "_" } else { "" };
+
+        code.push_str(&format!("impl {}::field_model::FieldStruct for {} {{\n", runtime, name));
+        code.push_str(&format!("    const FBE_TYPE: usize = {};\n", struct_def.id.unwrap_or(0)));
+        code.push_str(&format!("    const FIELDS_SIZE: usize = {};\n\n", fields_size));
+        code.push_str("    fn fields_extra(&self) -> usize {\n");
+        if extras.is_empty() {
+            code.push_str("        0\n");
+        } else {
+            code.push_str(&format!("        {}\n", extras.join(" + ")));
+        }
+        code.push_str("    }\n\n");
+        code.push_str(&format!(
+            "    fn write_fields(&self, {p}buffer: &mut WriteBuffer, {p}body: usize, {p}offset: usize) {{\n",
+            p = prefix
+        ));
+        for (field, slot) in struct_def.fields.iter().zip(&slots) {
+            code.push_str(&format!(
+                "        {}::write_field(&self.{}, buffer, body, {});\n",
+                self.get_field_codec(field),
+                field.name,
+                slot
+            ));
+        }
+        code.push_str("    }\n\n");
+        code.push_str(&format!(
+            "    fn read_fields({p}buffer: &ReadBuffer, {p}body: usize, {p}offset: usize) -> Self {{\n",
+            p = prefix
+        ));
+        code.push_str("        Self {\n");
+        for (field, slot) in struct_def.fields.iter().zip(&slots) {
+            code.push_str(&format!(
+                "            {}: {}::read_field(buffer, body, {}),\n",
+                field.name,
+                self.get_field_codec(field),
+                slot
+            ));
+        }
+        code.push_str("        }\n");
+        code.push_str("    }\n\n");
+        code.push_str(&format!(
+            "    fn try_read_fields({p}buffer: &ReadBuffer, {p}body: usize, {p}offset: usize, {p}budget: &mut {r}::limits::DecodeBudget) -> Result<Self, {r}::limits::DecodeError> {{\n",
+            p = prefix,
+            r = runtime
+        ));
+        code.push_str("        Ok(Self {\n");
+        for (field, slot) in struct_def.fields.iter().zip(&slots) {
+            code.push_str(&format!(
+                "            {}: {}::try_read_field(buffer, body, {}, budget)?,\n",
+                field.name,
+                self.get_field_codec(field),
+                slot
+            ));
+        }
+        code.push_str("        })\n");
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+        code
+    }
+
+    /// `{Name}Model` (pointer-based Model layout) and `{Name}FinalModel` (inline FinalModel layout) accessors
+    fn generate_models(&self, name: &str) -> String {
+        let runtime = &self.runtime;
+        let mut code = String::new();
+
+        code.push_str(&format!("/// {} Model (4-byte size, 4-byte pointer to the struct body)\n", name));
+        code.push_str(&format!("pub struct {}Model<'a> {{\n", name));
+        code.push_str("    buffer: &'a [u8],\n");
+        code.push_str("    offset: usize,\n");
+        code.push_str("}\n\n");
+        code.push_str(&format!("impl<'a> {}Model<'a> {{\n", name));
+        code.push_str("    pub fn new(buffer: &'a [u8], offset: usize) -> Self {\n");
+        code.push_str("        Self { buffer, offset }\n");
+        code.push_str("    }\n\n");
+        code.push_str(&format!("    pub fn get(&self) -> {} {{\n", name));
+        code.push_str(&format!("        {}::field_model::read_model(self.buffer, self.offset)\n", runtime));
+        code.push_str("    }\n\n");
+        code.push_str("    /// Get struct from untrusted input within decode limits\n");
+        code.push_str(&format!(
+            "    pub fn try_get(&self, limits: &{}::limits::DecodeLimits) -> Result<{}, {}::limits::DecodeError> {{\n",
+            runtime, name, runtime
+        ));
+        code.push_str(&format!("        {}::field_model::try_read_model(self.buffer, self.offset, limits)\n", runtime));
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+        code.push_str(&format!("impl {}::field_model::FieldModel for {}Model<'_> {{\n", runtime, name));
+        code.push_str("    fn offset(&self) -> usize {\n");
+        code.push_str("        self.offset\n");
+        code.push_str("    }\n\n");
+        code.push_str("    fn set_offset(&mut self, offset: usize) {\n");
+        code.push_str("        self.offset = offset;\n");
+        code.push_str("    }\n\n");
+        code.push_str("    fn size(&self) -> usize {\n");
+        code.push_str(&format!("        {}::field_model::model_size(self.buffer, self.offset)\n", runtime));
+        code.push_str("    }\n\n");
+        code.push_str("    /// Verify sizes, pointers and the struct type id\n");
+        code.push_str("    fn verify(&self) -> bool {\n");
+        code.push_str(&format!("        {}::field_model::verify_model::<{}>(self.buffer, self.offset)\n", runtime, name));
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+
+        code.push_str(&format!("/// {} Model writer\n", name));
+        code.push_str(&format!("pub struct {}ModelMut<'a> {{\n", name));
+        code.push_str("    buffer: &'a mut WriteBuffer,\n");
+        code.push_str("    offset: usize,\n");
+        code.push_str("}\n\n");
+        code.push_str(&format!("impl<'a> {}ModelMut<'a> {{\n", name));
+        code.push_str("    pub fn new(buffer: &'a mut WriteBuffer, offset: usize) -> Self {\n");
+        code.push_str("        Self { buffer, offset }\n");
+        code.push_str("    }\n\n");
+        code.push_str("    /// Write struct, replacing anything after the offset, return serialized size\n");
+        code.push_str(&format!("    pub fn set(&mut self, value: &{}) -> usize {{\n", name));
+        code.push_str(&format!("        {}::field_model::write_model(value, self.buffer, self.offset)\n", runtime));
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+
+        code.push_str(&format!("/// {} FinalModel (inline format)\n", name));
+        code.push_str(&format!("pub struct {}FinalModel<'a> {{\n", name));
+        code.push_str("    buffer: &'a [u8],\n");
+        code.push_str("    offset: usize,\n");
+        code.push_str("}\n\n");
+        code.push_str(&format!("impl<'a> {}FinalModel<'a> {{\n", name));
+        code.push_str("    pub fn new(buffer: &'a [u8], offset: usize) -> Self {\n");
+        code.push_str("        Self { buffer, offset }\n");
+        code.push_str("    }\n\n");
+        code.push_str(&format!("    pub fn get(&self) -> {} {{\n", name));
+        code.push_str(&format!(
+            "        {}::final_model::FinalValue::read_final(&ReadBuffer::borrowed(self.buffer), self.offset).0\n",
+            runtime
+        ));
+        code.push_str("    }\n\n");
+        code.push_str("    /// Get struct from untrusted input within decode limits\n");
+        code.push_str(&format!(
+            "    pub fn try_get(&self, limits: &{}::limits::DecodeLimits) -> Result<{}, {}::limits::DecodeError> {{\n",
+            runtime, name, runtime
+        ));
+        code.push_str(&format!(
+            "        let mut budget = {}::limits::DecodeBudget::for_message(*limits, self.buffer.len())?;\n",
+            runtime
+        ));
+        code.push_str(&format!(
+            "        Ok({}::final_model::FinalValue::try_read_final(&ReadBuffer::borrowed(self.buffer), self.offset, &mut budget)?.0)\n",
+            runtime
+        ));
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+        code.push_str(&format!("impl {}::final_model::FinalModel for {}FinalModel<'_> {{\n", runtime, name));
+        code.push_str("    fn offset(&self) -> usize {\n");
+        code.push_str("        self.offset\n");
+        code.push_str("    }\n\n");
+        code.push_str("    fn set_offset(&mut self, offset: usize) {\n");
+        code.push_str("        self.offset = offset;\n");
+        code.push_str("    }\n\n");
+        code.push_str("    fn size(&self) -> usize {\n");
+        code.push_str(&format!(
+            "        <{} as {}::final_model::FinalValue>::read_final_size(&ReadBuffer::borrowed(self.buffer), self.offset)\n",
+            name, runtime
+        ));
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+
+        code.push_str(&format!("/// {} FinalModel writer\n", name));
+        code.push_str(&format!("pub struct {}FinalModelMut<'a> {{\n", name));
+        code.push_str("    buffer: &'a mut WriteBuffer,\n");
+        code.push_str("    offset: usize,\n");
+        code.push_str("}\n\n");
+        code.push_str(&format!("impl<'a> {}FinalModelMut<'a> {{\n", name));
+        code.push_str("    pub fn new(buffer: &'a mut WriteBuffer, offset: usize) -> Self {\n");
+        code.push_str("        Self { buffer, offset }\n");
+        code.push_str("    }\n\n");
+        code.push_str("    /// Write struct, return serialized size\n");
+        code.push_str(&format!("    pub fn set(&mut self, value: &{}) -> usize {{\n", name));
+        code.push_str(&format!(
+            "        let end = self.buffer.offset() + self.offset + {}::final_model::FinalValue::final_size(value);\n",
+            runtime
+        ));
+        code.push_str("        if end > self.buffer.size() {\n");
+        code.push_str("            self.buffer.resize(end);\n");
+        code.push_str("        }\n");
+        code.push_str(&format!("        {}::final_model::FinalValue::write_final(value, self.buffer, self.offset)\n", runtime));
+        code.push_str("    }\n");
+        code.push_str("}\n\n");
+        code
+    }
+
+    /// Model layout codec of a field: its `FieldValue` impl, or the 1-byte FBE `char` functions
+    fn get_field_codec(&self, field: &FieldDef) -> String {
+        if field.fbe_type == "char" {
+            // Optional and array `char` fields are rejected by `check_field`
+            format!("{}::chars::latin1", self.runtime)
+        } else {
+            format!("<{} as {}::field_model::FieldValue>", self.map_field_type(field), self.runtime)
+        }
+    }
+
+    /// Whether a field writes data behind a pointer in the Model layout
+    fn has_extra(&self, field: &FieldDef) -> bool {
+        field.is_optional
+            || field.is_array
+            || matches!(field.fbe_type.as_str(), "string" | "bytes")
+            || self.structs.iter().any(|struct_def| struct_def.name == field.fbe_type)
+    }
+
+    /// Size of the field slot in the Model layout
+    fn get_slot_size(&self, field: &FieldDef) -> usize {
+        if field.is_optional {
+            // has_value flag and pointer to the value
+            5
+        } else if field.is_array {
+            4
+        } else if let Some(enum_def) = self.enums.iter().find(|enum_def| enum_def.name == field.fbe_type) {
+            get_type_size(&enum_def.base_type)
+        } else {
+            // Built-in scalars inline; strings, bytes and structs are pointers
+            get_type_size(&field.fbe_type)
+        }
+    }
+
+    /// Expression for the serialized size of a struct, matching `generate_serialize_field`
+    fn generate_size_hint(&self, struct_def: &StructDef) -> String {
+        let mut fixed = 0;
+        let mut terms = Vec::new();
+        for field in &struct_def.fields {
+            if uses_final_value(field) {
+                terms.push(format!("{}::final_model::FinalValue::final_size(&self.{})", self.runtime, field.name));
+            } else if field.fbe_type == "string" || field.fbe_type == "bytes" {
+                fixed += 4;
+                terms.push(format!("self.{}.len()", field.name));
+            } else {
+                fixed += get_type_size(&field.fbe_type);
+            }
+        }
+        if fixed > 0 || terms.is_empty() {
+            terms.insert(0, fixed.to_string());
+        }
+        terms.join(" + ")
+    }
+
+    fn generate_serialize_field(&self, field: &FieldDef) -> String {
+        let write_method = get_write_method(&field.fbe_type);
+        let size = get_type_size(&field.fbe_type);
+        
+        if uses_final_value(field) {
+            // Optional, array, enum and struct fields use the FinalModel layout of their value
+            format!("        offset += {}::final_model::FinalValue::write_final(&self.{}, buffer, offset);\n", self.runtime, field.name)
+        } else if field.fbe_type == "string" || field.fbe_type == "bytes" {
+            format!("        buffer.{}(offset, &self.{});\n        offset += 4 + self.{}.len();\n", write_method, field.name, field.name)
+        } else if field.fbe_type == "char" {
+            format!("        buffer.write_u8(offset, {}::chars::encode_char(self.{}).unwrap_or(b'?'));\n        offset += {};\n", self.runtime, field.name, size)
+        } else {
+            format!("        buffer.{}(offset, self.{});\n        offset += {};\n", write_method, field.name, size)
+        }
+    }
+
+    /// Vectored output of a field, referencing large `bytes`/`string` payloads
+    fn generate_write_vectored_field(&self, field: &FieldDef) -> String {
+        if field.fbe_type == "char" {
+            // Optional and array `char` fields are rejected by `check_field`
+            format!("        {}::chars::latin1::write_vectored(&self.{}, writer);\n", self.runtime, field.name)
+        } else {
+            format!("        {}::final_model::FinalValue::write_vectored(&self.{}, writer);\n", self.runtime, field.name)
+        }
+    }
+
+    fn generate_deserialize_field(&self, field: &FieldDef) -> String {
+        let read_method = get_read_method(&field.fbe_type);
+        
+        if uses_final_value(field) {
+            format!("            {}: {{ let (val, size) = {}::final_model::FinalValue::read_final(buffer, offset); offset += size; val }},\n", field.name, self.runtime)
+        } else if field.fbe_type == "string" || field.fbe_type == "bytes" {
+            format!("            {}: {{ let val = buffer.{}(offset); offset += 4 + buffer.read_u32(offset) as usize; val }},\n", field.name, read_method)
+        } else if field.fbe_type == "wchar" {
+            format!("            {}: {{ let val = buffer.{}(offset).unwrap_or(char::REPLACEMENT_CHARACTER); offset += 4; val }},\n", field.name, read_method)
+        } else {
+            let size = get_type_size(&field.fbe_type);
+            format!("            {}: {{ let val = buffer.{}(offset); offset += {}; val }},\n", field.name, read_method, size)
+        }
+    }
+
+    /// Like `generate_deserialize_field`, checking bounds and lengths against `budget`
+    fn generate_try_deserialize_field(&self, field: &FieldDef) -> String {
+        let read_method = get_read_method(&field.fbe_type);
+
+        if uses_final_value(field) {
+            format!("            {}: {{ let (val, size) = {}::final_model::FinalValue::try_read_final(buffer, offset, budget)?; offset += size; val }},\n", field.name, self.runtime)
+        } else if field.fbe_type == "string" || field.fbe_type == "bytes" {
+            format!("            {}: {{ let val = buffer.try_{}(offset, budget)?; offset += 4 + buffer.read_u32(offset) as usize; val }},\n", field.name, read_method)
+        } else if field.fbe_type == "wchar" {
+            format!("            {}: {{ buffer.check_bounds(offset, 4)?; let val = buffer.{}(offset)?; offset += 4; val }},\n", field.name, read_method)
+        } else {
+            let size = get_type_size(&field.fbe_type);
+            format!("            {}: {{ buffer.check_bounds(offset, {})?; let val = buffer.{}(offset); offset += {}; val }},\n", field.name, size, read_method, size)
+        }
+    }
+
+    /// Field size walk for `read_final_size`, matching `generate_deserialize_field`
+    fn generate_read_size_field(&self, field: &FieldDef) -> String {
+        if uses_final_value(field) {
+            format!(
+                "        offset += <{} as {}::final_model::FinalValue>::read_final_size(buffer, offset);\n",
+                self.map_field_type(field),
+                self.runtime
+            )
+        } else if field.fbe_type == "string" || field.fbe_type == "bytes" {
+            "        offset += 4 + buffer.read_u32(offset) as usize;\n".to_string()
+        } else {
+            format!("        offset += {};\n", get_type_size(&field.fbe_type))
+        }
+    }
+
+    /// Latin-1 check for `char` fields and the `char` fields of nested structs
+    fn generate_check_chars_field(&self, field: &FieldDef) -> String {
+        if field.fbe_type == "char" {
+            // Optional and array `char` fields are rejected by `check_field`
+            return format!("        {}::chars::encode_char(self.{})?;\n", self.runtime, field.name);
+        }
+        if !self.structs.iter().any(|struct_def| struct_def.name == field.fbe_type) {
+            return String::new();
+        }
+        match (field.is_optional, field.is_array) {
+            (false, false) => format!("        self.{}.check_chars()?;\n", field.name),
+            (true, false) => format!("        if let Some(value) = &self.{} {{\n            value.check_chars()?;\n        }}\n", field.name),
+            (false, true) => format!("        for value in &self.{} {{\n            value.check_chars()?;\n        }}\n", field.name),
+            (true, true) => format!(
+                "        for value in self.{}.iter().flatten() {{\n            value.check_chars()?;\n        }}\n",
+                field.name
+            ),
+        }
+    }
+
+    /// proptest strategy for a field value that serializes without panicking
+    fn arbitrary_strategy(&self, field: &FieldDef) -> String {
+        if field.fbe_type == "char" && !field.is_array && !field.is_optional {
+            // `char` fields hold Latin-1 characters only
+            return format!("{}::proptest::any::<u8>().prop_map(char::from)", self.runtime);
+        }
+        format!("{}::proptest::any::<{}>()", self.runtime, self.map_field_type(field))
+    }
+
+    /// Rust expression for a schema default value
+    fn default_value(&self, field: &FieldDef, default: &str) -> String {
+        if field.is_optional && default == "null" {
+            return "None".to_string();
+        }
+        let value = match (field.fbe_type.as_str(), default.trim_end_matches("()")) {
+            ("uuid", "uuid0") => format!("{}::Uuid::nil()", self.runtime),
+            ("uuid", "uuid1") => format!("{}::Uuid::v1()", self.runtime),
+            ("uuid", "uuid4") => format!("{}::Uuid::v4()", self.runtime),
+            ("uuid", _) => format!("{}.parse::<{}::Uuid>().unwrap()", default, self.runtime),
+            ("decimal", _) => format!(
+                "\"{}\".parse::<{}::Decimal>().unwrap()",
+                default.trim_matches('"'),
+                self.runtime
+            ),
+            ("timestamp", "utc") => format!("{}::Timestamp::utc()", self.runtime),
+            ("timestamp", _) => format!("{}::Timestamp::new({})", self.runtime, default),
+            ("string", _) => format!("String::from({})", default),
+            (fbe_type, value) if self.enums.iter().any(|enum_def| enum_def.name == fbe_type) => {
+                // `Kind.Large` or `Large`
+                let variant = value.rsplit('.').next().unwrap_or(value);
+                format!("{}::{}", fbe_type, to_pascal_case(&escape_keyword(variant)))
+            }
+            _ => default.to_string(),
+        };
+        if field.is_optional {
+            format!("Some({})", value)
+        } else {
+            value
+        }
+    }
+
+    /// FBE JSON serde adapter for fields whose Rust type has a different JSON shape
+    fn json_adapter(&self, field: &FieldDef) -> Option<String> {
+        let adapter = match field.fbe_type.as_str() {
+            "bytes" => "bytes",
+            "char" => "char",
+            "wchar" => "wchar",
+            _ => return None,
+        };
+        match (field.is_array, field.is_optional) {
+            (false, false) => Some(format!("{}::json::{}", self.runtime, adapter)),
+            (false, true) => Some(format!("{}::json::{}::option", self.runtime, adapter)),
+            (true, false) => Some(format!("{}::json::{}::vec", self.runtime, adapter)),
+            (true, true) => Some(format!("{}::json::{}::option_vec", self.runtime, adapter)),
+        }
+    }
+
+    /// Map FBE field type to Rust type, using `char` and runtime value types where available
+    fn map_type(&self, fbe_type: &str) -> String {
+        match fbe_type {
+            "char" | "wchar" => "char".to_string(),
+            "decimal" => format!("{}::Decimal", self.runtime),
+            "timestamp" => format!("{}::Timestamp", self.runtime),
+            "uuid" => format!("{}::Uuid", self.runtime),
+            _ => map_fbe_type(fbe_type),
+        }
+    }
+
+    fn map_field_type(&self, field: &FieldDef) -> String {
+        let base_type = self.map_type(&field.fbe_type);
+        
+        if field.is_array {
+            if field.is_optional {
+                format!("Option<Vec<{}>>", base_type)
+            } else {
+                format!("Vec<{}>", base_type)
+            }
+        } else if field.is_optional {
+            format!("Option<{}>", base_type)
+        } else {
+            base_type
+        }
+    }
+
+    fn generate_mod_file(&self, output_dir: &str, path_attributes: bool) -> Result<(), String> {
+        let mut names = Vec::new();
+        names.extend(self.enums.iter().map(|enum_def| to_snake_case(&enum_def.name)));
+        names.extend(self.flags.iter().map(|flags_def| to_snake_case(&flags_def.name)));
+        names.extend(self.structs.iter().map(|struct_def| to_snake_case(&struct_def.name)));
+
+        let mut code = if path_attributes {
+            // Plain comment: the file is meant to be include!d from OUT_DIR
+            String::from("// Generated FBE modules\n\n")
+        } else {
+            String::from("//! Generated FBE modules\n\n")
+        };
+        for name in names {
+            if path_attributes {
+                let path = Path::new(output_dir).join(format!("{}.rs", name));
+                code.push_str(&format!("#[path = {:?}]\n", path.display().to_string()));
+            }
+            code.push_str(&format!("pub mod {};\n", name));
+        }
+
+        fs::write(format!("{}/mod.rs", output_dir), code)
+            .map_err(|e| format!("Failed to write mod.rs: {}", e))?;
+        Ok(())
+    }
+}
+
+/// Built-in FBE field types
+const BUILTIN_TYPES: &[&str] = &[
+    "bool", "byte", "int8", "uint8", "int16", "uint16", "int32", "uint32", "int64", "uint64",
+    "float", "double", "char", "wchar", "string", "bytes", "decimal", "timestamp", "uuid",
+];
+
+/// Whether a field is encoded through its value's `FinalValue` impl
+///
+/// Optional and array fields, and fields of generated enum and struct types,
+/// are; built-in scalars, strings and bytes are written inline.
+fn uses_final_value(field: &FieldDef) -> bool {
+    field.is_optional || field.is_array || !BUILTIN_TYPES.contains(&field.fbe_type.as_str())
+}
+
+/// Reject struct fields the generated serializer cannot encode
+///
+/// Field types that are not built-in are checked against the parsed enums and
+/// structs in `Generator::check_types`, once all schemas are loaded.
+fn check_field(struct_name: &str, field: &FieldDef) -> Result<(), String> {
+    // Optional and array values use the 4-byte `wchar` layout for `char`
+    if field.fbe_type == "char" && (field.is_optional || field.is_array) {
+        return Err(format!(
+            "{}.{}: optional and array 'char' fields are not supported, use 'wchar'",
+            struct_name, field.name
+        ));
+    }
+    Ok(())
+}
+
+fn map_fbe_type(fbe_type: &str) -> String {
+    match fbe_type {
+        "bool" => "bool",
+        "byte" | "int8" => "i8",
+        "uint8" => "u8",
+        "int16" => "i16",
+        "uint16" => "u16",
+        "int32" => "i32",
+        "uint32" => "u32",
+        "int64" => "i64",
+        "uint64" => "u64",
+        "float" => "f32",
+        "double" => "f64",
+        "char" => "u8",
+        "wchar" => "u32",
+        "string" => "String",
+        "bytes" => "Vec<u8>",
+        _ => fbe_type,
+    }.to_string()
+}
+
+fn to_snake_case(s: &str) -> String {
+    let mut result = String::new();
+    for (i, ch) in s.chars().enumerate() {
+        if ch.is_uppercase() && i > 0 {
+            result.push('_');
+        }
+        result.push(ch.to_lowercase().next().unwrap());
+    }
+    result
+}
+
+fn to_pascal_case(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn to_screaming_snake_case(s: &str) -> String {
+    to_snake_case(s).to_uppercase()
+}
+
+fn escape_keyword(s: &str) -> String {
+    match s {
+        "type" | "match" | "loop" | "move" | "ref" | "self" | "Self" => format!("r#{}", s),
+        _ => s.to_string()
+    }
+}
+
+
+fn get_write_method(fbe_type: &str) -> String {
+    match fbe_type {
+        "bool" => "write_bool",
+        "byte" | "int8" => "write_i8",
+        "uint8" => "write_u8",
+        "int16" => "write_i16",
+        "uint16" => "write_u16",
+        "int32" => "write_i32",
+        "uint32" => "write_u32",
+        "int64" => "write_i64",
+        "uint64" => "write_u64",
+        "float" => "write_f32",
+        "double" => "write_f64",
+        "char" => "write_char",
+        "wchar" => "write_wchar",
+        "string" => "write_string",
+        "bytes" => "write_bytes",
+        "decimal" => "write_decimal",
+        "timestamp" => "write_timestamp",
+        "uuid" => "write_uuid",
+        _ => "write_i32",
+    }.to_string()
+}
+
+fn get_read_method(fbe_type: &str) -> String {
+    match fbe_type {
+        "bool" => "read_bool",
+        "byte" | "int8" => "read_i8",
+        "uint8" => "read_u8",
+        "int16" => "read_i16",
+        "uint16" => "read_u16",
+        "int32" => "read_i32",
+        "uint32" => "read_u32",
+        "int64" => "read_i64",
+        "uint64" => "read_u64",
+        "float" => "read_f32",
+        "double" => "read_f64",
+        "char" => "read_char",
+        "wchar" => "read_wchar",
+        "string" => "read_string",
+        "bytes" => "read_bytes",
+        "decimal" => "read_decimal",
+        "timestamp" => "read_timestamp",
+        "uuid" => "read_uuid",
+        _ => "read_i32",
+    }.to_string()
+}
+
+fn get_type_size(fbe_type: &str) -> usize {
+    match fbe_type {
+        "bool" | "byte" | "int8" | "uint8" | "char" => 1,
+        "int16" | "uint16" => 2,
+        "int32" | "uint32" | "float" | "wchar" => 4,
+        "int64" | "uint64" | "double" | "timestamp" => 8,
+        "decimal" | "uuid" => 16,
+        _ => 4,
+    }
+}
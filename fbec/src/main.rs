@@ -1,714 +1,49 @@
 //! Fast Binary Encoding compiler for Rust
-//! 
+//!
 //! Generates Rust code from .fbe schema files
 
-use regex::Regex;
-use std::fs;
-
 fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    
-    if args.len() < 3 {
-        eprintln!("Usage: fbec <input.fbe> <output_dir>");
-        std::process::exit(1);
-    }
-
-    let input_file = &args[1];
-    let output_dir = &args[2];
-
-    match generate(input_file, output_dir) {
-        Ok(_) => println!("✓ Code generation successful!"),
-        Err(e) => {
-            eprintln!("✗ Error: {}", e);
-            std::process::exit(1);
-        }
-    }
-}
-
-fn generate(input_file: &str, output_dir: &str) -> Result<(), String> {
-    let content = fs::read_to_string(input_file)
-        .map_err(|e| format!("Failed to read {}: {}", input_file, e))?;
-
-    fs::create_dir_all(output_dir)
-        .map_err(|e| format!("Failed to create output directory: {}", e))?;
-
-    let mut generator = Generator::new();
-    generator.parse(&content)?;
-    generator.generate_code(output_dir)?;
-
-    println!("Generated {} enums, {} flags, {} structs", 
-        generator.enums.len(), generator.flags.len(), generator.structs.len());
-
-    Ok(())
-}
-
-struct Generator {
-    enums: Vec<EnumDef>,
-    flags: Vec<FlagsDef>,
-    structs: Vec<StructDef>,
-}
-
-#[derive(Debug, Clone)]
-struct EnumDef {
-    name: String,
-    base_type: String,
-    values: Vec<(String, String)>,
-}
-
-#[derive(Debug, Clone)]
-struct FlagsDef {
-    name: String,
-    base_type: String,
-    values: Vec<(String, String)>,
-}
-
-#[derive(Debug, Clone)]
-struct StructDef {
-    name: String,
-    fields: Vec<FieldDef>,
-}
-
-#[derive(Debug, Clone)]
-struct FieldDef {
-    name: String,
-    fbe_type: String,
-    is_optional: bool,
-    is_array: bool,
-}
-
-impl Generator {
-    fn new() -> Self {
-        Self {
-            enums: Vec::new(),
-            flags: Vec::new(),
-            structs: Vec::new(),
-        }
-    }
-
-    fn parse(&mut self, content: &str) -> Result<(), String> {
-        self.parse_enums(content)?;
-        self.parse_flags(content)?;
-        self.parse_structs(content)?;
-        Ok(())
-    }
-
-    fn parse_enums(&mut self, content: &str) -> Result<(), String> {
-        let re = Regex::new(r"enum\s+(\w+)\s*:\s*(\w+)\s*\{([^}]+)\}").unwrap();
-        
-        for cap in re.captures_iter(content) {
-            let name = cap[1].to_string();
-            let base_type = cap[2].to_string();
-            let body = &cap[3];
-
-            let mut values = Vec::new();
-            let mut index = 0;
-            for line in body.lines() {
-                let line = line.trim();
-                if line.is_empty() || line.starts_with("//") {
-                    continue;
-                }
-
-                if let Some((key, val)) = line.split_once('=') {
-                    let key = key.trim().to_string();
-                    let val = val.trim().trim_end_matches(';').trim().to_string();
-                    values.push((key, val));
-                    index += 1;
-                } else {
-                    let key = line.trim_end_matches(';').trim().to_string();
-                    if !key.is_empty() {
-                        values.push((key, index.to_string()));
-                        index += 1;
-                    }
-                }
-            }
-
-            self.enums.push(EnumDef { name, base_type, values });
-        }
-
-        Ok(())
-    }
-
-    fn parse_flags(&mut self, content: &str) -> Result<(), String> {
-        let re = Regex::new(r"flags\s+(\w+)\s*:\s*(\w+)\s*\{([^}]+)\}").unwrap();
-        
-        for cap in re.captures_iter(content) {
-            let name = cap[1].to_string();
-            let base_type = cap[2].to_string();
-            let body = &cap[3];
-
-            let mut values = Vec::new();
-            for line in body.lines() {
-                let line = line.trim();
-                if line.is_empty() || line.starts_with("//") {
-                    continue;
-                }
-
-                if let Some((key, val)) = line.split_once('=') {
-                    let key = key.trim().to_string();
-                    let val = val.trim().trim_end_matches(';').trim().to_string();
-                    values.push((key, val));
-                }
-            }
-
-            self.flags.push(FlagsDef { name, base_type, values });
-        }
-
-        Ok(())
-    }
-
-    fn parse_structs(&mut self, content: &str) -> Result<(), String> {
-        let re = Regex::new(r"struct\s+(\w+)(?:\(\d+\))?\s*\{([^}]+)\}").unwrap();
-        
-        for cap in re.captures_iter(content) {
-            let name = cap[1].to_string();
-            let body = &cap[2];
-
-            let mut fields = Vec::new();
-            let field_re = Regex::new(r"(\w+)(\?)?(\[\])?\s+(\w+)").unwrap();
-
-            for line in body.lines() {
-                let line = line.trim();
-                if line.is_empty() || line.starts_with("//") || line.starts_with("[") {
-                    continue;
-                }
-
-                if let Some(cap) = field_re.captures(line) {
-                    let fbe_type = cap[1].to_string();
-                    let is_optional = cap.get(2).is_some();
-                    let is_array = cap.get(3).is_some();
-                    let name = cap[4].to_string();
-
-                    fields.push(FieldDef {
-                        name,
-                        fbe_type,
-                        is_optional,
-                        is_array,
-                    });
-                }
-            }
-
-            self.structs.push(StructDef { name, fields });
-        }
-
-        Ok(())
-    }
-
-    fn generate_code(&self, output_dir: &str) -> Result<(), String> {
-        for enum_def in &self.enums {
-            self.generate_enum(enum_def, output_dir)?;
-        }
-
-        for flags_def in &self.flags {
-            self.generate_flags(flags_def, output_dir)?;
-        }
-
-        for struct_def in &self.structs {
-            self.generate_struct(struct_def, output_dir)?;
-            self.generate_model(struct_def, output_dir)?;
-            self.generate_final_model(struct_def, output_dir)?;
-        }
-
-        self.generate_mod_file(output_dir)?;
-        Ok(())
-    }
-
-    fn generate_enum(&self, enum_def: &EnumDef, output_dir: &str) -> Result<(), String> {
-        let file_name = format!("{}/{}.rs", output_dir, to_snake_case(&enum_def.name));
-        let rust_type = map_fbe_type(&enum_def.base_type);
-
-        let mut code = format!("//! {} enum\n\n", enum_def.name);
-        code.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq)]\n");
-        code.push_str(&format!("#[repr({})]\n", rust_type));
-        code.push_str(&format!("pub enum {} {{\n", enum_def.name));
-        
-        for (key, _) in &enum_def.values {
-            code.push_str(&format!("    {},\n", to_pascal_case(&escape_keyword(key))));
-        }
-        code.push_str("}\n");
-
-        fs::write(&file_name, code)
-            .map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
-        Ok(())
-    }
-
-    fn generate_flags(&self, flags_def: &FlagsDef, output_dir: &str) -> Result<(), String> {
-        let file_name = format!("{}/{}.rs", output_dir, to_snake_case(&flags_def.name));
-        let rust_type = map_fbe_type(&flags_def.base_type);
-
-        let mut code = format!("//! {} flags\n\n", flags_def.name);
-        for (key, val) in &flags_def.values {
-            let const_name = to_screaming_snake_case(&escape_keyword(key));
-            code.push_str(&format!("pub const {}: {} = {};\n", const_name, rust_type, val));
-        }
-
-        fs::write(&file_name, code)
-            .map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
-        Ok(())
-    }
-
-    fn generate_struct(&self, struct_def: &StructDef, output_dir: &str) -> Result<(), String> {
-        let file_name = format!("{}/{}.rs", output_dir, to_snake_case(&struct_def.name));
-
-        let mut code = format!("//! {} struct\n\n", struct_def.name);
-        code.push_str("use crate::buffer::{{WriteBuffer, ReadBuffer}};\n");
-        code.push_str("use serde::{{Serialize, Deserialize}};\n");
-        code.push_str("use std::fmt;\n\n");
-        code.push_str("#[derive(Debug, Clone, Default, Serialize, Deserialize)]\n");
-        code.push_str(&format!("pub struct {} {{\n", struct_def.name));
-        
-        for field in &struct_def.fields {
-            let rust_type = self.map_field_type(field);
-            code.push_str(&format!("    pub {}: {},\n", field.name, rust_type));
-        }
-        code.push_str("}\n\n");
-
-        // Add serialization methods
-        code.push_str(&format!("impl {} {{\n", struct_def.name));
-        
-        // Serialize method
-        code.push_str("    pub fn serialize(&self, buffer: &mut WriteBuffer) -> usize {\n");
-        code.push_str("        let mut offset = 0;\n");
-        for field in &struct_def.fields {
-            code.push_str(&self.generate_serialize_field(field));
-        }
-        code.push_str("        offset\n");
-        code.push_str("    }\n\n");
-
-        // Deserialize method
-        code.push_str("    pub fn deserialize(buffer: &ReadBuffer) -> Self {\n");
-        code.push_str("        let mut offset = 0;\n");
-        code.push_str("        Self {\n");
-        for field in &struct_def.fields {
-            code.push_str(&self.generate_deserialize_field(field));
-        }
-        code.push_str("        }\n");
-        code.push_str("    }\n\n");
-
-        // Add JSON serialization methods
-        code.push_str("    /// Convert struct to JSON string\n");
-        code.push_str("    pub fn to_json(&self) -> String {\n");
-        code.push_str("        serde_json::to_string(self).unwrap()\n");
-        code.push_str("    }\n\n");
-
-        code.push_str("    /// Create struct from JSON string\n");
-        code.push_str("    pub fn from_json(json: &str) -> Self {\n");
-        code.push_str("        serde_json::from_str(json).unwrap()\n");
-        code.push_str("    }\n");
-        code.push_str("}\n\n");
-
-        // Add Display trait for logging
-        code.push_str(&format!("impl fmt::Display for {} {{\n", struct_def.name));
-        code.push_str("    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {\n");
-        code.push_str(&format!("        write!(f, \"{}(", struct_def.name));
-        for (i, field) in struct_def.fields.iter().enumerate() {
-            if i > 0 {
-                code.push_str(", ");
-            }
-            code.push_str(&format!("{}={{:?}}", field.name));
-        }
-        code.push_str(")\"\n");
-        for field in &struct_def.fields {
-            code.push_str(&format!("            , self.{}\n", field.name));
-        }
-        code.push_str("        )\n");
-        code.push_str("    }\n");
-        code.push_str("}\n");
-
-        fs::write(&file_name, code)
-            .map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
-        Ok(())
-    }
-
-    fn generate_serialize_field(&self, field: &FieldDef) -> String {
-        let write_method = get_write_method(&field.fbe_type);
-        let size = get_type_size(&field.fbe_type);
-        
-        if field.fbe_type == "string" {
-            format!("        buffer.write_string(offset, &self.{});\n        offset += 4 + self.{}.len();\n", field.name, field.name)
-        } else {
-            format!("        buffer.{}(offset, self.{});\n        offset += {};\n", write_method, field.name, size)
-        }
-    }
+    let mut includes = Vec::new();
+    let mut positional = Vec::new();
 
-    fn generate_deserialize_field(&self, field: &FieldDef) -> String {
-        let read_method = get_read_method(&field.fbe_type);
-        
-        if field.fbe_type == "string" {
-            format!("            {}: {{ let val = buffer.{}(offset); offset += 4 + val.len(); val }},\n", field.name, read_method)
-        } else {
-            let size = get_type_size(&field.fbe_type);
-            format!("            {}: {{ let val = buffer.{}(offset); offset += {}; val }},\n", field.name, read_method, size)
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-I" | "--include" => match args.next() {
+                Some(dir) => includes.push(dir),
+                None => usage(),
+            },
+            _ => positional.push(arg),
         }
     }
 
-    fn map_field_type(&self, field: &FieldDef) -> String {
-        let base_type = map_fbe_type(&field.fbe_type);
-        
-        if field.is_array {
-            if field.is_optional {
-                format!("Option<Vec<{}>>", base_type)
-            } else {
-                format!("Vec<{}>", base_type)
-            }
-        } else if field.is_optional {
-            format!("Option<{}>", base_type)
-        } else {
-            base_type
-        }
-    }
-
-    fn generate_model(&self, struct_def: &StructDef, output_dir: &str) -> Result<(), String> {
-        let file_name = format!("{}/{}_model.rs", output_dir, to_snake_case(&struct_def.name));
-
-        let mut code = format!("//! {} Model (FieldModel-based)\n\n", struct_def.name);
-        code.push_str("use crate::buffer::{{WriteBuffer, ReadBuffer}};\n");
-        code.push_str("use crate::field_model::*;\n\n");
-
-        // Struct definition
-        code.push_str("#[derive(Debug)]\n");
-        code.push_str(&format!("pub struct {}Model<'a> {{\n", struct_def.name));
-        code.push_str("    buffer: &'a [u8],\n");
-        code.push_str("    offset: usize,\n");
-        
-        // Field models
-        for field in &struct_def.fields {
-            let field_model_type = self.get_field_model_type(&field.fbe_type);
-            code.push_str(&format!("    {}: {},\n", field.name, field_model_type));
-        }
-        code.push_str("}\n\n");
-
-        // Mutable struct definition
-        code.push_str("#[derive(Debug)]\n");
-        code.push_str(&format!("pub struct {}ModelMut<'a> {{\n", struct_def.name));
-        code.push_str("    buffer: &'a mut WriteBuffer,\n");
-        code.push_str("    offset: usize,\n");
-        code.push_str("}\n\n");
-
-        // Read-only implementation
-        code.push_str(&format!("impl<'a> {}Model<'a> {{\n", struct_def.name));
-        code.push_str("    pub fn new(buffer: &'a [u8], offset: usize) -> Self {\n");
-        code.push_str("        Self {\n");
-        code.push_str("            buffer,\n");
-        code.push_str("            offset,\n");
-        
-        let mut field_offset = 0;
-        for field in &struct_def.fields {
-            let field_model_type = self.get_field_model_type(&field.fbe_type);
-            code.push_str(&format!("            {}: {}::new(buffer, offset + {}),\n", 
-                field.name, field_model_type, field_offset));
-            field_offset += self.get_field_size(&field.fbe_type);
-        }
-        
-        code.push_str("        }\n");
-        code.push_str("    }\n\n");
-
-        // Get method
-        code.push_str(&format!("    pub fn get(&self) -> {} {{\n", struct_def.name));
-        code.push_str(&format!("        {} {{\n", struct_def.name));
-        for field in &struct_def.fields {
-            code.push_str(&format!("            {}: self.{}.get(),\n", field.name, field.name));
-        }
-        code.push_str("        }\n");
-        code.push_str("    }\n");
-        code.push_str("}\n\n");
-
-        // Mutable implementation
-        code.push_str(&format!("impl<'a> {}ModelMut<'a> {{\n", struct_def.name));
-        code.push_str("    pub fn new(buffer: &'a mut WriteBuffer, offset: usize) -> Self {\n");
-        code.push_str("        Self { buffer, offset }\n");
-        code.push_str("    }\n\n");
-
-        // Set method
-        code.push_str(&format!("    pub fn set(&mut self, value: &{}) {{\n", struct_def.name));
-        let mut field_offset = 0;
-        for field in &struct_def.fields {
-            let field_model_type_mut = self.get_field_model_type_mut(&field.fbe_type);
-            code.push_str(&format!("        let mut field_{} = {}::new(self.buffer, self.offset + {});\n",
-                field.name, field_model_type_mut, field_offset));
-            code.push_str(&format!("        field_{}.set(value.{});\n", field.name, field.name));
-            field_offset += self.get_field_size(&field.fbe_type);
-        }
-        code.push_str("    }\n");
-        code.push_str("}\n");
-
-        fs::write(&file_name, code)
-            .map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
-        Ok(())
+    if positional.len() < 2 {
+        usage();
     }
 
-    fn generate_final_model(&self, struct_def: &StructDef, output_dir: &str) -> Result<(), String> {
-        let file_name = format!("{}/{}_final_model.rs", output_dir, to_snake_case(&struct_def.name));
-
-        let mut code = format!("//! {} FinalModel (inline format)\n\n", struct_def.name);
-        code.push_str("use crate::buffer::{{WriteBuffer, ReadBuffer}};\n");
-        code.push_str("use crate::final_model::*;\n\n");
-
-        // Struct definition
-        code.push_str("#[derive(Debug)]\n");
-        code.push_str(&format!("pub struct {}FinalModel<'a> {{\n", struct_def.name));
-        code.push_str("    buffer: &'a [u8],\n");
-        code.push_str("    offset: usize,\n");
-        code.push_str("}\n\n");
-
-        // Mutable struct definition
-        code.push_str("#[derive(Debug)]\n");
-        code.push_str(&format!("pub struct {}FinalModelMut<'a> {{\n", struct_def.name));
-        code.push_str("    buffer: &'a mut WriteBuffer,\n");
-        code.push_str("    offset: usize,\n");
-        code.push_str("}\n\n");
-
-        // Read-only implementation
-        code.push_str(&format!("impl<'a> {}FinalModel<'a> {{\n", struct_def.name));
-        code.push_str("    pub fn new(buffer: &'a [u8], offset: usize) -> Self {\n");
-        code.push_str("        Self { buffer, offset }\n");
-        code.push_str("    }\n\n");
+    let input_file = &positional[0];
+    let output_dir = &positional[1];
 
-        // Get method
-        code.push_str(&format!("    pub fn get(&self) -> {} {{\n", struct_def.name));
-        code.push_str("        let mut offset = self.offset;\n");
-        code.push_str(&format!("        {} {{\n", struct_def.name));
-        for field in &struct_def.fields {
-            let final_model_type = self.get_final_model_type(&field.fbe_type);
-            code.push_str(&format!("            {}: {{\n", field.name));
-            code.push_str(&format!("                let model = {}::new(self.buffer, offset);\n", final_model_type));
-            code.push_str("                let value = model.get();\n");
-            code.push_str("                offset += model.size();\n");
-            code.push_str("                value\n");
-            code.push_str("            },\n");
-        }
-        code.push_str("        }\n");
-        code.push_str("    }\n\n");
-
-        // Size method
-        code.push_str("    pub fn size(&self) -> usize {\n");
-        code.push_str("        let mut size = 0;\n");
-        code.push_str("        let mut offset = self.offset;\n");
-        for field in &struct_def.fields {
-            let final_model_type = self.get_final_model_type(&field.fbe_type);
-            code.push_str(&format!("        let model_{} = {}::new(self.buffer, offset);\n",
-                field.name, final_model_type));
-            code.push_str(&format!("        let field_size = model_{}.size();\n", field.name));
-            code.push_str("        size += field_size;\n");
-            code.push_str("        offset += field_size;\n");
-        }
-        code.push_str("        size\n");
-        code.push_str("    }\n");
-        code.push_str("}\n\n");
-
-        // Mutable implementation
-        code.push_str(&format!("impl<'a> {}FinalModelMut<'a> {{\n", struct_def.name));
-        code.push_str("    pub fn new(buffer: &'a mut WriteBuffer, offset: usize) -> Self {\n");
-        code.push_str("        Self { buffer, offset }\n");
-        code.push_str("    }\n\n");
-
-        // Set method
-        code.push_str(&format!("    pub fn set(&mut self, value: &{}) {{\n", struct_def.name));
-        code.push_str("        let mut offset = self.offset;\n");
-        for field in &struct_def.fields {
-            let final_model_type_mut = self.get_final_model_type_mut(&field.fbe_type);
-            code.push_str(&format!("        {{\n"));
-            code.push_str(&format!("            let mut model = {}::new(self.buffer, offset);\n", final_model_type_mut));
-            code.push_str(&format!("            model.set(value.{});\n", field.name));
-            code.push_str("            offset += model.size();\n");
-            code.push_str("        }\n");
-        }
-        code.push_str("    }\n");
-        code.push_str("}\n");
-
-        fs::write(&file_name, code)
-            .map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
-        Ok(())
-    }
-
-    fn get_field_model_type(&self, fbe_type: &str) -> String {
-        match fbe_type {
-            "bool" => "FieldModelBool<'a>".to_string(),
-            "int8" => "FieldModelI8<'a>".to_string(),
-            "uint8" => "FieldModelU8<'a>".to_string(),
-            "int16" => "FieldModelI16<'a>".to_string(),
-            "uint16" => "FieldModelU16<'a>".to_string(),
-            "int32" => "FieldModelI32<'a>".to_string(),
-            "uint32" => "FieldModelU32<'a>".to_string(),
-            "int64" => "FieldModelI64<'a>".to_string(),
-            "uint64" => "FieldModelU64<'a>".to_string(),
-            "float" => "FieldModelF32<'a>".to_string(),
-            "double" => "FieldModelF64<'a>".to_string(),
-            "string" => "FieldModelString<'a>".to_string(),
-            _ => format!("{}Model<'a>", fbe_type),
-        }
+    let mut config = fbec::Config::new();
+    config
+        .out_dir(output_dir)
+        .runtime_path("crate")
+        .rerun_if_changed(false)
+        .path_attributes(false);
+    for dir in &includes {
+        config.include(dir);
     }
 
-    fn get_field_model_type_mut(&self, fbe_type: &str) -> String {
-        match fbe_type {
-            "bool" => "FieldModelBoolMut".to_string(),
-            "int8" => "FieldModelI8Mut".to_string(),
-            "uint8" => "FieldModelU8Mut".to_string(),
-            "int16" => "FieldModelI16Mut".to_string(),
-            "uint16" => "FieldModelU16Mut".to_string(),
-            "int32" => "FieldModelI32Mut".to_string(),
-            "uint32" => "FieldModelU32Mut".to_string(),
-            "int64" => "FieldModelI64Mut".to_string(),
-            "uint64" => "FieldModelU64Mut".to_string(),
-            "float" => "FieldModelF32Mut".to_string(),
-            "double" => "FieldModelF64Mut".to_string(),
-            "string" => "FieldModelStringMut".to_string(),
-            _ => format!("{}ModelMut", fbe_type),
-        }
-    }
-
-    fn get_final_model_type(&self, fbe_type: &str) -> String {
-        match fbe_type {
-            "bool" => "FinalModelBool<'a>".to_string(),
-            "int8" => "FinalModelI8<'a>".to_string(),
-            "uint8" => "FinalModelU8<'a>".to_string(),
-            "int16" => "FinalModelI16<'a>".to_string(),
-            "uint16" => "FinalModelU16<'a>".to_string(),
-            "int32" => "FinalModelI32<'a>".to_string(),
-            "uint32" => "FinalModelU32<'a>".to_string(),
-            "int64" => "FinalModelI64<'a>".to_string(),
-            "uint64" => "FinalModelU64<'a>".to_string(),
-            "float" => "FinalModelF32<'a>".to_string(),
-            "double" => "FinalModelF64<'a>".to_string(),
-            "string" => "FinalModelString<'a>".to_string(),
-            _ => format!("{}FinalModel<'a>", fbe_type),
-        }
-    }
-
-    fn get_final_model_type_mut(&self, fbe_type: &str) -> String {
-        match fbe_type {
-            "bool" => "FinalModelBoolMut".to_string(),
-            "int8" => "FinalModelI8Mut".to_string(),
-            "uint8" => "FinalModelU8Mut".to_string(),
-            "int16" => "FinalModelI16Mut".to_string(),
-            "uint16" => "FinalModelU16Mut".to_string(),
-            "int32" => "FinalModelI32Mut".to_string(),
-            "uint32" => "FinalModelU32Mut".to_string(),
-            "int64" => "FinalModelI64Mut".to_string(),
-            "uint64" => "FinalModelU64Mut".to_string(),
-            "float" => "FinalModelF32Mut".to_string(),
-            "double" => "FinalModelF64Mut".to_string(),
-            "string" => "FinalModelStringMut".to_string(),
-            _ => format!("{}FinalModelMut", fbe_type),
-        }
-    }
-
-    fn get_field_size(&self, fbe_type: &str) -> usize {
-        match fbe_type {
-            "bool" | "int8" | "uint8" => 1,
-            "int16" | "uint16" => 2,
-            "int32" | "uint32" | "float" => 4,
-            "int64" | "uint64" | "double" => 8,
-            "string" => 4, // Pointer
-            _ => 4, // Default pointer size
-        }
-    }
-
-    fn generate_mod_file(&self, output_dir: &str) -> Result<(), String> {
-        let mut code = String::from("//! Generated FBE modules\n\n");
-
-        for enum_def in &self.enums {
-            code.push_str(&format!("pub mod {};\n", to_snake_case(&enum_def.name)));
-        }
-        for flags_def in &self.flags {
-            code.push_str(&format!("pub mod {};\n", to_snake_case(&flags_def.name)));
-        }
-        for struct_def in &self.structs {
-            code.push_str(&format!("pub mod {};\n", to_snake_case(&struct_def.name)));
-        }
-
-        fs::write(format!("{}/mod.rs", output_dir), code)
-            .map_err(|e| format!("Failed to write mod.rs: {}", e))?;
-        Ok(())
-    }
-}
-
-fn map_fbe_type(fbe_type: &str) -> String {
-    match fbe_type {
-        "bool" => "bool",
-        "byte" | "int8" => "i8",
-        "uint8" => "u8",
-        "int16" => "i16",
-        "uint16" => "u16",
-        "int32" => "i32",
-        "uint32" => "u32",
-        "int64" => "i64",
-        "uint64" => "u64",
-        "float" => "f32",
-        "double" => "f64",
-        "string" => "String",
-        _ => fbe_type,
-    }.to_string()
-}
-
-fn to_snake_case(s: &str) -> String {
-    let mut result = String::new();
-    for (i, ch) in s.chars().enumerate() {
-        if ch.is_uppercase() && i > 0 {
-            result.push('_');
+    match config.compile(&[input_file]) {
+        Ok(_) => println!("✓ Code generation successful!"),
+        Err(e) => {
+            eprintln!("✗ Error: {}", e);
+            std::process::exit(1);
         }
-        result.push(ch.to_lowercase().next().unwrap());
     }
-    result
-}
-
-fn to_pascal_case(s: &str) -> String {
-    s.chars().next().unwrap().to_uppercase().to_string() + &s[1..]
-}
-
-fn to_screaming_snake_case(s: &str) -> String {
-    to_snake_case(s).to_uppercase()
 }
 
-fn escape_keyword(s: &str) -> String {
-    match s {
-        "type" | "match" | "loop" | "move" | "ref" | "self" | "Self" => format!("r#{}", s),
-        _ => s.to_string()
-    }
-}
-
-
-fn get_write_method(fbe_type: &str) -> String {
-    match fbe_type {
-        "bool" => "write_bool",
-        "byte" | "int8" => "write_i8",
-        "uint8" => "write_u8",
-        "int16" => "write_i16",
-        "uint16" => "write_u16",
-        "int32" => "write_i32",
-        "uint32" => "write_u32",
-        "int64" => "write_i64",
-        "uint64" => "write_u64",
-        "float" => "write_f32",
-        "double" => "write_f64",
-        "string" => "write_string",
-        _ => "write_i32",
-    }.to_string()
-}
-
-fn get_read_method(fbe_type: &str) -> String {
-    match fbe_type {
-        "bool" => "read_bool",
-        "byte" | "int8" => "read_i8",
-        "uint8" => "read_u8",
-        "int16" => "read_i16",
-        "uint16" => "read_u16",
-        "int32" => "read_i32",
-        "uint32" => "read_u32",
-        "int64" => "read_i64",
-        "uint64" => "read_u64",
-        "float" => "read_f32",
-        "double" => "read_f64",
-        "string" => "read_string",
-        _ => "read_i32",
-    }.to_string()
-}
-
-fn get_type_size(fbe_type: &str) -> usize {
-    match fbe_type {
-        "bool" | "byte" | "int8" | "uint8" => 1,
-        "int16" | "uint16" => 2,
-        "int32" | "uint32" | "float" => 4,
-        "int64" | "uint64" | "double" => 8,
-        _ => 4,
-    }
+fn usage() -> ! {
+    eprintln!("Usage: fbec [-I <include_dir>]... <input.fbe> <output_dir>");
+    std::process::exit(1);
 }
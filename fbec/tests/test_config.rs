@@ -0,0 +1,255 @@
+//! Library API tests
+
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("fbec_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_compile_with_imports() {
+    let dir = temp_dir("imports");
+    let schemas = dir.join("schemas");
+    let out = dir.join("out");
+    fs::create_dir_all(schemas.join("shared")).unwrap();
+    fs::write(
+        schemas.join("shared").join("common.fbe"),
+        "package common;\n\nenum Side : byte\n{\n    Buy;\n    Sell;\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        schemas.join("trade.fbe"),
+        "package trade;\n\nimport common;\n\nstruct Trade\n{\n    int32 id;\n    string symbol;\n}\n",
+    )
+    .unwrap();
+
+    fbec::Config::new()
+        .include(&schemas)
+        .include(schemas.join("shared"))
+        .out_dir(&out)
+        .rerun_if_changed(false)
+        .compile(&["trade.fbe"])
+        .unwrap();
+
+    assert!(out.join("side.rs").exists());
+    let trade = fs::read_to_string(out.join("trade.rs")).unwrap();
+    assert!(trade.contains("use fbe::buffer::"));
+    assert!(trade.contains("impl fbe::sender::Serialize for Trade"));
+
+    let module = fs::read_to_string(out.join("mod.rs")).unwrap();
+    assert!(module.contains(&format!("#[path = {:?}]", out.join("side.rs").display().to_string())));
+    assert!(module.contains("pub mod trade;"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_compile_plain_modules() {
+    let dir = temp_dir("plain");
+    fs::write(dir.join("order.fbe"), "struct Order\n{\n    int32 id;\n}\n").unwrap();
+
+    fbec::Config::new()
+        .out_dir(dir.join("out"))
+        .runtime_path("crate")
+        .rerun_if_changed(false)
+        .path_attributes(false)
+        .compile(&[dir.join("order.fbe")])
+        .unwrap();
+
+    let module = fs::read_to_string(dir.join("out").join("mod.rs")).unwrap();
+    assert_eq!(module, "//! Generated FBE modules\n\npub mod order;\n");
+    let order = fs::read_to_string(dir.join("out").join("order.rs")).unwrap();
+    assert!(order.contains("use crate::buffer::"));
+
+    // Model and FinalModel accessors live in the struct module, next to the struct
+    let mut files: Vec<_> = fs::read_dir(dir.join("out"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    assert_eq!(files, ["mod.rs", "order.rs"]);
+    assert!(order.contains("impl crate::field_model::FieldStruct for Order {\n    const FBE_TYPE: usize = 0;\n    const FIELDS_SIZE: usize = 4;\n"));
+    assert!(order.contains("pub struct OrderModel<'a> {"));
+    assert!(order.contains("crate::field_model::write_model(value, self.buffer, self.offset)"));
+    assert!(order.contains("pub struct OrderFinalModelMut<'a> {"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_compile_missing_import() {
+    let dir = temp_dir("missing");
+    fs::write(dir.join("main.fbe"), "import missing;\n").unwrap();
+
+    let err = fbec::Config::new()
+        .out_dir(dir.join("out"))
+        .rerun_if_changed(false)
+        .compile(&[dir.join("main.fbe")])
+        .unwrap_err();
+    assert!(err.contains("missing.fbe"), "{}", err);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_compile_json_conventions() {
+    let dir = temp_dir("json");
+    fs::write(
+        dir.join("blob.fbe"),
+        "enum Kind : int32\n{\n    Small = 1;\n    Large;\n}\n\nstruct Blob\n{\n    bytes data;\n    uuid? owner;\n    decimal[] prices;\n    timestamp created;\n    char initial;\n    wchar letter;\n    bytes?[] chunks;\n    wchar?[] letters;\n}\n",
+    )
+    .unwrap();
+
+    fbec::Config::new()
+        .out_dir(dir.join("out"))
+        .rerun_if_changed(false)
+        .compile(&[dir.join("blob.fbe")])
+        .unwrap();
+
+    let kind = fs::read_to_string(dir.join("out").join("kind.rs")).unwrap();
+    assert!(kind.contains("    Small = 1,\n    Large,\n"));
+    assert!(kind.contains("#[cfg(feature = \"json\")]\nimpl serde::Serialize for Kind"));
+    assert!(kind.contains("serde::Serialize::serialize(&(*self as i32), serializer)"));
+
+    let blob = fs::read_to_string(dir.join("out").join("blob.rs")).unwrap();
+    assert!(blob.contains("#[cfg_attr(feature = \"json\", serde(with = \"fbe::json::bytes\"))]\n    pub data: Vec<u8>,"));
+    assert!(blob.contains("    pub owner: Option<fbe::Uuid>,"));
+    assert!(blob.contains("    pub prices: Vec<fbe::Decimal>,"));
+    assert!(blob.contains("    pub created: fbe::Timestamp,"));
+    assert!(blob.contains("#[cfg_attr(feature = \"json\", serde(with = \"fbe::json::char\"))]\n    pub initial: char,"));
+    assert!(blob.contains("#[cfg_attr(feature = \"json\", serde(with = \"fbe::json::wchar\"))]\n    pub letter: char,"));
+    assert!(blob.contains("#[cfg_attr(feature = \"json\", serde(with = \"fbe::json::bytes::option_vec\"))]\n    pub chunks: Option<Vec<Vec<u8>>>,"));
+    assert!(blob.contains("#[cfg_attr(feature = \"json\", serde(with = \"fbe::json::wchar::option_vec\"))]\n    pub letters: Option<Vec<char>>,"));
+    assert!(blob.contains("        fbe::chars::encode_char(self.initial)?;\n"));
+    assert!(blob.contains("        fbe::chars::latin1::write_vectored(&self.initial, writer);\n        fbe::final_model::FinalValue::write_vectored(&self.letter, writer);\n"));
+    assert!(blob.contains("    fn check_chars(&self) -> Result<(), fbe::chars::CharError> {\n        Self::check_chars(self)\n"));
+    assert!(blob.contains("buffer.write_u8(offset, fbe::chars::encode_char(self.initial).unwrap_or(b'?'));"));
+    assert!(blob.contains("let val = buffer.read_wchar(offset)?;"));
+    assert!(blob.contains("        offset += fbe::final_model::FinalValue::write_final(&self.owner, buffer, offset);\n"));
+    assert!(blob.contains("let (val, size) = fbe::final_model::FinalValue::try_read_final(buffer, offset, budget)?;"));
+    assert!(blob.contains("pub fn try_deserialize(buffer: &ReadBuffer, limits: &fbe::limits::DecodeLimits) -> Result<Self, fbe::limits::DecodeError>"));
+    assert!(blob.contains("let val = buffer.try_read_bytes(offset, budget)?;"));
+    assert!(blob.contains("        fbe::chars::latin1::write_field(&self.initial, buffer, body, offset + 21);\n"));
+    assert!(blob.contains("        <char as fbe::field_model::FieldValue>::write_field(&self.letter, buffer, body, offset + 22);\n"));
+    assert!(blob.contains("let val = buffer.read_bytes(offset); offset += 4 + buffer.read_u32(offset) as usize; val"));
+    assert!(blob.contains("#[cfg_attr(feature = \"json\", derive(serde::Serialize, serde::Deserialize))]\npub struct Blob"));
+    assert!(blob.contains("#[cfg(feature = \"json\")]\n    pub fn from_json(json: &str) -> fbe::json::Result<Self>"));
+    assert!(blob.contains("#[cfg(feature = \"json\")]\nimpl std::fmt::Display for Blob"));
+    assert!(blob.contains("#[cfg(feature = \"json\")]\n    fn log_json(&self)"));
+    assert!(!blob.contains("use serde"));
+    assert!(blob.contains("#[derive(Debug, Clone, PartialEq, Default)]\n"));
+    assert!(blob.contains("#[cfg(feature = \"proptest\")]\nimpl fbe::proptest::Arbitrary for Blob"));
+    assert!(blob.contains("fbe::proptest::any::<u8>().prop_map(char::from)"));
+    assert!(blob.contains("strategy.prop_map(|(data, (owner, (prices, (created, (initial, (letter, (chunks, (letters, ()))))))))| Self { data, owner, prices, created, initial, letter, chunks, letters }).boxed()"));
+    assert!(kind.contains("#[cfg(feature = \"proptest\")]\nimpl fbe::proptest::Arbitrary for Kind"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_compile_enum_and_struct_fields() {
+    let dir = temp_dir("nested");
+    fs::write(
+        dir.join("order.fbe"),
+        "enum OrderSide : byte\n{\n    Buy;\n    Sell;\n}\n\nstruct Leg\n{\n    char tag;\n}\n\nstruct Order\n{\n    OrderSide side = OrderSide.Sell;\n    OrderSide? hedge;\n    Leg leg;\n    Leg[] legs;\n}\n",
+    )
+    .unwrap();
+
+    fbec::Config::new()
+        .out_dir(dir.join("out"))
+        .rerun_if_changed(false)
+        .compile(&[dir.join("order.fbe")])
+        .unwrap();
+
+    let side = fs::read_to_string(dir.join("out").join("order_side.rs")).unwrap();
+    assert!(side.contains("impl Default for OrderSide {\n    fn default() -> Self {\n        Self::Buy\n"));
+    assert!(side.contains("impl TryFrom<i8> for OrderSide {"));
+    assert!(side.contains("impl fbe::final_model::FinalValue for OrderSide {"));
+    assert!(side.contains("(Self::try_from(value).unwrap_or_default(), size)"));
+
+    let order = fs::read_to_string(dir.join("out").join("order.rs")).unwrap();
+    assert!(order.contains("use super::leg::Leg;\nuse super::order_side::OrderSide;\n"));
+    assert!(order.contains("    pub side: OrderSide,\n    pub hedge: Option<OrderSide>,\n    pub leg: Leg,\n    pub legs: Vec<Leg>,\n"));
+    assert!(order.contains("            side: OrderSide::Sell,\n"));
+    assert!(order.contains("        offset += fbe::final_model::FinalValue::write_final(&self.leg, buffer, offset);\n"));
+    assert!(order.contains("        offset += <Vec<Leg> as fbe::final_model::FinalValue>::read_final_size(buffer, offset);\n"));
+    assert!(order.contains("        self.leg.check_chars()?;\n        for value in &self.legs {\n            value.check_chars()?;\n"));
+    assert!(order.contains("impl fbe::final_model::FinalValue for Order {"));
+    assert!(order.contains("        budget.enter()?;\n"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_compile_unsupported_fields() {
+    let dir = temp_dir("unsupported");
+    for (name, field) in [
+        ("unknown", "Side side;"),
+        ("flags", "Mask mask;"),
+        ("char_array", "char[] letters;"),
+        ("char_optional", "char? initial;"),
+    ] {
+        let schema = dir.join(format!("{}.fbe", name));
+        fs::write(&schema, format!("flags Mask : byte\n{{\n    A = 0x01;\n}}\n\nstruct Order\n{{\n    {}\n}}\n", field)).unwrap();
+
+        let err = fbec::Config::new()
+            .out_dir(dir.join("out"))
+            .rerun_if_changed(false)
+            .compile(&[schema])
+            .unwrap_err();
+        assert!(err.starts_with("Order."), "{}", err);
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_compile_empty_enum() {
+    let dir = temp_dir("empty_enum");
+    fs::write(dir.join("side.fbe"), "enum Side : byte\n{\n    // none\n}\n").unwrap();
+
+    let err = fbec::Config::new()
+        .out_dir(dir.join("out"))
+        .rerun_if_changed(false)
+        .compile(&[dir.join("side.fbe")])
+        .unwrap_err();
+    assert_eq!(err, "Side: enum has no values");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_compile_default_values() {
+    let dir = temp_dir("defaults");
+    fs::write(
+        dir.join("account.fbe"),
+        "struct Account\n{\n    uuid id = uuid1;\n    uuid? parent = null;\n    uuid session = uuid4();\n    string name = \"guest\";\n    decimal balance = 99.95;\n    timestamp created = utc;\n    int32 level;\n}\n",
+    )
+    .unwrap();
+
+    fbec::Config::new()
+        .out_dir(dir.join("out"))
+        .rerun_if_changed(false)
+        .compile(&[dir.join("account.fbe")])
+        .unwrap();
+
+    let account = fs::read_to_string(dir.join("out").join("account.rs")).unwrap();
+    assert!(account.contains("#[derive(Debug, Clone, PartialEq)]\n"));
+    assert!(account.contains("impl Default for Account {"));
+    assert!(account.contains("            id: fbe::Uuid::v1(),\n"));
+    assert!(account.contains("            parent: None,\n"));
+    assert!(account.contains("            session: fbe::Uuid::v4(),\n"));
+    assert!(account.contains("            name: String::from(\"guest\"),\n"));
+    assert!(account.contains("            balance: \"99.95\".parse::<fbe::Decimal>().unwrap(),\n"));
+    assert!(account.contains("            created: fbe::Timestamp::utc(),\n"));
+    assert!(account.contains("            level: Default::default(),\n"));
+    assert!(account.contains("    pub fn size_hint(&self) -> usize {\n        64 + fbe::final_model::FinalValue::final_size(&self.parent) + self.name.len()\n    }"));
+    assert!(account.contains("        buffer.reserve(buffer.offset() + self.size_hint());\n"));
+    assert!(account.contains("        buffer.set_size(buffer.offset() + size);\n        size\n"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,45 @@
+//! `Decimal` binary and text decoding
+#![no_main]
+
+use fbe::buffer::ReadBuffer;
+use fbe::decimal::RoundingMode;
+use fbe::Decimal;
+use fbe_fuzz::roundtrip;
+use libfuzzer_sys::fuzz_target;
+
+fn check(value: Decimal) {
+    assert_eq!(Decimal::from_bytes(value.to_bytes()).ok(), Some(value));
+    assert_eq!(value.to_string().parse::<Decimal>().ok(), Some(value));
+    assert_eq!(value.normalize(), value);
+    let _ = value.round_dp(2, RoundingMode::HalfEven);
+    let _ = value.to_f64();
+}
+
+fuzz_target!(|data: &[u8]| {
+    // Strict 16-byte .NET layout
+    let values: Vec<Decimal> = data
+        .chunks_exact(16)
+        .filter_map(|chunk| Decimal::from_bytes(chunk.try_into().unwrap()).ok())
+        .collect();
+    for &value in &values {
+        check(value);
+    }
+    if let [a, b, ..] = values[..] {
+        let _ = a.checked_add(b);
+        let _ = a.checked_sub(b);
+        let _ = a.checked_mul(b);
+        let _ = a.checked_div(b);
+    }
+
+    // Lossy buffer reader
+    if let Some((value, _)) = roundtrip::<Decimal>(&ReadBuffer::borrowed(data), 0) {
+        check(value);
+    }
+
+    // Text
+    if let Ok(text) = std::str::from_utf8(data)
+        && let Ok(value) = text.parse::<Decimal>()
+    {
+        check(value);
+    }
+});
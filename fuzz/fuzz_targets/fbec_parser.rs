@@ -0,0 +1,24 @@
+//! fbec schema parser and code generator
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use std::fs;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(schema) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let dir = std::env::temp_dir().join(format!("fbec-fuzz-{}", std::process::id()));
+    let out = dir.join("out");
+    let _ = fs::remove_dir_all(&out);
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("fuzz.fbe");
+    fs::write(&path, schema).unwrap();
+
+    // Errors are fine, panics are not
+    let _ = fbec::Config::new()
+        .out_dir(&out)
+        .rerun_if_changed(false)
+        .compile(&[&path]);
+});
@@ -0,0 +1,48 @@
+//! Field and final model collection, string and bytes `try_get`
+#![no_main]
+
+use fbe::buffer::ReadBuffer;
+use fbe::field_model::{
+    FieldModelArray, FieldModelBytes, FieldModelList, FieldModelMap, FieldModelSet, FieldModelString,
+    FieldModelVector,
+};
+use fbe::field_model_collections::{
+    FieldModelMapI32, FieldModelSetI32, FieldModelVectorI32, FieldModelVectorString,
+};
+use fbe::final_model::{
+    FinalModelArray, FinalModelBytes, FinalModelList, FinalModelMap, FinalModelSet, FinalModelString,
+    FinalModelVector,
+};
+use fbe::limits::{DecodeBudget, DecodeLimits};
+use fbe_fuzz::OFFSETS;
+use libfuzzer_sys::fuzz_target;
+
+fn read_i32(buffer: &[u8], offset: usize) -> i32 {
+    ReadBuffer::borrowed(buffer).read_i32(offset)
+}
+
+fuzz_target!(|data: &[u8]| {
+    for offset in OFFSETS {
+        let mut budget = DecodeBudget::new(DecodeLimits::default());
+
+        let _ = FieldModelVector::new(data, offset, read_i32).try_get(&mut budget);
+        let _ = FieldModelMap::new(data, offset, read_i32, read_i32).try_get(&mut budget);
+        let _ = FieldModelSet::new(data, offset, read_i32).try_get(&mut budget);
+        let _ = FieldModelList::new(data, offset, read_i32).try_get(&mut budget);
+        let _ = FieldModelArray::<_, 3>::new(data, offset, read_i32).try_get();
+        let _ = FieldModelString::new(data, offset).try_get(&mut budget);
+        let _ = FieldModelBytes::new(data, offset).try_get(&mut budget);
+        let _ = FieldModelVectorI32::new(data, offset).try_get(&mut budget);
+        let _ = FieldModelMapI32::new(data, offset).try_get(&mut budget);
+        let _ = FieldModelSetI32::new(data, offset).try_get(&mut budget);
+        let _ = FieldModelVectorString::new(data, offset).try_get(&mut budget);
+
+        let _ = FinalModelVector::new(data, offset, read_i32).try_get(&mut budget);
+        let _ = FinalModelMap::new(data, offset, read_i32, read_i32).try_get(&mut budget);
+        let _ = FinalModelSet::new(data, offset, read_i32).try_get(&mut budget);
+        let _ = FinalModelList::new(data, offset, read_i32).try_get(&mut budget);
+        let _ = FinalModelArray::<_, 3>::new(data, offset, read_i32).try_get();
+        let _ = FinalModelString::new(data, offset).try_get(&mut budget);
+        let _ = FinalModelBytes::new(data, offset).try_get(&mut budget);
+    }
+});
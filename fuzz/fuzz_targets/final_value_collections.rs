@@ -0,0 +1,39 @@
+//! `FinalValue::try_read_final` for vectors, arrays, maps and sets
+#![no_main]
+
+use fbe::buffer::ReadBuffer;
+use fbe::final_model::FinalValue;
+use fbe::limits::{DecodeBudget, DecodeLimits};
+use fbe::Decimal;
+use fbe_fuzz::{OFFSETS, roundtrip};
+use libfuzzer_sys::fuzz_target;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+fuzz_target!(|data: &[u8]| {
+    let reader = ReadBuffer::borrowed(data);
+    for offset in OFFSETS {
+        // Sequences re-encode to exactly the bytes they were read from
+        if let Some((value, size)) = roundtrip::<Vec<i32>>(&reader, offset) {
+            assert_eq!(value.final_size(), size);
+        }
+        if let Some((value, size)) = roundtrip::<Vec<Vec<u16>>>(&reader, offset) {
+            assert_eq!(value.final_size(), size);
+        }
+        if let Some((value, size)) = roundtrip::<[i64; 2]>(&reader, offset) {
+            assert_eq!(value.final_size(), size);
+        }
+        roundtrip::<Vec<u8>>(&reader, offset);
+        roundtrip::<Vec<Decimal>>(&reader, offset);
+
+        // Duplicate keys collapse, so only the decoded value must round-trip
+        roundtrip::<HashMap<i32, i64>>(&reader, offset);
+        roundtrip::<BTreeMap<u8, Vec<i32>>>(&reader, offset);
+        roundtrip::<HashSet<u32>>(&reader, offset);
+        roundtrip::<BTreeSet<i16>>(&reader, offset);
+
+        // Invalid UTF-8 is replaced, so strings are only decoded
+        let mut budget = DecodeBudget::new(DecodeLimits::default());
+        let _ = Vec::<String>::try_read_final(&reader, offset, &mut budget);
+        let _ = HashMap::<String, Vec<u8>>::try_read_final(&reader, offset, &mut budget);
+    }
+});
@@ -0,0 +1,31 @@
+//! Model (size header) and FinalModel struct deserializers
+#![no_main]
+
+use fbe::buffer::ReadBuffer;
+use fbe::limits::DecodeLimits;
+use fbe::model_final::Product;
+use fbe_fuzz::{Order, OrderFinalModel, OrderSizedModel};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let reader = ReadBuffer::borrowed(data);
+    let limits = DecodeLimits::default();
+
+    // Hand-written `Product` Model/FinalModel layout
+    if let Ok((product, size)) = Product::try_deserialize_model(&reader, &limits) {
+        assert!(size <= data.len());
+        assert_eq!(Product::deserialize_model(&reader).0.name, product.name);
+    }
+    if let Ok((product, _)) = Product::try_deserialize_final(&reader, &limits) {
+        assert_eq!(Product::deserialize_final(&reader).0.name, product.name);
+    }
+
+    // Derived struct models
+    if let Ok(order) = Order::try_deserialize(&reader, &limits) {
+        assert_eq!(Order::deserialize(&reader), order);
+        assert_eq!(OrderFinalModel::new(data, 0).try_get(&limits), Ok(order));
+    }
+    if let Ok(order) = OrderSizedModel::new(data, 0).try_get(&limits) {
+        assert_eq!(OrderSizedModel::new(data, 0).get(), order);
+    }
+});
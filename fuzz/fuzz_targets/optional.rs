@@ -0,0 +1,32 @@
+//! `FinalValue::try_read_final` for optionals
+#![no_main]
+
+use fbe::buffer::ReadBuffer;
+use fbe::final_model::FinalValue;
+use fbe::limits::{DecodeBudget, DecodeLimits};
+use fbe::{Decimal, Timestamp, Uuid};
+use fbe_fuzz::{OFFSETS, roundtrip};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let reader = ReadBuffer::borrowed(data);
+    for offset in OFFSETS {
+        if let Some((value, size)) = roundtrip::<Option<i32>>(&reader, offset) {
+            assert_eq!(value.final_size(), size);
+        }
+        if let Some((value, size)) = roundtrip::<Option<Option<i16>>>(&reader, offset) {
+            assert_eq!(value.final_size(), size);
+        }
+        if let Some((value, size)) = roundtrip::<Option<Vec<u64>>>(&reader, offset) {
+            assert_eq!(value.final_size(), size);
+        }
+        roundtrip::<Option<Decimal>>(&reader, offset);
+        roundtrip::<Option<Uuid>>(&reader, offset);
+        roundtrip::<Option<Timestamp>>(&reader, offset);
+        roundtrip::<Vec<Option<u8>>>(&reader, offset);
+
+        let mut budget = DecodeBudget::new(DecodeLimits::default());
+        let _ = Option::<String>::try_read_final(&reader, offset, &mut budget);
+        let _ = Option::<Vec<String>>::try_read_final(&reader, offset, &mut budget);
+    }
+});
@@ -0,0 +1,35 @@
+//! `ReadBuffer` bounded collection, string and bytes readers
+#![no_main]
+
+use fbe::buffer::ReadBuffer;
+use fbe::limits::{DecodeBudget, DecodeLimits};
+use fbe_fuzz::OFFSETS;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let reader = ReadBuffer::borrowed(data);
+    for offset in OFFSETS {
+        let mut budget = DecodeBudget::new(DecodeLimits::default());
+
+        // Whatever the bounded readers accept, the unchecked readers decode the same way
+        if let Ok(values) = reader.try_read_vector_i32(offset, &mut budget) {
+            assert_eq!(values, reader.read_vector_i32(offset));
+        }
+        if let Ok(values) = reader.try_read_vector::<u64>(offset, &mut budget) {
+            assert_eq!(values, reader.read_vector::<u64>(offset));
+        }
+        if let Ok(values) = reader.try_read_vector::<f64>(offset, &mut budget) {
+            assert_eq!(values.len(), reader.read_vector_f64(offset).len());
+        }
+        if let Ok(entries) = reader.try_read_map_i32(offset, &mut budget) {
+            assert_eq!(entries, reader.read_map_i32(offset));
+        }
+        if let Ok(value) = reader.try_read_string(offset, &mut budget) {
+            assert_eq!(value, reader.read_string(offset));
+        }
+        if let Ok(value) = reader.try_read_bytes(offset, &mut budget) {
+            assert_eq!(value, reader.read_bytes(offset));
+        }
+        let _ = reader.try_read_vector_string(offset, &mut budget);
+    }
+});
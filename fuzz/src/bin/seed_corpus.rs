@@ -0,0 +1,196 @@
+//! Regenerate the seed corpus from the cross-platform test vectors
+//!
+//! `cargo run --bin seed_corpus` rewrites `corpus/<target>/` with the byte
+//! layouts written by `tests/cross_platform_*.rs`, `tests/test_optional_cross.rs`,
+//! `tests/test_model_cross.rs` and `tests/test_decimal.rs`, plus the fbec test schemas.
+
+use fbe::buffer::WriteBuffer;
+use fbe::field_model::{FieldModelI32Mut, FieldModelOptionalMut};
+use fbe::final_model::{FinalModelI32Mut, FinalModelOptionalMut, FinalValue};
+use fbe::model_final::Product;
+use fbe::{Decimal, Timestamp, Uuid};
+use fbe_fuzz::{Line, Order, OrderSizedModelMut};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+fn write(target: &str, name: &str, data: &[u8]) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus").join(target);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(name), data).unwrap();
+}
+
+fn final_value<T: FinalValue>(value: &T) -> Vec<u8> {
+    let mut buffer = WriteBuffer::growable();
+    value.write_final(&mut buffer, 0);
+    buffer.data().to_vec()
+}
+
+/// `tests/cross_platform_collections.rs`
+fn collections() -> Vec<u8> {
+    let mut writer = WriteBuffer::new();
+    writer.reserve(1024);
+    writer.allocate(28);
+    writer.write_array_i32(16, &[40, 50, 60]);
+    writer.write_vector_i32(0, &[10, 20, 30]);
+    writer.write_map_i32(4, &[(1, 100), (2, 200)]);
+    writer.write_set_i32(8, &[70, 80, 90]);
+    writer.data().to_vec()
+}
+
+/// `tests/cross_platform_vector.rs`
+fn vector() -> Vec<u8> {
+    let mut writer = WriteBuffer::new();
+    writer.allocate(4);
+    writer.write_vector_i32(0, &[100, 200, 300, 400, 500]);
+    writer.data().to_vec()
+}
+
+/// `tests/cross_platform_types.rs`
+fn types() -> Vec<u8> {
+    let bytes_data = b"Binary\x00\xFF";
+    let mut writer = WriteBuffer::new();
+    writer.allocate(8);
+    writer.write_timestamp(0, Timestamp::new(1729526400000000000));
+    writer.allocate(16);
+    writer.write_uuid(8, uuid());
+    writer.allocate(4 + bytes_data.len());
+    writer.write_bytes(24, bytes_data);
+    writer.allocate(16);
+    writer.write_decimal(24 + 4 + bytes_data.len(), decimal());
+    writer.data().to_vec()
+}
+
+/// `tests/test_string_collections.rs`
+fn strings() -> Vec<u8> {
+    let mut writer = WriteBuffer::new();
+    writer.allocate(4);
+    writer.write_vector_string(0, &["Hello".to_string(), "Panilux".to_string(), "FBE".to_string()]);
+    writer.data().to_vec()
+}
+
+fn uuid() -> Uuid {
+    Uuid::from_bytes([
+        0x12, 0x3e, 0x45, 0x67, 0xe8, 0x9b, 0x12, 0xd3, 0xa4, 0x56, 0x42, 0x66, 0x55, 0x44, 0x00, 0x00,
+    ])
+}
+
+fn decimal() -> Decimal {
+    Decimal::try_new(123456123456, 6).unwrap()
+}
+
+/// `tests/test_optional_cross.rs`
+fn optionals() -> Vec<(&'static str, Vec<u8>)> {
+    let mut field_value = WriteBuffer::new();
+    field_value.allocate(100);
+    let model: FieldModelOptionalMut<i32, _> =
+        FieldModelOptionalMut::new(&mut field_value, 0, FieldModelI32Mut::new);
+    model.set_some(42, |model, value| model.set(value));
+
+    let mut field_null = WriteBuffer::new();
+    field_null.allocate(100);
+    let mut model: FieldModelOptionalMut<i32, _> =
+        FieldModelOptionalMut::new(&mut field_null, 0, FieldModelI32Mut::new);
+    model.set_none();
+
+    let mut final_value = WriteBuffer::new();
+    final_value.allocate(100);
+    let model: FinalModelOptionalMut<i32, _> =
+        FinalModelOptionalMut::new(&mut final_value, 0, FinalModelI32Mut::new);
+    model.set_some(99, |model, value| model.set(value));
+
+    let mut final_null = WriteBuffer::new();
+    final_null.allocate(100);
+    let mut model: FinalModelOptionalMut<i32, _> =
+        FinalModelOptionalMut::new(&mut final_null, 0, FinalModelI32Mut::new);
+    model.set_none();
+
+    vec![
+        ("optional_field_value", field_value.data().to_vec()),
+        ("optional_field_null", field_null.data().to_vec()),
+        ("optional_final_value", final_value.data().to_vec()),
+        ("optional_final_null", final_null.data().to_vec()),
+    ]
+}
+
+fn order() -> Order {
+    Order {
+        id: 12345,
+        symbol: "EURUSD".to_string(),
+        price: "1.23456".parse().unwrap(),
+        created: Timestamp::new(1729526400000000000),
+        owner: Some(uuid()),
+        tags: vec!["fx".to_string(), "spot".to_string()],
+        fills: HashMap::from([(1, 100), (2, -200)]),
+        lines: vec![
+            Line { quantity: 10, note: Some("first".to_string()) },
+            Line { quantity: -5, note: None },
+        ],
+    }
+}
+
+fn main() {
+    let readers = [
+        ("collections", collections()),
+        ("vector", vector()),
+        ("types", types()),
+        ("strings", strings()),
+    ];
+    for (name, data) in &readers {
+        write("read_buffer_collections", name, data);
+        write("field_model_collections", name, data);
+    }
+
+    write("final_value_collections", "vector_i32", &final_value(&vec![100i32, 200, 300, 400, 500]));
+    write("final_value_collections", "vector_vector_u16", &final_value(&vec![vec![1u16, 2], vec![], vec![3]]));
+    write("final_value_collections", "map_i32_i64", &final_value(&HashMap::from([(1i32, 100i64), (2, 200)])));
+    write("final_value_collections", "vector_string", &final_value(&vec!["Hello".to_string(), "FBE".to_string()]));
+    write("final_value_collections", "vector_decimal", &final_value(&vec![decimal(), -decimal()]));
+
+    for (name, data) in optionals() {
+        write("optional", name, &data);
+    }
+    write("optional", "option_vector_u64", &final_value(&Some(vec![1u64, 2, 3])));
+    write("optional", "option_uuid", &final_value(&Some(uuid())));
+
+    write("decimal", "types_decimal", &decimal().to_bytes());
+    write("decimal", "buffer_decimal", &[0x39, 0x30, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0x80]);
+    write("decimal", "text", b"-123.45");
+
+    // `tests/test_model_cross.rs`
+    let product = Product::new(123, "Laptop".to_string(), 999.99, 5);
+    let mut buffer = WriteBuffer::new();
+    product.serialize_model(&mut buffer);
+    write("model", "product_model", buffer.data());
+    let mut buffer = WriteBuffer::new();
+    product.serialize_final(&mut buffer);
+    write("model", "product_final", buffer.data());
+    let mut buffer = WriteBuffer::growable();
+    order().serialize(&mut buffer);
+    write("model", "order_final", buffer.data());
+    let mut buffer = WriteBuffer::growable();
+    OrderSizedModelMut::new(&mut buffer, 0).set(&order());
+    write("model", "order_model", buffer.data());
+
+    // `fbec/tests/test_config.rs`
+    write(
+        "fbec_parser",
+        "common.fbe",
+        b"package common;\n\nenum Side : byte\n{\n    Buy;\n    Sell;\n}\n\nflags Perm : int32\n{\n    Read = 0x01;\n    Write = 0x02;\n}\n",
+    );
+    write(
+        "fbec_parser",
+        "trade.fbe",
+        b"package trade;\n\nimport common;\n\nstruct Trade(1)\n{\n    int32 id;\n    string symbol;\n    double price;\n}\n",
+    );
+    write(
+        "fbec_parser",
+        "blob.fbe",
+        b"enum Kind : int32\n{\n    Small = 1;\n    Large;\n}\n\nstruct Blob\n{\n    bytes data;\n    uuid? owner;\n    decimal[] prices;\n    timestamp created;\n    char initial;\n    wchar letter;\n}\n",
+    );
+    write(
+        "fbec_parser",
+        "account.fbe",
+        b"struct Account\n{\n    uuid id = uuid1;\n    uuid? parent = null;\n    uuid session = uuid4();\n    string name = \"guest\";\n    decimal balance = 99.95;\n    timestamp created = utc;\n    int32 level;\n}\n",
+    );
+}
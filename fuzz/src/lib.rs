@@ -0,0 +1,52 @@
+//! Shared fixtures for the FBE fuzz targets and the seed corpus
+
+use fbe::FbeStruct;
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::final_model::FinalValue;
+use fbe::limits::{DecodeBudget, DecodeLimits};
+use fbe::{Decimal, Timestamp, Uuid};
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+/// Offsets probed by the reader targets, covering the cross-platform test layouts
+pub const OFFSETS: [usize; 6] = [0, 4, 8, 12, 16, 24];
+
+/// Derived struct for the Model/FinalModel targets
+#[derive(FbeStruct, Debug, Clone, PartialEq, Default)]
+pub struct Order {
+    pub id: i32,
+    pub symbol: String,
+    pub price: Decimal,
+    pub created: Timestamp,
+    pub owner: Option<Uuid>,
+    pub tags: Vec<String>,
+    pub fills: HashMap<u32, i64>,
+    pub lines: Vec<Line>,
+}
+
+/// Nested struct of [`Order`]
+#[derive(FbeStruct, Debug, Clone, PartialEq, Default)]
+pub struct Line {
+    pub quantity: i64,
+    pub note: Option<String>,
+}
+
+/// Decode `T` at offset within default limits and check that re-encoding it round-trips
+///
+/// Returns the decoded value and the number of input bytes it consumed.
+pub fn roundtrip<T>(reader: &ReadBuffer, offset: usize) -> Option<(T, usize)>
+where
+    T: FinalValue + PartialEq + Debug,
+{
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    let (value, size) = T::try_read_final(reader, offset, &mut budget).ok()?;
+
+    let mut buffer = WriteBuffer::growable();
+    let written = value.write_final(&mut buffer, 0);
+    assert_eq!(written, value.final_size());
+    let (back, back_size) = T::read_final(&ReadBuffer::borrowed(buffer.data()), 0);
+    assert_eq!(back_size, written);
+    assert_eq!(back, value);
+
+    Some((value, size))
+}
@@ -1,4 +1,6 @@
 use crate::buffer::{ReadBuffer, WriteBuffer};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Address {
@@ -2,6 +2,20 @@
 //!
 //! Based on original FBE Python implementation with exact API compatibility
 
+use crate::bulk::{self, Primitive};
+use crate::chars::{self, CharError};
+use crate::decimal::Decimal;
+use crate::limits::{DecodeBudget, DecodeError};
+use crate::timestamp::Timestamp;
+use crate::uuid::Uuid;
+#[cfg(feature = "bytes")]
+use bytes::{Bytes, BytesMut};
+use core::fmt;
+use core::ops::Deref;
+use alloc::sync::Arc;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
 /// Write buffer for FBE serialization
 ///
 /// Manages dynamic byte buffer with offset tracking and allocation
@@ -10,6 +24,7 @@ pub struct WriteBuffer {
     buffer: Vec<u8>,
     size: usize,
     offset: usize,
+    growable: bool,
 }
 
 impl Default for WriteBuffer {
@@ -26,6 +41,7 @@ impl WriteBuffer {
             buffer: Vec::new(),
             size: 0,
             offset: 0,
+            growable: false,
         }
     }
 
@@ -36,9 +52,70 @@ impl WriteBuffer {
             buffer: vec![0; capacity],
             size: 0,
             offset: 0,
+            growable: false,
+        }
+    }
+
+    /// Create a new growable write buffer
+    ///
+    /// Writes past the current size grow the buffer (amortized doubling) and
+    /// extend the size, so values can be streamed without calling
+    /// [`allocate`](Self::allocate) first.
+    #[must_use]
+    pub fn growable() -> Self {
+        Self {
+            growable: true,
+            ..Self::new()
+        }
+    }
+
+    /// Create a write buffer reusing the allocation of `BytesMut`
+    ///
+    /// The existing bytes become the written data and new data is appended
+    /// after them. The allocation is taken over only when `bytes` uniquely
+    /// owns it (e.g. a fresh `BytesMut::with_capacity`); bytes split off or
+    /// shared with other handles are copied into a new allocation.
+    #[cfg(feature = "bytes")]
+    #[must_use]
+    pub fn from_bytes_mut(bytes: BytesMut) -> Self {
+        let buffer = Vec::from(bytes);
+        Self {
+            size: buffer.len(),
+            buffer,
+            offset: 0,
+            growable: false,
         }
     }
 
+    /// Convert written data into `BytesMut`
+    ///
+    /// Goes through [`freeze`](Self::freeze); the frozen `Bytes` is the only
+    /// handle to the buffer's allocation, so it is reused rather than copied.
+    #[cfg(feature = "bytes")]
+    #[must_use]
+    pub fn into_bytes_mut(self) -> BytesMut {
+        BytesMut::from(self.freeze())
+    }
+
+    /// Freeze written data into immutable `Bytes` without copying
+    #[cfg(feature = "bytes")]
+    #[must_use]
+    pub fn freeze(mut self) -> Bytes {
+        self.buffer.truncate(self.size);
+        Bytes::from(self.buffer)
+    }
+
+    /// Check if writes past the current size grow the buffer
+    #[must_use]
+    pub const fn is_growable(&self) -> bool {
+        self.growable
+    }
+
+    /// Enable/Disable growing the buffer on writes past the current size
+    pub fn set_growable(&mut self, growable: bool) {
+        self.growable = growable;
+    }
+
     /// Check if buffer is empty
     #[must_use]
     pub const fn is_empty(&self) -> bool {
@@ -163,83 +240,98 @@ impl WriteBuffer {
         self.offset -= offset;
     }
 
+    /// Get `len` writable bytes at `offset`, growing the buffer first if growable
+    #[inline]
+    fn slot(&mut self, offset: usize, len: usize) -> &mut [u8] {
+        let start = self.offset + offset;
+        let end = start + len;
+        if self.growable && end > self.size {
+            self.reserve(end);
+            self.size = end;
+        }
+        &mut self.buffer[start..end]
+    }
+
     // Write primitive types
     #[inline]
     pub fn write_byte(&mut self, offset: usize, value: u8) {
         self.write_u8(offset, value);
     }
 
+    /// Write char as a Latin-1 byte, rejecting characters above U+00FF
     #[inline]
-    pub fn write_char(&mut self, offset: usize, value: u8) {
-        self.buffer[self.offset + offset] = value;
+    pub fn write_char(&mut self, offset: usize, value: char) -> Result<(), CharError> {
+        self.slot(offset, 1)[0] = chars::encode_char(value)?;
+        Ok(())
     }
 
+    /// Write wchar as a 4-byte Unicode code point
     #[inline]
-    pub fn write_wchar(&mut self, offset: usize, value: u32) {
-        self.write_u32(offset, value);
+    pub fn write_wchar(&mut self, offset: usize, value: char) {
+        self.write_u32(offset, chars::encode_wchar(value));
     }
 
     #[inline]
     pub fn write_bool(&mut self, offset: usize, value: bool) {
-        self.buffer[self.offset + offset] = value as u8;
+        self.slot(offset, 1)[0] = value as u8;
     }
 
     #[inline]
     pub fn write_i8(&mut self, offset: usize, value: i8) {
-        self.buffer[self.offset + offset] = value as u8;
+        self.slot(offset, 1)[0] = value as u8;
     }
 
     #[inline]
     pub fn write_u8(&mut self, offset: usize, value: u8) {
-        self.buffer[self.offset + offset] = value;
+        self.slot(offset, 1)[0] = value;
     }
 
     #[inline]
     pub fn write_i16(&mut self, offset: usize, value: i16) {
         let bytes = value.to_le_bytes();
-        self.buffer[self.offset + offset..self.offset + offset + 2].copy_from_slice(&bytes);
+        self.slot(offset, 2).copy_from_slice(&bytes);
     }
 
     #[inline]
     pub fn write_u16(&mut self, offset: usize, value: u16) {
         let bytes = value.to_le_bytes();
-        self.buffer[self.offset + offset..self.offset + offset + 2].copy_from_slice(&bytes);
+        self.slot(offset, 2).copy_from_slice(&bytes);
     }
 
     #[inline]
     pub fn write_i32(&mut self, offset: usize, value: i32) {
         let bytes = value.to_le_bytes();
-        self.buffer[self.offset + offset..self.offset + offset + 4].copy_from_slice(&bytes);
+        self.slot(offset, 4).copy_from_slice(&bytes);
     }
 
     #[inline]
     pub fn write_u32(&mut self, offset: usize, value: u32) {
         let bytes = value.to_le_bytes();
-        self.buffer[self.offset + offset..self.offset + offset + 4].copy_from_slice(&bytes);
+        self.slot(offset, 4).copy_from_slice(&bytes);
     }
 
     #[inline]
     pub fn write_i64(&mut self, offset: usize, value: i64) {
         let bytes = value.to_le_bytes();
-        self.buffer[self.offset + offset..self.offset + offset + 8].copy_from_slice(&bytes);
+        self.slot(offset, 8).copy_from_slice(&bytes);
     }
 
     #[inline]
     pub fn write_u64(&mut self, offset: usize, value: u64) {
         let bytes = value.to_le_bytes();
-        self.buffer[self.offset + offset..self.offset + offset + 8].copy_from_slice(&bytes);
+        self.slot(offset, 8).copy_from_slice(&bytes);
     }
 
     #[inline]
     pub fn write_f32(&mut self, offset: usize, value: f32) {
         let bytes = value.to_le_bytes();
-        self.buffer[self.offset + offset..self.offset + offset + 4].copy_from_slice(&bytes);
+        self.slot(offset, 4).copy_from_slice(&bytes);
     }
 
     #[inline]
     pub fn write_f64(&mut self, offset: usize, value: f64) {
         let bytes = value.to_le_bytes();
-        self.buffer[self.offset + offset..self.offset + offset + 8].copy_from_slice(&bytes);
+        self.slot(offset, 8).copy_from_slice(&bytes);
     }
 
     #[inline]
@@ -247,20 +339,19 @@ impl WriteBuffer {
         let len = value.len() as i32;
         self.write_i32(offset, len);
         let bytes = value.as_bytes();
-        self.buffer[self.offset + offset + 4..self.offset + offset + 4 + bytes.len()]
-            .copy_from_slice(bytes);
+        self.slot(offset + 4, bytes.len()).copy_from_slice(bytes);
     }
 
     /// Write timestamp as uint64 (nanoseconds since epoch)
     #[inline]
-    pub fn write_timestamp(&mut self, offset: usize, value: u64) {
-        self.write_u64(offset, value);
+    pub fn write_timestamp(&mut self, offset: usize, value: Timestamp) {
+        self.write_u64(offset, value.nanos());
     }
 
     /// Write UUID as 16 bytes (big-endian format)
     #[inline]
-    pub fn write_uuid(&mut self, offset: usize, value: &[u8; 16]) {
-        self.buffer[self.offset + offset..self.offset + offset + 16].copy_from_slice(value);
+    pub fn write_uuid(&mut self, offset: usize, value: Uuid) {
+        self.slot(offset, 16).copy_from_slice(value.as_bytes());
     }
 
     /// Write bytes (size-prefixed binary data)
@@ -268,24 +359,14 @@ impl WriteBuffer {
     pub fn write_bytes(&mut self, offset: usize, value: &[u8]) {
         let len = value.len() as i32;
         self.write_i32(offset, len);
-        self.buffer[self.offset + offset + 4..self.offset + offset + 4 + value.len()]
-            .copy_from_slice(value);
+        self.slot(offset + 4, value.len()).copy_from_slice(value);
     }
 
     /// Write decimal as 16 bytes (.NET Decimal format)
     /// Format: bytes 0-11 = unscaled value (96-bit), byte 14 = scale, byte 15 = sign
     #[inline]
-    pub fn write_decimal(&mut self, offset: usize, value: i128, scale: u8, negative: bool) {
-        // Write unscaled value to bytes 0-11 (96-bit little-endian)
-        let bytes = value.to_le_bytes();
-        self.buffer[self.offset + offset..self.offset + offset + 12].copy_from_slice(&bytes[..12]);
-        // Bytes 12-13 are unused (zero)
-        self.buffer[self.offset + offset + 12] = 0;
-        self.buffer[self.offset + offset + 13] = 0;
-        // Byte 14 = scale
-        self.buffer[self.offset + offset + 14] = scale;
-        // Byte 15 = sign
-        self.buffer[self.offset + offset + 15] = if negative { 0x80 } else { 0x00 };
+    pub fn write_decimal(&mut self, offset: usize, value: Decimal) {
+        self.slot(offset, 16).copy_from_slice(&value.to_bytes());
     }
 
     /// Write list of i32 values (linked list, same format as vector)
@@ -295,11 +376,11 @@ impl WriteBuffer {
         self.write_vector_i32(offset, values)
     }
 
-    /// Write vector of i32 values
+    /// Write vector of primitive values with a single bulk copy
     /// Format: 4-byte offset pointer → (4-byte size + elements)
-    pub fn write_vector_i32(&mut self, offset: usize, values: &[i32]) -> usize {
+    pub fn write_vector<T: Primitive>(&mut self, offset: usize, values: &[T]) -> usize {
         let size = values.len();
-        let data_size = 4 + (size * 4); // 4 bytes size + elements
+        let data_size = 4 + (size * T::SIZE); // 4 bytes size + elements
         let data_offset = self.allocate(data_size);
 
         // Write pointer at offset
@@ -309,20 +390,31 @@ impl WriteBuffer {
         self.write_u32(data_offset - self.offset, size as u32);
 
         // Write elements
-        for (i, &value) in values.iter().enumerate() {
-            self.write_i32(data_offset - self.offset + 4 + (i * 4), value);
-        }
+        bulk::encode(values, self.slot(data_offset - self.offset + 4, size * T::SIZE));
 
         data_size
     }
 
+    /// Write fixed-size array of primitive values with a single bulk copy
+    /// Format: N × element size (elements only)
+    #[inline]
+    pub fn write_array<T: Primitive>(&mut self, offset: usize, values: &[T]) -> usize {
+        let size = values.len() * T::SIZE;
+        bulk::encode(values, self.slot(offset, size));
+        size
+    }
+
+    /// Write vector of i32 values
+    /// Format: 4-byte offset pointer → (4-byte size + elements)
+    pub fn write_vector_i32(&mut self, offset: usize, values: &[i32]) -> usize {
+        self.write_vector(offset, values)
+    }
+
     /// Write fixed-size array of i32 values (inline, no pointer)
     /// Format: N × 4 bytes (elements only)
     #[inline]
     pub fn write_array_i32(&mut self, offset: usize, values: &[i32]) {
-        for (i, &value) in values.iter().enumerate() {
-            self.write_i32(offset + (i * 4), value);
-        }
+        self.write_array(offset, values);
     }
 
     /// Write map of i32 key-value pairs
@@ -394,41 +486,19 @@ impl WriteBuffer {
     // ========================================================================
 
     pub fn write_vector_f32(&mut self, offset: usize, values: &[f32]) -> usize {
-        let size = values.len();
-        let data_size = 4 + (size * 4);
-        let data_offset = self.allocate(data_size);
-        self.write_u32(offset, (data_offset - self.offset) as u32);
-        self.write_u32(data_offset - self.offset, size as u32);
-        for (i, &value) in values.iter().enumerate() {
-            self.write_f32(data_offset - self.offset + 4 + (i * 4), value);
-        }
-        data_size
+        self.write_vector(offset, values)
     }
 
     pub fn write_array_f32(&mut self, offset: usize, values: &[f32]) -> usize {
-        for (i, &value) in values.iter().enumerate() {
-            self.write_f32(offset + (i * 4), value);
-        }
-        values.len() * 4
+        self.write_array(offset, values)
     }
 
     pub fn write_vector_f64(&mut self, offset: usize, values: &[f64]) -> usize {
-        let size = values.len();
-        let data_size = 4 + (size * 8);
-        let data_offset = self.allocate(data_size);
-        self.write_u32(offset, (data_offset - self.offset) as u32);
-        self.write_u32(data_offset - self.offset, size as u32);
-        for (i, &value) in values.iter().enumerate() {
-            self.write_f64(data_offset - self.offset + 4 + (i * 8), value);
-        }
-        data_size
+        self.write_vector(offset, values)
     }
 
     pub fn write_array_f64(&mut self, offset: usize, values: &[f64]) -> usize {
-        for (i, &value) in values.iter().enumerate() {
-            self.write_f64(offset + (i * 8), value);
-        }
-        values.len() * 8
+        self.write_array(offset, values)
     }
 
     // Optional types
@@ -470,42 +540,162 @@ impl WriteBuffer {
     }
 }
 
+/// Bytes behind a read buffer
+#[derive(Clone)]
+enum Storage<'a> {
+    Owned(Vec<u8>),
+    Borrowed(&'a [u8]),
+    Shared(Arc<dyn AsRef<[u8]> + Send + Sync>),
+    #[cfg(feature = "bytes")]
+    Bytes(Bytes),
+}
+
+impl Deref for Storage<'_> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        match self {
+            Storage::Owned(data) => data,
+            Storage::Borrowed(data) => data,
+            Storage::Shared(data) => (**data).as_ref(),
+            #[cfg(feature = "bytes")]
+            Storage::Bytes(data) => data,
+        }
+    }
+}
+
+impl fmt::Debug for Storage<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
 /// Read buffer for FBE deserialization
+///
+/// The buffer either owns its bytes, borrows a slice for `'a`, or shares an
+/// external backing such as a memory-mapped file, so large inputs are read
+/// in place without copying.
 #[derive(Debug, Clone)]
-pub struct ReadBuffer {
-    buffer: Vec<u8>,
+pub struct ReadBuffer<'a> {
+    buffer: Storage<'a>,
     size: usize,
     offset: usize,
 }
 
-impl Default for ReadBuffer {
+impl Default for ReadBuffer<'_> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl From<Vec<u8>> for ReadBuffer {
+impl From<Vec<u8>> for ReadBuffer<'_> {
     fn from(data: Vec<u8>) -> Self {
         let size = data.len();
         Self {
-            buffer: data,
+            buffer: Storage::Owned(data),
             size,
             offset: 0,
         }
     }
 }
 
-impl ReadBuffer {
+impl<'a> From<&'a [u8]> for ReadBuffer<'a> {
+    fn from(data: &'a [u8]) -> Self {
+        Self::borrowed(data)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl From<Bytes> for ReadBuffer<'_> {
+    fn from(data: Bytes) -> Self {
+        Self::from_bytes(data)
+    }
+}
+
+impl<'a> ReadBuffer<'a> {
     /// Create a new read buffer
     #[must_use]
     pub fn new() -> Self {
         Self {
-            buffer: Vec::new(),
+            buffer: Storage::Owned(Vec::new()),
             size: 0,
             offset: 0,
         }
     }
 
+    /// Create a read buffer over borrowed bytes without copying
+    #[must_use]
+    pub fn borrowed(data: &'a [u8]) -> Self {
+        Self {
+            buffer: Storage::Borrowed(data),
+            size: data.len(),
+            offset: 0,
+        }
+    }
+
+    /// Create a read buffer over shared bytes without copying
+    ///
+    /// Any backing that derefs to bytes works, e.g. `Arc<Vec<u8>>` or a
+    /// `memmap2::Mmap`.
+    #[must_use]
+    pub fn from_shared<T>(data: Arc<T>) -> Self
+    where
+        T: AsRef<[u8]> + Send + Sync + 'static,
+    {
+        let size = (*data).as_ref().len();
+        Self {
+            buffer: Storage::Shared(data),
+            size,
+            offset: 0,
+        }
+    }
+
+    /// Create a read buffer over `Bytes` without copying
+    ///
+    /// `bytes` fields can then be read as `Bytes` sub-slices with
+    /// [`read_bytes_shared`](Self::read_bytes_shared).
+    #[cfg(feature = "bytes")]
+    #[must_use]
+    pub fn from_bytes(data: Bytes) -> Self {
+        Self {
+            size: data.len(),
+            buffer: Storage::Bytes(data),
+            offset: 0,
+        }
+    }
+
+    /// Map a file into memory and read it in place
+    ///
+    /// The file must not be truncated or modified while it is mapped:
+    /// reading a truncated mapping raises `SIGBUS`, and concurrent writes
+    /// show up in already decoded offsets.
+    #[cfg(feature = "mmap")]
+    pub fn map_file<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: the mapping is read-only and the caller guarantees the
+        // file is not truncated or modified while it is mapped
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Ok(Self::from_shared(Arc::new(map)))
+    }
+
+    /// Copy borrowed bytes so the buffer no longer borrows its input
+    #[must_use]
+    pub fn into_owned(self) -> ReadBuffer<'static> {
+        let buffer = match self.buffer {
+            Storage::Owned(data) => Storage::Owned(data),
+            Storage::Borrowed(data) => Storage::Owned(data.to_vec()),
+            Storage::Shared(data) => Storage::Shared(data),
+            #[cfg(feature = "bytes")]
+            Storage::Bytes(data) => Storage::Bytes(data),
+        };
+        ReadBuffer {
+            buffer,
+            size: self.size,
+            offset: self.offset,
+        }
+    }
+
     /// Get buffer data
     #[must_use]
     pub fn data(&self) -> &[u8] {
@@ -530,20 +720,20 @@ impl ReadBuffer {
         self.offset
     }
 
-    /// Attach a given memory buffer
-    pub fn attach_buffer(&mut self, buffer: &[u8], offset: usize, size: usize) {
+    /// Attach a given memory buffer without copying
+    pub fn attach_buffer(&mut self, buffer: &'a [u8], offset: usize, size: usize) {
         assert!(!buffer.is_empty(), "Invalid buffer!");
         assert!(size > 0, "Invalid size!");
         assert!(offset <= size, "Invalid offset!");
 
-        self.buffer = buffer.to_vec();
+        self.buffer = Storage::Borrowed(buffer);
         self.size = size;
         self.offset = offset;
     }
 
     /// Reset buffer and offset
     pub fn reset(&mut self) {
-        self.buffer.clear();
+        self.buffer = Storage::Owned(Vec::new());
         self.size = 0;
         self.offset = 0;
     }
@@ -565,16 +755,17 @@ impl ReadBuffer {
         self.read_u8(offset)
     }
 
+    /// Read char from a Latin-1 byte
     #[must_use]
     #[inline]
-    pub fn read_char(&self, offset: usize) -> u8 {
-        self.buffer[self.offset + offset]
+    pub fn read_char(&self, offset: usize) -> char {
+        chars::decode_char(self.buffer[self.offset + offset])
     }
 
-    #[must_use]
+    /// Read wchar, rejecting code points that are not Unicode scalar values
     #[inline]
-    pub fn read_wchar(&self, offset: usize) -> u32 {
-        self.read_u32(offset)
+    pub fn read_wchar(&self, offset: usize) -> Result<char, CharError> {
+        chars::decode_wchar(self.read_u32(offset))
     }
 
     #[must_use]
@@ -662,16 +853,16 @@ impl ReadBuffer {
     /// Read timestamp as uint64 (nanoseconds since epoch)
     #[must_use]
     #[inline]
-    pub fn read_timestamp(&self, offset: usize) -> u64 {
-        self.read_u64(offset)
+    pub fn read_timestamp(&self, offset: usize) -> Timestamp {
+        Timestamp::new(self.read_u64(offset))
     }
 
     /// Read UUID as 16 bytes
     #[must_use]
     #[inline]
-    pub fn read_uuid(&self, offset: usize) -> [u8; 16] {
+    pub fn read_uuid(&self, offset: usize) -> Uuid {
         let bytes = &self.buffer[self.offset + offset..self.offset + offset + 16];
-        bytes.try_into().unwrap()
+        Uuid::from_bytes(bytes.try_into().unwrap())
     }
 
     /// Read bytes (size-prefixed binary data)
@@ -682,24 +873,27 @@ impl ReadBuffer {
         self.buffer[self.offset + offset + 4..self.offset + offset + 4 + len].to_vec()
     }
 
-    /// Read decimal as (value, scale, negative)
-    /// Returns: (unscaled i128 value, scale u8, is_negative bool)
+    /// Read bytes as `Bytes`
+    ///
+    /// Buffers created from `Bytes` return a sub-slice sharing the same
+    /// allocation; other buffers copy the data.
+    #[cfg(feature = "bytes")]
     #[must_use]
-    #[inline]
-    pub fn read_decimal(&self, offset: usize) -> (i128, u8, bool) {
-        // Read 96-bit unscaled value from bytes 0-11
-        let mut value_bytes = [0u8; 16];
-        value_bytes[..12]
-            .copy_from_slice(&self.buffer[self.offset + offset..self.offset + offset + 12]);
-        let value = i128::from_le_bytes(value_bytes);
-
-        // Read scale from byte 14
-        let scale = self.buffer[self.offset + offset + 14];
-
-        // Read sign from byte 15
-        let negative = (self.buffer[self.offset + offset + 15] & 0x80) != 0;
+    pub fn read_bytes_shared(&self, offset: usize) -> Bytes {
+        let len = self.read_i32(offset) as usize;
+        let start = self.offset + offset + 4;
+        match &self.buffer {
+            Storage::Bytes(data) => data.slice(start..start + len),
+            data => Bytes::copy_from_slice(&data[start..start + len]),
+        }
+    }
 
-        (value, scale, negative)
+    /// Read decimal (.NET Decimal format)
+    /// A scale above 28 from a non-conforming peer is rounded into range
+    #[must_use]
+    #[inline]
+    pub fn read_decimal(&self, offset: usize) -> Decimal {
+        Decimal::from_bytes_lossy(&self.buffer[self.offset + offset..self.offset + offset + 16])
     }
 
     /// Read list of i32 values (linked list, same format as vector)
@@ -710,26 +904,68 @@ impl ReadBuffer {
         self.read_vector_i32(offset)
     }
 
-    /// Read vector of i32 values
+    /// Read vector of primitive values with a single bulk copy
     /// Format: 4-byte offset pointer → (4-byte size + elements)
     #[must_use]
-    pub fn read_vector_i32(&self, offset: usize) -> Vec<i32> {
+    pub fn read_vector<T: Primitive>(&self, offset: usize) -> Vec<T> {
         // Read pointer
         let data_offset = self.read_u32(offset) as usize;
         if data_offset == 0 {
             return Vec::new();
         }
 
-        // Read size
+        // Read size and elements
         let size = self.read_u32(data_offset) as usize;
+        self.read_array(data_offset + 4, size)
+    }
 
-        // Read elements
-        let mut result = Vec::with_capacity(size);
-        for i in 0..size {
-            result.push(self.read_i32(data_offset + 4 + (i * 4)));
+    /// Read fixed-size array of primitive values with a single bulk copy
+    /// Format: N × element size (elements only)
+    #[must_use]
+    #[inline]
+    pub fn read_array<T: Primitive>(&self, offset: usize, count: usize) -> Vec<T> {
+        bulk::decode(self.array_bytes::<T>(offset, count))
+    }
+
+    /// View vector of primitive values in place
+    ///
+    /// Returns `None` when the elements are not aligned for `T` in memory
+    /// (or on big-endian targets); fall back to [`read_vector`](Self::read_vector).
+    #[must_use]
+    pub fn view_vector<T: Primitive>(&self, offset: usize) -> Option<&[T]> {
+        let data_offset = self.read_u32(offset) as usize;
+        if data_offset == 0 {
+            return Some(&[]);
         }
+        let size = self.read_u32(data_offset) as usize;
+        self.view_array(data_offset + 4, size)
+    }
 
-        result
+    /// View fixed-size array of primitive values in place
+    ///
+    /// Returns `None` when the elements are not aligned for `T` in memory
+    /// (or on big-endian targets); fall back to [`read_array`](Self::read_array).
+    #[must_use]
+    #[inline]
+    pub fn view_array<T: Primitive>(&self, offset: usize, count: usize) -> Option<&[T]> {
+        bulk::view(self.array_bytes::<T>(offset, count))
+    }
+
+    #[inline]
+    fn array_bytes<T: Primitive>(&self, offset: usize, count: usize) -> &[u8] {
+        let start = self.offset + offset;
+        let end = count
+            .checked_mul(T::SIZE)
+            .and_then(|size| start.checked_add(size))
+            .expect("array size overflows usize");
+        &self.buffer[start..end]
+    }
+
+    /// Read vector of i32 values
+    /// Format: 4-byte offset pointer → (4-byte size + elements)
+    #[must_use]
+    pub fn read_vector_i32(&self, offset: usize) -> Vec<i32> {
+        self.read_vector(offset)
     }
 
     /// Read fixed-size array of i32 values (inline, no pointer)
@@ -737,11 +973,7 @@ impl ReadBuffer {
     #[must_use]
     #[inline]
     pub fn read_array_i32(&self, offset: usize, size: usize) -> Vec<i32> {
-        let mut result = Vec::with_capacity(size);
-        for i in 0..size {
-            result.push(self.read_i32(offset + (i * 4)));
-        }
-        result
+        self.read_array(offset, size)
     }
 
     /// Read map of i32 key-value pairs
@@ -819,45 +1051,19 @@ impl ReadBuffer {
     // ========================================================================
 
     pub fn read_vector_f32(&self, offset: usize) -> Vec<f32> {
-        let pointer = self.read_u32(offset) as usize;
-        if pointer == 0 {
-            return Vec::new();
-        }
-        let size = self.read_u32(pointer) as usize;
-        let mut values = Vec::with_capacity(size);
-        for i in 0..size {
-            values.push(self.read_f32(pointer + 4 + (i * 4)));
-        }
-        values
+        self.read_vector(offset)
     }
 
     pub fn read_array_f32(&self, offset: usize, count: usize) -> Vec<f32> {
-        let mut values = Vec::with_capacity(count);
-        for i in 0..count {
-            values.push(self.read_f32(offset + (i * 4)));
-        }
-        values
+        self.read_array(offset, count)
     }
 
     pub fn read_vector_f64(&self, offset: usize) -> Vec<f64> {
-        let pointer = self.read_u32(offset) as usize;
-        if pointer == 0 {
-            return Vec::new();
-        }
-        let size = self.read_u32(pointer) as usize;
-        let mut values = Vec::with_capacity(size);
-        for i in 0..size {
-            values.push(self.read_f64(pointer + 4 + (i * 8)));
-        }
-        values
+        self.read_vector(offset)
     }
 
     pub fn read_array_f64(&self, offset: usize, count: usize) -> Vec<f64> {
-        let mut values = Vec::with_capacity(count);
-        for i in 0..count {
-            values.push(self.read_f64(offset + (i * 8)));
-        }
-        values
+        self.read_array(offset, count)
     }
 
     // Optional types
@@ -888,6 +1094,132 @@ impl ReadBuffer {
         let data_offset = self.read_u32(offset + 1) as usize;
         Some(self.read_f64(data_offset))
     }
+
+    // ========================================================================
+    // Bounded reads for untrusted input
+    // ========================================================================
+
+    /// Check that `size` bytes at offset lie inside the buffer
+    pub fn check_bounds(&self, offset: usize, size: usize) -> Result<(), DecodeError> {
+        let len = self.size.min(self.buffer.len());
+        match self.offset.checked_add(offset).and_then(|start| start.checked_add(size)) {
+            Some(end) if end <= len => Ok(()),
+            _ => Err(DecodeError::OutOfBounds { offset, size, len }),
+        }
+    }
+
+    /// Check that `count` items of `item_size` bytes at offset lie inside the buffer
+    ///
+    /// A total size overflowing `usize` is reported as `OutOfBounds`.
+    pub fn check_items(&self, offset: usize, count: usize, item_size: usize) -> Result<(), DecodeError> {
+        let size = count.checked_mul(item_size).ok_or(DecodeError::OutOfBounds {
+            offset,
+            size: usize::MAX,
+            len: self.size.min(self.buffer.len()),
+        })?;
+        self.check_bounds(offset, size)
+    }
+
+    /// Read u32 size or pointer, checking bounds
+    pub fn try_read_u32(&self, offset: usize) -> Result<u32, DecodeError> {
+        self.check_bounds(offset, 4)?;
+        Ok(self.read_u32(offset))
+    }
+
+    /// Size-prefixed payload, with its length checked against the budget
+    fn try_read_payload(&self, offset: usize, budget: &mut DecodeBudget) -> Result<&[u8], DecodeError> {
+        let len = self.try_read_u32(offset)? as usize;
+        budget.string(len)?;
+        self.check_bounds(offset + 4, len)?;
+        let start = self.offset + offset + 4;
+        Ok(&self.buffer[start..start + len])
+    }
+
+    /// Read string, checking its length against the budget
+    pub fn try_read_string(&self, offset: usize, budget: &mut DecodeBudget) -> Result<String, DecodeError> {
+        let bytes = self.try_read_payload(offset, budget)?;
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    /// Read bytes, checking their length against the budget
+    pub fn try_read_bytes(&self, offset: usize, budget: &mut DecodeBudget) -> Result<Vec<u8>, DecodeError> {
+        Ok(self.try_read_payload(offset, budget)?.to_vec())
+    }
+
+    /// Pointer and element count of a pointer-based collection, checked against the budget
+    fn try_read_collection(
+        &self,
+        offset: usize,
+        item_size: usize,
+        budget: &mut DecodeBudget,
+    ) -> Result<Option<(usize, usize)>, DecodeError> {
+        let pointer = self.try_read_u32(offset)? as usize;
+        if pointer == 0 {
+            return Ok(None);
+        }
+        let size = self.try_read_u32(pointer)? as usize;
+        budget.collection(size, item_size)?;
+        Ok(Some((pointer + 4, size)))
+    }
+
+    /// Read vector of primitive values, checking its length against the budget
+    pub fn try_read_vector<T: Primitive>(&self, offset: usize, budget: &mut DecodeBudget) -> Result<Vec<T>, DecodeError> {
+        let Some((data_offset, size)) = self.try_read_collection(offset, T::SIZE, budget)? else {
+            return Ok(Vec::new());
+        };
+        self.check_items(data_offset, size, T::SIZE)?;
+        Ok(self.read_array(data_offset, size))
+    }
+
+    /// Read vector of i32 values, checking its length against the budget
+    pub fn try_read_vector_i32(&self, offset: usize, budget: &mut DecodeBudget) -> Result<Vec<i32>, DecodeError> {
+        self.try_read_vector(offset, budget)
+    }
+
+    /// Read map of i32 key-value pairs, checking its length against the budget
+    pub fn try_read_map_i32(&self, offset: usize, budget: &mut DecodeBudget) -> Result<Vec<(i32, i32)>, DecodeError> {
+        let Some((data_offset, size)) = self.try_read_collection(offset, 8, budget)? else {
+            return Ok(Vec::new());
+        };
+        self.check_items(data_offset, size, 8)?;
+        Ok(self.read_map_i32(offset))
+    }
+
+    /// Read vector of strings, checking every length against the budget
+    pub fn try_read_vector_string(&self, offset: usize, budget: &mut DecodeBudget) -> Result<Vec<String>, DecodeError> {
+        let item_size = core::mem::size_of::<String>();
+        let Some((data_offset, size)) = self.try_read_collection(offset, item_size, budget)? else {
+            return Ok(Vec::new());
+        };
+        self.try_read_strings(data_offset, size, budget)
+    }
+
+    /// Read fixed-size array of i32 values, checking bounds
+    pub fn try_read_array_i32(&self, offset: usize, count: usize) -> Result<Vec<i32>, DecodeError> {
+        self.check_items(offset, count, 4)?;
+        Ok(self.read_array(offset, count))
+    }
+
+    /// Read fixed-size array of strings, checking every length against the budget
+    pub fn try_read_array_string(&self, offset: usize, count: usize, budget: &mut DecodeBudget) -> Result<Vec<String>, DecodeError> {
+        budget.collection(count, core::mem::size_of::<String>())?;
+        self.try_read_strings(offset, count, budget)
+    }
+
+    /// `count` consecutive size-prefixed strings
+    fn try_read_strings(&self, offset: usize, count: usize, budget: &mut DecodeBudget) -> Result<Vec<String>, DecodeError> {
+        // Every string has at least its 4-byte size prefix
+        self.check_items(offset, count, 4)?;
+
+        let mut values = Vec::with_capacity(count);
+        let mut current_offset = offset;
+        for _ in 0..count {
+            let bytes = self.try_read_payload(current_offset, budget)?;
+            current_offset += 4 + bytes.len();
+            values.push(String::from_utf8_lossy(bytes).into_owned());
+        }
+        Ok(values)
+    }
 }
 
 #[cfg(test)]
@@ -926,4 +1258,45 @@ mod tests {
         assert_eq!(reader.read_f64(4), 3.14159);
         assert_eq!(reader.read_bool(12), true);
     }
+
+    #[test]
+    fn test_write_buffer_growable() {
+        let mut writer = WriteBuffer::growable();
+        assert!(writer.is_growable());
+
+        writer.write_i32(0, 42);
+        writer.write_string(4, "grow");
+        assert_eq!(writer.size(), 12);
+        // Writes inside the current size do not shrink it
+        writer.write_u8(0, 7);
+        assert_eq!(writer.size(), 12);
+
+        // Pointer-based data is appended after streamed data
+        let offset = writer.allocate(8);
+        assert_eq!(offset, 12);
+        writer.write_f64(offset, 1.5);
+        assert_eq!(writer.size(), 20);
+
+        let mut reader = ReadBuffer::new();
+        reader.attach_buffer(writer.data(), 0, writer.size());
+        assert_eq!(reader.read_u8(0), 7);
+        assert_eq!(reader.read_string(4), "grow");
+        assert_eq!(reader.read_f64(12), 1.5);
+
+        // Capacity doubles instead of growing by each write
+        let mut writer = WriteBuffer::growable();
+        for i in 0..1000 {
+            writer.write_u32(i * 4, i as u32);
+        }
+        assert_eq!(writer.size(), 4000);
+        assert!(writer.capacity() < 8000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_write_buffer_fixed_overflow() {
+        let mut writer = WriteBuffer::new();
+        writer.allocate(2);
+        writer.write_i32(0, 42);
+    }
 }
@@ -0,0 +1,100 @@
+//! Fast Binary Encoding bulk primitive encoding
+//!
+//! Vectors and arrays of primitives are stored as consecutive little-endian
+//! elements. On little-endian targets that layout is the in-memory layout, so
+//! whole slices are encoded and decoded with a single copy, and aligned input
+//! can be viewed as `&[T]` without copying at all. Big-endian targets fall
+//! back to a chunked conversion.
+
+use bytemuck::Pod;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Primitive element with a fixed-size little-endian encoding
+pub trait Primitive: Pod + sealed::Sealed {
+    /// Encoded size in bytes
+    const SIZE: usize;
+
+    /// Decode one element from `SIZE` little-endian bytes
+    fn from_le_chunk(chunk: &[u8]) -> Self;
+
+    /// Encode one element into `SIZE` little-endian bytes
+    fn to_le_chunk(self, chunk: &mut [u8]);
+}
+
+macro_rules! impl_primitive {
+    ($($type:ty),*) => {
+        $(
+            impl sealed::Sealed for $type {}
+
+            impl Primitive for $type {
+                const SIZE: usize = size_of::<$type>();
+
+                #[inline]
+                fn from_le_chunk(chunk: &[u8]) -> Self {
+                    <$type>::from_le_bytes(chunk.try_into().unwrap())
+                }
+
+                #[inline]
+                fn to_le_chunk(self, chunk: &mut [u8]) {
+                    chunk.copy_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive!(i8, u8, i16, u16, i32, u32, i64, u64, f32, f64);
+
+/// Encode elements into `values.len() * T::SIZE` bytes
+///
+/// # Panics
+///
+/// Panics if `out` has a different length.
+#[inline]
+pub fn encode<T: Primitive>(values: &[T], out: &mut [u8]) {
+    assert_eq!(out.len(), values.len() * T::SIZE, "Invalid bulk output size!");
+    if cfg!(target_endian = "little") {
+        out.copy_from_slice(bytemuck::cast_slice(values));
+    } else {
+        for (chunk, &value) in out.chunks_exact_mut(T::SIZE).zip(values) {
+            value.to_le_chunk(chunk);
+        }
+    }
+}
+
+/// Decode all elements of `data`, which must hold a whole number of elements
+///
+/// # Panics
+///
+/// Panics if the length of `data` is not a multiple of `T::SIZE`.
+#[must_use]
+#[inline]
+pub fn decode<T: Primitive>(data: &[u8]) -> Vec<T> {
+    assert_eq!(data.len() % T::SIZE, 0, "Invalid bulk input size!");
+    if cfg!(target_endian = "little") {
+        let mut values = vec![T::zeroed(); data.len() / T::SIZE];
+        bytemuck::cast_slice_mut(&mut values).copy_from_slice(data);
+        values
+    } else {
+        data.chunks_exact(T::SIZE).map(T::from_le_chunk).collect()
+    }
+}
+
+/// View `data` as elements without copying
+///
+/// Returns `None` on big-endian targets, or when `data` is not aligned for
+/// `T` or does not hold a whole number of elements; use [`decode`] then.
+#[must_use]
+#[inline]
+pub fn view<T: Primitive>(data: &[u8]) -> Option<&[T]> {
+    if cfg!(target_endian = "little") {
+        bytemuck::try_cast_slice(data).ok()
+    } else {
+        None
+    }
+}
@@ -0,0 +1,133 @@
+//! Fast Binary Encoding character conversions
+//!
+//! FBE `char` is a single byte and maps to Rust [`char`] through Latin-1
+//! (ISO 8859-1, a superset of ASCII), so only `U+0000..=U+00FF` can be
+//! written. FBE `wchar` is a 4-byte code point and must be a valid Unicode
+//! scalar value (no surrogates, at most `U+10FFFF`).
+
+use core::fmt;
+
+/// Character that cannot be represented as FBE `char`/`wchar`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharError {
+    /// Character outside Latin-1 written as `char`
+    NotLatin1(char),
+    /// `wchar` code point that is not a Unicode scalar value
+    InvalidWChar(u32),
+}
+
+impl fmt::Display for CharError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CharError::NotLatin1(value) => {
+                write!(f, "Character {:?} (U+{:04X}) is not Latin-1", value, u32::from(*value))
+            }
+            CharError::InvalidWChar(value) => write!(f, "Invalid wchar value: {:#x}", value),
+        }
+    }
+}
+
+impl core::error::Error for CharError {}
+
+/// Convert character into FBE `char` byte
+pub fn encode_char(value: char) -> Result<u8, CharError> {
+    u8::try_from(value).map_err(|_| CharError::NotLatin1(value))
+}
+
+/// Convert FBE `char` byte into character
+#[must_use]
+pub fn decode_char(value: u8) -> char {
+    char::from(value)
+}
+
+/// Convert character into FBE `wchar` code point
+#[must_use]
+pub fn encode_wchar(value: char) -> u32 {
+    u32::from(value)
+}
+
+/// Convert FBE `wchar` code point into character
+pub fn decode_wchar(value: u32) -> Result<char, CharError> {
+    char::from_u32(value).ok_or(CharError::InvalidWChar(value))
+}
+
+/// FBE `char` codec for `char` fields
+///
+/// Rust [`char`] encodes as FBE `wchar` by default. Fields declared as FBE
+/// `char` (`#[fbe(char)]` in the derive, `char` in fbec schemas) use these
+/// functions instead of [`FinalValue`](crate::final_model::FinalValue) and
+/// [`FieldValue`](crate::field_model::FieldValue): one Latin-1 byte, `?` for
+/// characters outside Latin-1. [`check_chars`] reports those characters
+/// before writing, as generated `try_serialize` does.
+pub mod latin1 {
+    use super::{decode_char, encode_char, CharError};
+    use crate::buffer::{ReadBuffer, WriteBuffer};
+    use crate::limits::{DecodeBudget, DecodeError};
+    use crate::vectored::VectoredWriter;
+
+    /// Size of the field slot in the Model layout
+    pub const FBE_SIZE: usize = 1;
+
+    /// Encode character, `?` outside Latin-1
+    #[must_use]
+    pub fn encode_lossy(value: char) -> u8 {
+        encode_char(value).unwrap_or(b'?')
+    }
+
+    /// Check that the character is Latin-1
+    pub fn check_chars(value: &char) -> Result<(), CharError> {
+        encode_char(*value).map(|_| ())
+    }
+
+    /// Get serialized size in bytes
+    pub fn final_size(_value: &char) -> usize {
+        1
+    }
+
+    /// Write character at offset and return serialized size
+    pub fn write_final(value: &char, buffer: &mut WriteBuffer, offset: usize) -> usize {
+        buffer.write_u8(offset, encode_lossy(*value));
+        1
+    }
+
+    /// Read character at offset and return (value, serialized size)
+    pub fn read_final(buffer: &ReadBuffer, offset: usize) -> (char, usize) {
+        (decode_char(buffer.read_u8(offset)), 1)
+    }
+
+    /// Get serialized size of the character at offset
+    pub fn read_final_size(_buffer: &ReadBuffer, _offset: usize) -> usize {
+        1
+    }
+
+    /// Read character at offset, checking bounds
+    pub fn try_read_final(buffer: &ReadBuffer, offset: usize, _budget: &mut DecodeBudget) -> Result<(char, usize), DecodeError> {
+        buffer.check_bounds(offset, 1)?;
+        Ok(read_final(buffer, offset))
+    }
+
+    /// Append character to a vectored writer
+    pub fn write_vectored(value: &char, writer: &mut VectoredWriter<'_>) {
+        writer.append_inline(&encode_lossy(*value));
+    }
+
+    /// Get size of the data written behind pointers (none)
+    pub fn fbe_extra(_value: &char) -> usize {
+        0
+    }
+
+    /// Write character into the Model slot at offset
+    pub fn write_field(value: &char, buffer: &mut WriteBuffer, _base: usize, offset: usize) {
+        write_final(value, buffer, offset);
+    }
+
+    /// Read character from the Model slot at offset
+    pub fn read_field(buffer: &ReadBuffer, _base: usize, offset: usize) -> char {
+        read_final(buffer, offset).0
+    }
+
+    /// Read character from the Model slot at offset, checking bounds
+    pub fn try_read_field(buffer: &ReadBuffer, _base: usize, offset: usize, budget: &mut DecodeBudget) -> Result<char, DecodeError> {
+        Ok(try_read_final(buffer, offset, budget)?.0)
+    }
+}
@@ -0,0 +1,830 @@
+//! Fast Binary Encoding decimal type
+//!
+//! FBE `decimal` uses the .NET `System.Decimal` layout: a 96-bit unsigned
+//! mantissa, a power-of-ten scale (0-28) and a sign, 16 bytes on the wire:
+//!
+//! | Bytes | Content                       |
+//! |-------|-------------------------------|
+//! | 0-11  | mantissa (little-endian)      |
+//! | 12-13 | zero                          |
+//! | 14    | scale                         |
+//! | 15    | sign (`0x80` = negative)      |
+//!
+//! Arithmetic is exact while the result fits, otherwise it is rounded to the
+//! largest scale that fits (banker's rounding for operators, any
+//! [`RoundingMode`] with the `*_round` methods). Results that do not fit at
+//! scale 0 are an overflow.
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use core::str::FromStr;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Exclusive upper bound of the 96-bit mantissa
+const MANTISSA_LIMIT: u128 = 1 << 96;
+
+/// Maximum decimal scale (digits after the decimal point)
+pub const MAX_SCALE: u8 = 28;
+
+/// FBE decimal value
+///
+/// Equality, ordering and hashing compare numeric values, so `1.0 == 1.00`.
+#[derive(Clone, Copy, Default)]
+pub struct Decimal {
+    mantissa: u128,
+    scale: u8,
+    negative: bool,
+}
+
+/// Decimal construction, parsing or arithmetic error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecimalError {
+    /// Value does not fit into a 96-bit mantissa
+    Overflow,
+    /// Scale is greater than 28
+    ScaleOutOfRange(u32),
+    /// Division by zero
+    DivisionByZero,
+    /// Invalid decimal string
+    Parse(String),
+}
+
+impl fmt::Display for DecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecimalError::Overflow => f.write_str("Decimal overflow"),
+            DecimalError::ScaleOutOfRange(scale) => {
+                write!(f, "Decimal scale {} is out of range (0-{})", scale, MAX_SCALE)
+            }
+            DecimalError::DivisionByZero => f.write_str("Decimal division by zero"),
+            DecimalError::Parse(text) => write!(f, "Invalid decimal: {:?}", text),
+        }
+    }
+}
+
+impl core::error::Error for DecimalError {}
+
+/// Rounding mode for dropped decimal digits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round half to even (banker's rounding, .NET default)
+    #[default]
+    HalfEven,
+    /// Round half away from zero
+    HalfUp,
+    /// Round half toward zero
+    HalfDown,
+    /// Round away from zero
+    Up,
+    /// Round toward zero (truncate)
+    Down,
+    /// Round toward positive infinity
+    Ceiling,
+    /// Round toward negative infinity
+    Floor,
+}
+
+impl RoundingMode {
+    /// Check if the kept magnitude must be incremented, given the first
+    /// dropped digit and whether any further dropped digit is non-zero
+    fn round_up(self, digit: u8, sticky: bool, odd: bool, negative: bool) -> bool {
+        let inexact = digit != 0 || sticky;
+        match self {
+            RoundingMode::HalfEven => digit > 5 || (digit == 5 && (sticky || odd)),
+            RoundingMode::HalfUp => digit >= 5,
+            RoundingMode::HalfDown => digit > 5 || (digit == 5 && sticky),
+            RoundingMode::Up => inexact,
+            RoundingMode::Down => false,
+            RoundingMode::Ceiling => inexact && !negative,
+            RoundingMode::Floor => inexact && negative,
+        }
+    }
+}
+
+impl Decimal {
+    /// Zero
+    pub const ZERO: Self = Self::from_parts_unchecked(0, 0, false);
+    /// One
+    pub const ONE: Self = Self::from_parts_unchecked(1, 0, false);
+    /// Largest value (79228162514264337593543950335)
+    pub const MAX: Self = Self::from_parts_unchecked(MANTISSA_LIMIT - 1, 0, false);
+    /// Smallest value (-79228162514264337593543950335)
+    pub const MIN: Self = Self::from_parts_unchecked(MANTISSA_LIMIT - 1, 0, true);
+
+    const fn from_parts_unchecked(mantissa: u128, scale: u8, negative: bool) -> Self {
+        Self {
+            mantissa,
+            scale,
+            negative: negative && mantissa != 0,
+        }
+    }
+
+    /// Create decimal from unsigned mantissa, scale and sign
+    pub fn from_parts(mantissa: u128, scale: u8, negative: bool) -> Result<Self, DecimalError> {
+        if mantissa >= MANTISSA_LIMIT {
+            return Err(DecimalError::Overflow);
+        }
+        if scale > MAX_SCALE {
+            return Err(DecimalError::ScaleOutOfRange(scale.into()));
+        }
+        Ok(Self::from_parts_unchecked(mantissa, scale, negative))
+    }
+
+    /// Create decimal from signed mantissa and scale, e.g. `(-12345, 2)` is `-123.45`
+    pub fn try_new(mantissa: i128, scale: u8) -> Result<Self, DecimalError> {
+        Self::from_parts(mantissa.unsigned_abs(), scale, mantissa < 0)
+    }
+
+    /// Split into unsigned mantissa, scale and sign
+    #[must_use]
+    pub const fn into_parts(self) -> (u128, u8, bool) {
+        (self.mantissa, self.scale, self.negative)
+    }
+
+    /// Signed mantissa (value = mantissa / 10^scale)
+    #[must_use]
+    pub const fn mantissa(&self) -> i128 {
+        if self.negative {
+            -(self.mantissa as i128)
+        } else {
+            self.mantissa as i128
+        }
+    }
+
+    /// Number of digits after the decimal point
+    #[must_use]
+    pub const fn scale(&self) -> u8 {
+        self.scale
+    }
+
+    /// Check if value is negative (zero is never negative)
+    #[must_use]
+    pub const fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Check if value is zero
+    #[must_use]
+    pub const fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    /// Absolute value
+    #[must_use]
+    pub const fn abs(self) -> Self {
+        Self::from_parts_unchecked(self.mantissa, self.scale, false)
+    }
+
+    /// Remove trailing fractional zeros, e.g. `1.2300` becomes `1.23`
+    #[must_use]
+    pub fn normalize(self) -> Self {
+        let (mut mantissa, mut scale) = (self.mantissa, self.scale);
+        while scale > 0 && mantissa % 10 == 0 {
+            mantissa /= 10;
+            scale -= 1;
+        }
+        Self::from_parts_unchecked(mantissa, scale, self.negative)
+    }
+
+    /// Round to at most `scale` digits after the decimal point
+    #[must_use]
+    pub fn round_dp(self, scale: u8, mode: RoundingMode) -> Self {
+        if scale >= self.scale {
+            return self;
+        }
+        let drop = u32::from(self.scale - scale);
+        round_wide(
+            Wide::from_u128(self.mantissa),
+            self.scale.into(),
+            self.negative,
+            false,
+            mode,
+            drop,
+        )
+        .expect("rounding to fewer digits cannot overflow")
+    }
+
+    /// Convert to the nearest `f64`
+    #[must_use]
+    pub fn to_f64(&self) -> f64 {
+        // Parsing the decimal text rounds once, to the nearest value
+        self.to_string().parse().expect("Decimal text is a valid float")
+    }
+
+    // ========================================================================
+    // Arithmetic
+    // ========================================================================
+
+    /// Add with the given rounding mode for digits that do not fit
+    pub fn add_round(self, other: Self, mode: RoundingMode) -> Result<Self, DecimalError> {
+        let scale = self.scale.max(other.scale);
+        let a = self.upscaled(scale);
+        let b = other.upscaled(scale);
+        let (mantissa, negative) = if self.negative == other.negative {
+            (a.add(b), self.negative)
+        } else if a >= b {
+            (a.sub(b), self.negative)
+        } else {
+            (b.sub(a), other.negative)
+        };
+        round_wide(mantissa, scale.into(), negative, false, mode, 0)
+    }
+
+    /// Subtract with the given rounding mode for digits that do not fit
+    pub fn sub_round(self, other: Self, mode: RoundingMode) -> Result<Self, DecimalError> {
+        self.add_round(-other, mode)
+    }
+
+    /// Multiply with the given rounding mode for digits that do not fit
+    pub fn mul_round(self, other: Self, mode: RoundingMode) -> Result<Self, DecimalError> {
+        let mantissa = Wide::from_u128(self.mantissa)
+            .mul_u128(other.mantissa)
+            .expect("96-bit product fits into 256 bits");
+        let scale = u32::from(self.scale) + u32::from(other.scale);
+        round_wide(mantissa, scale, self.negative != other.negative, false, mode, 0)
+    }
+
+    /// Divide with the given rounding mode for digits that do not fit
+    ///
+    /// Exact quotients keep only the digits they need (`1 / 4 = 0.25`),
+    /// inexact ones use as many digits as fit (`1 / 3 = 0.3333333333333333333333333333`).
+    pub fn div_round(self, other: Self, mode: RoundingMode) -> Result<Self, DecimalError> {
+        if other.is_zero() {
+            return Err(DecimalError::DivisionByZero);
+        }
+        let divisor = other.mantissa;
+        let digits_limit = pow10(MAX_SCALE.into());
+        let mut quotient = self.mantissa / divisor;
+        let mut remainder = self.mantissa % divisor;
+        let mut scale = i32::from(self.scale) - i32::from(other.scale);
+
+        // Long division, one decimal digit at a time
+        while scale < 0 || (remainder != 0 && scale < MAX_SCALE.into() && quotient < digits_limit) {
+            if quotient >= digits_limit {
+                return Err(DecimalError::Overflow);
+            }
+            remainder *= 10;
+            quotient = quotient * 10 + remainder / divisor;
+            remainder %= divisor;
+            scale += 1;
+        }
+
+        // Guard digit for rounding
+        remainder *= 10;
+        quotient = quotient * 10 + remainder / divisor;
+        remainder %= divisor;
+        scale += 1;
+
+        round_wide(
+            Wide::from_u128(quotient),
+            scale as u32,
+            self.negative != other.negative,
+            remainder != 0,
+            mode,
+            1,
+        )
+    }
+
+    /// Checked addition (banker's rounding), `None` on overflow
+    #[must_use]
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.add_round(other, RoundingMode::HalfEven).ok()
+    }
+
+    /// Checked subtraction (banker's rounding), `None` on overflow
+    #[must_use]
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.sub_round(other, RoundingMode::HalfEven).ok()
+    }
+
+    /// Checked multiplication (banker's rounding), `None` on overflow
+    #[must_use]
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        self.mul_round(other, RoundingMode::HalfEven).ok()
+    }
+
+    /// Checked division (banker's rounding), `None` on overflow or division by zero
+    #[must_use]
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        self.div_round(other, RoundingMode::HalfEven).ok()
+    }
+
+    /// Mantissa scaled up to a larger scale
+    fn upscaled(&self, scale: u8) -> Wide {
+        Wide::from_u128(self.mantissa)
+            .mul_u128(pow10((scale - self.scale).into()))
+            .expect("96-bit mantissa times 10^28 fits into 256 bits")
+    }
+
+    // ========================================================================
+    // FBE binary layout
+    // ========================================================================
+
+    /// Encode into the 16-byte FBE layout
+    #[must_use]
+    pub fn to_bytes(&self) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        bytes[..12].copy_from_slice(&self.mantissa.to_le_bytes()[..12]);
+        bytes[14] = self.scale;
+        bytes[15] = if self.negative { 0x80 } else { 0x00 };
+        bytes
+    }
+
+    /// Decode from the 16-byte FBE layout
+    pub fn from_bytes(bytes: [u8; 16]) -> Result<Self, DecimalError> {
+        let (mantissa, scale, negative) = split_bytes(&bytes);
+        Self::from_parts(mantissa, scale, negative)
+    }
+
+    /// Decode from the 16-byte FBE layout, rounding an invalid scale into range
+    pub(crate) fn from_bytes_lossy(bytes: &[u8]) -> Self {
+        let (mantissa, scale, negative) = split_bytes(bytes);
+        round_wide(
+            Wide::from_u128(mantissa),
+            scale.into(),
+            negative,
+            false,
+            RoundingMode::HalfEven,
+            0,
+        )
+        .expect("reducing the scale of a 96-bit mantissa cannot overflow")
+    }
+}
+
+/// Split FBE layout into mantissa, scale and sign
+fn split_bytes(bytes: &[u8]) -> (u128, u8, bool) {
+    let mut mantissa = [0u8; 16];
+    mantissa[..12].copy_from_slice(&bytes[..12]);
+    (u128::from_le_bytes(mantissa), bytes[14], bytes[15] & 0x80 != 0)
+}
+
+#[inline]
+fn pow10(exp: u32) -> u128 {
+    10u128.pow(exp)
+}
+
+/// Round a wide mantissa into a decimal, dropping at least `min_drop` digits
+/// and then as many as needed to fit the scale and mantissa limits
+fn round_wide(
+    mut mantissa: Wide,
+    mut scale: u32,
+    negative: bool,
+    mut sticky: bool,
+    mode: RoundingMode,
+    min_drop: u32,
+) -> Result<Decimal, DecimalError> {
+    let mut dropped = 0;
+    let mut digit = 0;
+    while dropped < min_drop || scale > u32::from(MAX_SCALE) || !mantissa.fits_mantissa() {
+        if scale == 0 {
+            return Err(DecimalError::Overflow);
+        }
+        sticky |= digit != 0;
+        let (quotient, remainder) = mantissa.divrem_u64(10);
+        mantissa = quotient;
+        digit = remainder as u8;
+        scale -= 1;
+        dropped += 1;
+    }
+
+    let mut value = mantissa.to_u128().expect("mantissa fits into 96 bits");
+    if dropped > 0 && mode.round_up(digit, sticky, value & 1 == 1, negative) {
+        value += 1;
+        if value == MANTISSA_LIMIT {
+            return round_wide(Wide::from_u128(value), scale, negative, false, mode, 1);
+        }
+    }
+    Ok(Decimal::from_parts_unchecked(value, scale as u8, negative))
+}
+
+// ============================================================================
+// 256-bit unsigned helper for exact intermediate results
+// ============================================================================
+
+/// Unsigned 256-bit integer (little-endian 64-bit limbs)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Wide([u64; 4]);
+
+impl Wide {
+    fn from_u128(value: u128) -> Self {
+        Self([value as u64, (value >> 64) as u64, 0, 0])
+    }
+
+    fn to_u128(self) -> Option<u128> {
+        (self.0[2] == 0 && self.0[3] == 0)
+            .then(|| u128::from(self.0[0]) | u128::from(self.0[1]) << 64)
+    }
+
+    fn fits_mantissa(self) -> bool {
+        self.to_u128().is_some_and(|value| value < MANTISSA_LIMIT)
+    }
+
+    fn mul_u128(self, factor: u128) -> Option<Self> {
+        let factor = [factor as u64, (factor >> 64) as u64];
+        let mut result = [0u64; 6];
+        for (j, &f) in factor.iter().enumerate() {
+            let mut carry = 0u128;
+            for (i, &limb) in self.0.iter().enumerate() {
+                let value = u128::from(result[i + j]) + u128::from(limb) * u128::from(f) + carry;
+                result[i + j] = value as u64;
+                carry = value >> 64;
+            }
+            for slot in &mut result[4 + j..] {
+                let value = u128::from(*slot) + carry;
+                *slot = value as u64;
+                carry = value >> 64;
+            }
+        }
+        (result[4] == 0 && result[5] == 0)
+            .then(|| Self([result[0], result[1], result[2], result[3]]))
+    }
+
+    fn add(self, other: Self) -> Self {
+        let mut result = [0u64; 4];
+        let mut carry = 0u128;
+        for (i, slot) in result.iter_mut().enumerate() {
+            let value = u128::from(self.0[i]) + u128::from(other.0[i]) + carry;
+            *slot = value as u64;
+            carry = value >> 64;
+        }
+        debug_assert_eq!(carry, 0, "decimal intermediate sum overflow");
+        Self(result)
+    }
+
+    /// Subtract a smaller or equal value
+    fn sub(self, other: Self) -> Self {
+        let mut result = [0u64; 4];
+        let mut borrow = false;
+        for (i, slot) in result.iter_mut().enumerate() {
+            let (value, b1) = self.0[i].overflowing_sub(other.0[i]);
+            let (value, b2) = value.overflowing_sub(u64::from(borrow));
+            *slot = value;
+            borrow = b1 || b2;
+        }
+        Self(result)
+    }
+
+    fn divrem_u64(self, divisor: u64) -> (Self, u64) {
+        let mut result = [0u64; 4];
+        let mut remainder = 0u128;
+        for i in (0..4).rev() {
+            let value = remainder << 64 | u128::from(self.0[i]);
+            result[i] = (value / u128::from(divisor)) as u64;
+            remainder = value % u128::from(divisor);
+        }
+        (Self(result), remainder as u64)
+    }
+}
+
+impl PartialOrd for Wide {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Wide {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+// ============================================================================
+// Comparison
+// ============================================================================
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let magnitude = || {
+            let scale = self.scale.max(other.scale);
+            self.upscaled(scale).cmp(&other.upscaled(scale))
+        };
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => magnitude(),
+            (true, true) => magnitude().reverse(),
+        }
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalize().into_parts().hash(state);
+    }
+}
+
+// ============================================================================
+// Operators (banker's rounding, panic on overflow like integer operators)
+// ============================================================================
+
+impl Neg for Decimal {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::from_parts_unchecked(self.mantissa, self.scale, !self.negative)
+    }
+}
+
+macro_rules! impl_decimal_operator {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $round:ident, $message:literal) => {
+        impl $trait for Decimal {
+            type Output = Self;
+
+            fn $method(self, other: Self) -> Self {
+                match self.$round(other, RoundingMode::HalfEven) {
+                    Ok(value) => value,
+                    Err(err) => panic!(concat!("attempt to ", $message, ": {}"), err),
+                }
+            }
+        }
+
+        impl $assign_trait for Decimal {
+            fn $assign_method(&mut self, other: Self) {
+                *self = $trait::$method(*self, other);
+            }
+        }
+    };
+}
+
+impl_decimal_operator!(Add, add, AddAssign, add_assign, add_round, "add decimals");
+impl_decimal_operator!(Sub, sub, SubAssign, sub_assign, sub_round, "subtract decimals");
+impl_decimal_operator!(Mul, mul, MulAssign, mul_assign, mul_round, "multiply decimals");
+impl_decimal_operator!(Div, div, DivAssign, div_assign, div_round, "divide decimals");
+
+// ============================================================================
+// Text format
+// ============================================================================
+
+impl fmt::Display for Decimal {
+    /// Plain notation keeping the scale, e.g. `-123.450`; a precision
+    /// (`{:.2}`) rounds half to even or pads with zeros
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (value, padding) = match f.precision() {
+            Some(precision) if precision < self.scale.into() => {
+                (self.round_dp(precision as u8, RoundingMode::HalfEven), 0)
+            }
+            Some(precision) => (*self, precision - usize::from(self.scale)),
+            None => (*self, 0),
+        };
+
+        let digits = value.mantissa.to_string();
+        let scale = usize::from(value.scale);
+        let mut text = String::with_capacity(digits.len() + scale + padding + 2);
+        if scale == 0 {
+            text.push_str(&digits);
+            if padding > 0 {
+                text.push('.');
+            }
+        } else if digits.len() > scale {
+            let (integer, fraction) = digits.split_at(digits.len() - scale);
+            text.push_str(integer);
+            text.push('.');
+            text.push_str(fraction);
+        } else {
+            text.push_str("0.");
+            text.push_str(&"0".repeat(scale - digits.len()));
+            text.push_str(&digits);
+        }
+        text.push_str(&"0".repeat(padding));
+        // `pad` would truncate to the precision, `pad_integral` only handles sign and width
+        f.pad_integral(!value.negative, "", &text)
+    }
+}
+
+impl fmt::Debug for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Decimal({})", self)
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = DecimalError;
+
+    /// Parse plain notation (`-123.45`, `+.5`, `42`); digits beyond the
+    /// 96-bit/28-scale limits are rounded half to even
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let error = || DecimalError::Parse(text.to_string());
+        let (negative, unsigned) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(error());
+        }
+
+        let mut mantissa = Wide::from_u128(0);
+        for c in integer.bytes().chain(fraction.bytes()) {
+            if !c.is_ascii_digit() {
+                return Err(error());
+            }
+            mantissa = mantissa
+                .mul_u128(10)
+                .ok_or_else(error)?
+                .add(Wide::from_u128(u128::from(c - b'0')));
+        }
+        round_wide(
+            mantissa,
+            fraction.len() as u32,
+            negative,
+            false,
+            RoundingMode::HalfEven,
+            0,
+        )
+    }
+}
+
+// ============================================================================
+// Conversions
+// ============================================================================
+
+macro_rules! impl_decimal_from_int {
+    ($($type:ty),*) => {
+        $(
+            impl From<$type> for Decimal {
+                fn from(value: $type) -> Self {
+                    Self::from_parts_unchecked(value.unsigned_abs().into(), 0, value < 0)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_decimal_from_uint {
+    ($($type:ty),*) => {
+        $(
+            impl From<$type> for Decimal {
+                fn from(value: $type) -> Self {
+                    Self::from_parts_unchecked(value.into(), 0, false)
+                }
+            }
+        )*
+    };
+}
+
+impl_decimal_from_int!(i8, i16, i32, i64);
+impl_decimal_from_uint!(u8, u16, u32, u64);
+
+impl TryFrom<i128> for Decimal {
+    type Error = DecimalError;
+
+    fn try_from(value: i128) -> Result<Self, Self::Error> {
+        Self::try_new(value, 0)
+    }
+}
+
+impl TryFrom<u128> for Decimal {
+    type Error = DecimalError;
+
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        Self::from_parts(value, 0, false)
+    }
+}
+
+impl TryFrom<f64> for Decimal {
+    type Error = DecimalError;
+
+    /// Shortest decimal representation of the float, rounded to fit
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        if !value.is_finite() {
+            return Err(DecimalError::Parse(value.to_string()));
+        }
+        value.to_string().parse()
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl From<rust_decimal::Decimal> for Decimal {
+    fn from(value: rust_decimal::Decimal) -> Self {
+        Self::from_parts_unchecked(
+            value.mantissa().unsigned_abs(),
+            value.scale() as u8,
+            value.is_sign_negative(),
+        )
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl From<Decimal> for rust_decimal::Decimal {
+    fn from(value: Decimal) -> Self {
+        rust_decimal::Decimal::from_i128_with_scale(value.mantissa(), value.scale.into())
+    }
+}
+
+// ============================================================================
+// serde (FBE JSON: string, binary formats: 16-byte FBE layout)
+// ============================================================================
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Decimal {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serde::Serialize::serialize(&self.to_bytes(), serializer)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Decimal {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let text = <alloc::borrow::Cow<'de, str> as serde::Deserialize>::deserialize(deserializer)?;
+            text.parse().map_err(serde::de::Error::custom)
+        } else {
+            let bytes = <[u8; 16] as serde::Deserialize>::deserialize(deserializer)?;
+            Self::from_bytes(bytes).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+// ============================================================================
+// proptest (any 96-bit mantissa, scale and sign)
+// ============================================================================
+
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for Decimal {
+    type Parameters = ();
+    type Strategy = proptest::strategy::Map<
+        (core::ops::Range<u128>, core::ops::RangeInclusive<u8>, proptest::bool::Any),
+        fn((u128, u8, bool)) -> Self,
+    >;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        proptest::strategy::Strategy::prop_map(
+            (0..MANTISSA_LIMIT, 0..=MAX_SCALE, proptest::bool::ANY),
+            |(mantissa, scale, negative)| Self::from_parts_unchecked(mantissa, scale, negative),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(text: &str) -> Decimal {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_round_wide_modes() {
+        let cases = [
+            ("2.5", [2, 3, 2, 3, 2, 3, 2]),
+            ("-2.5", [-2, -3, -2, -3, -2, -2, -3]),
+            ("2.51", [3, 3, 3, 3, 2, 3, 2]),
+            ("3.5", [4, 4, 3, 4, 3, 4, 3]),
+            ("-0.1", [0, 0, 0, -1, 0, 0, -1]),
+        ];
+        let modes = [
+            RoundingMode::HalfEven,
+            RoundingMode::HalfUp,
+            RoundingMode::HalfDown,
+            RoundingMode::Up,
+            RoundingMode::Down,
+            RoundingMode::Ceiling,
+            RoundingMode::Floor,
+        ];
+        for (text, expected) in cases {
+            for (mode, expected) in modes.iter().zip(expected) {
+                assert_eq!(
+                    dec(text).round_dp(0, *mode),
+                    Decimal::from(expected),
+                    "{} {:?}",
+                    text,
+                    mode
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_wide() {
+        let max = Wide::from_u128(u128::MAX);
+        let product = max.mul_u128(u128::MAX).unwrap();
+        assert_eq!(product.0, [1, 0, u64::MAX - 1, u64::MAX]);
+        assert!(product.mul_u128(2).is_none());
+        assert_eq!(product.sub(product), Wide::from_u128(0));
+        let (quotient, remainder) = Wide::from_u128(1234567).divrem_u64(10);
+        assert_eq!((quotient.to_u128(), remainder), (Some(123456), 7));
+        assert!(Wide::from_u128(MANTISSA_LIMIT - 1).fits_mantissa());
+        assert!(!Wide::from_u128(MANTISSA_LIMIT).fits_mantissa());
+    }
+}
@@ -3,6 +3,8 @@
  */
 
 use crate::buffer::{ReadBuffer, WriteBuffer};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 // Numeric defaults
 #[derive(Debug, Clone, PartialEq)]
@@ -4,6 +4,18 @@
 //! Following original FBE design with Rust zero-cost abstractions.
 
 use crate::buffer::{ReadBuffer, WriteBuffer};
+use crate::chars::CharError;
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+use crate::collections::{HashMap, HashSet};
+use crate::decimal::Decimal;
+use crate::limits::{DecodeBudget, DecodeError, DecodeLimits};
+use crate::timestamp::Timestamp;
+use crate::uuid::Uuid;
+use alloc::collections::{BTreeMap, BTreeSet};
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+use core::hash::Hash;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 /// Base trait for all field models
 pub trait FieldModel {
@@ -45,6 +57,19 @@ pub trait FieldModel {
 
 macro_rules! impl_primitive_field_model {
     ($name:ident, $name_mut:ident, $type:ty, $size:expr, $read_fn:ident, $write_fn:ident) => {
+        impl_primitive_field_model!($name, $name_mut, $type, $type, (), $size, $read_fn, $write_fn);
+    };
+    // Fallible values: `get` returns `$get` and `set` returns `$set`
+    (
+        $name:ident,
+        $name_mut:ident,
+        $type:ty,
+        $get:ty,
+        $set:ty,
+        $size:expr,
+        $read_fn:ident,
+        $write_fn:ident
+    ) => {
         pub struct $name<'a> {
             buffer: &'a [u8],
             offset: usize,
@@ -55,8 +80,8 @@ macro_rules! impl_primitive_field_model {
                 Self { buffer, offset }
             }
 
-            pub fn get(&self) -> $type {
-                ReadBuffer::from(self.buffer.to_vec()).$read_fn(self.offset)
+            pub fn get(&self) -> $get {
+                ReadBuffer::borrowed(self.buffer).$read_fn(self.offset)
             }
         }
 
@@ -82,8 +107,8 @@ macro_rules! impl_primitive_field_model {
                 Self { buffer, offset }
             }
 
-            pub fn set(&mut self, value: $type) {
-                self.buffer.$write_fn(self.offset, value);
+            pub fn set(&mut self, value: $type) -> $set {
+                self.buffer.$write_fn(self.offset, value)
             }
         }
 
@@ -114,8 +139,26 @@ impl_primitive_field_model!(
     write_bool
 );
 impl_primitive_field_model!(FieldModelByte, FieldModelByteMut, u8, 1, read_byte, write_byte);
-impl_primitive_field_model!(FieldModelChar, FieldModelCharMut, u8, 1, read_char, write_char);
-impl_primitive_field_model!(FieldModelWChar, FieldModelWCharMut, u32, 4, read_wchar, write_wchar);
+impl_primitive_field_model!(
+    FieldModelChar,
+    FieldModelCharMut,
+    char,
+    char,
+    Result<(), CharError>,
+    1,
+    read_char,
+    write_char
+);
+impl_primitive_field_model!(
+    FieldModelWChar,
+    FieldModelWCharMut,
+    char,
+    Result<char, CharError>,
+    (),
+    4,
+    read_wchar,
+    write_wchar
+);
 impl_primitive_field_model!(FieldModelI8, FieldModelI8Mut, i8, 1, read_i8, write_i8);
 impl_primitive_field_model!(FieldModelI16, FieldModelI16Mut, i16, 2, read_i16, write_i16);
 impl_primitive_field_model!(FieldModelI32, FieldModelI32Mut, i32, 4, read_i32, write_i32);
@@ -142,7 +185,12 @@ impl<'a> FieldModelString<'a> {
     }
 
     pub fn get(&self) -> String {
-        ReadBuffer::from(self.buffer.to_vec()).read_string(self.offset)
+        ReadBuffer::borrowed(self.buffer).read_string(self.offset)
+    }
+
+    /// Get value, checking its length against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<String, DecodeError> {
+        ReadBuffer::borrowed(self.buffer).try_read_string(self.offset, budget)
     }
 }
 
@@ -200,7 +248,7 @@ impl<'a> FieldModel for FieldModelStringMut<'a> {
 impl_primitive_field_model!(
     FieldModelTimestamp,
     FieldModelTimestampMut,
-    u64,
+    Timestamp,
     8,
     read_timestamp,
     write_timestamp
@@ -210,72 +258,14 @@ impl_primitive_field_model!(
 // UUID
 // ============================================================================
 
-pub struct FieldModelUuid<'a> {
-    buffer: &'a [u8],
-    offset: usize,
-}
-
-impl<'a> FieldModelUuid<'a> {
-    pub fn new(buffer: &'a [u8], offset: usize) -> Self {
-        Self { buffer, offset }
-    }
-
-    pub fn get(&self) -> String {
-        let bytes = ReadBuffer::from(self.buffer.to_vec()).read_uuid(self.offset);
-        // Convert binary to UUID string
-        format!("{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
-            bytes[0], bytes[1], bytes[2], bytes[3],
-            bytes[4], bytes[5],
-            bytes[6], bytes[7],
-            bytes[8], bytes[9],
-            bytes[10], bytes[11], bytes[12], bytes[13], bytes[14], bytes[15])
-    }
-}
-
-impl<'a> FieldModel for FieldModelUuid<'a> {
-    fn offset(&self) -> usize {
-        self.offset
-    }
-    fn set_offset(&mut self, offset: usize) {
-        self.offset = offset;
-    }
-    fn size(&self) -> usize {
-        16
-    }
-}
-
-pub struct FieldModelUuidMut<'a> {
-    buffer: &'a mut WriteBuffer,
-    offset: usize,
-}
-
-impl<'a> FieldModelUuidMut<'a> {
-    pub fn new(buffer: &'a mut WriteBuffer, offset: usize) -> Self {
-        Self { buffer, offset }
-    }
-
-    pub fn set(&mut self, value: &str) {
-        // Parse UUID string to binary
-        let uuid_str = value.replace("-", "");
-        let mut bytes = [0u8; 16];
-        for i in 0..16 {
-            bytes[i] = u8::from_str_radix(&uuid_str[i * 2..i * 2 + 2], 16).unwrap_or(0);
-        }
-        self.buffer.write_uuid(self.offset, &bytes);
-    }
-}
-
-impl<'a> FieldModel for FieldModelUuidMut<'a> {
-    fn offset(&self) -> usize {
-        self.offset
-    }
-    fn set_offset(&mut self, offset: usize) {
-        self.offset = offset;
-    }
-    fn size(&self) -> usize {
-        16
-    }
-}
+impl_primitive_field_model!(
+    FieldModelUuid,
+    FieldModelUuidMut,
+    Uuid,
+    16,
+    read_uuid,
+    write_uuid
+);
 
 // ============================================================================
 // Bytes
@@ -292,7 +282,12 @@ impl<'a> FieldModelBytes<'a> {
     }
 
     pub fn get(&self) -> Vec<u8> {
-        ReadBuffer::from(self.buffer.to_vec()).read_bytes(self.offset)
+        ReadBuffer::borrowed(self.buffer).read_bytes(self.offset)
+    }
+
+    /// Get value, checking its length against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<Vec<u8>, DecodeError> {
+        ReadBuffer::borrowed(self.buffer).try_read_bytes(self.offset, budget)
     }
 }
 
@@ -347,66 +342,31 @@ impl<'a> FieldModel for FieldModelBytesMut<'a> {
 // Decimal
 // ============================================================================
 
-pub struct FieldModelDecimal<'a> {
-    buffer: &'a [u8],
-    offset: usize,
-}
-
-impl<'a> FieldModelDecimal<'a> {
-    pub fn new(buffer: &'a [u8], offset: usize) -> Self {
-        Self { buffer, offset }
-    }
-
-    pub fn get(&self) -> (i128, u8, bool) {
-        ReadBuffer::from(self.buffer.to_vec()).read_decimal(self.offset)
-    }
-}
-
-impl<'a> FieldModel for FieldModelDecimal<'a> {
-    fn offset(&self) -> usize {
-        self.offset
-    }
-    fn set_offset(&mut self, offset: usize) {
-        self.offset = offset;
-    }
-    fn size(&self) -> usize {
-        16
-    }
-}
-
-pub struct FieldModelDecimalMut<'a> {
-    buffer: &'a mut WriteBuffer,
-    offset: usize,
-}
-
-impl<'a> FieldModelDecimalMut<'a> {
-    pub fn new(buffer: &'a mut WriteBuffer, offset: usize) -> Self {
-        Self { buffer, offset }
-    }
-
-    pub fn set(&mut self, value: i128, scale: u8, negative: bool) {
-        self.buffer
-            .write_decimal(self.offset, value, scale, negative);
-    }
-}
-
-impl<'a> FieldModel for FieldModelDecimalMut<'a> {
-    fn offset(&self) -> usize {
-        self.offset
-    }
-    fn set_offset(&mut self, offset: usize) {
-        self.offset = offset;
-    }
-    fn size(&self) -> usize {
-        16
-    }
-}
-
+impl_primitive_field_model!(
+    FieldModelDecimal,
+    FieldModelDecimalMut,
+    Decimal,
+    16,
+    read_decimal,
+    write_decimal
+);
 
 // ============================================================================
 // Collection Field Models
 // ============================================================================
 
+/// Check a pointer-based collection header and its items against the budget
+fn try_collection(buffer: &[u8], offset: usize, item_size: usize, budget: &mut DecodeBudget) -> Result<(), DecodeError> {
+    let read_buf = ReadBuffer::borrowed(buffer);
+    let pointer = read_buf.try_read_u32(offset)? as usize;
+    if pointer == 0 {
+        return Ok(());
+    }
+    let size = read_buf.try_read_u32(pointer)? as usize;
+    budget.collection(size, item_size)?;
+    read_buf.check_items(pointer + 4, size, item_size)
+}
+
 /// FieldModel for vector<T> (pointer-based, dynamic size)
 pub struct FieldModelVector<'a, T> {
     buffer: &'a [u8],
@@ -437,11 +397,17 @@ impl<'a, T> FieldModelVector<'a, T> {
         let mut item_offset = pointer + 4;
         for _ in 0..size {
             result.push((self.item_model)(self.buffer, item_offset));
-            item_offset += std::mem::size_of::<T>();
+            item_offset += core::mem::size_of::<T>();
         }
         
         result
     }
+
+    /// Get collection, checking its length against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<Vec<T>, DecodeError> {
+        try_collection(self.buffer, self.offset, core::mem::size_of::<T>(), budget)?;
+        Ok(self.get())
+    }
 }
 
 impl<'a, T> FieldModel for FieldModelVector<'a, T> {
@@ -476,11 +442,17 @@ impl<'a, T, const N: usize> FieldModelArray<'a, T, N> {
         
         for _ in 0..N {
             result.push((self.item_model)(self.buffer, item_offset));
-            item_offset += std::mem::size_of::<T>();
+            item_offset += core::mem::size_of::<T>();
         }
         
         result.try_into().unwrap_or_else(|_| panic!("Array size mismatch"))
     }
+
+    /// Get array, checking that it lies inside the buffer
+    pub fn try_get(&self) -> Result<[T; N], DecodeError> {
+        ReadBuffer::borrowed(self.buffer).check_items(self.offset, N, core::mem::size_of::<T>())?;
+        Ok(self.get())
+    }
 }
 
 impl<'a, T, const N: usize> FieldModel for FieldModelArray<'a, T, N> {
@@ -493,10 +465,11 @@ impl<'a, T, const N: usize> FieldModel for FieldModelArray<'a, T, N> {
     }
 
     fn size(&self) -> usize {
-        N * std::mem::size_of::<T>()
+        N * core::mem::size_of::<T>()
     }
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 /// FieldModel for map<K, V> (pointer-based)
 pub struct FieldModelMap<'a, K, V> {
     buffer: &'a [u8],
@@ -505,6 +478,7 @@ pub struct FieldModelMap<'a, K, V> {
     value_model: fn(&'a [u8], usize) -> V,
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 impl<'a, K, V> FieldModelMap<'a, K, V> {
     pub fn new(
         buffer: &'a [u8],
@@ -515,34 +489,44 @@ impl<'a, K, V> FieldModelMap<'a, K, V> {
         Self { buffer, offset, key_model, value_model }
     }
 
-    pub fn get(&self) -> std::collections::HashMap<K, V>
+    pub fn get(&self) -> crate::collections::HashMap<K, V>
     where
-        K: std::hash::Hash + Eq,
+        K: core::hash::Hash + Eq,
     {
         let mut read_buf = ReadBuffer::new();
         read_buf.attach_buffer(self.buffer, 0, self.buffer.len());
         
         let pointer = read_buf.read_u32(self.offset) as usize;
         if pointer == 0 {
-            return std::collections::HashMap::new();
+            return crate::collections::HashMap::new();
         }
         
         let size = read_buf.read_u32(pointer) as usize;
-        let mut result = std::collections::HashMap::with_capacity(size);
+        let mut result = crate::collections::HashMap::with_capacity(size);
         
         let mut item_offset = pointer + 4;
         for _ in 0..size {
             let key = (self.key_model)(self.buffer, item_offset);
-            item_offset += std::mem::size_of::<K>();
+            item_offset += core::mem::size_of::<K>();
             let value = (self.value_model)(self.buffer, item_offset);
-            item_offset += std::mem::size_of::<V>();
+            item_offset += core::mem::size_of::<V>();
             result.insert(key, value);
         }
         
         result
     }
+
+    /// Get collection, checking its length against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<crate::collections::HashMap<K, V>, DecodeError>
+    where
+        K: core::hash::Hash + Eq,
+    {
+        try_collection(self.buffer, self.offset, core::mem::size_of::<K>() + core::mem::size_of::<V>(), budget)?;
+        Ok(self.get())
+    }
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 impl<'a, K, V> FieldModel for FieldModelMap<'a, K, V> {
     fn offset(&self) -> usize {
         self.offset
@@ -557,6 +541,7 @@ impl<'a, K, V> FieldModel for FieldModelMap<'a, K, V> {
     }
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 /// FieldModel for set<T> (pointer-based)
 pub struct FieldModelSet<'a, T> {
     buffer: &'a [u8],
@@ -564,36 +549,47 @@ pub struct FieldModelSet<'a, T> {
     item_model: fn(&'a [u8], usize) -> T,
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 impl<'a, T> FieldModelSet<'a, T> {
     pub fn new(buffer: &'a [u8], offset: usize, item_model: fn(&'a [u8], usize) -> T) -> Self {
         Self { buffer, offset, item_model }
     }
 
-    pub fn get(&self) -> std::collections::HashSet<T>
+    pub fn get(&self) -> crate::collections::HashSet<T>
     where
-        T: std::hash::Hash + Eq,
+        T: core::hash::Hash + Eq,
     {
         let mut read_buf = ReadBuffer::new();
         read_buf.attach_buffer(self.buffer, 0, self.buffer.len());
         
         let pointer = read_buf.read_u32(self.offset) as usize;
         if pointer == 0 {
-            return std::collections::HashSet::new();
+            return crate::collections::HashSet::new();
         }
         
         let size = read_buf.read_u32(pointer) as usize;
-        let mut result = std::collections::HashSet::with_capacity(size);
+        let mut result = crate::collections::HashSet::with_capacity(size);
         
         let mut item_offset = pointer + 4;
         for _ in 0..size {
             result.insert((self.item_model)(self.buffer, item_offset));
-            item_offset += std::mem::size_of::<T>();
+            item_offset += core::mem::size_of::<T>();
         }
         
         result
     }
+
+    /// Get collection, checking its length against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<crate::collections::HashSet<T>, DecodeError>
+    where
+        T: core::hash::Hash + Eq,
+    {
+        try_collection(self.buffer, self.offset, core::mem::size_of::<T>(), budget)?;
+        Ok(self.get())
+    }
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 impl<'a, T> FieldModel for FieldModelSet<'a, T> {
     fn offset(&self) -> usize {
         self.offset
@@ -620,26 +616,32 @@ impl<'a, T> FieldModelList<'a, T> {
         Self { buffer, offset, item_model }
     }
 
-    pub fn get(&self) -> std::collections::LinkedList<T> {
+    pub fn get(&self) -> alloc::collections::LinkedList<T> {
         let mut read_buf = ReadBuffer::new();
         read_buf.attach_buffer(self.buffer, 0, self.buffer.len());
         
         let pointer = read_buf.read_u32(self.offset) as usize;
         if pointer == 0 {
-            return std::collections::LinkedList::new();
+            return alloc::collections::LinkedList::new();
         }
         
         let size = read_buf.read_u32(pointer) as usize;
-        let mut result = std::collections::LinkedList::new();
+        let mut result = alloc::collections::LinkedList::new();
         
         let mut item_offset = pointer + 4;
         for _ in 0..size {
             result.push_back((self.item_model)(self.buffer, item_offset));
-            item_offset += std::mem::size_of::<T>();
+            item_offset += core::mem::size_of::<T>();
         }
         
         result
     }
+
+    /// Get collection, checking its length against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<alloc::collections::LinkedList<T>, DecodeError> {
+        try_collection(self.buffer, self.offset, core::mem::size_of::<T>(), budget)?;
+        Ok(self.get())
+    }
 }
 
 impl<'a, T> FieldModel for FieldModelList<'a, T> {
@@ -673,7 +675,7 @@ where
     buffer: &'a [u8],
     offset: usize,
     value_model_fn: fn(&'a [u8], usize) -> M,
-    _phantom: std::marker::PhantomData<T>,
+    _phantom: core::marker::PhantomData<T>,
 }
 
 impl<'a, T, M> FieldModelOptional<'a, T, M>
@@ -686,7 +688,7 @@ where
             buffer,
             offset,
             value_model_fn,
-            _phantom: std::marker::PhantomData,
+            _phantom: core::marker::PhantomData,
         }
     }
 
@@ -753,7 +755,7 @@ where
     buffer: &'a mut WriteBuffer,
     offset: usize,
     value_model_fn: fn(&'a mut WriteBuffer, usize) -> M,
-    _phantom: std::marker::PhantomData<T>,
+    _phantom: core::marker::PhantomData<T>,
 }
 
 impl<'a, T, M> FieldModelOptionalMut<'a, T, M>
@@ -770,7 +772,7 @@ where
             buffer,
             offset,
             value_model_fn,
-            _phantom: std::marker::PhantomData,
+            _phantom: core::marker::PhantomData,
         }
     }
 
@@ -809,4 +811,580 @@ where
     }
 }
 
+// ============================================================================
+// FieldValue (pointer-based Model layout)
+// ============================================================================
+
+/// Value encoding in the pointer-based FBE Model layout
+///
+/// Every value takes a fixed slot of [`FBE_SIZE`](Self::FBE_SIZE) bytes in
+/// its parent. Strings, bytes, collections, optionals and structs keep a
+/// `u32` pointer in the slot and their data at the end of the buffer. As in
+/// upstream FBE, pointers are relative to `base`: the body of the enclosing
+/// struct, the value of the enclosing optional, or the start of the message.
+///
+/// Offsets and `base` are relative to the buffer offset, like the
+/// `read_*`/`write_*` methods. `read_field` trusts its input; use
+/// [`try_read_field`](Self::try_read_field) for untrusted data.
+pub trait FieldValue: Sized {
+    /// Size of the field slot in bytes
+    const FBE_SIZE: usize;
+
+    /// Size of the data written behind pointers, allocated by `write_field`
+    fn fbe_extra(&self) -> usize;
+
+    /// Write value into the slot at offset, allocating pointed-to data
+    fn write_field(&self, buffer: &mut WriteBuffer, base: usize, offset: usize);
+
+    /// Read value from the slot at offset
+    fn read_field(buffer: &ReadBuffer, base: usize, offset: usize) -> Self;
+
+    /// Read value from the slot at offset, checking bounds, pointers and lengths against the budget
+    fn try_read_field(buffer: &ReadBuffer, base: usize, offset: usize, budget: &mut DecodeBudget) -> Result<Self, DecodeError>;
+}
+
+/// Struct in the Model layout
+///
+/// The struct body is `[u32 size][u32 type][fields]`. Structs extending a
+/// base struct inline the base fields first instead of pointing to them.
+pub trait FieldStruct: FieldValue {
+    /// FBE type id stored in the struct body
+    const FBE_TYPE: usize;
+
+    /// Size of the field slots in the struct body
+    const FIELDS_SIZE: usize;
+
+    /// Size of the data written behind the field pointers
+    fn fields_extra(&self) -> usize;
+
+    /// Write field slots at offset, with pointers relative to the struct body
+    fn write_fields(&self, buffer: &mut WriteBuffer, body: usize, offset: usize);
+
+    /// Read field slots at offset
+    fn read_fields(buffer: &ReadBuffer, body: usize, offset: usize) -> Self;
+
+    /// Read field slots at offset within the budget
+    fn try_read_fields(buffer: &ReadBuffer, body: usize, offset: usize, budget: &mut DecodeBudget) -> Result<Self, DecodeError>;
+}
+
+/// Allocate `size` bytes at the end of the buffer and point to them from the slot at offset
+fn allocate_field(buffer: &mut WriteBuffer, base: usize, offset: usize, size: usize) -> usize {
+    let data = buffer.allocate(size) - buffer.offset();
+    buffer.write_u32(offset, (data - base) as u32);
+    data
+}
+
+/// Read the pointer in the slot at offset, `None` for a null pointer
+fn try_read_pointer(buffer: &ReadBuffer, base: usize, offset: usize) -> Result<Option<usize>, DecodeError> {
+    match buffer.try_read_u32(offset)? {
+        0 => Ok(None),
+        pointer => Ok(Some(base.saturating_add(pointer as usize))),
+    }
+}
+
+/// [`FieldValue::fbe_extra`] of a struct
+pub fn struct_extra<T: FieldStruct>(value: &T) -> usize {
+    8 + T::FIELDS_SIZE + value.fields_extra()
+}
+
+/// [`FieldValue::write_field`] of a struct: allocate the body and write its header and fields
+pub fn write_struct<T: FieldStruct>(value: &T, buffer: &mut WriteBuffer, base: usize, offset: usize) {
+    let size = 8 + T::FIELDS_SIZE;
+    let body = allocate_field(buffer, base, offset, size);
+    buffer.write_u32(body, size as u32);
+    buffer.write_u32(body + 4, T::FBE_TYPE as u32);
+    value.write_fields(buffer, body, body + 8);
+}
+
+/// [`FieldValue::read_field`] of a struct
+pub fn read_struct<T: FieldStruct>(buffer: &ReadBuffer, base: usize, offset: usize) -> T {
+    let body = base + buffer.read_u32(offset) as usize;
+    T::read_fields(buffer, body, body + 8)
+}
+
+/// [`FieldValue::try_read_field`] of a struct
+///
+/// Fails on a null pointer or a body smaller than the known fields.
+pub fn try_read_struct<T: FieldStruct>(buffer: &ReadBuffer, base: usize, offset: usize, budget: &mut DecodeBudget) -> Result<T, DecodeError> {
+    let body = try_read_pointer(buffer, base, offset)?.ok_or(DecodeError::InvalidModel { offset })?;
+    let size = buffer.try_read_u32(body)? as usize;
+    if size < 8 + T::FIELDS_SIZE {
+        return Err(DecodeError::InvalidModel { offset: body });
+    }
+    buffer.check_bounds(body, size)?;
+    budget.enter()?;
+    let value = T::try_read_fields(buffer, body, body + 8, budget)?;
+    budget.leave();
+    Ok(value)
+}
+
+/// Write a struct in the Model layout at offset and return the full size
+///
+/// The layout is a `u32` full size (header included) and a `u32` pointer to
+/// the struct body, followed by the body and all pointed-to data. Anything
+/// in the buffer after offset is replaced.
+pub fn write_model<T: FieldStruct>(value: &T, buffer: &mut WriteBuffer, offset: usize) -> usize {
+    let start = buffer.offset() + offset;
+    buffer.reserve(start + 8 + value.fbe_extra());
+    buffer.resize(start + 8);
+    value.write_field(buffer, offset, offset + 4);
+    let size = buffer.size() - start;
+    buffer.write_u32(offset, size as u32);
+    size
+}
+
+/// Full size of the Model at offset, 0 if the header is out of bounds
+pub fn model_size(data: &[u8], offset: usize) -> usize {
+    match data.get(offset..offset.saturating_add(4)) {
+        Some(header) => u32::from_le_bytes([header[0], header[1], header[2], header[3]]) as usize,
+        None => 0,
+    }
+}
+
+/// Read a struct in the Model layout at offset
+pub fn read_model<T: FieldStruct>(data: &[u8], offset: usize) -> T {
+    T::read_field(&ReadBuffer::borrowed(data), offset, offset + 4)
+}
+
+/// Read a struct in the Model layout at offset from untrusted input
+///
+/// Pointers must stay within the full size from the header.
+pub fn try_read_model<T: FieldStruct>(data: &[u8], offset: usize, limits: &DecodeLimits) -> Result<T, DecodeError> {
+    let buffer = ReadBuffer::borrowed(data);
+    let size = buffer.try_read_u32(offset)? as usize;
+    let mut budget = DecodeBudget::for_message(*limits, size)?;
+    if size < 8 {
+        return Err(DecodeError::InvalidModel { offset });
+    }
+    buffer.check_bounds(offset, size)?;
+    let message = ReadBuffer::borrowed(&data[..offset + size]);
+    T::try_read_field(&message, offset, offset + 4, &mut budget)
+}
+
+/// Verify the Model at offset: sizes, pointers and the struct type id
+pub fn verify_model<T: FieldStruct>(data: &[u8], offset: usize) -> bool {
+    let buffer = ReadBuffer::borrowed(data);
+    let fbe_type = try_read_pointer(&buffer, offset, offset + 4)
+        .ok()
+        .flatten()
+        .and_then(|body| buffer.try_read_u32(body.saturating_add(4)).ok());
+    fbe_type == Some(T::FBE_TYPE as u32) && try_read_model::<T>(data, offset, &DecodeLimits::unlimited()).is_ok()
+}
+
+macro_rules! impl_primitive_field_value {
+    ($type:ty, $size:expr, $read_fn:ident, $write_fn:ident) => {
+        impl FieldValue for $type {
+            const FBE_SIZE: usize = $size;
+
+            #[inline]
+            fn fbe_extra(&self) -> usize {
+                0
+            }
 
+            #[inline]
+            fn write_field(&self, buffer: &mut WriteBuffer, _base: usize, offset: usize) {
+                buffer.$write_fn(offset, *self);
+            }
+
+            #[inline]
+            fn read_field(buffer: &ReadBuffer, _base: usize, offset: usize) -> Self {
+                buffer.$read_fn(offset)
+            }
+
+            #[inline]
+            fn try_read_field(buffer: &ReadBuffer, _base: usize, offset: usize, _budget: &mut DecodeBudget) -> Result<Self, DecodeError> {
+                buffer.check_bounds(offset, $size)?;
+                Ok(buffer.$read_fn(offset))
+            }
+        }
+    };
+}
+
+impl_primitive_field_value!(bool, 1, read_bool, write_bool);
+impl_primitive_field_value!(i8, 1, read_i8, write_i8);
+impl_primitive_field_value!(i16, 2, read_i16, write_i16);
+impl_primitive_field_value!(i32, 4, read_i32, write_i32);
+impl_primitive_field_value!(i64, 8, read_i64, write_i64);
+impl_primitive_field_value!(u8, 1, read_u8, write_u8);
+impl_primitive_field_value!(u16, 2, read_u16, write_u16);
+impl_primitive_field_value!(u32, 4, read_u32, write_u32);
+impl_primitive_field_value!(u64, 8, read_u64, write_u64);
+impl_primitive_field_value!(f32, 4, read_f32, write_f32);
+impl_primitive_field_value!(f64, 8, read_f64, write_f64);
+impl_primitive_field_value!(Decimal, 16, read_decimal, write_decimal);
+impl_primitive_field_value!(Timestamp, 8, read_timestamp, write_timestamp);
+impl_primitive_field_value!(Uuid, 16, read_uuid, write_uuid);
+
+/// Rust `char` is FBE `wchar`, as in the FinalModel layout
+impl FieldValue for char {
+    const FBE_SIZE: usize = 4;
+
+    #[inline]
+    fn fbe_extra(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn write_field(&self, buffer: &mut WriteBuffer, _base: usize, offset: usize) {
+        buffer.write_wchar(offset, *self);
+    }
+
+    #[inline]
+    fn read_field(buffer: &ReadBuffer, _base: usize, offset: usize) -> Self {
+        buffer.read_wchar(offset).unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+
+    #[inline]
+    fn try_read_field(buffer: &ReadBuffer, _base: usize, offset: usize, _budget: &mut DecodeBudget) -> Result<Self, DecodeError> {
+        buffer.check_bounds(offset, 4)?;
+        Ok(buffer.read_wchar(offset)?)
+    }
+}
+
+impl FieldValue for String {
+    const FBE_SIZE: usize = 4;
+
+    fn fbe_extra(&self) -> usize {
+        4 + self.len()
+    }
+
+    fn write_field(&self, buffer: &mut WriteBuffer, base: usize, offset: usize) {
+        let data = allocate_field(buffer, base, offset, 4 + self.len());
+        buffer.write_string(data, self);
+    }
+
+    fn read_field(buffer: &ReadBuffer, base: usize, offset: usize) -> Self {
+        match buffer.read_u32(offset) as usize {
+            0 => String::new(),
+            pointer => buffer.read_string(base + pointer),
+        }
+    }
+
+    fn try_read_field(buffer: &ReadBuffer, base: usize, offset: usize, budget: &mut DecodeBudget) -> Result<Self, DecodeError> {
+        match try_read_pointer(buffer, base, offset)? {
+            Some(data) => buffer.try_read_string(data, budget),
+            None => Ok(String::new()),
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl FieldValue for bytes::Bytes {
+    const FBE_SIZE: usize = 4;
+
+    fn fbe_extra(&self) -> usize {
+        4 + self.len()
+    }
+
+    fn write_field(&self, buffer: &mut WriteBuffer, base: usize, offset: usize) {
+        let data = allocate_field(buffer, base, offset, 4 + self.len());
+        buffer.write_bytes(data, self);
+    }
+
+    fn read_field(buffer: &ReadBuffer, base: usize, offset: usize) -> Self {
+        match buffer.read_u32(offset) as usize {
+            0 => bytes::Bytes::new(),
+            pointer => buffer.read_bytes_shared(base + pointer),
+        }
+    }
+
+    fn try_read_field(buffer: &ReadBuffer, base: usize, offset: usize, budget: &mut DecodeBudget) -> Result<Self, DecodeError> {
+        let Some(data) = try_read_pointer(buffer, base, offset)? else {
+            return Ok(bytes::Bytes::new());
+        };
+        let len = buffer.try_read_u32(data)? as usize;
+        budget.string(len)?;
+        buffer.check_bounds(data + 4, len)?;
+        Ok(buffer.read_bytes_shared(data))
+    }
+}
+
+/// Optional values are a `u8` has_value flag and a pointer to the value
+///
+/// Pointers inside the value are relative to the value itself.
+impl<T: FieldValue> FieldValue for Option<T> {
+    const FBE_SIZE: usize = 5;
+
+    fn fbe_extra(&self) -> usize {
+        self.as_ref().map_or(0, |value| T::FBE_SIZE + value.fbe_extra())
+    }
+
+    fn write_field(&self, buffer: &mut WriteBuffer, base: usize, offset: usize) {
+        match self {
+            Some(value) => {
+                buffer.write_u8(offset, 1);
+                let data = allocate_field(buffer, base, offset + 1, T::FBE_SIZE);
+                value.write_field(buffer, data, data);
+            }
+            None => {
+                buffer.write_u8(offset, 0);
+                buffer.write_u32(offset + 1, 0);
+            }
+        }
+    }
+
+    fn read_field(buffer: &ReadBuffer, base: usize, offset: usize) -> Self {
+        if buffer.read_u8(offset) == 0 {
+            return None;
+        }
+        let data = base + buffer.read_u32(offset + 1) as usize;
+        Some(T::read_field(buffer, data, data))
+    }
+
+    fn try_read_field(buffer: &ReadBuffer, base: usize, offset: usize, budget: &mut DecodeBudget) -> Result<Self, DecodeError> {
+        buffer.check_bounds(offset, 5)?;
+        if buffer.read_u8(offset) == 0 {
+            return Ok(None);
+        }
+        let data = try_read_pointer(buffer, base, offset + 1)?.ok_or(DecodeError::InvalidModel { offset })?;
+        Ok(Some(T::try_read_field(buffer, data, data, budget)?))
+    }
+}
+
+/// Arrays are inline item slots
+impl<T: FieldValue, const N: usize> FieldValue for [T; N] {
+    const FBE_SIZE: usize = N * T::FBE_SIZE;
+
+    fn fbe_extra(&self) -> usize {
+        self.iter().map(FieldValue::fbe_extra).sum()
+    }
+
+    fn write_field(&self, buffer: &mut WriteBuffer, base: usize, offset: usize) {
+        for (index, item) in self.iter().enumerate() {
+            item.write_field(buffer, base, offset + index * T::FBE_SIZE);
+        }
+    }
+
+    fn read_field(buffer: &ReadBuffer, base: usize, offset: usize) -> Self {
+        core::array::from_fn(|index| T::read_field(buffer, base, offset + index * T::FBE_SIZE))
+    }
+
+    fn try_read_field(buffer: &ReadBuffer, base: usize, offset: usize, budget: &mut DecodeBudget) -> Result<Self, DecodeError> {
+        let items = (0..N)
+            .map(|index| T::try_read_field(buffer, base, offset + index * T::FBE_SIZE, budget))
+            .collect::<Result<Vec<T>, DecodeError>>()?;
+        match items.try_into() {
+            Ok(result) => Ok(result),
+            Err(_) => unreachable!("array reads exactly N items"),
+        }
+    }
+}
+
+/// Point to `[u32 count][item slots]`, items pointing relative to `base`
+fn write_field_items<'a, T, I>(buffer: &mut WriteBuffer, base: usize, offset: usize, count: usize, items: I)
+where
+    T: FieldValue + 'a,
+    I: Iterator<Item = &'a T>,
+{
+    let data = allocate_field(buffer, base, offset, 4 + count * T::FBE_SIZE);
+    buffer.write_u32(data, count as u32);
+    for (index, item) in items.enumerate() {
+        item.write_field(buffer, base, data + 4 + index * T::FBE_SIZE);
+    }
+}
+
+fn read_field_items<T, C>(buffer: &ReadBuffer, base: usize, offset: usize) -> C
+where
+    T: FieldValue,
+    C: FromIterator<T>,
+{
+    let data = match buffer.read_u32(offset) as usize {
+        0 => return core::iter::empty().collect(),
+        pointer => base + pointer,
+    };
+    let count = buffer.read_u32(data) as usize;
+    (0..count)
+        .map(|index| T::read_field(buffer, base, data + 4 + index * T::FBE_SIZE))
+        .collect()
+}
+
+fn try_read_field_items<T, C>(buffer: &ReadBuffer, base: usize, offset: usize, budget: &mut DecodeBudget) -> Result<C, DecodeError>
+where
+    T: FieldValue,
+    C: FromIterator<T>,
+{
+    let Some(data) = try_read_pointer(buffer, base, offset)? else {
+        return Ok(core::iter::empty().collect());
+    };
+    let count = buffer.try_read_u32(data)? as usize;
+    budget.collection(count, core::mem::size_of::<T>())?;
+    buffer.check_items(data + 4, count, T::FBE_SIZE)?;
+    budget.enter()?;
+    let items = (0..count)
+        .map(|index| T::try_read_field(buffer, base, data + 4 + index * T::FBE_SIZE, budget))
+        .collect::<Result<C, DecodeError>>()?;
+    budget.leave();
+    Ok(items)
+}
+
+/// Point to `[u32 count][key/value slots]`
+fn write_field_entries<'a, K, V, I>(buffer: &mut WriteBuffer, base: usize, offset: usize, count: usize, entries: I)
+where
+    K: FieldValue + 'a,
+    V: FieldValue + 'a,
+    I: Iterator<Item = (&'a K, &'a V)>,
+{
+    let entry_size = K::FBE_SIZE + V::FBE_SIZE;
+    let data = allocate_field(buffer, base, offset, 4 + count * entry_size);
+    buffer.write_u32(data, count as u32);
+    for (index, (key, value)) in entries.enumerate() {
+        let entry = data + 4 + index * entry_size;
+        key.write_field(buffer, base, entry);
+        value.write_field(buffer, base, entry + K::FBE_SIZE);
+    }
+}
+
+fn read_field_entries<K, V, C>(buffer: &ReadBuffer, base: usize, offset: usize) -> C
+where
+    K: FieldValue,
+    V: FieldValue,
+    C: FromIterator<(K, V)>,
+{
+    let data = match buffer.read_u32(offset) as usize {
+        0 => return core::iter::empty().collect(),
+        pointer => base + pointer,
+    };
+    let count = buffer.read_u32(data) as usize;
+    let entry_size = K::FBE_SIZE + V::FBE_SIZE;
+    (0..count)
+        .map(|index| {
+            let entry = data + 4 + index * entry_size;
+            (K::read_field(buffer, base, entry), V::read_field(buffer, base, entry + K::FBE_SIZE))
+        })
+        .collect()
+}
+
+fn try_read_field_entries<K, V, C>(buffer: &ReadBuffer, base: usize, offset: usize, budget: &mut DecodeBudget) -> Result<C, DecodeError>
+where
+    K: FieldValue,
+    V: FieldValue,
+    C: FromIterator<(K, V)>,
+{
+    let Some(data) = try_read_pointer(buffer, base, offset)? else {
+        return Ok(core::iter::empty().collect());
+    };
+    let count = buffer.try_read_u32(data)? as usize;
+    let entry_size = K::FBE_SIZE + V::FBE_SIZE;
+    budget.collection(count, core::mem::size_of::<(K, V)>())?;
+    buffer.check_items(data + 4, count, entry_size)?;
+    budget.enter()?;
+    let entries = (0..count)
+        .map(|index| {
+            let entry = data + 4 + index * entry_size;
+            let key = K::try_read_field(buffer, base, entry, budget)?;
+            let value = V::try_read_field(buffer, base, entry + K::FBE_SIZE, budget)?;
+            Ok((key, value))
+        })
+        .collect::<Result<C, DecodeError>>()?;
+    budget.leave();
+    Ok(entries)
+}
+
+/// Vectors point to `[u32 count][item slots]`; `Vec<u8>` is the `bytes` layout
+impl<T: FieldValue> FieldValue for Vec<T> {
+    const FBE_SIZE: usize = 4;
+
+    fn fbe_extra(&self) -> usize {
+        4 + self.iter().map(|item| T::FBE_SIZE + item.fbe_extra()).sum::<usize>()
+    }
+
+    fn write_field(&self, buffer: &mut WriteBuffer, base: usize, offset: usize) {
+        write_field_items(buffer, base, offset, self.len(), self.iter());
+    }
+
+    fn read_field(buffer: &ReadBuffer, base: usize, offset: usize) -> Self {
+        read_field_items(buffer, base, offset)
+    }
+
+    fn try_read_field(buffer: &ReadBuffer, base: usize, offset: usize, budget: &mut DecodeBudget) -> Result<Self, DecodeError> {
+        try_read_field_items(buffer, base, offset, budget)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+impl<T: FieldValue + Eq + Hash> FieldValue for HashSet<T> {
+    const FBE_SIZE: usize = 4;
+
+    fn fbe_extra(&self) -> usize {
+        4 + self.iter().map(|item| T::FBE_SIZE + item.fbe_extra()).sum::<usize>()
+    }
+
+    fn write_field(&self, buffer: &mut WriteBuffer, base: usize, offset: usize) {
+        write_field_items(buffer, base, offset, self.len(), self.iter());
+    }
+
+    fn read_field(buffer: &ReadBuffer, base: usize, offset: usize) -> Self {
+        read_field_items(buffer, base, offset)
+    }
+
+    fn try_read_field(buffer: &ReadBuffer, base: usize, offset: usize, budget: &mut DecodeBudget) -> Result<Self, DecodeError> {
+        try_read_field_items(buffer, base, offset, budget)
+    }
+}
+
+impl<T: FieldValue + Ord> FieldValue for BTreeSet<T> {
+    const FBE_SIZE: usize = 4;
+
+    fn fbe_extra(&self) -> usize {
+        4 + self.iter().map(|item| T::FBE_SIZE + item.fbe_extra()).sum::<usize>()
+    }
+
+    fn write_field(&self, buffer: &mut WriteBuffer, base: usize, offset: usize) {
+        write_field_items(buffer, base, offset, self.len(), self.iter());
+    }
+
+    fn read_field(buffer: &ReadBuffer, base: usize, offset: usize) -> Self {
+        read_field_items(buffer, base, offset)
+    }
+
+    fn try_read_field(buffer: &ReadBuffer, base: usize, offset: usize, budget: &mut DecodeBudget) -> Result<Self, DecodeError> {
+        try_read_field_items(buffer, base, offset, budget)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+impl<K: FieldValue + Eq + Hash, V: FieldValue> FieldValue for HashMap<K, V> {
+    const FBE_SIZE: usize = 4;
+
+    fn fbe_extra(&self) -> usize {
+        4 + self
+            .iter()
+            .map(|(key, value)| K::FBE_SIZE + V::FBE_SIZE + key.fbe_extra() + value.fbe_extra())
+            .sum::<usize>()
+    }
+
+    fn write_field(&self, buffer: &mut WriteBuffer, base: usize, offset: usize) {
+        write_field_entries(buffer, base, offset, self.len(), self.iter());
+    }
+
+    fn read_field(buffer: &ReadBuffer, base: usize, offset: usize) -> Self {
+        read_field_entries(buffer, base, offset)
+    }
+
+    fn try_read_field(buffer: &ReadBuffer, base: usize, offset: usize, budget: &mut DecodeBudget) -> Result<Self, DecodeError> {
+        try_read_field_entries(buffer, base, offset, budget)
+    }
+}
+
+impl<K: FieldValue + Ord, V: FieldValue> FieldValue for BTreeMap<K, V> {
+    const FBE_SIZE: usize = 4;
+
+    fn fbe_extra(&self) -> usize {
+        4 + self
+            .iter()
+            .map(|(key, value)| K::FBE_SIZE + V::FBE_SIZE + key.fbe_extra() + value.fbe_extra())
+            .sum::<usize>()
+    }
+
+    fn write_field(&self, buffer: &mut WriteBuffer, base: usize, offset: usize) {
+        write_field_entries(buffer, base, offset, self.len(), self.iter());
+    }
+
+    fn read_field(buffer: &ReadBuffer, base: usize, offset: usize) -> Self {
+        read_field_entries(buffer, base, offset)
+    }
+
+    fn try_read_field(buffer: &ReadBuffer, base: usize, offset: usize, budget: &mut DecodeBudget) -> Result<Self, DecodeError> {
+        try_read_field_entries(buffer, base, offset, budget)
+    }
+}
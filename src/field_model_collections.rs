@@ -4,6 +4,9 @@
 
 use crate::buffer::{ReadBuffer, WriteBuffer};
 use crate::field_model::FieldModel;
+use crate::limits::{DecodeBudget, DecodeError};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 // ============================================================================
 // Vector<T> - Dynamic array
@@ -20,7 +23,12 @@ impl<'a> FieldModelVectorI32<'a> {
     }
 
     pub fn get(&self) -> Vec<i32> {
-        ReadBuffer::from(self.buffer.to_vec()).read_vector_i32(self.offset)
+        ReadBuffer::borrowed(self.buffer).read_vector_i32(self.offset)
+    }
+
+    /// Get values, checking their count against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<Vec<i32>, DecodeError> {
+        ReadBuffer::borrowed(self.buffer).try_read_vector_i32(self.offset, budget)
     }
 }
 
@@ -108,7 +116,12 @@ impl<'a> FieldModelArrayI32<'a> {
     }
 
     pub fn get(&self) -> Vec<i32> {
-        ReadBuffer::from(self.buffer.to_vec()).read_array_i32(self.offset, self.count)
+        ReadBuffer::borrowed(self.buffer).read_array_i32(self.offset, self.count)
+    }
+
+    /// Get values, checking bounds
+    pub fn try_get(&self) -> Result<Vec<i32>, DecodeError> {
+        ReadBuffer::borrowed(self.buffer).try_read_array_i32(self.offset, self.count)
     }
 }
 
@@ -167,24 +180,34 @@ impl<'a> FieldModel for FieldModelArrayI32Mut<'a> {
 // Map<K,V> - Key-value pairs
 // ============================================================================
 
-use std::collections::HashMap;
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+use crate::collections::HashMap;
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 pub struct FieldModelMapI32<'a> {
     buffer: &'a [u8],
     offset: usize,
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 impl<'a> FieldModelMapI32<'a> {
     pub fn new(buffer: &'a [u8], offset: usize) -> Self {
         Self { buffer, offset }
     }
 
     pub fn get(&self) -> HashMap<i32, i32> {
-        let pairs = ReadBuffer::from(self.buffer.to_vec()).read_map_i32(self.offset);
+        let pairs = ReadBuffer::borrowed(self.buffer).read_map_i32(self.offset);
         pairs.into_iter().collect()
     }
+
+    /// Get entries, checking their count against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<HashMap<i32, i32>, DecodeError> {
+        let pairs = ReadBuffer::borrowed(self.buffer).try_read_map_i32(self.offset, budget)?;
+        Ok(pairs.into_iter().collect())
+    }
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 impl<'a> FieldModel for FieldModelMapI32<'a> {
     fn offset(&self) -> usize {
         self.offset
@@ -222,11 +245,13 @@ impl<'a> FieldModel for FieldModelMapI32<'a> {
     }
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 pub struct FieldModelMapI32Mut<'a> {
     buffer: &'a mut WriteBuffer,
     offset: usize,
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 impl<'a> FieldModelMapI32Mut<'a> {
     pub fn new(buffer: &'a mut WriteBuffer, offset: usize) -> Self {
         Self { buffer, offset }
@@ -239,6 +264,7 @@ impl<'a> FieldModelMapI32Mut<'a> {
     }
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 impl<'a> FieldModel for FieldModelMapI32Mut<'a> {
     fn offset(&self) -> usize {
         self.offset
@@ -255,24 +281,34 @@ impl<'a> FieldModel for FieldModelMapI32Mut<'a> {
 // Set<T> - Unique values
 // ============================================================================
 
-use std::collections::HashSet;
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+use crate::collections::HashSet;
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 pub struct FieldModelSetI32<'a> {
     buffer: &'a [u8],
     offset: usize,
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 impl<'a> FieldModelSetI32<'a> {
     pub fn new(buffer: &'a [u8], offset: usize) -> Self {
         Self { buffer, offset }
     }
 
     pub fn get(&self) -> HashSet<i32> {
-        let vec = ReadBuffer::from(self.buffer.to_vec()).read_set_i32(self.offset);
+        let vec = ReadBuffer::borrowed(self.buffer).read_set_i32(self.offset);
         vec.into_iter().collect()
     }
+
+    /// Get values, checking their count against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<HashSet<i32>, DecodeError> {
+        let vec = ReadBuffer::borrowed(self.buffer).try_read_vector_i32(self.offset, budget)?;
+        Ok(vec.into_iter().collect())
+    }
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 impl<'a> FieldModel for FieldModelSetI32<'a> {
     fn offset(&self) -> usize {
         self.offset
@@ -310,11 +346,13 @@ impl<'a> FieldModel for FieldModelSetI32<'a> {
     }
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 pub struct FieldModelSetI32Mut<'a> {
     buffer: &'a mut WriteBuffer,
     offset: usize,
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 impl<'a> FieldModelSetI32Mut<'a> {
     pub fn new(buffer: &'a mut WriteBuffer, offset: usize) -> Self {
         Self { buffer, offset }
@@ -326,6 +364,7 @@ impl<'a> FieldModelSetI32Mut<'a> {
     }
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 impl<'a> FieldModel for FieldModelSetI32Mut<'a> {
     fn offset(&self) -> usize {
         self.offset
@@ -353,7 +392,12 @@ impl<'a> FieldModelVectorString<'a> {
     }
 
     pub fn get(&self) -> Vec<String> {
-        ReadBuffer::from(self.buffer.to_vec()).read_vector_string(self.offset)
+        ReadBuffer::borrowed(self.buffer).read_vector_string(self.offset)
+    }
+
+    /// Get values, checking their count and lengths against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<Vec<String>, DecodeError> {
+        ReadBuffer::borrowed(self.buffer).try_read_vector_string(self.offset, budget)
     }
 }
 
@@ -452,7 +496,12 @@ impl<'a> FieldModelArrayString<'a> {
     }
 
     pub fn get(&self) -> Vec<String> {
-        ReadBuffer::from(self.buffer.to_vec()).read_array_string(self.offset, self.count)
+        ReadBuffer::borrowed(self.buffer).read_array_string(self.offset, self.count)
+    }
+
+    /// Get values, checking every length against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<Vec<String>, DecodeError> {
+        ReadBuffer::borrowed(self.buffer).try_read_array_string(self.offset, self.count, budget)
     }
 }
 
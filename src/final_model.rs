@@ -4,6 +4,19 @@
 //! All data is inline, no pointers.
 
 use crate::buffer::{ReadBuffer, WriteBuffer};
+use crate::chars::CharError;
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+use crate::collections::{HashMap, HashSet};
+use crate::decimal::Decimal;
+use crate::limits::{DecodeBudget, DecodeError};
+use crate::timestamp::Timestamp;
+use crate::uuid::Uuid;
+use crate::vectored::VectoredWriter;
+use alloc::collections::{BTreeMap, BTreeSet};
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+use core::hash::Hash;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 /// Base trait for all final models
 pub trait FinalModel {
@@ -28,6 +41,19 @@ pub trait FinalModel {
 
 macro_rules! impl_primitive_final_model {
     ($name:ident, $name_mut:ident, $type:ty, $size:expr, $read_fn:ident, $write_fn:ident) => {
+        impl_primitive_final_model!($name, $name_mut, $type, $type, (), $size, $read_fn, $write_fn);
+    };
+    // Fallible values: `get` returns `$get` and `set` returns `$set`
+    (
+        $name:ident,
+        $name_mut:ident,
+        $type:ty,
+        $get:ty,
+        $set:ty,
+        $size:expr,
+        $read_fn:ident,
+        $write_fn:ident
+    ) => {
         pub struct $name<'a> {
             buffer: &'a [u8],
             offset: usize,
@@ -38,8 +64,8 @@ macro_rules! impl_primitive_final_model {
                 Self { buffer, offset }
             }
 
-            pub fn get(&self) -> $type {
-                ReadBuffer::from(self.buffer.to_vec()).$read_fn(self.offset)
+            pub fn get(&self) -> $get {
+                ReadBuffer::borrowed(self.buffer).$read_fn(self.offset)
             }
         }
 
@@ -65,8 +91,8 @@ macro_rules! impl_primitive_final_model {
                 Self { buffer, offset }
             }
 
-            pub fn set(&mut self, value: $type) {
-                self.buffer.$write_fn(self.offset, value);
+            pub fn set(&mut self, value: $type) -> $set {
+                self.buffer.$write_fn(self.offset, value)
             }
         }
 
@@ -97,8 +123,26 @@ impl_primitive_final_model!(
     write_bool
 );
 impl_primitive_final_model!(FinalModelByte, FinalModelByteMut, u8, 1, read_byte, write_byte);
-impl_primitive_final_model!(FinalModelChar, FinalModelCharMut, u8, 1, read_char, write_char);
-impl_primitive_final_model!(FinalModelWChar, FinalModelWCharMut, u32, 4, read_wchar, write_wchar);
+impl_primitive_final_model!(
+    FinalModelChar,
+    FinalModelCharMut,
+    char,
+    char,
+    Result<(), CharError>,
+    1,
+    read_char,
+    write_char
+);
+impl_primitive_final_model!(
+    FinalModelWChar,
+    FinalModelWCharMut,
+    char,
+    Result<char, CharError>,
+    (),
+    4,
+    read_wchar,
+    write_wchar
+);
 impl_primitive_final_model!(FinalModelI8, FinalModelI8Mut, i8, 1, read_i8, write_i8);
 impl_primitive_final_model!(FinalModelI16, FinalModelI16Mut, i16, 2, read_i16, write_i16);
 impl_primitive_final_model!(FinalModelI32, FinalModelI32Mut, i32, 4, read_i32, write_i32);
@@ -125,7 +169,12 @@ impl<'a> FinalModelString<'a> {
     }
 
     pub fn get(&self) -> String {
-        ReadBuffer::from(self.buffer.to_vec()).read_string(self.offset)
+        ReadBuffer::borrowed(self.buffer).read_string(self.offset)
+    }
+
+    /// Get value, checking its length against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<String, DecodeError> {
+        ReadBuffer::borrowed(self.buffer).try_read_string(self.offset, budget)
     }
 }
 
@@ -188,7 +237,12 @@ impl<'a> FinalModelBytes<'a> {
     }
 
     pub fn get(&self) -> Vec<u8> {
-        ReadBuffer::from(self.buffer.to_vec()).read_bytes(self.offset)
+        ReadBuffer::borrowed(self.buffer).read_bytes(self.offset)
+    }
+
+    /// Get value, checking its length against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<Vec<u8>, DecodeError> {
+        ReadBuffer::borrowed(self.buffer).try_read_bytes(self.offset, budget)
     }
 }
 
@@ -213,59 +267,14 @@ impl<'a> FinalModel for FinalModelBytes<'a> {
 // Decimal (inline format: 16 bytes)
 // ============================================================================
 
-pub struct FinalModelDecimal<'a> {
-    buffer: &'a [u8],
-    offset: usize,
-}
-
-impl<'a> FinalModelDecimal<'a> {
-    pub fn new(buffer: &'a [u8], offset: usize) -> Self {
-        Self { buffer, offset }
-    }
-
-    pub fn get(&self) -> (i128, u8, bool) {
-        ReadBuffer::from(self.buffer.to_vec()).read_decimal(self.offset)
-    }
-}
-
-impl<'a> FinalModel for FinalModelDecimal<'a> {
-    fn offset(&self) -> usize {
-        self.offset
-    }
-    fn set_offset(&mut self, offset: usize) {
-        self.offset = offset;
-    }
-    fn size(&self) -> usize {
-        16
-    }
-}
-
-pub struct FinalModelDecimalMut<'a> {
-    buffer: &'a mut WriteBuffer,
-    offset: usize,
-}
-
-impl<'a> FinalModelDecimalMut<'a> {
-    pub fn new(buffer: &'a mut WriteBuffer, offset: usize) -> Self {
-        Self { buffer, offset }
-    }
-
-    pub fn set(&mut self, value: i128, scale: u8, negative: bool) {
-        self.buffer.write_decimal(self.offset, value, scale, negative);
-    }
-}
-
-impl<'a> FinalModel for FinalModelDecimalMut<'a> {
-    fn offset(&self) -> usize {
-        self.offset
-    }
-    fn set_offset(&mut self, offset: usize) {
-        self.offset = offset;
-    }
-    fn size(&self) -> usize {
-        16
-    }
-}
+impl_primitive_final_model!(
+    FinalModelDecimal,
+    FinalModelDecimalMut,
+    Decimal,
+    16,
+    read_decimal,
+    write_decimal
+);
 
 // ============================================================================
 // Timestamp (inline format: 8 bytes)
@@ -274,7 +283,7 @@ impl<'a> FinalModel for FinalModelDecimalMut<'a> {
 impl_primitive_final_model!(
     FinalModelTimestamp,
     FinalModelTimestampMut,
-    u64,
+    Timestamp,
     8,
     read_timestamp,
     write_timestamp
@@ -284,40 +293,14 @@ impl_primitive_final_model!(
 // UUID (inline format: 16 bytes)
 // ============================================================================
 
-pub struct FinalModelUuid<'a> {
-    buffer: &'a [u8],
-    offset: usize,
-}
-
-impl<'a> FinalModelUuid<'a> {
-    pub fn new(buffer: &'a [u8], offset: usize) -> Self {
-        Self { buffer, offset }
-    }
-
-    pub fn get(&self) -> String {
-        let bytes = ReadBuffer::from(self.buffer.to_vec()).read_uuid(self.offset);
-        format!(
-            "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
-            bytes[0], bytes[1], bytes[2], bytes[3],
-            bytes[4], bytes[5],
-            bytes[6], bytes[7],
-            bytes[8], bytes[9],
-            bytes[10], bytes[11], bytes[12], bytes[13], bytes[14], bytes[15]
-        )
-    }
-}
-
-impl<'a> FinalModel for FinalModelUuid<'a> {
-    fn offset(&self) -> usize {
-        self.offset
-    }
-    fn set_offset(&mut self, offset: usize) {
-        self.offset = offset;
-    }
-    fn size(&self) -> usize {
-        16
-    }
-}
+impl_primitive_final_model!(
+    FinalModelUuid,
+    FinalModelUuidMut,
+    Uuid,
+    16,
+    read_uuid,
+    write_uuid
+);
 
 #[cfg(test)]
 mod tests {
@@ -365,6 +348,14 @@ mod tests {
 // Collection Final Models
 // ============================================================================
 
+/// Check an inline collection header and its items against the budget
+fn try_inline_collection(buffer: &[u8], offset: usize, item_size: usize, budget: &mut DecodeBudget) -> Result<(), DecodeError> {
+    let read_buf = ReadBuffer::borrowed(buffer);
+    let size = read_buf.try_read_u32(offset)? as usize;
+    budget.collection(size, item_size)?;
+    read_buf.check_items(offset + 4, size, item_size)
+}
+
 /// FinalModel for vector<T> (inline format)
 pub struct FinalModelVector<'a, T> {
     buffer: &'a [u8],
@@ -387,17 +378,23 @@ impl<'a, T> FinalModelVector<'a, T> {
         let mut item_offset = self.offset + 4;
         for _ in 0..size {
             result.push((self.item_model)(self.buffer, item_offset));
-            item_offset += std::mem::size_of::<T>();
+            item_offset += core::mem::size_of::<T>();
         }
         
         result
     }
 
+    /// Get collection, checking its length against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<Vec<T>, DecodeError> {
+        try_inline_collection(self.buffer, self.offset, core::mem::size_of::<T>(), budget)?;
+        Ok(self.get())
+    }
+
     pub fn size(&self) -> usize {
         let mut read_buf = crate::buffer::ReadBuffer::new();
         read_buf.attach_buffer(self.buffer, 0, self.buffer.len());
         let size = read_buf.read_u32(self.offset) as usize;
-        4 + size * std::mem::size_of::<T>()
+        4 + size * core::mem::size_of::<T>()
     }
 }
 
@@ -419,17 +416,24 @@ impl<'a, T, const N: usize> FinalModelArray<'a, T, N> {
         
         for _ in 0..N {
             result.push((self.item_model)(self.buffer, item_offset));
-            item_offset += std::mem::size_of::<T>();
+            item_offset += core::mem::size_of::<T>();
         }
         
         result.try_into().unwrap_or_else(|_| panic!("Array size mismatch"))
     }
 
+    /// Get array, checking that it lies inside the buffer
+    pub fn try_get(&self) -> Result<[T; N], DecodeError> {
+        ReadBuffer::borrowed(self.buffer).check_items(self.offset, N, core::mem::size_of::<T>())?;
+        Ok(self.get())
+    }
+
     pub fn size(&self) -> usize {
-        N * std::mem::size_of::<T>()
+        N * core::mem::size_of::<T>()
     }
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 /// FinalModel for map<K, V> (inline format)
 pub struct FinalModelMap<'a, K, V> {
     buffer: &'a [u8],
@@ -438,6 +442,7 @@ pub struct FinalModelMap<'a, K, V> {
     value_model: fn(&'a [u8], usize) -> V,
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 impl<'a, K, V> FinalModelMap<'a, K, V> {
     pub fn new(
         buffer: &'a [u8],
@@ -448,36 +453,46 @@ impl<'a, K, V> FinalModelMap<'a, K, V> {
         Self { buffer, offset, key_model, value_model }
     }
 
-    pub fn get(&self) -> std::collections::HashMap<K, V>
+    pub fn get(&self) -> crate::collections::HashMap<K, V>
     where
-        K: std::hash::Hash + Eq,
+        K: core::hash::Hash + Eq,
     {
         let mut read_buf = crate::buffer::ReadBuffer::new();
         read_buf.attach_buffer(self.buffer, 0, self.buffer.len());
         
         let size = read_buf.read_u32(self.offset) as usize;
-        let mut result = std::collections::HashMap::with_capacity(size);
+        let mut result = crate::collections::HashMap::with_capacity(size);
         
         let mut item_offset = self.offset + 4;
         for _ in 0..size {
             let key = (self.key_model)(self.buffer, item_offset);
-            item_offset += std::mem::size_of::<K>();
+            item_offset += core::mem::size_of::<K>();
             let value = (self.value_model)(self.buffer, item_offset);
-            item_offset += std::mem::size_of::<V>();
+            item_offset += core::mem::size_of::<V>();
             result.insert(key, value);
         }
         
         result
     }
 
+    /// Get collection, checking its length against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<crate::collections::HashMap<K, V>, DecodeError>
+    where
+        K: core::hash::Hash + Eq,
+    {
+        try_inline_collection(self.buffer, self.offset, core::mem::size_of::<K>() + core::mem::size_of::<V>(), budget)?;
+        Ok(self.get())
+    }
+
     pub fn size(&self) -> usize {
         let mut read_buf = crate::buffer::ReadBuffer::new();
         read_buf.attach_buffer(self.buffer, 0, self.buffer.len());
         let size = read_buf.read_u32(self.offset) as usize;
-        4 + size * (std::mem::size_of::<K>() + std::mem::size_of::<V>())
+        4 + size * (core::mem::size_of::<K>() + core::mem::size_of::<V>())
     }
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 /// FinalModel for set<T> (inline format)
 pub struct FinalModelSet<'a, T> {
     buffer: &'a [u8],
@@ -485,35 +500,45 @@ pub struct FinalModelSet<'a, T> {
     item_model: fn(&'a [u8], usize) -> T,
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 impl<'a, T> FinalModelSet<'a, T> {
     pub fn new(buffer: &'a [u8], offset: usize, item_model: fn(&'a [u8], usize) -> T) -> Self {
         Self { buffer, offset, item_model }
     }
 
-    pub fn get(&self) -> std::collections::HashSet<T>
+    pub fn get(&self) -> crate::collections::HashSet<T>
     where
-        T: std::hash::Hash + Eq,
+        T: core::hash::Hash + Eq,
     {
         let mut read_buf = crate::buffer::ReadBuffer::new();
         read_buf.attach_buffer(self.buffer, 0, self.buffer.len());
         
         let size = read_buf.read_u32(self.offset) as usize;
-        let mut result = std::collections::HashSet::with_capacity(size);
+        let mut result = crate::collections::HashSet::with_capacity(size);
         
         let mut item_offset = self.offset + 4;
         for _ in 0..size {
             result.insert((self.item_model)(self.buffer, item_offset));
-            item_offset += std::mem::size_of::<T>();
+            item_offset += core::mem::size_of::<T>();
         }
         
         result
     }
 
+    /// Get collection, checking its length against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<crate::collections::HashSet<T>, DecodeError>
+    where
+        T: core::hash::Hash + Eq,
+    {
+        try_inline_collection(self.buffer, self.offset, core::mem::size_of::<T>(), budget)?;
+        Ok(self.get())
+    }
+
     pub fn size(&self) -> usize {
         let mut read_buf = crate::buffer::ReadBuffer::new();
         read_buf.attach_buffer(self.buffer, 0, self.buffer.len());
         let size = read_buf.read_u32(self.offset) as usize;
-        4 + size * std::mem::size_of::<T>()
+        4 + size * core::mem::size_of::<T>()
     }
 }
 
@@ -529,27 +554,33 @@ impl<'a, T> FinalModelList<'a, T> {
         Self { buffer, offset, item_model }
     }
 
-    pub fn get(&self) -> std::collections::LinkedList<T> {
+    pub fn get(&self) -> alloc::collections::LinkedList<T> {
         let mut read_buf = crate::buffer::ReadBuffer::new();
         read_buf.attach_buffer(self.buffer, 0, self.buffer.len());
         
         let size = read_buf.read_u32(self.offset) as usize;
-        let mut result = std::collections::LinkedList::new();
+        let mut result = alloc::collections::LinkedList::new();
         
         let mut item_offset = self.offset + 4;
         for _ in 0..size {
             result.push_back((self.item_model)(self.buffer, item_offset));
-            item_offset += std::mem::size_of::<T>();
+            item_offset += core::mem::size_of::<T>();
         }
         
         result
     }
 
+    /// Get collection, checking its length against the budget
+    pub fn try_get(&self, budget: &mut DecodeBudget) -> Result<alloc::collections::LinkedList<T>, DecodeError> {
+        try_inline_collection(self.buffer, self.offset, core::mem::size_of::<T>(), budget)?;
+        Ok(self.get())
+    }
+
     pub fn size(&self) -> usize {
         let mut read_buf = crate::buffer::ReadBuffer::new();
         read_buf.attach_buffer(self.buffer, 0, self.buffer.len());
         let size = read_buf.read_u32(self.offset) as usize;
-        4 + size * std::mem::size_of::<T>()
+        4 + size * core::mem::size_of::<T>()
     }
 }
 
@@ -570,7 +601,7 @@ where
     buffer: &'a [u8],
     offset: usize,
     value_model_fn: fn(&'a [u8], usize) -> M,
-    _phantom: std::marker::PhantomData<T>,
+    _phantom: core::marker::PhantomData<T>,
 }
 
 impl<'a, T, M> FinalModelOptional<'a, T, M>
@@ -583,7 +614,7 @@ where
             buffer,
             offset,
             value_model_fn,
-            _phantom: std::marker::PhantomData,
+            _phantom: core::marker::PhantomData,
         }
     }
 
@@ -644,7 +675,7 @@ where
     buffer: &'a mut crate::buffer::WriteBuffer,
     offset: usize,
     value_model_fn: fn(&'a mut crate::buffer::WriteBuffer, usize) -> M,
-    _phantom: std::marker::PhantomData<T>,
+    _phantom: core::marker::PhantomData<T>,
 }
 
 impl<'a, T, M> FinalModelOptionalMut<'a, T, M>
@@ -661,7 +692,7 @@ where
             buffer,
             offset,
             value_model_fn,
-            _phantom: std::marker::PhantomData,
+            _phantom: core::marker::PhantomData,
         }
     }
 
@@ -705,3 +736,606 @@ where
 }
 
 
+// ============================================================================
+// Inline values (used by derived structs)
+// ============================================================================
+
+/// Inline FinalModel encoding of a value
+///
+/// Implemented for primitives, strings, collections and optionals, and by
+/// `#[derive(FbeStruct)]`, `#[derive(FbeEnum)]` and `#[derive(FbeFlags)]`.
+pub trait FinalValue: Sized {
+    /// Get serialized size in bytes
+    fn final_size(&self) -> usize;
+
+    /// Write value at offset and return serialized size
+    fn write_final(&self, buffer: &mut WriteBuffer, offset: usize) -> usize;
+
+    /// Read value at offset and return (value, serialized size)
+    fn read_final(buffer: &ReadBuffer, offset: usize) -> (Self, usize);
+
+    /// Get serialized size of the value at offset without decoding it
+    ///
+    /// Overridden by every runtime type and by the derives; the default
+    /// decodes the value with [`read_final`](Self::read_final).
+    fn read_final_size(buffer: &ReadBuffer, offset: usize) -> usize {
+        Self::read_final(buffer, offset).1
+    }
+
+    /// Read value at offset, checking bounds and lengths against the budget
+    ///
+    /// Overridden by every runtime type and by the derives; the default
+    /// performs no checks and falls back to [`read_final`](Self::read_final).
+    fn try_read_final(buffer: &ReadBuffer, offset: usize, budget: &mut DecodeBudget) -> Result<(Self, usize), DecodeError> {
+        let _ = budget;
+        Ok(Self::read_final(buffer, offset))
+    }
+
+    /// Append value to a vectored writer, referencing large `bytes`/`string` payloads
+    fn write_vectored<'a>(&'a self, writer: &mut VectoredWriter<'a>) {
+        writer.append_inline(self);
+    }
+
+    /// Check that FBE `char` fields are Latin-1 before writing
+    ///
+    /// Rust `char` is written as `wchar` and always succeeds; containers
+    /// forward to their items and structs with FBE `char` fields override it.
+    fn check_chars(&self) -> Result<(), CharError> {
+        Ok(())
+    }
+
+    /// View a slice of values as raw bytes (only for `u8`, the `bytes` element)
+    #[doc(hidden)]
+    fn slice_as_bytes(values: &[Self]) -> Option<&[u8]> {
+        let _ = values;
+        None
+    }
+}
+
+macro_rules! impl_primitive_final_value {
+    ($type:ty, $size:expr, $read_fn:ident, $write_fn:ident $(, $extra:item)*) => {
+        impl FinalValue for $type {
+            #[inline]
+            fn final_size(&self) -> usize {
+                $size
+            }
+
+            #[inline]
+            fn write_final(&self, buffer: &mut WriteBuffer, offset: usize) -> usize {
+                buffer.$write_fn(offset, *self);
+                $size
+            }
+
+            #[inline]
+            fn read_final(buffer: &ReadBuffer, offset: usize) -> (Self, usize) {
+                (buffer.$read_fn(offset), $size)
+            }
+
+            #[inline]
+            fn read_final_size(_buffer: &ReadBuffer, _offset: usize) -> usize {
+                $size
+            }
+
+            #[inline]
+            fn try_read_final(buffer: &ReadBuffer, offset: usize, _budget: &mut DecodeBudget) -> Result<(Self, usize), DecodeError> {
+                buffer.check_bounds(offset, $size)?;
+                Ok(Self::read_final(buffer, offset))
+            }
+
+            $($extra)*
+        }
+    };
+}
+
+impl_primitive_final_value!(bool, 1, read_bool, write_bool);
+impl_primitive_final_value!(i8, 1, read_i8, write_i8);
+impl_primitive_final_value!(i16, 2, read_i16, write_i16);
+impl_primitive_final_value!(i32, 4, read_i32, write_i32);
+impl_primitive_final_value!(i64, 8, read_i64, write_i64);
+impl_primitive_final_value!(u8, 1, read_u8, write_u8, fn slice_as_bytes(values: &[Self]) -> Option<&[u8]> {
+    Some(values)
+});
+impl_primitive_final_value!(u16, 2, read_u16, write_u16);
+impl_primitive_final_value!(u32, 4, read_u32, write_u32);
+impl_primitive_final_value!(u64, 8, read_u64, write_u64);
+impl_primitive_final_value!(f32, 4, read_f32, write_f32);
+impl_primitive_final_value!(f64, 8, read_f64, write_f64);
+impl_primitive_final_value!(Decimal, 16, read_decimal, write_decimal);
+impl_primitive_final_value!(Timestamp, 8, read_timestamp, write_timestamp);
+impl_primitive_final_value!(Uuid, 16, read_uuid, write_uuid);
+
+/// `char` uses the 4-byte `wchar` layout, like `fbe::serde`; invalid code
+/// points read as U+FFFD, like invalid UTF-8 in strings
+impl FinalValue for char {
+    #[inline]
+    fn final_size(&self) -> usize {
+        4
+    }
+
+    #[inline]
+    fn write_final(&self, buffer: &mut WriteBuffer, offset: usize) -> usize {
+        buffer.write_wchar(offset, *self);
+        4
+    }
+
+    #[inline]
+    fn read_final(buffer: &ReadBuffer, offset: usize) -> (Self, usize) {
+        let value = buffer.read_wchar(offset).unwrap_or(char::REPLACEMENT_CHARACTER);
+        (value, 4)
+    }
+
+    #[inline]
+    fn read_final_size(_buffer: &ReadBuffer, _offset: usize) -> usize {
+        4
+    }
+
+    #[inline]
+    fn try_read_final(buffer: &ReadBuffer, offset: usize, _budget: &mut DecodeBudget) -> Result<(Self, usize), DecodeError> {
+        buffer.check_bounds(offset, 4)?;
+        Ok((buffer.read_wchar(offset)?, 4))
+    }
+}
+
+impl FinalValue for String {
+    fn final_size(&self) -> usize {
+        4 + self.len()
+    }
+
+    fn write_final(&self, buffer: &mut WriteBuffer, offset: usize) -> usize {
+        buffer.write_string(offset, self);
+        4 + self.len()
+    }
+
+    fn read_final(buffer: &ReadBuffer, offset: usize) -> (Self, usize) {
+        let size = 4 + buffer.read_u32(offset) as usize;
+        (buffer.read_string(offset), size)
+    }
+
+    fn read_final_size(buffer: &ReadBuffer, offset: usize) -> usize {
+        4 + buffer.read_u32(offset) as usize
+    }
+
+    fn try_read_final(buffer: &ReadBuffer, offset: usize, budget: &mut DecodeBudget) -> Result<(Self, usize), DecodeError> {
+        let value = buffer.try_read_string(offset, budget)?;
+        Ok((value, 4 + buffer.read_u32(offset) as usize))
+    }
+
+    fn write_vectored<'a>(&'a self, writer: &mut VectoredWriter<'a>) {
+        writer.append_bytes(self.as_bytes());
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl FinalValue for bytes::Bytes {
+    fn final_size(&self) -> usize {
+        4 + self.len()
+    }
+
+    fn write_final(&self, buffer: &mut WriteBuffer, offset: usize) -> usize {
+        buffer.write_bytes(offset, self);
+        4 + self.len()
+    }
+
+    fn read_final(buffer: &ReadBuffer, offset: usize) -> (Self, usize) {
+        let size = 4 + buffer.read_u32(offset) as usize;
+        (buffer.read_bytes_shared(offset), size)
+    }
+
+    fn read_final_size(buffer: &ReadBuffer, offset: usize) -> usize {
+        4 + buffer.read_u32(offset) as usize
+    }
+
+    fn try_read_final(buffer: &ReadBuffer, offset: usize, budget: &mut DecodeBudget) -> Result<(Self, usize), DecodeError> {
+        let len = buffer.try_read_u32(offset)? as usize;
+        budget.string(len)?;
+        buffer.check_bounds(offset + 4, len)?;
+        Ok((buffer.read_bytes_shared(offset), 4 + len))
+    }
+
+    fn write_vectored<'a>(&'a self, writer: &mut VectoredWriter<'a>) {
+        writer.append_bytes(self);
+    }
+}
+
+impl<T: FinalValue> FinalValue for Option<T> {
+    fn final_size(&self) -> usize {
+        1 + self.as_ref().map_or(0, FinalValue::final_size)
+    }
+
+    fn write_final(&self, buffer: &mut WriteBuffer, offset: usize) -> usize {
+        match self {
+            Some(value) => {
+                buffer.write_byte(offset, 1);
+                1 + value.write_final(buffer, offset + 1)
+            }
+            None => {
+                buffer.write_byte(offset, 0);
+                1
+            }
+        }
+    }
+
+    fn read_final(buffer: &ReadBuffer, offset: usize) -> (Self, usize) {
+        if buffer.read_byte(offset) == 0 {
+            return (None, 1);
+        }
+        let (value, size) = T::read_final(buffer, offset + 1);
+        (Some(value), 1 + size)
+    }
+
+    fn read_final_size(buffer: &ReadBuffer, offset: usize) -> usize {
+        if buffer.read_byte(offset) == 0 {
+            return 1;
+        }
+        1 + T::read_final_size(buffer, offset + 1)
+    }
+
+    fn try_read_final(buffer: &ReadBuffer, offset: usize, budget: &mut DecodeBudget) -> Result<(Self, usize), DecodeError> {
+        buffer.check_bounds(offset, 1)?;
+        if buffer.read_byte(offset) == 0 {
+            return Ok((None, 1));
+        }
+        let (value, size) = T::try_read_final(buffer, offset + 1, budget)?;
+        Ok((Some(value), 1 + size))
+    }
+
+    fn write_vectored<'a>(&'a self, writer: &mut VectoredWriter<'a>) {
+        writer.append_inline(&self.is_some());
+        if let Some(value) = self {
+            value.write_vectored(writer);
+        }
+    }
+
+    fn check_chars(&self) -> Result<(), CharError> {
+        self.as_ref().map_or(Ok(()), FinalValue::check_chars)
+    }
+}
+
+impl<T: FinalValue, const N: usize> FinalValue for [T; N] {
+    fn final_size(&self) -> usize {
+        self.iter().map(FinalValue::final_size).sum()
+    }
+
+    fn write_final(&self, buffer: &mut WriteBuffer, offset: usize) -> usize {
+        let mut size = 0;
+        for item in self {
+            size += item.write_final(buffer, offset + size);
+        }
+        size
+    }
+
+    fn read_final(buffer: &ReadBuffer, offset: usize) -> (Self, usize) {
+        let mut size = 0;
+        let result = core::array::from_fn(|_| {
+            let (item, item_size) = T::read_final(buffer, offset + size);
+            size += item_size;
+            item
+        });
+        (result, size)
+    }
+
+    fn read_final_size(buffer: &ReadBuffer, offset: usize) -> usize {
+        read_final_items_size::<T>(buffer, offset, N)
+    }
+
+    fn try_read_final(buffer: &ReadBuffer, offset: usize, budget: &mut DecodeBudget) -> Result<(Self, usize), DecodeError> {
+        let (items, size) = try_read_final_items::<T, Vec<T>>(buffer, offset, N, budget)?;
+        match items.try_into() {
+            Ok(result) => Ok((result, size)),
+            Err(_) => unreachable!("array reads exactly N items"),
+        }
+    }
+
+    fn write_vectored<'a>(&'a self, writer: &mut VectoredWriter<'a>) {
+        for item in self {
+            item.write_vectored(writer);
+        }
+    }
+
+    fn check_chars(&self) -> Result<(), CharError> {
+        self.iter().try_for_each(FinalValue::check_chars)
+    }
+}
+
+fn write_final_items<'a, T, I>(buffer: &mut WriteBuffer, offset: usize, count: usize, items: I) -> usize
+where
+    T: FinalValue + 'a,
+    I: Iterator<Item = &'a T>,
+{
+    buffer.write_u32(offset, count as u32);
+    let mut size = 4;
+    for item in items {
+        size += item.write_final(buffer, offset + size);
+    }
+    size
+}
+
+fn read_final_items<T, C>(buffer: &ReadBuffer, offset: usize) -> (C, usize)
+where
+    T: FinalValue,
+    C: FromIterator<T>,
+{
+    let count = buffer.read_u32(offset) as usize;
+    let mut size = 4;
+    let items = (0..count)
+        .map(|_| {
+            let (item, item_size) = T::read_final(buffer, offset + size);
+            size += item_size;
+            item
+        })
+        .collect();
+    (items, size)
+}
+
+/// Walk `count` items and return their total serialized size
+fn read_final_items_size<T: FinalValue>(buffer: &ReadBuffer, offset: usize, count: usize) -> usize {
+    let mut size = 0;
+    for _ in 0..count {
+        size += T::read_final_size(buffer, offset + size);
+    }
+    size
+}
+
+/// Walk `count` key/value entries and return their total serialized size
+fn read_final_entries_size<K: FinalValue, V: FinalValue>(buffer: &ReadBuffer, offset: usize, count: usize) -> usize {
+    let mut size = 0;
+    for _ in 0..count {
+        size += K::read_final_size(buffer, offset + size);
+        size += V::read_final_size(buffer, offset + size);
+    }
+    size
+}
+
+/// Read `count` items, checking the count and every item against the budget
+fn try_read_final_items<T, C>(
+    buffer: &ReadBuffer,
+    offset: usize,
+    count: usize,
+    budget: &mut DecodeBudget,
+) -> Result<(C, usize), DecodeError>
+where
+    T: FinalValue,
+    C: FromIterator<T>,
+{
+    budget.collection(count, core::mem::size_of::<T>())?;
+    budget.enter()?;
+    let mut size = 0;
+    let items = (0..count)
+        .map(|_| {
+            let (item, item_size) = T::try_read_final(buffer, offset + size, budget)?;
+            size += item_size;
+            Ok(item)
+        })
+        .collect::<Result<C, DecodeError>>()?;
+    budget.leave();
+    Ok((items, size))
+}
+
+fn try_read_final_sized<T, C>(buffer: &ReadBuffer, offset: usize, budget: &mut DecodeBudget) -> Result<(C, usize), DecodeError>
+where
+    T: FinalValue,
+    C: FromIterator<T>,
+{
+    let count = buffer.try_read_u32(offset)? as usize;
+    let (items, size) = try_read_final_items(buffer, offset + 4, count, budget)?;
+    Ok((items, 4 + size))
+}
+
+fn write_final_entries<'a, K, V, I>(
+    buffer: &mut WriteBuffer,
+    offset: usize,
+    count: usize,
+    entries: I,
+) -> usize
+where
+    K: FinalValue + 'a,
+    V: FinalValue + 'a,
+    I: Iterator<Item = (&'a K, &'a V)>,
+{
+    buffer.write_u32(offset, count as u32);
+    let mut size = 4;
+    for (key, value) in entries {
+        size += key.write_final(buffer, offset + size);
+        size += value.write_final(buffer, offset + size);
+    }
+    size
+}
+
+fn read_final_entries<K, V, C>(buffer: &ReadBuffer, offset: usize) -> (C, usize)
+where
+    K: FinalValue,
+    V: FinalValue,
+    C: FromIterator<(K, V)>,
+{
+    let count = buffer.read_u32(offset) as usize;
+    let mut size = 4;
+    let entries = (0..count)
+        .map(|_| {
+            let (key, key_size) = K::read_final(buffer, offset + size);
+            size += key_size;
+            let (value, value_size) = V::read_final(buffer, offset + size);
+            size += value_size;
+            (key, value)
+        })
+        .collect();
+    (entries, size)
+}
+
+fn try_read_final_entries<K, V, C>(buffer: &ReadBuffer, offset: usize, budget: &mut DecodeBudget) -> Result<(C, usize), DecodeError>
+where
+    K: FinalValue,
+    V: FinalValue,
+    C: FromIterator<(K, V)>,
+{
+    let count = buffer.try_read_u32(offset)? as usize;
+    budget.collection(count, core::mem::size_of::<(K, V)>())?;
+    budget.enter()?;
+    let mut size = 4;
+    let entries = (0..count)
+        .map(|_| {
+            let (key, key_size) = K::try_read_final(buffer, offset + size, budget)?;
+            size += key_size;
+            let (value, value_size) = V::try_read_final(buffer, offset + size, budget)?;
+            size += value_size;
+            Ok((key, value))
+        })
+        .collect::<Result<C, DecodeError>>()?;
+    budget.leave();
+    Ok((entries, size))
+}
+
+impl<T: FinalValue> FinalValue for Vec<T> {
+    fn final_size(&self) -> usize {
+        4 + self.iter().map(FinalValue::final_size).sum::<usize>()
+    }
+
+    fn write_final(&self, buffer: &mut WriteBuffer, offset: usize) -> usize {
+        write_final_items(buffer, offset, self.len(), self.iter())
+    }
+
+    fn read_final(buffer: &ReadBuffer, offset: usize) -> (Self, usize) {
+        read_final_items(buffer, offset)
+    }
+
+    fn read_final_size(buffer: &ReadBuffer, offset: usize) -> usize {
+        4 + read_final_items_size::<T>(buffer, offset + 4, buffer.read_u32(offset) as usize)
+    }
+
+    fn try_read_final(buffer: &ReadBuffer, offset: usize, budget: &mut DecodeBudget) -> Result<(Self, usize), DecodeError> {
+        try_read_final_sized(buffer, offset, budget)
+    }
+
+    /// `Vec<u8>` is the `bytes` type and is referenced as one payload
+    fn write_vectored<'a>(&'a self, writer: &mut VectoredWriter<'a>) {
+        if let Some(bytes) = T::slice_as_bytes(self) {
+            writer.append_bytes(bytes);
+            return;
+        }
+        writer.append_inline(&(self.len() as u32));
+        for item in self {
+            item.write_vectored(writer);
+        }
+    }
+
+    fn check_chars(&self) -> Result<(), CharError> {
+        self.iter().try_for_each(FinalValue::check_chars)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+impl<T: FinalValue + Eq + Hash> FinalValue for HashSet<T> {
+    fn final_size(&self) -> usize {
+        4 + self.iter().map(FinalValue::final_size).sum::<usize>()
+    }
+
+    fn write_final(&self, buffer: &mut WriteBuffer, offset: usize) -> usize {
+        write_final_items(buffer, offset, self.len(), self.iter())
+    }
+
+    fn read_final(buffer: &ReadBuffer, offset: usize) -> (Self, usize) {
+        read_final_items(buffer, offset)
+    }
+
+    fn read_final_size(buffer: &ReadBuffer, offset: usize) -> usize {
+        4 + read_final_items_size::<T>(buffer, offset + 4, buffer.read_u32(offset) as usize)
+    }
+
+    fn try_read_final(buffer: &ReadBuffer, offset: usize, budget: &mut DecodeBudget) -> Result<(Self, usize), DecodeError> {
+        try_read_final_sized(buffer, offset, budget)
+    }
+
+    fn check_chars(&self) -> Result<(), CharError> {
+        self.iter().try_for_each(FinalValue::check_chars)
+    }
+}
+
+impl<T: FinalValue + Ord> FinalValue for BTreeSet<T> {
+    fn final_size(&self) -> usize {
+        4 + self.iter().map(FinalValue::final_size).sum::<usize>()
+    }
+
+    fn write_final(&self, buffer: &mut WriteBuffer, offset: usize) -> usize {
+        write_final_items(buffer, offset, self.len(), self.iter())
+    }
+
+    fn read_final(buffer: &ReadBuffer, offset: usize) -> (Self, usize) {
+        read_final_items(buffer, offset)
+    }
+
+    fn read_final_size(buffer: &ReadBuffer, offset: usize) -> usize {
+        4 + read_final_items_size::<T>(buffer, offset + 4, buffer.read_u32(offset) as usize)
+    }
+
+    fn try_read_final(buffer: &ReadBuffer, offset: usize, budget: &mut DecodeBudget) -> Result<(Self, usize), DecodeError> {
+        try_read_final_sized(buffer, offset, budget)
+    }
+
+    fn check_chars(&self) -> Result<(), CharError> {
+        self.iter().try_for_each(FinalValue::check_chars)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+impl<K: FinalValue + Eq + Hash, V: FinalValue> FinalValue for HashMap<K, V> {
+    fn final_size(&self) -> usize {
+        4 + self
+            .iter()
+            .map(|(key, value)| key.final_size() + value.final_size())
+            .sum::<usize>()
+    }
+
+    fn write_final(&self, buffer: &mut WriteBuffer, offset: usize) -> usize {
+        write_final_entries(buffer, offset, self.len(), self.iter())
+    }
+
+    fn read_final(buffer: &ReadBuffer, offset: usize) -> (Self, usize) {
+        read_final_entries(buffer, offset)
+    }
+
+    fn read_final_size(buffer: &ReadBuffer, offset: usize) -> usize {
+        4 + read_final_entries_size::<K, V>(buffer, offset + 4, buffer.read_u32(offset) as usize)
+    }
+
+    fn try_read_final(buffer: &ReadBuffer, offset: usize, budget: &mut DecodeBudget) -> Result<(Self, usize), DecodeError> {
+        try_read_final_entries(buffer, offset, budget)
+    }
+
+    fn check_chars(&self) -> Result<(), CharError> {
+        self.iter().try_for_each(|(key, value)| {
+            key.check_chars()?;
+            value.check_chars()
+        })
+    }
+}
+
+impl<K: FinalValue + Ord, V: FinalValue> FinalValue for BTreeMap<K, V> {
+    fn final_size(&self) -> usize {
+        4 + self
+            .iter()
+            .map(|(key, value)| key.final_size() + value.final_size())
+            .sum::<usize>()
+    }
+
+    fn write_final(&self, buffer: &mut WriteBuffer, offset: usize) -> usize {
+        write_final_entries(buffer, offset, self.len(), self.iter())
+    }
+
+    fn read_final(buffer: &ReadBuffer, offset: usize) -> (Self, usize) {
+        read_final_entries(buffer, offset)
+    }
+
+    fn read_final_size(buffer: &ReadBuffer, offset: usize) -> usize {
+        4 + read_final_entries_size::<K, V>(buffer, offset + 4, buffer.read_u32(offset) as usize)
+    }
+
+    fn try_read_final(buffer: &ReadBuffer, offset: usize, budget: &mut DecodeBudget) -> Result<(Self, usize), DecodeError> {
+        try_read_final_entries(buffer, offset, budget)
+    }
+
+    fn check_chars(&self) -> Result<(), CharError> {
+        self.iter().try_for_each(|(key, value)| {
+            key.check_chars()?;
+            value.check_chars()
+        })
+    }
+}
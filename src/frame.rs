@@ -0,0 +1,247 @@
+//! Fast Binary Encoding message framing
+//!
+//! Messages on a stream are prefixed with the 8-byte FBE header:
+//! 4-byte total frame size (header included) and 4-byte FBE type id,
+//! both little-endian.
+
+use std::io;
+
+/// Frame header size (4-byte size + 4-byte type)
+pub const FRAME_HEADER_SIZE: usize = 8;
+
+/// Default limit for a single frame (64 MiB)
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 64 * 1024 * 1024;
+
+/// Owned message frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub fbe_type: u32,
+    pub payload: Vec<u8>,
+}
+
+impl Frame {
+    /// Create a new frame
+    #[must_use]
+    pub fn new(fbe_type: u32, payload: Vec<u8>) -> Self {
+        Self { fbe_type, payload }
+    }
+
+    /// Get frame size (header included)
+    #[must_use]
+    pub fn size(&self) -> usize {
+        FRAME_HEADER_SIZE + self.payload.len()
+    }
+
+    /// Borrow frame as a frame reference
+    #[must_use]
+    pub fn as_frame_ref(&self) -> FrameRef<'_> {
+        FrameRef::new(self.fbe_type, &self.payload)
+    }
+}
+
+/// Borrowed message frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameRef<'a> {
+    pub fbe_type: u32,
+    pub payload: &'a [u8],
+}
+
+impl<'a> FrameRef<'a> {
+    /// Create a new frame reference
+    #[must_use]
+    pub fn new(fbe_type: u32, payload: &'a [u8]) -> Self {
+        Self { fbe_type, payload }
+    }
+
+    /// Get frame size (header included)
+    #[must_use]
+    pub fn size(&self) -> usize {
+        FRAME_HEADER_SIZE + self.payload.len()
+    }
+
+    /// Encode frame header
+    ///
+    /// Fails with `InvalidInput` when the frame size does not fit the 4-byte size field.
+    pub fn header(&self) -> io::Result<[u8; FRAME_HEADER_SIZE]> {
+        encode_header(self.fbe_type, self.payload.len())
+    }
+
+    /// Copy into an owned frame
+    #[must_use]
+    pub fn to_frame(&self) -> Frame {
+        Frame::new(self.fbe_type, self.payload.to_vec())
+    }
+}
+
+/// Encode frame header for a payload of `payload_size` bytes
+///
+/// Fails with `InvalidInput` when the frame size does not fit the 4-byte size field.
+pub fn encode_header(fbe_type: u32, payload_size: usize) -> io::Result<[u8; FRAME_HEADER_SIZE]> {
+    let size = check_frame_size(payload_size, u32::MAX as usize)?;
+    let mut header = [0u8; FRAME_HEADER_SIZE];
+    header[..4].copy_from_slice(&(size as u32).to_le_bytes());
+    header[4..].copy_from_slice(&fbe_type.to_le_bytes());
+    Ok(header)
+}
+
+/// Check an outgoing frame against a frame size limit
+///
+/// Returns the frame size (header included), or `InvalidInput` when it
+/// exceeds `max_frame_size` or the 4-byte size field.
+pub fn check_frame_size(payload_size: usize, max_frame_size: usize) -> io::Result<usize> {
+    let size = payload_size.saturating_add(FRAME_HEADER_SIZE);
+    let limit = max_frame_size.min(u32::MAX as usize);
+    if size > limit {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Frame size {} exceeds limit {}", size, limit),
+        ));
+    }
+    Ok(size)
+}
+
+/// Parse frame header from the beginning of `data`
+///
+/// Returns `None` when fewer than `FRAME_HEADER_SIZE` bytes are available,
+/// otherwise the total frame size and FBE type id.
+pub fn parse_header(data: &[u8], max_frame_size: usize) -> io::Result<Option<(usize, u32)>> {
+    if data.len() < FRAME_HEADER_SIZE {
+        return Ok(None);
+    }
+
+    let size = u32::from_le_bytes(data[..4].try_into().unwrap()) as usize;
+    let fbe_type = u32::from_le_bytes(data[4..8].try_into().unwrap());
+
+    if size < FRAME_HEADER_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Invalid frame size: {}", size),
+        ));
+    }
+    if size > max_frame_size {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Frame size {} exceeds limit {}", size, max_frame_size),
+        ));
+    }
+
+    Ok(Some((size, fbe_type)))
+}
+
+/// Error reported when a receiver rejects a forwarded frame
+pub(crate) fn rejected(frame: FrameRef<'_>) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "Receiver rejected frame (type {}, {} bytes)",
+            frame.fbe_type,
+            frame.payload.len()
+        ),
+    )
+}
+
+/// Incremental frame decoder for chunked input
+#[derive(Debug, Clone)]
+pub struct FrameDecoder {
+    buffer: Vec<u8>,
+    max_frame_size: usize,
+}
+
+impl Default for FrameDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrameDecoder {
+    /// Create a new frame decoder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_max_frame_size(DEFAULT_MAX_FRAME_SIZE)
+    }
+
+    /// Create a new frame decoder with the given frame size limit
+    #[must_use]
+    pub fn with_max_frame_size(max_frame_size: usize) -> Self {
+        Self {
+            buffer: Vec::new(),
+            max_frame_size,
+        }
+    }
+
+    /// Get frame size limit
+    #[must_use]
+    pub const fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    /// Get number of buffered bytes
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Append received data
+    pub fn push(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Take the next complete frame, if any
+    pub fn next_frame(&mut self) -> io::Result<Option<Frame>> {
+        let Some((size, fbe_type)) = parse_header(&self.buffer, self.max_frame_size)? else {
+            return Ok(None);
+        };
+        if self.buffer.len() < size {
+            return Ok(None);
+        }
+
+        let payload = self.buffer[FRAME_HEADER_SIZE..size].to_vec();
+        self.buffer.drain(..size);
+        Ok(Some(Frame::new(fbe_type, payload)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_decoder_chunked() {
+        let frame = Frame::new(5, vec![1, 2, 3, 4]);
+        let mut data = frame.as_frame_ref().header().unwrap().to_vec();
+        data.extend_from_slice(&frame.payload);
+        assert_eq!(&data[..8], &[12, 0, 0, 0, 5, 0, 0, 0]);
+
+        let mut decoder = FrameDecoder::new();
+        for byte in &data[..data.len() - 1] {
+            decoder.push(std::slice::from_ref(byte));
+            assert_eq!(decoder.next_frame().unwrap(), None);
+        }
+        decoder.push(&data[data.len() - 1..]);
+        assert_eq!(decoder.next_frame().unwrap(), Some(frame));
+        assert_eq!(decoder.pending(), 0);
+    }
+
+    #[test]
+    fn test_frame_decoder_invalid_size() {
+        let mut decoder = FrameDecoder::with_max_frame_size(16);
+        decoder.push(&[4, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(decoder.next_frame().is_err());
+
+        let mut decoder = FrameDecoder::with_max_frame_size(16);
+        decoder.push(&[17, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(decoder.next_frame().is_err());
+    }
+
+    #[test]
+    fn test_check_frame_size() {
+        assert_eq!(check_frame_size(8, 16).unwrap(), 16);
+        assert_eq!(check_frame_size(9, 16).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+        // Sizes beyond the 4-byte size field are rejected instead of truncated
+        let payload_size = u32::MAX as usize - FRAME_HEADER_SIZE + 1;
+        assert!(check_frame_size(payload_size, usize::MAX).is_err());
+        assert!(encode_header(0, payload_size).is_err());
+        assert!(encode_header(0, payload_size - 1).is_ok());
+    }
+}
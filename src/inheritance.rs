@@ -3,6 +3,8 @@
  */
 
 use crate::buffer::{ReadBuffer, WriteBuffer};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 // Base struct: Person
 #[derive(Debug, Clone, PartialEq)]
@@ -25,7 +27,13 @@ impl Person {
         Self { name, age }
     }
 
+    /// Serialized size
+    pub fn size_hint(&self) -> usize {
+        8 + self.name.len()
+    }
+
     pub fn serialize(&self, buffer: &mut WriteBuffer) -> usize {
+        buffer.reserve(buffer.offset() + self.size_hint());
         let mut offset = 0;
         buffer.write_string(offset, &self.name);
         offset += 4 + self.name.len();
@@ -76,7 +84,13 @@ impl Employee {
         }
     }
 
+    /// Serialized size
+    pub fn size_hint(&self) -> usize {
+        20 + self.name.len() + self.company.len()
+    }
+
     pub fn serialize(&self, buffer: &mut WriteBuffer) -> usize {
+        buffer.reserve(buffer.offset() + self.size_hint());
         // Serialize base fields first
         let mut offset = 0;
         buffer.write_string(offset, &self.name);
@@ -148,7 +162,13 @@ impl Manager {
         }
     }
 
+    /// Serialized size
+    pub fn size_hint(&self) -> usize {
+        24 + self.name.len() + self.company.len()
+    }
+
     pub fn serialize(&self, buffer: &mut WriteBuffer) -> usize {
+        buffer.reserve(buffer.offset() + self.size_hint());
         // Serialize all base fields in order
         let mut offset = 0;
         buffer.write_string(offset, &self.name);
@@ -238,4 +258,17 @@ mod tests {
         let manager2 = Manager::deserialize(&read_buffer);
         assert_eq!(manager, manager2);
     }
+
+    #[test]
+    fn test_size_hint_single_allocation() {
+        let manager = Manager::new("Dana".to_string(), 45, "Panilux".to_string(), 120000.0, 8);
+        let mut buffer = WriteBuffer::new();
+        let size = manager.serialize(&mut buffer);
+        assert_eq!(size, manager.size_hint());
+        assert_eq!(buffer.capacity(), size);
+
+        let mut read_buffer = ReadBuffer::new();
+        read_buffer.attach_buffer(buffer.data(), 0, buffer.data().len());
+        assert_eq!(Manager::deserialize(&read_buffer), manager);
+    }
 }
@@ -0,0 +1,368 @@
+//! Fast Binary Encoding JSON conventions
+//!
+//! FBE peers (C++, C#, Java, Python, JavaScript...) exchange JSON with the
+//! following value shapes:
+//!
+//! | FBE type                        | JSON value                                  |
+//! |---------------------------------|---------------------------------------------|
+//! | `bool`                          | `true` / `false`                            |
+//! | `byte`..`uint64`, `float`, `double` | number                                  |
+//! | `char`, `wchar`                 | number (code unit)                          |
+//! | `string`                        | string                                      |
+//! | `bytes`                         | base64 string                               |
+//! | `decimal`                       | string, e.g. `"-123.456"`                   |
+//! | `timestamp`                     | number (nanoseconds since Unix epoch)       |
+//! | `uuid`                          | string, e.g. `"123e4567-e89b-12d3-a456-426655440000"` |
+//! | enum, flags                     | number (underlying value)                   |
+//! | optional                        | `null` or value                             |
+//! | array, vector, list, set        | array                                       |
+//! | map, hash                       | object, keys as strings                     |
+//! | struct                          | object with one member per field            |
+//!
+//! Primitives, strings, optionals, collections and [`Decimal`](crate::Decimal),
+//! [`Timestamp`](crate::Timestamp) and [`Uuid`](crate::Uuid) already have these
+//! shapes with their serde impls. Types whose in-memory representation differs
+//! use the adapters below with `#[serde(with = "...")]`; each adapter also
+//! has `option`, `vec` and `option_vec` variants for `Option<T>`, `Vec<T>`
+//! and `Option<Vec<T>>` fields:
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Blob {
+//!     #[serde(with = "fbe::json::bytes")]
+//!     data: Vec<u8>,
+//!     #[serde(with = "fbe::json::uuid::option")]
+//!     owner: Option<[u8; 16]>,
+//! }
+//!
+//! let blob = Blob { data: b"FBE".to_vec(), owner: None };
+//! let json = fbe::json::to_json(&blob).unwrap();
+//! assert_eq!(json, r#"{"data":"RkJF","owner":null}"#);
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+// ============================================================================
+// Error
+// ============================================================================
+
+/// JSON encoding/decoding error
+#[derive(Debug)]
+pub struct Error(serde_json::Error);
+
+impl Error {
+    /// Line of the JSON input where the error occurred (1-based, 0 if unknown)
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.0.line()
+    }
+
+    /// Column of the JSON input where the error occurred (1-based, 0 if unknown)
+    #[must_use]
+    pub fn column(&self) -> usize {
+        self.0.column()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "JSON error: {}", self.0)
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error(err)
+    }
+}
+
+/// JSON result
+pub type Result<T> = core::result::Result<T, Error>;
+
+// ============================================================================
+// Encoding / decoding
+// ============================================================================
+
+/// Convert value to FBE JSON string
+pub fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    Ok(serde_json::to_string(value)?)
+}
+
+/// Create value from FBE JSON string
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T> {
+    Ok(serde_json::from_str(json)?)
+}
+
+// ============================================================================
+// Value text formats
+// ============================================================================
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode bytes as standard padded base64
+pub(crate) fn encode_base64(data: &[u8]) -> String {
+    let mut result = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bits = (u32::from(chunk[0]) << 16)
+            | (u32::from(chunk.get(1).copied().unwrap_or(0)) << 8)
+            | u32::from(chunk.get(2).copied().unwrap_or(0));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(BASE64_ALPHABET[(bits >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+/// Decode standard padded base64
+pub(crate) fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let text = text.as_bytes();
+    if !text.len().is_multiple_of(4) {
+        return None;
+    }
+    let mut result = Vec::with_capacity(text.len() / 4 * 3);
+    for (index, chunk) in text.chunks(4).enumerate() {
+        let last = index == text.len() / 4 - 1;
+        let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 || (padding > 0 && !last) {
+            return None;
+        }
+        let mut bits = 0u32;
+        for &c in &chunk[..4 - padding] {
+            let value = BASE64_ALPHABET.iter().position(|&a| a == c)? as u32;
+            bits = (bits << 6) | value;
+        }
+        bits <<= 6 * padding as u32;
+        let bytes = bits.to_be_bytes();
+        result.extend_from_slice(&bytes[1..4 - padding]);
+    }
+    Some(result)
+}
+
+// ============================================================================
+// serde adapters
+// ============================================================================
+
+/// Adapter module for values serialized through a JSON representation `$repr`
+///
+/// `try $format` returns a `Result` whose error is reported through `S::Error::custom`.
+macro_rules! adapter {
+    ($(#[$doc:meta])* $module:ident, $ty:ty, $repr:ty, try $format:expr, $parse:expr, $expecting:literal) => {
+        $(#[$doc])*
+        pub mod $module {
+            #[cfg(not(feature = "std"))]
+            use crate::prelude::*;
+            use serde::de::Error as _;
+            use serde::ser::Error as _;
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            struct Ref<'a>(&'a $ty);
+
+            impl Serialize for Ref<'_> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serialize(self.0, serializer)
+                }
+            }
+
+            struct Owned($ty);
+
+            impl<'de> Deserialize<'de> for Owned {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserialize(deserializer).map(Owned)
+                }
+            }
+
+            /// Serialize value
+            pub fn serialize<S: Serializer>(value: &$ty, serializer: S) -> Result<S::Ok, S::Error> {
+                let repr = $format(value).map_err(S::Error::custom)?;
+                Serialize::serialize(&repr, serializer)
+            }
+
+            /// Deserialize value
+            pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<$ty, D::Error> {
+                let repr = <$repr>::deserialize(deserializer)?;
+                $parse(&repr).ok_or_else(|| {
+                    D::Error::custom(format!(concat!("invalid ", $expecting, ": {:?}"), repr))
+                })
+            }
+
+            /// Adapter for optional values
+            pub mod option {
+                use super::{Owned, Ref};
+                use serde::{Deserialize, Deserializer, Serializer};
+
+                /// Serialize optional value
+                pub fn serialize<S: Serializer>(
+                    value: &Option<$ty>,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    match value {
+                        Some(value) => serializer.serialize_some(&Ref(value)),
+                        None => serializer.serialize_none(),
+                    }
+                }
+
+                /// Deserialize optional value
+                pub fn deserialize<'de, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Option<$ty>, D::Error> {
+                    Ok(Option::<Owned>::deserialize(deserializer)?.map(|value| value.0))
+                }
+            }
+
+            /// Adapter for vectors of values
+            pub mod vec {
+                #[cfg(not(feature = "std"))]
+                use crate::prelude::*;
+                use super::{Owned, Ref};
+                use serde::{Deserialize, Deserializer, Serializer};
+
+                /// Serialize vector of values
+                pub fn serialize<S: Serializer>(
+                    value: &[$ty],
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    serializer.collect_seq(value.iter().map(Ref))
+                }
+
+                /// Deserialize vector of values
+                pub fn deserialize<'de, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Vec<$ty>, D::Error> {
+                    Ok(Vec::<Owned>::deserialize(deserializer)?
+                        .into_iter()
+                        .map(|value| value.0)
+                        .collect())
+                }
+            }
+
+            /// Adapter for optional vectors of values
+            pub mod option_vec {
+                #[cfg(not(feature = "std"))]
+                use crate::prelude::*;
+                use super::{Owned, Ref};
+                use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+                struct Seq<'a>(&'a [$ty]);
+
+                impl Serialize for Seq<'_> {
+                    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                        serializer.collect_seq(self.0.iter().map(Ref))
+                    }
+                }
+
+                /// Serialize optional vector of values
+                pub fn serialize<S: Serializer>(
+                    value: &Option<Vec<$ty>>,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    match value {
+                        Some(value) => serializer.serialize_some(&Seq(value)),
+                        None => serializer.serialize_none(),
+                    }
+                }
+
+                /// Deserialize optional vector of values
+                pub fn deserialize<'de, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Option<Vec<$ty>>, D::Error> {
+                    Ok(Option::<Vec<Owned>>::deserialize(deserializer)?
+                        .map(|values| values.into_iter().map(|value| value.0).collect()))
+                }
+            }
+        }
+    };
+    ($(#[$doc:meta])* $module:ident, $ty:ty, $repr:ty, $format:expr, $parse:expr, $expecting:literal) => {
+        adapter!(
+            $(#[$doc])*
+            $module,
+            $ty,
+            $repr,
+            try |value: &$ty| Ok::<_, core::convert::Infallible>($format(value)),
+            $parse,
+            $expecting
+        );
+    };
+}
+
+adapter!(
+    /// `bytes` as base64 string (`Vec<u8>`)
+    bytes,
+    alloc::vec::Vec<u8>,
+    alloc::borrow::Cow<str>,
+    |value: &Vec<u8>| super::encode_base64(value),
+    super::decode_base64,
+    "base64"
+);
+
+adapter!(
+    /// `uuid` as string (`[u8; 16]`, big-endian byte order)
+    uuid,
+    [u8; 16],
+    alloc::borrow::Cow<str>,
+    |value: &[u8; 16]| crate::uuid::Uuid::from_bytes(*value).to_string(),
+    |text: &str| text.parse::<crate::uuid::Uuid>().ok().map(crate::uuid::Uuid::into_bytes),
+    "uuid"
+);
+
+adapter!(
+    /// `char` as number (Latin-1 code unit)
+    ///
+    /// Characters outside Latin-1 fail to serialize.
+    char,
+    char,
+    u32,
+    try |value: &char| crate::chars::encode_char(*value).map(u32::from),
+    |value: &u32| u8::try_from(*value).ok().map(crate::chars::decode_char),
+    "char"
+);
+
+adapter!(
+    /// `wchar` as number (Unicode code point)
+    wchar,
+    char,
+    u32,
+    |value: &char| crate::chars::encode_wchar(*value),
+    |value: &u32| crate::chars::decode_wchar(*value).ok(),
+    "wchar"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        for (data, text) in [
+            (&b""[..], ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (b"fooba", "Zm9vYmE="),
+            (b"foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(encode_base64(data), text);
+            assert_eq!(decode_base64(text).unwrap(), data);
+        }
+        assert!(decode_base64("Zm9").is_none());
+        assert!(decode_base64("Zg==Zg==").is_none());
+        assert!(decode_base64("Z!==").is_none());
+    }
+}
@@ -3,7 +3,9 @@
  */
 
 use crate::buffer::{ReadBuffer, WriteBuffer};
-use std::hash::{Hash, Hasher};
+use core::hash::{Hash, Hasher};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 // Single key field: Order
 #[derive(Debug, Clone)]
@@ -22,7 +24,13 @@ impl Order {
         self.id
     }
 
+    /// Serialized size
+    pub fn size_hint(&self) -> usize {
+        16 + self.symbol.len()
+    }
+
     pub fn serialize(&self, buffer: &mut WriteBuffer) -> usize {
+        buffer.reserve(buffer.offset() + self.size_hint());
         let mut offset = 0;
         buffer.write_i32(offset, self.id);
         offset += 4;
@@ -75,7 +83,13 @@ impl Balance {
         &self.currency
     }
 
+    /// Serialized size
+    pub fn size_hint(&self) -> usize {
+        12 + self.currency.len()
+    }
+
     pub fn serialize(&self, buffer: &mut WriteBuffer) -> usize {
+        buffer.reserve(buffer.offset() + self.size_hint());
         let mut offset = 0;
         buffer.write_string(offset, &self.currency);
         offset += 4 + self.currency.len();
@@ -131,7 +145,13 @@ impl UserSession {
         (self.user_id, &self.session_id)
     }
 
+    /// Serialized size
+    pub fn size_hint(&self) -> usize {
+        20 + self.session_id.len() + self.ip_address.len()
+    }
+
     pub fn serialize(&self, buffer: &mut WriteBuffer) -> usize {
+        buffer.reserve(buffer.offset() + self.size_hint());
         let mut offset = 0;
         buffer.write_i32(offset, self.user_id);
         offset += 4;
@@ -195,7 +215,13 @@ impl LogEntry {
         }
     }
 
+    /// Serialized size
+    pub fn size_hint(&self) -> usize {
+        16 + self.message.len() + self.level.len()
+    }
+
     pub fn serialize(&self, buffer: &mut WriteBuffer) -> usize {
+        buffer.reserve(buffer.offset() + self.size_hint());
         let mut offset = 0;
         buffer.write_i64(offset, self.timestamp);
         offset += 8;
@@ -225,7 +251,8 @@ impl LogEntry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    #[cfg(any(feature = "std", feature = "hashbrown"))]
+    use crate::collections::HashMap;
 
     #[test]
     fn test_order_single_key() {
@@ -300,6 +327,7 @@ mod tests {
         assert_ne!(log1, log3);
     }
 
+    #[cfg(any(feature = "std", feature = "hashbrown"))]
     #[test]
     fn test_hash_map_usage() {
         let mut order_map: HashMap<i32, Order> = HashMap::new();
@@ -315,9 +343,10 @@ mod tests {
         assert_eq!(order_map.get(&1).unwrap().symbol, "AAPL");
     }
 
+    #[cfg(any(feature = "std", feature = "hashbrown"))]
     #[test]
     fn test_hash_set_usage() {
-        use std::collections::HashSet;
+        use crate::collections::HashSet;
 
         let mut balance_set: HashSet<Balance> = HashSet::new();
 
@@ -1,23 +1,91 @@
 //! Fast Binary Encoding (FBE) runtime library for Rust
 //!
 //! Based on the original FBE architecture from https://github.com/chronoxor/FastBinaryEncoding
+//!
+//! The `std` feature (enabled by default) adds I/O, transports, buffer
+//! pooling and clock-based constructors. Without it the crate is `no_std`
+//! and the buffers, field models and final models only need `alloc`; hash
+//! collections then need the `hashbrown` feature.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
 pub mod address;
 pub mod buffer;
+pub mod bulk;
+pub mod chars;
+pub mod decimal;
 pub mod defaults;
 pub mod field_model;
 pub mod field_model_collections;
 pub mod final_model;
+#[cfg(feature = "std")]
+pub mod frame;
 pub mod inheritance;
+#[cfg(feature = "json")]
+pub mod json;
 pub mod keys;
+pub mod limits;
+pub mod logging;
 pub mod model;
 pub mod model_final;
+#[cfg(feature = "std")]
+pub mod pool;
+#[cfg(feature = "proptest")]
+pub mod proptest;
 pub mod receiver;
+#[cfg(feature = "std")]
+pub mod record;
 pub mod sender;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod timestamp;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+#[cfg(feature = "std")]
+pub mod transport;
 pub mod user_with_address;
+pub mod uuid;
+pub mod vectored;
 
 pub use buffer::{ReadBuffer, WriteBuffer};
+pub use decimal::Decimal;
 pub use field_model::FieldModel;
+#[cfg(feature = "std")]
+pub use frame::{Frame, FrameDecoder, FrameRef};
+pub use limits::{DecodeBudget, DecodeError, DecodeLimits};
+pub use logging::{Direction, MessageLog};
 pub use model::Model;
+#[cfg(feature = "std")]
+pub use pool::{BufferPool, PooledBuffer};
 pub use receiver::{Receiver, Deserialize};
 pub use sender::{Sender, Serialize};
+pub use timestamp::Timestamp;
+pub use uuid::Uuid;
+pub use vectored::VectoredWriter;
+
+#[cfg(feature = "derive")]
+pub use fbe_derive::{FbeEnum, FbeFlags, FbeStruct};
+
+/// Hash map and set used by the collection models
+///
+/// These are the `std` collections, or `hashbrown` ones without `std`
+/// (enable the `hashbrown` feature).
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+pub mod collections {
+    #[cfg(feature = "std")]
+    pub use std::collections::{HashMap, HashSet};
+
+    #[cfg(not(feature = "std"))]
+    pub use hashbrown::{HashMap, HashSet};
+}
+
+/// `alloc` items that the `std` prelude would otherwise provide
+#[cfg(not(feature = "std"))]
+mod prelude {
+    pub use alloc::format;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec;
+    pub use alloc::vec::Vec;
+}
@@ -0,0 +1,194 @@
+//! Fast Binary Encoding decode limits
+//!
+//! Sizes and counts in FBE data are attacker-controlled when the input is
+//! untrusted. The `try_*` readers on [`ReadBuffer`](crate::buffer::ReadBuffer),
+//! the field and final models, [`FinalValue::try_read_final`](crate::final_model::FinalValue::try_read_final)
+//! and generated code check every length against a [`DecodeBudget`] before
+//! allocating, and return a [`DecodeError`] instead of panicking or
+//! reserving memory proportional to a crafted `u32`.
+
+use crate::chars::CharError;
+use core::fmt;
+
+/// Configurable limits for decoding untrusted input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    /// Maximum size of a whole message in bytes
+    pub max_message_size: usize,
+    /// Maximum number of elements in a vector, list, set or map
+    pub max_collection_len: usize,
+    /// Maximum length of a string or bytes value
+    pub max_string_len: usize,
+    /// Maximum nesting depth of structs and collections
+    pub max_depth: usize,
+    /// Maximum total bytes allocated for one message
+    pub max_allocation: usize,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self {
+            max_message_size: 64 * 1024 * 1024,
+            max_collection_len: 1024 * 1024,
+            max_string_len: 16 * 1024 * 1024,
+            max_depth: 64,
+            max_allocation: 256 * 1024 * 1024,
+        }
+    }
+}
+
+impl DecodeLimits {
+    /// Limits that only reject out-of-bounds data
+    #[must_use]
+    pub const fn unlimited() -> Self {
+        Self {
+            max_message_size: usize::MAX,
+            max_collection_len: usize::MAX,
+            max_string_len: usize::MAX,
+            max_depth: usize::MAX,
+            max_allocation: usize::MAX,
+        }
+    }
+}
+
+/// Decode limit exceeded or data out of bounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// Message is larger than `max_message_size`
+    MessageTooLarge { size: usize, limit: usize },
+    /// Collection has more elements than `max_collection_len`
+    CollectionTooLong { len: usize, limit: usize },
+    /// String or bytes value is longer than `max_string_len`
+    StringTooLong { len: usize, limit: usize },
+    /// Structs or collections are nested deeper than `max_depth`
+    TooDeep { limit: usize },
+    /// Decoding would allocate more than `max_allocation` bytes
+    AllocationExceeded { limit: usize },
+    /// Value extends past the end of the buffer
+    OutOfBounds { offset: usize, size: usize, len: usize },
+    /// `wchar` value that is not a Unicode scalar value
+    InvalidChar(CharError),
+    /// Null pointer or too small size in the pointer-based Model layout
+    InvalidModel { offset: usize },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::MessageTooLarge { size, limit } => {
+                write!(f, "Message size {} exceeds limit {}", size, limit)
+            }
+            DecodeError::CollectionTooLong { len, limit } => {
+                write!(f, "Collection length {} exceeds limit {}", len, limit)
+            }
+            DecodeError::StringTooLong { len, limit } => {
+                write!(f, "String length {} exceeds limit {}", len, limit)
+            }
+            DecodeError::TooDeep { limit } => write!(f, "Nesting depth exceeds limit {}", limit),
+            DecodeError::AllocationExceeded { limit } => {
+                write!(f, "Total allocation exceeds limit {}", limit)
+            }
+            DecodeError::OutOfBounds { offset, size, len } => {
+                write!(f, "{} bytes at offset {} are out of bounds of {} bytes", size, offset, len)
+            }
+            DecodeError::InvalidChar(err) => err.fmt(f),
+            DecodeError::InvalidModel { offset } => {
+                write!(f, "Invalid Model pointer or size at offset {}", offset)
+            }
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+impl From<CharError> for DecodeError {
+    fn from(err: CharError) -> Self {
+        DecodeError::InvalidChar(err)
+    }
+}
+
+/// Running totals for decoding one message against [`DecodeLimits`]
+#[derive(Debug, Clone)]
+pub struct DecodeBudget {
+    limits: DecodeLimits,
+    allocated: usize,
+    depth: usize,
+}
+
+impl DecodeBudget {
+    /// Create a budget with nothing allocated yet
+    #[must_use]
+    pub fn new(limits: DecodeLimits) -> Self {
+        Self { limits, allocated: 0, depth: 0 }
+    }
+
+    /// Create a budget for a message of `size` bytes
+    pub fn for_message(limits: DecodeLimits, size: usize) -> Result<Self, DecodeError> {
+        if size > limits.max_message_size {
+            return Err(DecodeError::MessageTooLarge { size, limit: limits.max_message_size });
+        }
+        Ok(Self::new(limits))
+    }
+
+    /// Get decode limits
+    #[must_use]
+    pub const fn limits(&self) -> &DecodeLimits {
+        &self.limits
+    }
+
+    /// Get bytes allocated so far
+    #[must_use]
+    pub const fn allocated(&self) -> usize {
+        self.allocated
+    }
+
+    /// Get current nesting depth
+    #[must_use]
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Account for `size` allocated bytes
+    pub fn allocate(&mut self, size: usize) -> Result<(), DecodeError> {
+        let limit = self.limits.max_allocation;
+        match self.allocated.checked_add(size) {
+            Some(total) if total <= limit => {
+                self.allocated = total;
+                Ok(())
+            }
+            _ => Err(DecodeError::AllocationExceeded { limit }),
+        }
+    }
+
+    /// Check a string or bytes length and account for its allocation
+    pub fn string(&mut self, len: usize) -> Result<(), DecodeError> {
+        let limit = self.limits.max_string_len;
+        if len > limit {
+            return Err(DecodeError::StringTooLong { len, limit });
+        }
+        self.allocate(len)
+    }
+
+    /// Check a collection length and account for `len` items of `item_size` bytes
+    pub fn collection(&mut self, len: usize, item_size: usize) -> Result<(), DecodeError> {
+        let limit = self.limits.max_collection_len;
+        if len > limit {
+            return Err(DecodeError::CollectionTooLong { len, limit });
+        }
+        self.allocate(len.saturating_mul(item_size))
+    }
+
+    /// Enter a nested struct or collection
+    pub fn enter(&mut self) -> Result<(), DecodeError> {
+        if self.depth >= self.limits.max_depth {
+            return Err(DecodeError::TooDeep { limit: self.limits.max_depth });
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Leave a nested struct or collection
+    pub fn leave(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+}
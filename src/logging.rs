@@ -0,0 +1,107 @@
+//! Fast Binary Encoding message logging
+//!
+//! Structured log records produced by senders and receivers. Records are
+//! forwarded to the `log` and `tracing` ecosystems when the corresponding
+//! cargo features are enabled.
+
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Message direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Send,
+    Receive,
+}
+
+/// Structured log record describing a single message
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageLog {
+    /// Message direction
+    pub direction: Direction,
+    /// Rust type name of the message
+    pub type_name: &'static str,
+    /// FBE type id of the message
+    pub type_id: usize,
+    /// Serialized size in bytes
+    pub size: usize,
+    /// Optional JSON rendering of the message
+    pub json: Option<String>,
+}
+
+impl MessageLog {
+    /// Create a new log record without JSON rendering
+    #[must_use]
+    pub fn new(direction: Direction, type_name: &'static str, type_id: usize, size: usize) -> Self {
+        Self {
+            direction,
+            type_name,
+            type_id,
+            size,
+            json: None,
+        }
+    }
+
+    /// Attach JSON rendering to the log record
+    #[must_use]
+    pub fn with_json(mut self, json: Option<String>) -> Self {
+        self.json = json;
+        self
+    }
+}
+
+impl fmt::Display for MessageLog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let verb = match self.direction {
+            Direction::Send => "Sending",
+            Direction::Receive => "Received",
+        };
+        write!(
+            f,
+            "{} {} (type {}, {} bytes)",
+            verb, self.type_name, self.type_id, self.size
+        )?;
+        if let Some(json) = &self.json {
+            write!(f, ": {}", json)?;
+        }
+        Ok(())
+    }
+}
+
+/// Forward a log record to the enabled logging backends
+pub fn emit(record: &MessageLog) {
+    #[cfg(feature = "log")]
+    log::debug!(target: "fbe", "{}", record);
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        target: "fbe",
+        direction = ?record.direction,
+        type_name = record.type_name,
+        type_id = record.type_id,
+        size = record.size,
+        json = record.json.as_deref(),
+        "message"
+    );
+
+    let _ = record;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_log_display() {
+        let record = MessageLog::new(Direction::Send, "Order", 1, 42);
+        assert_eq!(record.to_string(), "Sending Order (type 1, 42 bytes)");
+
+        let record = MessageLog::new(Direction::Receive, "Order", 1, 42)
+            .with_json(Some("{\"id\":1}".to_string()));
+        assert_eq!(
+            record.to_string(),
+            "Received Order (type 1, 42 bytes): {\"id\":1}"
+        );
+    }
+}
@@ -32,5 +32,5 @@ pub trait Model {
     fn buffer_mut(&mut self) -> &mut WriteBuffer;
 
     /// Get read buffer reference
-    fn buffer(&self) -> &ReadBuffer;
+    fn buffer(&self) -> &ReadBuffer<'_>;
 }
@@ -3,6 +3,9 @@
  */
 
 use crate::buffer::{ReadBuffer, WriteBuffer};
+use crate::limits::{DecodeBudget, DecodeError, DecodeLimits};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 // Product struct for testing
 #[derive(Debug, Clone, PartialEq)]
@@ -66,7 +69,7 @@ impl Product {
         off += 4;
 
         let name = buffer.read_string(off);
-        off += 4 + name.len();
+        off += 4 + buffer.read_u32(off) as usize;
 
         let price = buffer.read_f64(off);
         off += 8;
@@ -81,6 +84,27 @@ impl Product {
         }
     }
 
+    /// Deserialize struct data (without header), checking bounds and lengths
+    fn try_deserialize_struct(buffer: &ReadBuffer, offset: usize, budget: &mut DecodeBudget) -> Result<(Self, usize), DecodeError> {
+        let mut off = offset;
+
+        buffer.check_bounds(off, 4)?;
+        let id = buffer.read_i32(off);
+        off += 4;
+
+        let name = buffer.try_read_string(off, budget)?;
+        off += 4 + buffer.read_u32(off) as usize;
+
+        buffer.check_bounds(off, 12)?;
+        let price = buffer.read_f64(off);
+        off += 8;
+
+        let quantity = buffer.read_i32(off);
+        off += 4;
+
+        Ok((Self { id, name, price, quantity }, off - offset))
+    }
+
     /// Serialize with Model (4-byte header)
     /// Format: [4-byte size][struct data]
     pub fn serialize_model(&self, buffer: &mut WriteBuffer) -> usize {
@@ -110,6 +134,17 @@ impl Product {
         (product, total_size)
     }
 
+    /// Deserialize with Model (4-byte header) from untrusted input within decode limits
+    pub fn try_deserialize_model(buffer: &ReadBuffer, limits: &DecodeLimits) -> Result<(Self, usize), DecodeError> {
+        let total_size = buffer.try_read_u32(0)? as usize;
+        let mut budget = DecodeBudget::for_message(*limits, total_size)?;
+        buffer.check_bounds(0, total_size)?;
+
+        let (product, _) = Self::try_deserialize_struct(buffer, 4, &mut budget)?;
+
+        Ok((product, total_size))
+    }
+
     /// Serialize with FinalModel (no header)
     /// Format: [struct data]
     pub fn serialize_final(&self, buffer: &mut WriteBuffer) -> usize {
@@ -131,6 +166,12 @@ impl Product {
 
         (product, size)
     }
+
+    /// Deserialize with FinalModel (no header) from untrusted input within decode limits
+    pub fn try_deserialize_final(buffer: &ReadBuffer, limits: &DecodeLimits) -> Result<(Self, usize), DecodeError> {
+        let mut budget = DecodeBudget::for_message(*limits, buffer.size())?;
+        Self::try_deserialize_struct(buffer, 0, &mut budget)
+    }
 }
 
 #[cfg(test)]
@@ -232,4 +273,42 @@ mod tests {
         // Data should be identical (excluding header)
         assert_eq!(model_data_without_header, final_data);
     }
+
+    #[test]
+    fn test_try_deserialize_truncated() {
+        let product = Product::new(123, "Laptop".to_string(), 999.99, 5);
+
+        let mut buffer = WriteBuffer::new();
+        let model_size = product.serialize_model(&mut buffer);
+        let data = buffer.data()[..model_size].to_vec();
+
+        let limits = DecodeLimits::default();
+        let read_buffer = ReadBuffer::from(data.clone());
+        assert_eq!(Product::try_deserialize_model(&read_buffer, &limits), Ok((product.clone(), model_size)));
+        let read_buffer = ReadBuffer::from(data[4..].to_vec());
+        assert_eq!(Product::try_deserialize_final(&read_buffer, &limits), Ok((product, model_size - 4)));
+
+        for len in 0..model_size {
+            let read_buffer = ReadBuffer::from(data[..len].to_vec());
+            assert!(Product::try_deserialize_model(&read_buffer, &limits).is_err());
+        }
+    }
+
+    #[test]
+    fn test_deserialize_invalid_utf8_name() {
+        let product = Product::new(7, "ab".to_string(), 1.5, 3);
+
+        let mut buffer = WriteBuffer::new();
+        let size = product.serialize_final(&mut buffer);
+        let mut data = buffer.data()[..size].to_vec();
+        data[8..10].copy_from_slice(&[0xFF, 0xFE]);
+
+        // Lossy decoding changes the name length, fields after it must not shift
+        let read_buffer = ReadBuffer::from(data);
+        let (decoded, _) = Product::deserialize_final(&read_buffer);
+        assert_eq!(decoded.price, 1.5);
+        assert_eq!(decoded.quantity, 3);
+        let (decoded, _) = Product::try_deserialize_final(&read_buffer, &DecodeLimits::default()).unwrap();
+        assert_eq!(decoded.quantity, 3);
+    }
 }
@@ -0,0 +1,296 @@
+//! Fast Binary Encoding write buffer pool
+//!
+//! [`BufferPool`] hands out cleared, growable [`WriteBuffer`]s that keep the
+//! capacity of earlier messages and go back to the pool when the
+//! [`PooledBuffer`] guard is dropped. Each thread keeps a small cache of
+//! released buffers, so the common acquire/release cycle never takes a lock;
+//! the shared free list behind a mutex is only touched when that cache is
+//! empty or full. The pool is cheap to clone and can be shared between
+//! threads.
+//!
+//! Thread caches hold up to 8 buffers per pool on top of the shared free
+//! list. When a pool is dropped, the cache of the dropping thread is freed
+//! at once; caches on other threads are freed the next time those threads
+//! miss their cache of any pool, or when they exit.
+
+use crate::buffer::WriteBuffer;
+use crate::sender::Serialize;
+use std::cell::RefCell;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+/// Buffers of one pool cached per thread before releases spill into the shared free list
+const LOCAL_CACHE_SIZE: usize = 8;
+
+static NEXT_POOL_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Per-thread cache of released buffers for one pool
+struct LocalCache {
+    id: usize,
+    pool: Weak<Shared>,
+    buffers: Vec<WriteBuffer>,
+}
+
+thread_local! {
+    static LOCAL_CACHES: RefCell<Vec<LocalCache>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Drop caches of pools that no longer exist
+fn prune(caches: &mut Vec<LocalCache>) {
+    caches.retain(|cache| cache.pool.strong_count() > 0);
+}
+
+struct Shared {
+    id: usize,
+    free: Mutex<Vec<WriteBuffer>>,
+    max_buffers: usize,
+    max_capacity: usize,
+}
+
+impl Shared {
+    fn acquire(&self) -> Option<WriteBuffer> {
+        let local = LOCAL_CACHES
+            .try_with(|caches| {
+                let mut caches = caches.borrow_mut();
+                let buffer = caches
+                    .iter_mut()
+                    .find(|cache| cache.id == self.id)
+                    .and_then(|cache| cache.buffers.pop());
+                if buffer.is_none() {
+                    prune(&mut caches);
+                }
+                buffer
+            })
+            .ok()
+            .flatten();
+        local.or_else(|| self.free.lock().unwrap_or_else(|e| e.into_inner()).pop())
+    }
+
+    fn release(self: &Arc<Self>, mut buffer: WriteBuffer) {
+        if buffer.capacity() > self.max_capacity {
+            return;
+        }
+        buffer.reset();
+        buffer.set_growable(true);
+
+        let spilled = LOCAL_CACHES.try_with(|caches| {
+            let mut caches = caches.borrow_mut();
+            let index = match caches.iter().position(|cache| cache.id == self.id) {
+                Some(index) => index,
+                None => {
+                    prune(&mut caches);
+                    caches.push(LocalCache {
+                        id: self.id,
+                        pool: Arc::downgrade(self),
+                        buffers: Vec::with_capacity(LOCAL_CACHE_SIZE),
+                    });
+                    caches.len() - 1
+                }
+            };
+            let buffers = &mut caches[index].buffers;
+            if buffers.len() < LOCAL_CACHE_SIZE {
+                buffers.push(buffer);
+                None
+            } else {
+                Some(buffer)
+            }
+        });
+        let Ok(Some(buffer)) = spilled else {
+            return;
+        };
+
+        let mut free = self.free.lock().unwrap_or_else(|e| e.into_inner());
+        if free.len() < self.max_buffers {
+            free.push(buffer);
+        }
+    }
+}
+
+impl Drop for Shared {
+    fn drop(&mut self) {
+        // The pool is gone, so its own cache is pruned with the others
+        let _ = LOCAL_CACHES.try_with(|caches| {
+            if let Ok(mut caches) = caches.try_borrow_mut() {
+                prune(&mut caches);
+            }
+        });
+    }
+}
+
+/// Thread-safe pool of reusable write buffers
+#[derive(Clone)]
+pub struct BufferPool {
+    shared: Arc<Shared>,
+}
+
+impl Default for BufferPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for BufferPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferPool")
+            .field("max_buffers", &self.shared.max_buffers)
+            .field("max_capacity", &self.shared.max_capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BufferPool {
+    /// Default number of buffers kept in the shared free list
+    pub const DEFAULT_MAX_BUFFERS: usize = 64;
+
+    /// Default largest buffer capacity returned to the pool
+    pub const DEFAULT_MAX_CAPACITY: usize = 1 << 20;
+
+    /// Create a new buffer pool with default limits
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_limits(Self::DEFAULT_MAX_BUFFERS, Self::DEFAULT_MAX_CAPACITY)
+    }
+
+    /// Create a new buffer pool
+    ///
+    /// At most `max_buffers` released buffers are kept in the shared free
+    /// list, and buffers that grew beyond `max_capacity` bytes are freed
+    /// instead of being returned, so one oversized message does not pin its
+    /// memory forever. `max_buffers` does not count the per-thread caches,
+    /// which hold up to 8 more buffers per thread that released one.
+    #[must_use]
+    pub fn with_limits(max_buffers: usize, max_capacity: usize) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                id: NEXT_POOL_ID.fetch_add(1, Ordering::Relaxed),
+                free: Mutex::new(Vec::new()),
+                max_buffers,
+                max_capacity,
+            }),
+        }
+    }
+
+    /// Get the number of buffers in the shared free list
+    #[must_use]
+    pub fn available(&self) -> usize {
+        self.shared.free.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Take a cleared growable buffer from the pool
+    #[must_use]
+    pub fn acquire(&self) -> PooledBuffer<'_> {
+        let buffer = self.shared.acquire().unwrap_or_else(WriteBuffer::growable);
+        PooledBuffer {
+            buffer: Some(buffer),
+            pool: &self.shared,
+        }
+    }
+
+    /// Serialize a value into a pooled buffer
+    pub fn serialize<T>(&self, value: &T) -> PooledBuffer<'_>
+    where
+        T: Serialize + ?Sized,
+    {
+        let mut buffer = self.acquire();
+        buffer.reserve(value.size_hint());
+        value.serialize(&mut buffer);
+        buffer
+    }
+}
+
+/// Write buffer borrowed from a [`BufferPool`], returned to it on drop
+pub struct PooledBuffer<'a> {
+    buffer: Option<WriteBuffer>,
+    pool: &'a Arc<Shared>,
+}
+
+impl PooledBuffer<'_> {
+    /// Detach the buffer from the pool
+    #[must_use]
+    pub fn into_inner(mut self) -> WriteBuffer {
+        self.buffer.take().expect("pooled buffer is present until drop")
+    }
+}
+
+impl Deref for PooledBuffer<'_> {
+    type Target = WriteBuffer;
+
+    fn deref(&self) -> &WriteBuffer {
+        self.buffer.as_ref().expect("pooled buffer is present until drop")
+    }
+}
+
+impl DerefMut for PooledBuffer<'_> {
+    fn deref_mut(&mut self) -> &mut WriteBuffer {
+        self.buffer.as_mut().expect("pooled buffer is present until drop")
+    }
+}
+
+impl fmt::Debug for PooledBuffer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PooledBuffer").field(&**self).finish()
+    }
+}
+
+impl Drop for PooledBuffer<'_> {
+    fn drop(&mut self) {
+        if let Some(buffer) = self.buffer.take() {
+            self.pool.release(buffer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached(pool: &BufferPool) -> Option<usize> {
+        LOCAL_CACHES.with(|caches| {
+            let caches = caches.borrow();
+            caches.iter().find(|cache| cache.id == pool.shared.id).map(|cache| cache.buffers.len())
+        })
+    }
+
+    fn caches() -> usize {
+        LOCAL_CACHES.with(|caches| caches.borrow().len())
+    }
+
+    #[test]
+    fn test_drop_frees_local_cache() {
+        let pool = BufferPool::new();
+        drop(pool.acquire());
+        assert_eq!(cached(&pool), Some(1));
+        let before = caches();
+        drop(pool);
+        assert_eq!(caches(), before - 1);
+    }
+
+    #[test]
+    fn test_acquire_miss_prunes_other_threads() {
+        let pool = BufferPool::new();
+        let (released, ready) = std::sync::mpsc::channel();
+        let (dropped, wait) = std::sync::mpsc::channel();
+        let thread = {
+            let pool = pool.clone();
+            std::thread::spawn(move || {
+                drop(pool.acquire());
+                drop(pool);
+                released.send(()).unwrap();
+                wait.recv().unwrap();
+
+                // The last reference was dropped on the other thread
+                assert_eq!(caches(), 1);
+                let other = BufferPool::new();
+                drop(other.acquire());
+                assert_eq!(cached(&other), Some(1));
+                assert_eq!(caches(), 1);
+            })
+        };
+        ready.recv().unwrap();
+        drop(pool);
+        dropped.send(()).unwrap();
+        thread.join().unwrap();
+    }
+}
@@ -0,0 +1,151 @@
+//! Fast Binary Encoding property-based testing support
+//!
+//! With the `proptest` feature [`Decimal`](crate::Decimal),
+//! [`Timestamp`](crate::Timestamp) and [`Uuid`](crate::Uuid) implement
+//! [`Arbitrary`], and so do types deriving `FbeStruct`, `FbeEnum` and
+//! `FbeFlags` (field types must implement `Arbitrary`, derived types `Debug`).
+//! fbec generated structs implement it under the including crate's
+//! `proptest` feature.
+//!
+//! The `assert_*` helpers check that any value survives encoding and that
+//! the size predictions hold:
+//!
+//! | Helper                       | Checks                                         |
+//! |------------------------------|------------------------------------------------|
+//! | [`assert_roundtrip`]         | Model and FinalModel layouts of a value        |
+//! | [`assert_message_roundtrip`] | `Serialize`/`Deserialize` messages             |
+//! | `assert_json_roundtrip`      | FBE JSON (`json` feature)                      |
+//!
+//! ```
+//! use fbe::proptest::assert_roundtrip;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn decimal_roundtrip(value: fbe::Decimal, tags: Vec<Option<String>>) {
+//!         assert_roundtrip(&value);
+//!         assert_roundtrip(&tags);
+//!     }
+//! }
+//! # decimal_roundtrip();
+//! ```
+
+use crate::buffer::{ReadBuffer, WriteBuffer};
+use crate::field_model::FieldValue;
+use crate::final_model::FinalValue;
+use crate::limits::{DecodeBudget, DecodeLimits};
+use crate::receiver::Deserialize;
+use crate::sender::Serialize;
+use crate::vectored::VectoredWriter;
+use core::fmt::Debug;
+
+pub use proptest::arbitrary::{Arbitrary, any};
+pub use proptest::strategy::{BoxedStrategy, Just, Strategy};
+
+/// Assert that a value round-trips through the Model and FinalModel layouts
+///
+/// Checks that `final_size` and `read_final_size` match the written size,
+/// that vectored output matches and that `read_final` and `try_read_final`
+/// consume exactly the written bytes and return the value. In the Model
+/// layout the value is written into a field slot, which must take
+/// `FBE_SIZE` bytes plus `fbe_extra` bytes of pointed-to data, and read back
+/// with `read_field` and `try_read_field`.
+#[track_caller]
+pub fn assert_roundtrip<T>(value: &T)
+where
+    T: FinalValue + FieldValue + PartialEq + Debug,
+{
+    let size = value.final_size();
+
+    // FinalModel (inline data)
+    let mut buffer = WriteBuffer::growable();
+    let offset = buffer.allocate(size);
+    assert_eq!(value.write_final(&mut buffer, offset), size, "FinalModel size of {:?}", value);
+    assert_eq!(buffer.size(), size, "FinalModel wrote past final_size for {:?}", value);
+    assert_decoded(value, buffer.data(), 0, size, "FinalModel");
+
+    let mut writer = VectoredWriter::new();
+    value.write_vectored(&mut writer);
+    assert_eq!(writer.to_vec(), buffer.data(), "vectored output of {:?}", value);
+
+    // Model (field slot with pointers relative to the start of the buffer)
+    let mut buffer = WriteBuffer::growable();
+    let offset = buffer.allocate(T::FBE_SIZE);
+    value.write_field(&mut buffer, offset, offset);
+    assert_eq!(buffer.size(), T::FBE_SIZE + value.fbe_extra(), "Model field size of {:?}", value);
+
+    let reader = ReadBuffer::borrowed(buffer.data());
+    assert_eq!(&T::read_field(&reader, offset, offset), value, "Model read_field");
+    let mut budget = DecodeBudget::new(DecodeLimits::unlimited());
+    match T::try_read_field(&reader, offset, offset, &mut budget) {
+        Ok(decoded) => assert_eq!(&decoded, value, "Model try_read_field"),
+        Err(err) => panic!("Model try_read_field of {:?} failed: {}", value, err),
+    }
+    assert_eq!(budget.depth(), 0, "Model try_read_field left nested depth for {:?}", value);
+}
+
+/// Decode `data` at `offset` with and without decode limits
+#[track_caller]
+fn assert_decoded<T>(value: &T, data: &[u8], offset: usize, size: usize, layout: &str)
+where
+    T: FinalValue + PartialEq + Debug,
+{
+    let reader = ReadBuffer::borrowed(data);
+    let (decoded, read) = T::read_final(&reader, offset);
+    assert_eq!(&decoded, value, "{} read_final", layout);
+    assert_eq!(read, size, "{} read_final size of {:?}", layout, value);
+    assert_eq!(T::read_final_size(&reader, offset), size, "{} read_final_size of {:?}", layout, value);
+
+    let mut budget = DecodeBudget::new(DecodeLimits::unlimited());
+    match T::try_read_final(&reader, offset, &mut budget) {
+        Ok((decoded, read)) => {
+            assert_eq!(&decoded, value, "{} try_read_final", layout);
+            assert_eq!(read, size, "{} try_read_final size of {:?}", layout, value);
+        }
+        Err(err) => panic!("{} try_read_final of {:?} failed: {}", layout, value, err),
+    }
+    assert_eq!(budget.depth(), 0, "{} try_read_final left nested depth for {:?}", layout, value);
+}
+
+/// Assert that a message round-trips through `Serialize` and `Deserialize`
+///
+/// Serializes into a fixed-size buffer reserved from `size_hint`, so data
+/// written without `allocate` or `set_size` is caught, checks the returned
+/// size against the written data and a non-zero `size_hint`, then decodes
+/// with `deserialize` and `try_deserialize`.
+#[track_caller]
+pub fn assert_message_roundtrip<T>(value: &T)
+where
+    T: Serialize + Deserialize + PartialEq + Debug,
+{
+    let mut buffer = WriteBuffer::new();
+    buffer.reserve(value.size_hint());
+    let size = value.serialize(&mut buffer);
+    assert_eq!(size, buffer.size(), "serialized size of {:?}", value);
+    if value.size_hint() != 0 {
+        assert_eq!(value.size_hint(), size, "size_hint of {:?}", value);
+    }
+
+    let reader = ReadBuffer::borrowed(buffer.data());
+    assert_eq!(&T::deserialize(&reader), value, "deserialize");
+    match T::try_deserialize(&reader, &DecodeLimits::unlimited()) {
+        Ok(decoded) => assert_eq!(&decoded, value, "try_deserialize"),
+        Err(err) => panic!("try_deserialize of {:?} failed: {}", value, err),
+    }
+}
+
+/// Assert that a value round-trips through FBE JSON
+#[cfg(feature = "json")]
+#[track_caller]
+pub fn assert_json_roundtrip<T>(value: &T)
+where
+    T: ::serde::Serialize + ::serde::de::DeserializeOwned + PartialEq + Debug,
+{
+    let json = match crate::json::to_json(value) {
+        Ok(json) => json,
+        Err(err) => panic!("to_json of {:?} failed: {}", value, err),
+    };
+    match crate::json::from_json::<T>(&json) {
+        Ok(decoded) => assert_eq!(&decoded, value, "from_json of {}", json),
+        Err(err) => panic!("from_json of {} failed: {}", json, err),
+    }
+}
@@ -1,6 +1,10 @@
 //! Fast Binary Encoding receiver
 
 use crate::buffer::ReadBuffer;
+use crate::limits::{DecodeBudget, DecodeError, DecodeLimits};
+use crate::logging::{self, Direction, MessageLog};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 /// Fast Binary Encoding base receiver
 pub trait Receiver {
@@ -13,11 +17,23 @@ pub trait Receiver {
     /// Receive message handler (must be implemented)
     fn on_receive(&mut self, data: &[u8]) -> bool;
     
+    /// Receive typed message handler (can be overridden to use the FBE type id)
+    fn on_receive_frame(&mut self, fbe_type: usize, data: &[u8]) -> bool {
+        let _ = fbe_type;
+        self.on_receive(data)
+    }
+
     /// Receive log message handler (can be overridden)
     fn on_receive_log(&self, message: &str) {
         // Default: do nothing
         let _ = message;
     }
+
+    /// Receive structured log record handler (can be overridden)
+    fn on_receive_message(&self, record: &MessageLog) {
+        logging::emit(record);
+        self.on_receive_log(&record.to_string());
+    }
     
     /// Receive and process data
     fn receive(&mut self, data: &[u8]) -> bool {
@@ -29,10 +45,79 @@ pub trait Receiver {
         
         self.on_receive(data)
     }
+
+    /// Receive and process a message payload with its FBE type id
+    fn receive_frame(&mut self, fbe_type: usize, data: &[u8]) -> bool {
+        if self.is_logging() {
+            self.on_receive_log(&format!("Received {} bytes (type {})", data.len(), fbe_type));
+        }
+
+        self.on_receive_frame(fbe_type, data)
+    }
+
+    /// Deserialize a received struct
+    fn receive_value<T>(&self, data: &[u8]) -> T
+    where
+        T: Deserialize,
+    {
+        let buffer = ReadBuffer::borrowed(data);
+        let value = T::deserialize(&buffer);
+
+        if self.is_logging() {
+            let record = MessageLog::new(
+                Direction::Receive,
+                core::any::type_name::<T>(),
+                T::FBE_TYPE,
+                data.len(),
+            )
+            .with_json(value.log_json());
+            self.on_receive_message(&record);
+        }
+
+        value
+    }
+
+    /// Deserialize a received struct from untrusted input within decode limits
+    fn try_receive_value<T>(&self, data: &[u8], limits: &DecodeLimits) -> Result<T, DecodeError>
+    where
+        T: Deserialize,
+    {
+        let buffer = ReadBuffer::borrowed(data);
+        let value = T::try_deserialize(&buffer, limits)?;
+
+        if self.is_logging() {
+            let record = MessageLog::new(
+                Direction::Receive,
+                core::any::type_name::<T>(),
+                T::FBE_TYPE,
+                data.len(),
+            )
+            .with_json(value.log_json());
+            self.on_receive_message(&record);
+        }
+
+        Ok(value)
+    }
 }
 
 /// Trait for types that can be deserialized
 pub trait Deserialize: Sized {
+    /// FBE type id reported in log records
+    const FBE_TYPE: usize = 0;
+
     fn deserialize(buffer: &ReadBuffer) -> Self;
-}
 
+    /// Deserialize untrusted input, returning an error when a decode limit is exceeded
+    ///
+    /// Overridden by `#[derive(FbeStruct)]` and generated code; the default
+    /// only checks the message size.
+    fn try_deserialize(buffer: &ReadBuffer, limits: &DecodeLimits) -> Result<Self, DecodeError> {
+        DecodeBudget::for_message(*limits, buffer.size())?;
+        Ok(Self::deserialize(buffer))
+    }
+
+    /// JSON rendering reported in log records
+    fn log_json(&self) -> Option<String> {
+        None
+    }
+}
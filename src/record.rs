@@ -0,0 +1,489 @@
+//! Fast Binary Encoding message record/replay
+//!
+//! Captures FBE traffic into log files and feeds it back into receivers.
+//!
+//! # File format (version 1)
+//!
+//! All integers are little-endian.
+//!
+//! File header (16 bytes):
+//!
+//! | Offset | Size | Field                                        |
+//! |--------|------|----------------------------------------------|
+//! | 0      | 6    | Magic `FBEREC`                               |
+//! | 6      | 2    | Format version (`1`)                         |
+//! | 8      | 8    | Recording start, nanoseconds since Unix epoch |
+//!
+//! Followed by records until end of file. Record layout (17-byte header):
+//!
+//! | Offset | Size | Field                                        |
+//! |--------|------|----------------------------------------------|
+//! | 0      | 8    | Timestamp, nanoseconds since Unix epoch       |
+//! | 8      | 1    | Direction (`0` = send, `1` = receive)         |
+//! | 9      | 4    | FBE type id (`0` when unknown)                |
+//! | 13     | 4    | Payload size                                 |
+//! | 17     | N    | Payload (serialized message)                 |
+
+use crate::buffer::WriteBuffer;
+use crate::frame::{self, FrameRef};
+use crate::limits::{DecodeError, DecodeLimits};
+use crate::logging::{Direction, MessageLog};
+use crate::receiver::Receiver;
+use crate::sender::Sender;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Record file magic
+pub const RECORD_MAGIC: [u8; 6] = *b"FBEREC";
+
+/// Current record file format version
+pub const RECORD_VERSION: u16 = 1;
+
+/// Record file header size
+pub const RECORD_FILE_HEADER_SIZE: usize = 16;
+
+/// Record header size
+pub const RECORD_HEADER_SIZE: usize = 17;
+
+/// Current time in nanoseconds since Unix epoch
+fn now_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos() as u64)
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// ============================================================================
+// Records
+// ============================================================================
+
+/// Single recorded message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Timestamp, nanoseconds since Unix epoch
+    pub timestamp: u64,
+    pub direction: Direction,
+    pub fbe_type: u32,
+    pub payload: Vec<u8>,
+}
+
+/// Record file writer
+pub struct RecordWriter<W: Write> {
+    writer: W,
+    start: u64,
+}
+
+impl RecordWriter<BufWriter<File>> {
+    /// Create a new record file
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<W: Write> RecordWriter<W> {
+    /// Create a new record writer and write the file header
+    pub fn new(writer: W) -> io::Result<Self> {
+        Self::with_start(writer, now_nanos())
+    }
+
+    /// Create a new record writer with an explicit start timestamp
+    pub fn with_start(mut writer: W, start: u64) -> io::Result<Self> {
+        let mut header = [0u8; RECORD_FILE_HEADER_SIZE];
+        header[..6].copy_from_slice(&RECORD_MAGIC);
+        header[6..8].copy_from_slice(&RECORD_VERSION.to_le_bytes());
+        header[8..].copy_from_slice(&start.to_le_bytes());
+        writer.write_all(&header)?;
+        Ok(Self { writer, start })
+    }
+
+    /// Get recording start timestamp
+    #[must_use]
+    pub const fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Append a message stamped with the current time
+    pub fn write(&mut self, direction: Direction, fbe_type: u32, payload: &[u8]) -> io::Result<()> {
+        self.write_at(now_nanos(), direction, fbe_type, payload)
+    }
+
+    /// Append a message with an explicit timestamp
+    pub fn write_at(
+        &mut self,
+        timestamp: u64,
+        direction: Direction,
+        fbe_type: u32,
+        payload: &[u8],
+    ) -> io::Result<()> {
+        let size = u32::try_from(payload.len())
+            .map_err(|_| invalid_data(format!("Record payload too large: {}", payload.len())))?;
+
+        let mut header = [0u8; RECORD_HEADER_SIZE];
+        header[..8].copy_from_slice(&timestamp.to_le_bytes());
+        header[8] = match direction {
+            Direction::Send => 0,
+            Direction::Receive => 1,
+        };
+        header[9..13].copy_from_slice(&fbe_type.to_le_bytes());
+        header[13..].copy_from_slice(&size.to_le_bytes());
+        self.writer.write_all(&header)?;
+        self.writer.write_all(payload)
+    }
+
+    /// Append a record
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        self.write_at(record.timestamp, record.direction, record.fbe_type, &record.payload)
+    }
+
+    /// Flush the underlying writer
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Consume writer and return the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Record file reader
+pub struct RecordReader<R: Read> {
+    reader: R,
+    version: u16,
+    start: u64,
+    limits: DecodeLimits,
+}
+
+impl RecordReader<BufReader<File>> {
+    /// Open a record file
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> RecordReader<R> {
+    /// Create a new record reader and validate the file header
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut header = [0u8; RECORD_FILE_HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+
+        if header[..6] != RECORD_MAGIC {
+            return Err(invalid_data("Invalid record file magic".to_string()));
+        }
+        let version = u16::from_le_bytes([header[6], header[7]]);
+        if version != RECORD_VERSION {
+            return Err(invalid_data(format!(
+                "Unsupported record file version: {}",
+                version
+            )));
+        }
+        let start = u64::from_le_bytes(header[8..].try_into().unwrap());
+
+        Ok(Self {
+            reader,
+            version,
+            start,
+            limits: DecodeLimits::default(),
+        })
+    }
+
+    /// Get file format version
+    #[must_use]
+    pub const fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Get recording start timestamp
+    #[must_use]
+    pub const fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Get decode limits; `max_message_size` caps record payloads
+    pub fn decode_limits(&self) -> &DecodeLimits {
+        &self.limits
+    }
+
+    /// Set decode limits
+    pub fn set_decode_limits(&mut self, limits: DecodeLimits) {
+        self.limits = limits;
+    }
+
+    /// Read the next record, `None` at end of file
+    ///
+    /// Payloads larger than `max_message_size` are rejected with
+    /// `InvalidData` before anything is allocated.
+    pub fn read_record(&mut self) -> io::Result<Option<Record>> {
+        let mut header = [0u8; RECORD_HEADER_SIZE];
+        let mut filled = 0;
+        while filled < RECORD_HEADER_SIZE {
+            match self.reader.read(&mut header[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "Truncated record header",
+                    ));
+                }
+                Ok(size) => filled += size,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        let timestamp = u64::from_le_bytes(header[..8].try_into().unwrap());
+        let direction = match header[8] {
+            0 => Direction::Send,
+            1 => Direction::Receive,
+            value => return Err(invalid_data(format!("Invalid record direction: {}", value))),
+        };
+        let fbe_type = u32::from_le_bytes(header[9..13].try_into().unwrap());
+        let size = u32::from_le_bytes(header[13..].try_into().unwrap()) as usize;
+        if size > self.limits.max_message_size {
+            let err = DecodeError::MessageTooLarge { size, limit: self.limits.max_message_size };
+            return Err(io::Error::new(io::ErrorKind::InvalidData, err));
+        }
+
+        let mut payload = vec![0; size];
+        self.reader.read_exact(&mut payload)?;
+
+        Ok(Some(Record {
+            timestamp,
+            direction,
+            fbe_type,
+            payload,
+        }))
+    }
+}
+
+impl<R: Read> Iterator for RecordReader<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+// ============================================================================
+// Recorder
+// ============================================================================
+
+/// Sender/Receiver wrapper appending every message to a record file
+///
+/// Recording failures never interrupt the wrapped sender or receiver; the
+/// last error can be inspected with `take_error()`.
+pub struct Recorder<T, W: Write = BufWriter<File>> {
+    inner: T,
+    writer: RecordWriter<W>,
+    error: Option<io::Error>,
+}
+
+impl<T, W: Write> Recorder<T, W> {
+    /// Wrap a sender or receiver
+    pub fn new(inner: T, writer: RecordWriter<W>) -> Self {
+        Self {
+            inner,
+            writer,
+            error: None,
+        }
+    }
+
+    /// Get wrapped sender/receiver reference
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Get mutable wrapped sender/receiver reference
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Take the last recording error
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Flush the record file
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Flush and return the wrapped sender/receiver and record writer
+    pub fn into_parts(mut self) -> io::Result<(T, RecordWriter<W>)> {
+        self.writer.flush()?;
+        Ok((self.inner, self.writer))
+    }
+
+    fn record(&mut self, direction: Direction, fbe_type: usize, data: &[u8]) {
+        if let Err(err) = self.writer.write(direction, fbe_type as u32, data) {
+            self.error = Some(err);
+        }
+    }
+}
+
+impl<T: Sender, W: Write> Sender for Recorder<T, W> {
+    fn is_logging(&self) -> bool {
+        self.inner.is_logging()
+    }
+
+    fn set_logging(&mut self, enable: bool) {
+        self.inner.set_logging(enable);
+    }
+
+    fn send_buffer(&mut self) -> &mut WriteBuffer {
+        self.inner.send_buffer()
+    }
+
+    fn on_send(&mut self, data: &[u8]) -> usize {
+        self.record(Direction::Send, 0, data);
+        self.inner.on_send(data)
+    }
+
+    fn on_send_frame(&mut self, fbe_type: usize, data: &[u8]) -> usize {
+        self.record(Direction::Send, fbe_type, data);
+        self.inner.on_send_frame(fbe_type, data)
+    }
+
+    fn on_send_log(&self, message: &str) {
+        self.inner.on_send_log(message);
+    }
+
+    fn on_send_message(&self, record: &MessageLog) {
+        self.inner.on_send_message(record);
+    }
+}
+
+impl<T: Receiver, W: Write> Receiver for Recorder<T, W> {
+    fn is_logging(&self) -> bool {
+        self.inner.is_logging()
+    }
+
+    fn set_logging(&mut self, enable: bool) {
+        self.inner.set_logging(enable);
+    }
+
+    fn on_receive(&mut self, data: &[u8]) -> bool {
+        self.record(Direction::Receive, 0, data);
+        self.inner.on_receive(data)
+    }
+
+    fn on_receive_frame(&mut self, fbe_type: usize, data: &[u8]) -> bool {
+        self.record(Direction::Receive, fbe_type, data);
+        self.inner.on_receive_frame(fbe_type, data)
+    }
+
+    fn on_receive_log(&self, message: &str) {
+        self.inner.on_receive_log(message);
+    }
+
+    fn on_receive_message(&self, record: &MessageLog) {
+        self.inner.on_receive_message(record);
+    }
+}
+
+// ============================================================================
+// Replayer
+// ============================================================================
+
+/// Replay speed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    /// Preserve the recorded gaps between messages
+    Original,
+    /// Divide the recorded gaps by the given factor
+    ///
+    /// Gaps too long for a `Duration` saturate; a factor that is not
+    /// positive (including NaN) replays without delays.
+    Accelerated(f64),
+    /// Replay without delays
+    Max,
+}
+
+/// Record file replayer feeding messages into a receiver
+pub struct Replayer<R: Read> {
+    reader: RecordReader<R>,
+    speed: ReplaySpeed,
+    direction: Option<Direction>,
+}
+
+impl Replayer<BufReader<File>> {
+    /// Open a record file for replay
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(RecordReader::open(path)?))
+    }
+}
+
+impl<R: Read> Replayer<R> {
+    /// Create a new replayer (original speed, all directions)
+    pub fn new(reader: RecordReader<R>) -> Self {
+        Self {
+            reader,
+            speed: ReplaySpeed::Original,
+            direction: None,
+        }
+    }
+
+    /// Set replay speed
+    #[must_use]
+    pub fn speed(mut self, speed: ReplaySpeed) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Replay only messages recorded in the given direction
+    #[must_use]
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// Replay all remaining records into a receiver
+    ///
+    /// Records are delivered through `Receiver::receive_frame` with their
+    /// recorded FBE type id. Returns the number of replayed messages, or an
+    /// `InvalidData` error as soon as the receiver rejects a message.
+    pub fn replay<T>(&mut self, receiver: &mut T) -> io::Result<usize>
+    where
+        T: Receiver + ?Sized,
+    {
+        let started = Instant::now();
+        let mut first: Option<u64> = None;
+        let mut count = 0;
+
+        while let Some(record) = self.reader.read_record()? {
+            if self.direction.is_some_and(|direction| direction != record.direction) {
+                continue;
+            }
+
+            let first = *first.get_or_insert(record.timestamp);
+            let offset = Duration::from_nanos(record.timestamp.saturating_sub(first));
+            let target = match self.speed {
+                ReplaySpeed::Original => Some(offset),
+                ReplaySpeed::Accelerated(factor) if factor > 0.0 => Some(
+                    Duration::try_from_secs_f64(offset.as_secs_f64() / factor)
+                        .unwrap_or(Duration::MAX),
+                ),
+                ReplaySpeed::Accelerated(_) | ReplaySpeed::Max => None,
+            };
+            if let Some(target) = target {
+                let elapsed = started.elapsed();
+                if target > elapsed {
+                    thread::sleep(target - elapsed);
+                }
+            }
+
+            if !receiver.receive_frame(record.fbe_type as usize, &record.payload) {
+                return Err(frame::rejected(FrameRef::new(record.fbe_type, &record.payload)));
+            }
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
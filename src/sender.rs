@@ -1,6 +1,15 @@
 //! Fast Binary Encoding sender
 
 use crate::buffer::WriteBuffer;
+#[cfg(feature = "std")]
+use crate::final_model::FinalValue;
+use crate::logging::{self, Direction, MessageLog};
+#[cfg(feature = "std")]
+use crate::vectored::VectoredWriter;
+#[cfg(feature = "std")]
+use std::io::IoSlice;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 /// Fast Binary Encoding base sender
 pub trait Sender {
@@ -10,14 +19,29 @@ pub trait Sender {
     /// Enable/Disable logging
     fn set_logging(&mut self, enable: bool);
     
+    /// Send buffer reused across messages (must be implemented)
+    fn send_buffer(&mut self) -> &mut WriteBuffer;
+
     /// Send message handler (must be implemented)
     fn on_send(&mut self, data: &[u8]) -> usize;
+
+    /// Send typed message handler (can be overridden to frame messages)
+    fn on_send_frame(&mut self, fbe_type: usize, data: &[u8]) -> usize {
+        let _ = fbe_type;
+        self.on_send(data)
+    }
     
     /// Send log message handler (can be overridden)
     fn on_send_log(&self, message: &str) {
         // Default: do nothing
         let _ = message;
     }
+
+    /// Send structured log record handler (can be overridden)
+    fn on_send_message(&self, record: &MessageLog) {
+        logging::emit(record);
+        self.on_send_log(&record.to_string());
+    }
     
     /// Send serialized data
     fn send_serialized(&mut self, data: &[u8]) -> usize {
@@ -33,22 +57,94 @@ pub trait Sender {
     /// Send a struct
     fn send<T>(&mut self, value: &T) -> usize 
     where
-        T: Serialize + std::fmt::Display,
+        T: Serialize + ?Sized,
     {
-        let mut buffer = WriteBuffer::new();
+        // Take the send buffer to keep its capacity while `self` is borrowed
+        let mut buffer = core::mem::take(self.send_buffer());
+        let growable = buffer.is_growable();
+        buffer.reset();
+        buffer.set_growable(true);
+        buffer.reserve(value.size_hint());
         value.serialize(&mut buffer);
+        buffer.set_growable(growable);
         
         if self.is_logging() {
-            self.on_send_log(&format!("Sending struct: {}", std::any::type_name::<T>()));
-            self.on_send_log(&format!("{}", value));
+            let record = MessageLog::new(
+                Direction::Send,
+                core::any::type_name::<T>(),
+                T::FBE_TYPE,
+                buffer.size(),
+            )
+            .with_json(value.log_json());
+            self.on_send_message(&record);
         }
         
-        self.send_serialized(buffer.data())
+        let sent = self.on_send_frame(T::FBE_TYPE, buffer.data());
+        *self.send_buffer() = buffer;
+        sent
+    }
+
+    #[cfg(feature = "std")]
+    /// Send vectored message handler (can be overridden to avoid copying payloads)
+    ///
+    /// The default implementation joins the slices and calls `on_send_frame`.
+    fn on_send_vectored(&mut self, fbe_type: usize, data: &[IoSlice<'_>]) -> usize {
+        let mut buffer = core::mem::take(self.send_buffer());
+        let growable = buffer.is_growable();
+        buffer.reset();
+        buffer.set_growable(true);
+        for slice in data {
+            let offset = buffer.size();
+            buffer.write_array(offset, slice);
+        }
+        buffer.set_growable(growable);
+        let sent = self.on_send_frame(fbe_type, buffer.data());
+        *self.send_buffer() = buffer;
+        sent
+    }
+
+    /// Send a struct, referencing large `bytes`/`string` payloads instead of copying them
+    ///
+    /// The payload is the FinalModel layout of `value`, which is what
+    /// `#[derive(FbeStruct)]` and fbec generated structs serialize; both
+    /// implement `FinalValue::write_vectored` field by field.
+    #[cfg(feature = "std")]
+    fn send_vectored<T>(&mut self, value: &T) -> usize
+    where
+        T: Serialize + FinalValue,
+    {
+        let mut writer = VectoredWriter::new();
+        writer.append(value);
+
+        if self.is_logging() {
+            let record = MessageLog::new(
+                Direction::Send,
+                core::any::type_name::<T>(),
+                T::FBE_TYPE,
+                writer.size(),
+            )
+            .with_json(value.log_json());
+            self.on_send_message(&record);
+        }
+
+        self.on_send_vectored(T::FBE_TYPE, &writer.io_slices())
     }
 }
 
 /// Trait for types that can be serialized
 pub trait Serialize {
+    /// FBE type id reported in log records
+    const FBE_TYPE: usize = 0;
+
     fn serialize(&self, buffer: &mut WriteBuffer) -> usize;
-}
 
+    /// Expected serialized size, reserved up front so sending needs one allocation
+    fn size_hint(&self) -> usize {
+        0
+    }
+
+    /// JSON rendering reported in log records
+    fn log_json(&self) -> Option<String> {
+        None
+    }
+}
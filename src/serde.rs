@@ -0,0 +1,856 @@
+//! Fast Binary Encoding serde data format
+//!
+//! Encodes any `serde::Serialize`/`serde::Deserialize` type into FBE binary,
+//! so Rust-first types can talk to FBE peers without fbec generated code.
+//!
+//! Values use the FinalModel inline layout (all integers little-endian):
+//!
+//! | Rust/serde type                 | FBE layout                                  |
+//! |---------------------------------|---------------------------------------------|
+//! | `bool`, `i8`..`u64`, `f32`, `f64` | fixed size primitive                      |
+//! | `i128`, `u128`                  | 16 bytes                                    |
+//! | `char`                          | `wchar` (4 bytes)                           |
+//! | `String`, `&str`                | 4-byte size + UTF-8 bytes                   |
+//! | bytes                           | 4-byte size + data                          |
+//! | `Option<T>`                     | 1-byte has_value flag + value               |
+//! | sequence, set                   | 4-byte count + items                        |
+//! | map                             | 4-byte count + key/value pairs              |
+//! | tuple, `[T; N]`                 | items inline, no count                      |
+//! | struct                          | fields inline in declaration order          |
+//! | enum                            | 4-byte variant index (+ variant data)       |
+//! | unit, unit struct               | nothing                                     |
+//!
+//! The size-prefixed variants (`to_vec_size_prefixed`,
+//! `from_slice_size_prefixed`) put a 4-byte total size header (header
+//! included) in front of the FinalModel data, as `Product::serialize_model`
+//! does. They are a framing convenience, not an FBE layout.
+//!
+//! The pointer-based FBE Model layout is out of scope for this format: serde
+//! types always encode inline, and peers expecting FBE Model buffers cannot
+//! read them.
+
+use ::serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use ::serde::ser::{self, Serialize};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Size-prefixed layout header size
+const SIZE_HEADER: usize = 4;
+
+// ============================================================================
+// Error
+// ============================================================================
+
+/// Serde data format error
+#[derive(Debug)]
+pub enum Error {
+    /// Custom error from a `Serialize`/`Deserialize` implementation
+    Message(String),
+    /// I/O error from the output writer
+    #[cfg(feature = "std")]
+    Io(io::Error),
+    /// Input ended before the value was complete
+    Eof,
+    /// Bytes left after the value was decoded
+    TrailingBytes(usize),
+    /// Invalid bool value
+    InvalidBool(u8),
+    /// Invalid optional has_value flag
+    InvalidOptional(u8),
+    /// Invalid `wchar` code point
+    InvalidChar(u32),
+    /// String is not valid UTF-8
+    InvalidUtf8,
+    /// Invalid size header
+    InvalidSize(usize),
+    /// Collection or string is too large for a 4-byte size
+    TooLarge(usize),
+    /// FBE binary is not self-describing
+    AnyUnsupported,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Message(message) => f.write_str(message),
+            #[cfg(feature = "std")]
+            Error::Io(err) => write!(f, "I/O error: {}", err),
+            Error::Eof => f.write_str("Unexpected end of input"),
+            Error::TrailingBytes(count) => write!(f, "{} trailing bytes after value", count),
+            Error::InvalidBool(value) => write!(f, "Invalid bool value: {}", value),
+            Error::InvalidOptional(value) => write!(f, "Invalid optional flag: {}", value),
+            Error::InvalidChar(value) => write!(f, "Invalid wchar value: {:#x}", value),
+            Error::InvalidUtf8 => f.write_str("Invalid UTF-8 string"),
+            Error::InvalidSize(size) => write!(f, "Invalid size header: {}", size),
+            Error::TooLarge(size) => write!(f, "Size {} does not fit into 4 bytes", size),
+            Error::AnyUnsupported => f.write_str("FBE binary format is not self-describing"),
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+/// Serde data format result
+pub type Result<T> = core::result::Result<T, Error>;
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Serialize a value into FinalModel layout
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>>
+where
+    T: Serialize + ?Sized,
+{
+    let mut serializer = Serializer::new();
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_inner())
+}
+
+/// Serialize a value into FinalModel layout and write it
+#[cfg(feature = "std")]
+pub fn to_writer<W, T>(mut writer: W, value: &T) -> Result<()>
+where
+    W: io::Write,
+    T: Serialize + ?Sized,
+{
+    writer.write_all(&to_vec(value)?)?;
+    Ok(())
+}
+
+/// Serialize a value into FinalModel layout behind a 4-byte size header
+pub fn to_vec_size_prefixed<T>(value: &T) -> Result<Vec<u8>>
+where
+    T: Serialize + ?Sized,
+{
+    let mut serializer = Serializer::new();
+    serializer.output.extend_from_slice(&[0; SIZE_HEADER]);
+    value.serialize(&mut serializer)?;
+    let mut output = serializer.into_inner();
+    let size = checked_size(output.len())?;
+    output[..SIZE_HEADER].copy_from_slice(&size.to_le_bytes());
+    Ok(output)
+}
+
+/// Serialize a value into size-prefixed FinalModel layout and write it
+#[cfg(feature = "std")]
+pub fn to_writer_size_prefixed<W, T>(mut writer: W, value: &T) -> Result<()>
+where
+    W: io::Write,
+    T: Serialize + ?Sized,
+{
+    writer.write_all(&to_vec_size_prefixed(value)?)?;
+    Ok(())
+}
+
+/// Deserialize a value from FinalModel layout
+///
+/// Fails if any bytes are left after the value.
+pub fn from_slice<'de, T>(data: &'de [u8]) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    let mut deserializer = Deserializer::new(data);
+    let value = T::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Deserialize a value from FinalModel layout behind a 4-byte size header
+pub fn from_slice_size_prefixed<'de, T>(data: &'de [u8]) -> Result<T>
+where
+    T: de::Deserialize<'de>,
+{
+    if data.len() < SIZE_HEADER {
+        return Err(Error::Eof);
+    }
+    let size = u32::from_le_bytes(data[..SIZE_HEADER].try_into().unwrap()) as usize;
+    if size < SIZE_HEADER || size != data.len() {
+        return Err(Error::InvalidSize(size));
+    }
+    from_slice(&data[SIZE_HEADER..])
+}
+
+/// Deserialize an owned value from a reader in FinalModel layout
+#[cfg(feature = "std")]
+pub fn from_reader<R, T>(mut reader: R) -> Result<T>
+where
+    R: io::Read,
+    T: de::DeserializeOwned,
+{
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    from_slice(&data)
+}
+
+fn checked_size(size: usize) -> Result<u32> {
+    u32::try_from(size).map_err(|_| Error::TooLarge(size))
+}
+
+// ============================================================================
+// Serializer
+// ============================================================================
+
+/// FBE FinalModel serializer
+#[derive(Debug, Default)]
+pub struct Serializer {
+    output: Vec<u8>,
+}
+
+impl Serializer {
+    /// Create a new serializer
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get serialized data
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        &self.output
+    }
+
+    /// Consume serializer and return serialized data
+    #[must_use]
+    pub fn into_inner(self) -> Vec<u8> {
+        self.output
+    }
+
+    fn write_size(&mut self, size: usize) -> Result<()> {
+        let size = checked_size(size)?;
+        self.output.extend_from_slice(&size.to_le_bytes());
+        Ok(())
+    }
+
+    /// Write a count placeholder, patched when the collection ends
+    fn begin_collection(&mut self, len: Option<usize>) -> Result<Collection<'_>> {
+        let position = self.output.len();
+        self.write_size(len.unwrap_or(0))?;
+        Ok(Collection {
+            serializer: self,
+            position,
+            count: 0,
+        })
+    }
+}
+
+/// Sequence/map serializer patching the 4-byte count on completion
+pub struct Collection<'a> {
+    serializer: &'a mut Serializer,
+    position: usize,
+    count: usize,
+}
+
+impl Collection<'_> {
+    fn finish(self) -> Result<()> {
+        let count = checked_size(self.count)?;
+        self.serializer.output[self.position..self.position + 4]
+            .copy_from_slice(&count.to_le_bytes());
+        Ok(())
+    }
+}
+
+macro_rules! serialize_le {
+    ($($method:ident: $type:ty),* $(,)?) => {
+        $(
+            fn $method(self, value: $type) -> Result<()> {
+                self.output.extend_from_slice(&value.to_le_bytes());
+                Ok(())
+            }
+        )*
+    };
+}
+
+impl<'a> ser::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Collection<'a>;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Collection<'a>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    serialize_le!(
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_i128: i128,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_u128: u128,
+        serialize_f32: f32,
+        serialize_f64: f64,
+    );
+
+    fn serialize_bool(self, value: bool) -> Result<()> {
+        self.output.push(u8::from(value));
+        Ok(())
+    }
+
+    fn serialize_char(self, value: char) -> Result<()> {
+        self.serialize_u32(value as u32)
+    }
+
+    fn serialize_str(self, value: &str) -> Result<()> {
+        self.serialize_bytes(value.as_bytes())
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<()> {
+        self.write_size(value.len())?;
+        self.output.extend_from_slice(value);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.output.push(0);
+        Ok(())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.output.push(1);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<()> {
+        self.serialize_u32(variant_index)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.serialize_u32(variant_index)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Collection<'a>> {
+        self.begin_collection(len)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.serialize_u32(variant_index)?;
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Collection<'a>> {
+        self.begin_collection(len)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.serialize_u32(variant_index)?;
+        Ok(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+impl ser::SerializeSeq for Collection<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.count += 1;
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for Collection<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.count += 1;
+        key.serialize(&mut *self.serializer)
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()
+    }
+}
+
+macro_rules! serialize_inline {
+    ($trait:ident, $method:ident $(, $key:ident)?) => {
+        impl ser::$trait for &mut Serializer {
+            type Ok = ();
+            type Error = Error;
+
+            fn $method<T>(&mut self, $($key: &'static str,)? value: &T) -> Result<()>
+            where
+                T: Serialize + ?Sized,
+            {
+                value.serialize(&mut **self)
+            }
+
+            fn end(self) -> Result<()> {
+                Ok(())
+            }
+        }
+    };
+}
+
+serialize_inline!(SerializeTuple, serialize_element);
+serialize_inline!(SerializeTupleStruct, serialize_field);
+serialize_inline!(SerializeTupleVariant, serialize_field);
+serialize_inline!(SerializeStruct, serialize_field, _key);
+serialize_inline!(SerializeStructVariant, serialize_field, _key);
+
+// ============================================================================
+// Deserializer
+// ============================================================================
+
+/// FBE FinalModel deserializer
+pub struct Deserializer<'de> {
+    input: &'de [u8],
+    offset: usize,
+}
+
+impl<'de> Deserializer<'de> {
+    /// Create a new deserializer over FinalModel data
+    #[must_use]
+    pub fn new(input: &'de [u8]) -> Self {
+        Self { input, offset: 0 }
+    }
+
+    /// Get current read offset
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Check that the whole input was consumed
+    pub fn end(&self) -> Result<()> {
+        match self.input.len() - self.offset {
+            0 => Ok(()),
+            remaining => Err(Error::TrailingBytes(remaining)),
+        }
+    }
+
+    fn take(&mut self, size: usize) -> Result<&'de [u8]> {
+        let end = self.offset.checked_add(size).ok_or(Error::Eof)?;
+        let data = self.input.get(self.offset..end).ok_or(Error::Eof)?;
+        self.offset = end;
+        Ok(data)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn read_size(&mut self) -> Result<usize> {
+        Ok(u32::from_le_bytes(self.take_array()?) as usize)
+    }
+
+    fn read_bytes(&mut self) -> Result<&'de [u8]> {
+        let size = self.read_size()?;
+        self.take(size)
+    }
+
+    fn read_str(&mut self) -> Result<&'de str> {
+        core::str::from_utf8(self.read_bytes()?).map_err(|_| Error::InvalidUtf8)
+    }
+}
+
+macro_rules! deserialize_le {
+    ($($method:ident: $type:ty => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de>,
+            {
+                visitor.$visit(<$type>::from_le_bytes(self.take_array()?))
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    deserialize_le!(
+        deserialize_i8: i8 => visit_i8,
+        deserialize_i16: i16 => visit_i16,
+        deserialize_i32: i32 => visit_i32,
+        deserialize_i64: i64 => visit_i64,
+        deserialize_i128: i128 => visit_i128,
+        deserialize_u8: u8 => visit_u8,
+        deserialize_u16: u16 => visit_u16,
+        deserialize_u32: u32 => visit_u32,
+        deserialize_u64: u64 => visit_u64,
+        deserialize_u128: u128 => visit_u128,
+        deserialize_f32: f32 => visit_f32,
+        deserialize_f64: f64 => visit_f64,
+    );
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::AnyUnsupported)
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.take_array::<1>()?[0] {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            value => Err(Error::InvalidBool(value)),
+        }
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let value = u32::from_le_bytes(self.take_array()?);
+        visitor.visit_char(char::from_u32(value).ok_or(Error::InvalidChar(value))?)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_borrowed_str(self.read_str()?)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_borrowed_bytes(self.read_bytes()?)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.take_array::<1>()?[0] {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            value => Err(Error::InvalidOptional(value)),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let count = self.read_size()?;
+        visitor.visit_seq(Access {
+            deserializer: self,
+            remaining: count,
+        })
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Access {
+            deserializer: self,
+            remaining: len,
+        })
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let count = self.read_size()?;
+        visitor.visit_map(Access {
+            deserializer: self,
+            remaining: count,
+        })
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_u32(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, _visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::AnyUnsupported)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Sequence/map access over a known number of items
+struct Access<'a, 'de> {
+    deserializer: &'a mut Deserializer<'de>,
+    remaining: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for Access<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Access<'_, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.deserializer)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self)>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let index = u32::from_le_bytes(self.take_array()?);
+        let value = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(index))?;
+        Ok((value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
@@ -0,0 +1,272 @@
+//! Fast Binary Encoding timestamp type
+//!
+//! FBE `timestamp` is an unsigned 64-bit count of nanoseconds since the Unix
+//! epoch (1970-01-01T00:00:00Z), which covers dates up to year 2554.
+
+use core::fmt;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// FBE timestamp (nanoseconds since Unix epoch)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(u64);
+
+/// Time value that does not fit into an FBE timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimestampRangeError;
+
+impl fmt::Display for TimestampRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Time is out of FBE timestamp range (1970-01-01 to 2554-07-21)")
+    }
+}
+
+impl core::error::Error for TimestampRangeError {}
+
+impl Timestamp {
+    /// Unix epoch (1970-01-01T00:00:00Z)
+    pub const EPOCH: Self = Self(0);
+
+    /// Create timestamp from nanoseconds since Unix epoch
+    #[must_use]
+    pub const fn new(nanos: u64) -> Self {
+        Self(nanos)
+    }
+
+    /// Create timestamp from seconds and nanoseconds since Unix epoch
+    #[must_use]
+    pub const fn from_secs_nanos(secs: u64, nanos: u32) -> Option<Self> {
+        match secs.checked_mul(NANOS_PER_SECOND) {
+            Some(value) => match value.checked_add(nanos as u64) {
+                Some(value) => Some(Self(value)),
+                None => None,
+            },
+            None => None,
+        }
+    }
+
+    /// Current UTC time
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn utc() -> Self {
+        Self::try_from(SystemTime::now()).unwrap_or(Self(u64::MAX))
+    }
+
+    /// Nanoseconds since Unix epoch
+    #[must_use]
+    pub const fn nanos(&self) -> u64 {
+        self.0
+    }
+
+    /// Whole seconds since Unix epoch
+    #[must_use]
+    pub const fn secs(&self) -> u64 {
+        self.0 / NANOS_PER_SECOND
+    }
+
+    /// Nanoseconds within the current second
+    #[must_use]
+    pub const fn subsec_nanos(&self) -> u32 {
+        (self.0 % NANOS_PER_SECOND) as u32
+    }
+
+    /// Format as RFC 3339 UTC, e.g. `2024-10-21T12:00:00.123Z`
+    ///
+    /// Fractional seconds are omitted when zero, otherwise written with
+    /// millisecond, microsecond or nanosecond precision.
+    #[must_use]
+    pub fn to_rfc3339(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.secs();
+        let (year, month, day) = civil_from_days(secs / SECONDS_PER_DAY);
+        let time = secs % SECONDS_PER_DAY;
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year,
+            month,
+            day,
+            time / 3600,
+            time % 3600 / 60,
+            time % 60
+        )?;
+        match self.subsec_nanos() {
+            0 => {}
+            nanos if nanos % 1_000_000 == 0 => write!(f, ".{:03}", nanos / 1_000_000)?,
+            nanos if nanos % 1_000 == 0 => write!(f, ".{:06}", nanos / 1_000)?,
+            nanos => write!(f, ".{:09}", nanos)?,
+        }
+        f.write_str("Z")
+    }
+}
+
+/// Convert days since Unix epoch into a (year, month, day) civil date
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // Howard Hinnant's algorithm, shifted to start the year in March
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+// ============================================================================
+// Conversions
+// ============================================================================
+
+impl From<u64> for Timestamp {
+    fn from(nanos: u64) -> Self {
+        Self(nanos)
+    }
+}
+
+impl From<Timestamp> for u64 {
+    fn from(value: Timestamp) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<Duration> for Timestamp {
+    type Error = TimestampRangeError;
+
+    /// Duration since Unix epoch
+    fn try_from(value: Duration) -> Result<Self, Self::Error> {
+        u64::try_from(value.as_nanos())
+            .map(Self)
+            .map_err(|_| TimestampRangeError)
+    }
+}
+
+impl From<Timestamp> for Duration {
+    /// Duration since Unix epoch
+    fn from(value: Timestamp) -> Self {
+        Duration::from_nanos(value.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<SystemTime> for Timestamp {
+    type Error = TimestampRangeError;
+
+    fn try_from(value: SystemTime) -> Result<Self, Self::Error> {
+        value
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| TimestampRangeError)
+            .and_then(Self::try_from)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Timestamp> for SystemTime {
+    fn from(value: Timestamp) -> Self {
+        UNIX_EPOCH + Duration::from(value)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::DateTime<chrono::Utc>> for Timestamp {
+    type Error = TimestampRangeError;
+
+    fn try_from(value: chrono::DateTime<chrono::Utc>) -> Result<Self, Self::Error> {
+        let secs = u64::try_from(value.timestamp()).map_err(|_| TimestampRangeError)?;
+        Self::from_secs_nanos(secs, value.timestamp_subsec_nanos()).ok_or(TimestampRangeError)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Timestamp> for chrono::DateTime<chrono::Utc> {
+    fn from(value: Timestamp) -> Self {
+        chrono::DateTime::from_timestamp(value.secs() as i64, value.subsec_nanos())
+            .expect("FBE timestamp is within chrono range")
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<time::OffsetDateTime> for Timestamp {
+    type Error = TimestampRangeError;
+
+    fn try_from(value: time::OffsetDateTime) -> Result<Self, Self::Error> {
+        u64::try_from(value.unix_timestamp_nanos())
+            .map(Self)
+            .map_err(|_| TimestampRangeError)
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<Timestamp> for time::OffsetDateTime {
+    fn from(value: Timestamp) -> Self {
+        time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(value.0))
+            .expect("FBE timestamp is within time range")
+    }
+}
+
+// ============================================================================
+// serde (FBE JSON: nanoseconds as number)
+// ============================================================================
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Timestamp {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Timestamp {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <u64 as serde::Deserialize>::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for Timestamp {
+    type Parameters = ();
+    type Strategy = proptest::strategy::Map<proptest::num::u64::Any, fn(u64) -> Self>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        proptest::strategy::Strategy::prop_map(proptest::num::u64::ANY, Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc3339() {
+        assert_eq!(Timestamp::EPOCH.to_rfc3339(), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            Timestamp::new(1_729_512_000_000_000_000).to_rfc3339(),
+            "2024-10-21T12:00:00Z"
+        );
+        assert_eq!(
+            Timestamp::new(951_782_400_123_000_000).to_rfc3339(),
+            "2000-02-29T00:00:00.123Z"
+        );
+        assert_eq!(
+            Timestamp::new(1_609_459_199_000_001_000).to_rfc3339(),
+            "2020-12-31T23:59:59.000001Z"
+        );
+        assert_eq!(
+            Timestamp::new(u64::MAX).to_rfc3339(),
+            "2554-07-21T23:34:33.709551615Z"
+        );
+    }
+}
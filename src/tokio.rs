@@ -0,0 +1,288 @@
+//! Fast Binary Encoding Tokio transport
+//!
+//! Framed codec for `tokio_util::codec` and async sender/receiver adapters
+//! over `AsyncRead`/`AsyncWrite`. Enabled with the `tokio` cargo feature.
+
+use crate::buffer::{ReadBuffer, WriteBuffer};
+use crate::frame::{self, Frame, FrameRef, DEFAULT_MAX_FRAME_SIZE, FRAME_HEADER_SIZE};
+use crate::limits::DecodeLimits;
+use crate::logging::{self, Direction, MessageLog};
+use crate::receiver::{Deserialize, Receiver};
+use crate::sender::Serialize;
+use ::tokio::io::{AsyncRead, AsyncWrite};
+use bytes::BytesMut;
+use futures_util::{SinkExt, StreamExt};
+use std::io;
+use tokio_util::codec::{Decoder, Encoder, FramedRead, FramedWrite};
+
+// ============================================================================
+// Codec
+// ============================================================================
+
+/// FBE message codec
+#[derive(Debug, Clone)]
+pub struct FbeCodec {
+    max_frame_size: usize,
+}
+
+impl Default for FbeCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FbeCodec {
+    /// Create a new codec
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_max_frame_size(DEFAULT_MAX_FRAME_SIZE)
+    }
+
+    /// Create a new codec with the given frame size limit
+    #[must_use]
+    pub fn with_max_frame_size(max_frame_size: usize) -> Self {
+        Self { max_frame_size }
+    }
+
+    /// Get frame size limit
+    #[must_use]
+    pub const fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+}
+
+impl Decoder for FbeCodec {
+    type Item = Frame;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<Frame>> {
+        let Some((size, fbe_type)) = frame::parse_header(src, self.max_frame_size)? else {
+            return Ok(None);
+        };
+        if src.len() < size {
+            src.reserve(size - src.len());
+            return Ok(None);
+        }
+
+        let data = src.split_to(size);
+        Ok(Some(Frame::new(fbe_type, data[FRAME_HEADER_SIZE..].to_vec())))
+    }
+}
+
+impl<'a> Encoder<FrameRef<'a>> for FbeCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: FrameRef<'a>, dst: &mut BytesMut) -> io::Result<()> {
+        let size = frame::check_frame_size(item.payload.len(), self.max_frame_size)?;
+        dst.reserve(size);
+        dst.extend_from_slice(&item.header()?);
+        dst.extend_from_slice(item.payload);
+        Ok(())
+    }
+}
+
+impl Encoder<Frame> for FbeCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: Frame, dst: &mut BytesMut) -> io::Result<()> {
+        self.encode(item.as_frame_ref(), dst)
+    }
+}
+
+// ============================================================================
+// Async sender
+// ============================================================================
+
+/// Async FBE sender over `AsyncWrite`
+pub struct AsyncSender<W> {
+    framed: FramedWrite<W, FbeCodec>,
+    buffer: WriteBuffer,
+    logging: bool,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncSender<W> {
+    /// Create a new async sender
+    pub fn new(writer: W) -> Self {
+        Self::with_codec(writer, FbeCodec::new())
+    }
+
+    /// Create a new async sender with the given codec
+    pub fn with_codec(writer: W, codec: FbeCodec) -> Self {
+        Self {
+            framed: FramedWrite::new(writer, codec),
+            buffer: WriteBuffer::growable(),
+            logging: false,
+        }
+    }
+
+    /// Get logging flag
+    pub fn is_logging(&self) -> bool {
+        self.logging
+    }
+
+    /// Enable/Disable logging
+    pub fn set_logging(&mut self, enable: bool) {
+        self.logging = enable;
+    }
+
+    /// Send serialized data as a frame of the given type
+    pub async fn send_serialized(&mut self, fbe_type: u32, data: &[u8]) -> io::Result<usize> {
+        let frame = FrameRef::new(fbe_type, data);
+        let size = frame.size();
+        self.framed.send(frame).await?;
+        Ok(size)
+    }
+
+    /// Send a struct and return the frame size
+    pub async fn send<T>(&mut self, value: &T) -> io::Result<usize>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.buffer.reset();
+        self.buffer.reserve(value.size_hint());
+        value.serialize(&mut self.buffer);
+
+        if self.logging {
+            let record = MessageLog::new(
+                Direction::Send,
+                std::any::type_name::<T>(),
+                T::FBE_TYPE,
+                self.buffer.size(),
+            )
+            .with_json(value.log_json());
+            logging::emit(&record);
+        }
+
+        let frame = FrameRef::new(T::FBE_TYPE as u32, self.buffer.data());
+        let size = frame.size();
+        self.framed.send(frame).await?;
+        Ok(size)
+    }
+
+    /// Get writer reference
+    pub fn get_ref(&self) -> &W {
+        self.framed.get_ref()
+    }
+
+    /// Consume sender and return the writer
+    pub fn into_inner(self) -> W {
+        self.framed.into_inner()
+    }
+}
+
+// ============================================================================
+// Async receiver
+// ============================================================================
+
+/// Async FBE receiver over `AsyncRead`
+pub struct AsyncReceiver<R> {
+    framed: FramedRead<R, FbeCodec>,
+    logging: bool,
+    limits: DecodeLimits,
+}
+
+impl<R: AsyncRead + Unpin> AsyncReceiver<R> {
+    /// Create a new async receiver
+    pub fn new(reader: R) -> Self {
+        Self::with_codec(reader, FbeCodec::new())
+    }
+
+    /// Create a new async receiver with the given codec
+    pub fn with_codec(reader: R, codec: FbeCodec) -> Self {
+        Self {
+            framed: FramedRead::new(reader, codec),
+            logging: false,
+            limits: DecodeLimits::default(),
+        }
+    }
+
+    /// Get logging flag
+    pub fn is_logging(&self) -> bool {
+        self.logging
+    }
+
+    /// Enable/Disable logging
+    pub fn set_logging(&mut self, enable: bool) {
+        self.logging = enable;
+    }
+
+    /// Get decode limits applied by `read_value`
+    pub fn decode_limits(&self) -> &DecodeLimits {
+        &self.limits
+    }
+
+    /// Set decode limits applied by `read_value`
+    pub fn set_decode_limits(&mut self, limits: DecodeLimits) {
+        self.limits = limits;
+    }
+
+    /// Receive the next frame, `None` at end of stream
+    pub async fn receive(&mut self) -> io::Result<Option<Frame>> {
+        self.framed.next().await.transpose()
+    }
+
+    /// Receive and deserialize the next struct, `None` at end of stream
+    pub async fn read_value<T>(&mut self) -> io::Result<Option<T>>
+    where
+        T: Deserialize,
+    {
+        let Some(frame) = self.receive().await? else {
+            return Ok(None);
+        };
+        if T::FBE_TYPE != 0 && frame.fbe_type as usize != T::FBE_TYPE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Unexpected FBE type {} (expected {})",
+                    frame.fbe_type,
+                    T::FBE_TYPE
+                ),
+            ));
+        }
+
+        let size = frame.payload.len();
+        let value = T::try_deserialize(&ReadBuffer::from(frame.payload), &self.limits)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        if self.logging {
+            let record = MessageLog::new(
+                Direction::Receive,
+                std::any::type_name::<T>(),
+                T::FBE_TYPE,
+                size,
+            )
+            .with_json(value.log_json());
+            logging::emit(&record);
+        }
+
+        Ok(Some(value))
+    }
+
+    /// Forward every received frame payload with its type id to a receiver until end of stream
+    ///
+    /// Returns the number of forwarded frames, or an `InvalidData` error as
+    /// soon as the receiver rejects a frame.
+    pub async fn forward<T>(&mut self, receiver: &mut T) -> io::Result<usize>
+    where
+        T: Receiver + ?Sized,
+    {
+        let mut count = 0;
+        while let Some(frame) = self.receive().await? {
+            if !receiver.receive_frame(frame.fbe_type as usize, &frame.payload) {
+                return Err(frame::rejected(frame.as_frame_ref()));
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Get reader reference
+    pub fn get_ref(&self) -> &R {
+        self.framed.get_ref()
+    }
+
+    /// Consume receiver and return the reader
+    pub fn into_inner(self) -> R {
+        self.framed.into_inner()
+    }
+}
@@ -0,0 +1,626 @@
+//! Fast Binary Encoding blocking transports
+//!
+//! `Sender`/`Receiver` implementations over `std::io` streams, TCP and
+//! Unix domain sockets, plus a small threaded TCP server. Messages are
+//! framed with the 8-byte FBE header from the `frame` module.
+
+use crate::buffer::{ReadBuffer, WriteBuffer};
+use crate::final_model::FinalValue;
+use crate::frame::{self, DEFAULT_MAX_FRAME_SIZE, Frame, FrameDecoder, FrameRef};
+use crate::limits::DecodeLimits;
+use crate::logging::{Direction, MessageLog};
+use crate::receiver::{Deserialize, Receiver};
+use crate::sender::{Sender, Serialize};
+use crate::vectored;
+use std::io::{self, IoSlice, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+#[cfg(unix)]
+use std::path::Path;
+
+/// Read chunk size used by stream receivers
+const READ_CHUNK_SIZE: usize = 8192;
+
+// ============================================================================
+// Stream sender
+// ============================================================================
+
+/// Sender writing framed messages to any `std::io::Write`
+pub struct StreamSender<W> {
+    writer: W,
+    buffer: WriteBuffer,
+    logging: bool,
+    max_frame_size: usize,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> StreamSender<W> {
+    /// Create a new stream sender
+    pub fn new(writer: W) -> Self {
+        Self::with_max_frame_size(writer, DEFAULT_MAX_FRAME_SIZE)
+    }
+
+    /// Create a new stream sender with the given frame size limit
+    pub fn with_max_frame_size(writer: W, max_frame_size: usize) -> Self {
+        Self {
+            writer,
+            buffer: WriteBuffer::new(),
+            logging: false,
+            max_frame_size,
+            error: None,
+        }
+    }
+
+    /// Get frame size limit
+    ///
+    /// Larger frames fail with `InvalidInput` before anything is written.
+    pub const fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    /// Send a struct as one frame
+    ///
+    /// Shadows [`Sender::send`] to report I/O errors instead of returning 0.
+    pub fn send<T>(&mut self, value: &T) -> io::Result<usize>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.error = None;
+        let size = Sender::send(self, value);
+        self.result(size)
+    }
+
+    /// Send a struct as one frame without copying large payloads
+    ///
+    /// Shadows [`Sender::send_vectored`] to report I/O errors instead of returning 0.
+    pub fn send_vectored<T>(&mut self, value: &T) -> io::Result<usize>
+    where
+        T: Serialize + FinalValue,
+    {
+        self.error = None;
+        let size = Sender::send_vectored(self, value);
+        self.result(size)
+    }
+
+    /// Send serialized data as an untyped frame
+    ///
+    /// Shadows [`Sender::send_serialized`] to report I/O errors instead of returning 0.
+    pub fn send_serialized(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.error = None;
+        let size = Sender::send_serialized(self, data);
+        self.result(size)
+    }
+
+    /// Send serialized data as a frame of the given FBE type
+    pub fn send_frame(&mut self, fbe_type: u32, data: &[u8]) -> io::Result<usize> {
+        self.write_frame(FrameRef::new(fbe_type, data))
+    }
+
+    /// Take the last I/O error of a call through the `Sender` trait
+    ///
+    /// `Sender` methods return a plain size, so generic callers get 0 on
+    /// failure and find the error here.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Flush the underlying writer
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Get writer reference
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Get mutable writer reference
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consume sender and return the writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn result(&mut self, size: usize) -> io::Result<usize> {
+        match self.error.take() {
+            Some(err) => Err(err),
+            None => Ok(size),
+        }
+    }
+
+    fn write_frame(&mut self, frame: FrameRef<'_>) -> io::Result<usize> {
+        let size = frame::check_frame_size(frame.payload.len(), self.max_frame_size)?;
+        self.writer.write_all(&frame.header()?)?;
+        self.writer.write_all(frame.payload)?;
+        self.writer.flush()?;
+        Ok(size)
+    }
+
+    fn write_frame_vectored(&mut self, fbe_type: u32, data: &[IoSlice<'_>]) -> io::Result<usize> {
+        let payload_size = data.iter().map(|slice| slice.len()).sum();
+        let size = frame::check_frame_size(payload_size, self.max_frame_size)?;
+        let header = frame::encode_header(fbe_type, payload_size)?;
+        let mut slices = Vec::with_capacity(data.len() + 1);
+        slices.push(IoSlice::new(&header));
+        slices.extend_from_slice(data);
+        vectored::write_all_vectored(&mut self.writer, &mut slices)?;
+        self.writer.flush()?;
+        Ok(size)
+    }
+}
+
+impl<W: Write> Sender for StreamSender<W> {
+    fn is_logging(&self) -> bool {
+        self.logging
+    }
+
+    fn set_logging(&mut self, enable: bool) {
+        self.logging = enable;
+    }
+
+    fn send_buffer(&mut self) -> &mut WriteBuffer {
+        &mut self.buffer
+    }
+
+    fn on_send(&mut self, data: &[u8]) -> usize {
+        self.on_send_frame(0, data)
+    }
+
+    fn on_send_frame(&mut self, fbe_type: usize, data: &[u8]) -> usize {
+        match self.write_frame(FrameRef::new(fbe_type as u32, data)) {
+            Ok(size) => size,
+            Err(err) => {
+                self.error = Some(err);
+                0
+            }
+        }
+    }
+
+    fn on_send_vectored(&mut self, fbe_type: usize, data: &[IoSlice<'_>]) -> usize {
+        match self.write_frame_vectored(fbe_type as u32, data) {
+            Ok(size) => size,
+            Err(err) => {
+                self.error = Some(err);
+                0
+            }
+        }
+    }
+}
+
+/// Sender over a TCP stream
+pub type TcpSender = StreamSender<TcpStream>;
+
+impl StreamSender<TcpStream> {
+    /// Connect to a TCP server
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+        Ok(Self::new(stream))
+    }
+}
+
+/// Sender over a Unix domain socket
+#[cfg(unix)]
+pub type UnixSender = StreamSender<UnixStream>;
+
+#[cfg(unix)]
+impl StreamSender<UnixStream> {
+    /// Connect to a Unix domain socket
+    pub fn connect_unix<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(UnixStream::connect(path)?))
+    }
+}
+
+// ============================================================================
+// Frame receiver
+// ============================================================================
+
+/// Frame handler invoked for every reassembled frame
+pub trait FrameHandler {
+    /// Handle a frame, returning `false` to reject it
+    fn on_frame(&mut self, frame: &Frame) -> bool;
+}
+
+impl<F> FrameHandler for F
+where
+    F: FnMut(&Frame) -> bool,
+{
+    fn on_frame(&mut self, frame: &Frame) -> bool {
+        self(frame)
+    }
+}
+
+/// Receiver reassembling frames from raw chunks and dispatching them to a handler
+pub struct FrameReceiver<H> {
+    decoder: FrameDecoder,
+    handler: H,
+    logging: bool,
+    error: Option<io::Error>,
+}
+
+impl<H: FrameHandler> FrameReceiver<H> {
+    /// Create a new frame receiver
+    pub fn new(handler: H) -> Self {
+        Self::with_decoder(handler, FrameDecoder::new())
+    }
+
+    /// Create a new frame receiver with the given decoder
+    pub fn with_decoder(handler: H, decoder: FrameDecoder) -> Self {
+        Self {
+            decoder,
+            handler,
+            logging: false,
+            error: None,
+        }
+    }
+
+    /// Take the last framing error (receives return `false` on failure)
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Get handler reference
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    /// Get mutable handler reference
+    pub fn handler_mut(&mut self) -> &mut H {
+        &mut self.handler
+    }
+
+    /// Consume receiver and return the handler
+    pub fn into_handler(self) -> H {
+        self.handler
+    }
+}
+
+impl<H: FrameHandler> Receiver for FrameReceiver<H> {
+    fn is_logging(&self) -> bool {
+        self.logging
+    }
+
+    fn set_logging(&mut self, enable: bool) {
+        self.logging = enable;
+    }
+
+    fn on_receive(&mut self, data: &[u8]) -> bool {
+        self.decoder.push(data);
+        loop {
+            match self.decoder.next_frame() {
+                Ok(Some(frame)) => {
+                    if self.logging {
+                        self.on_receive_log(&format!(
+                            "Received frame (type {}, {} bytes)",
+                            frame.fbe_type,
+                            frame.payload.len()
+                        ));
+                    }
+                    if !self.handler.on_frame(&frame) {
+                        return false;
+                    }
+                }
+                Ok(None) => return true,
+                Err(err) => {
+                    self.error = Some(err);
+                    return false;
+                }
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Stream receiver
+// ============================================================================
+
+/// Receiver reading framed messages from any `std::io::Read`
+pub struct StreamReceiver<R> {
+    reader: R,
+    decoder: FrameDecoder,
+    chunk: Vec<u8>,
+    logging: bool,
+    limits: DecodeLimits,
+}
+
+impl<R: Read> StreamReceiver<R> {
+    /// Create a new stream receiver
+    pub fn new(reader: R) -> Self {
+        Self::with_decoder(reader, FrameDecoder::new())
+    }
+
+    /// Create a new stream receiver with the given decoder
+    pub fn with_decoder(reader: R, decoder: FrameDecoder) -> Self {
+        Self {
+            reader,
+            decoder,
+            chunk: vec![0; READ_CHUNK_SIZE],
+            logging: false,
+            limits: DecodeLimits::default(),
+        }
+    }
+
+    /// Get logging flag
+    pub fn is_logging(&self) -> bool {
+        self.logging
+    }
+
+    /// Enable/Disable logging
+    pub fn set_logging(&mut self, enable: bool) {
+        self.logging = enable;
+    }
+
+    /// Get decode limits applied by `read_value`
+    pub fn decode_limits(&self) -> &DecodeLimits {
+        &self.limits
+    }
+
+    /// Set decode limits applied by `read_value`
+    pub fn set_decode_limits(&mut self, limits: DecodeLimits) {
+        self.limits = limits;
+    }
+
+    /// Read the next frame, `None` at end of stream
+    ///
+    /// Partially received frames are kept across read timeouts.
+    pub fn read_frame(&mut self) -> io::Result<Option<Frame>> {
+        loop {
+            if let Some(frame) = self.decoder.next_frame()? {
+                return Ok(Some(frame));
+            }
+
+            let size = match self.reader.read(&mut self.chunk) {
+                Ok(size) => size,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            if size == 0 {
+                if self.decoder.pending() > 0 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "Stream closed in the middle of a frame",
+                    ));
+                }
+                return Ok(None);
+            }
+            self.decoder.push(&self.chunk[..size]);
+        }
+    }
+
+    /// Read and deserialize the next struct, `None` at end of stream
+    pub fn read_value<T>(&mut self) -> io::Result<Option<T>>
+    where
+        T: Deserialize,
+    {
+        let Some(frame) = self.read_frame()? else {
+            return Ok(None);
+        };
+        if T::FBE_TYPE != 0 && frame.fbe_type as usize != T::FBE_TYPE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Unexpected FBE type {} (expected {})",
+                    frame.fbe_type,
+                    T::FBE_TYPE
+                ),
+            ));
+        }
+
+        let size = frame.payload.len();
+        let value = T::try_deserialize(&ReadBuffer::from(frame.payload), &self.limits)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        if self.logging {
+            let record = MessageLog::new(
+                Direction::Receive,
+                std::any::type_name::<T>(),
+                T::FBE_TYPE,
+                size,
+            )
+            .with_json(value.log_json());
+            crate::logging::emit(&record);
+        }
+
+        Ok(Some(value))
+    }
+
+    /// Forward every frame payload with its type id to a receiver until end of stream
+    ///
+    /// Returns the number of forwarded frames, or an `InvalidData` error as
+    /// soon as the receiver rejects a frame.
+    pub fn forward<T>(&mut self, receiver: &mut T) -> io::Result<usize>
+    where
+        T: Receiver + ?Sized,
+    {
+        let mut count = 0;
+        while let Some(frame) = self.read_frame()? {
+            if !receiver.receive_frame(frame.fbe_type as usize, &frame.payload) {
+                return Err(frame::rejected(frame.as_frame_ref()));
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Get reader reference
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Consume receiver and return the reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+/// Receiver over a TCP stream
+pub type TcpReceiver = StreamReceiver<TcpStream>;
+
+/// Receiver over a Unix domain socket
+#[cfg(unix)]
+pub type UnixReceiver = StreamReceiver<UnixStream>;
+
+// ============================================================================
+// TCP server
+// ============================================================================
+
+/// Handle used to stop a running `TcpServer`
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownHandle {
+    flag: Arc<AtomicBool>,
+}
+
+impl ShutdownHandle {
+    /// Request server shutdown
+    pub fn shutdown(&self) {
+        self.flag.store(true, Ordering::SeqCst);
+    }
+
+    /// Check whether shutdown was requested
+    #[must_use]
+    pub fn is_shutdown(&self) -> bool {
+        self.flag.load(Ordering::SeqCst)
+    }
+}
+
+/// Accepted client connection passed to server handlers
+pub struct Connection {
+    peer_addr: SocketAddr,
+    sender: TcpSender,
+}
+
+impl Connection {
+    /// Get peer address
+    #[must_use]
+    pub fn peer_addr(&self) -> SocketAddr {
+        self.peer_addr
+    }
+
+    /// Get sender replying to the peer
+    pub fn sender(&mut self) -> &mut TcpSender {
+        &mut self.sender
+    }
+}
+
+/// Threaded TCP server dispatching decoded frames to a handler
+///
+/// Every connection is served on its own thread. After `shutdown()` the
+/// server stops accepting, lets connection threads finish their current
+/// frame and joins them before `run` returns.
+pub struct TcpServer {
+    listener: TcpListener,
+    shutdown: ShutdownHandle,
+    poll_interval: Duration,
+}
+
+impl TcpServer {
+    /// Bind a new server
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            shutdown: ShutdownHandle::default(),
+            poll_interval: Duration::from_millis(10),
+        })
+    }
+
+    /// Get local address
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Get shutdown handle
+    #[must_use]
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Set interval used to poll for new connections and shutdown requests
+    pub fn set_poll_interval(&mut self, interval: Duration) {
+        self.poll_interval = interval;
+    }
+
+    /// Serve connections until shutdown is requested
+    pub fn run<H>(self, handler: H) -> io::Result<()>
+    where
+        H: Fn(&mut Connection, Frame) + Send + Sync + 'static,
+    {
+        let handler = Arc::new(handler);
+        let mut workers = Vec::new();
+
+        while !self.shutdown.is_shutdown() {
+            match self.listener.accept() {
+                Ok((stream, peer_addr)) => {
+                    let handler = Arc::clone(&handler);
+                    let shutdown = self.shutdown.clone();
+                    let poll_interval = self.poll_interval;
+                    workers.push(thread::spawn(move || {
+                        let _ = serve_connection(stream, peer_addr, &*handler, &shutdown, poll_interval);
+                    }));
+                }
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                    thread::sleep(self.poll_interval);
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => {
+                    self.shutdown.shutdown();
+                    join_workers(workers);
+                    return Err(err);
+                }
+            }
+            workers.retain(|worker| !worker.is_finished());
+        }
+
+        join_workers(workers);
+        Ok(())
+    }
+}
+
+fn serve_connection<H>(
+    stream: TcpStream,
+    peer_addr: SocketAddr,
+    handler: &H,
+    shutdown: &ShutdownHandle,
+    poll_interval: Duration,
+) -> io::Result<()>
+where
+    H: Fn(&mut Connection, Frame),
+{
+    stream.set_nonblocking(false)?;
+    stream.set_nodelay(true)?;
+    stream.set_read_timeout(Some(poll_interval.max(Duration::from_millis(1))))?;
+
+    let mut connection = Connection {
+        peer_addr,
+        sender: StreamSender::new(stream.try_clone()?),
+    };
+    let mut receiver = StreamReceiver::new(stream);
+
+    while !shutdown.is_shutdown() {
+        match receiver.read_frame() {
+            Ok(Some(frame)) => handler(&mut connection, frame),
+            Ok(None) => break,
+            Err(err)
+                if err.kind() == io::ErrorKind::WouldBlock
+                    || err.kind() == io::ErrorKind::TimedOut => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+fn join_workers(workers: Vec<thread::JoinHandle<()>>) {
+    for worker in workers {
+        let _ = worker.join();
+    }
+}
@@ -1,5 +1,7 @@
 use crate::address::Address;
 use crate::buffer::{ReadBuffer, WriteBuffer};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct UserWithAddress {
@@ -0,0 +1,277 @@
+//! Fast Binary Encoding UUID type
+//!
+//! FBE `uuid` is 16 bytes in big-endian (RFC 4122) byte order. Schema
+//! defaults `uuid0`, `uuid1` and `uuid4` map to [`Uuid::nil`], [`Uuid::v1`]
+//! and [`Uuid::v4`].
+
+use core::fmt;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::collections::hash_map::RandomState;
+#[cfg(feature = "std")]
+use std::hash::{BuildHasher, Hasher};
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// 100 ns intervals between the Gregorian reform (1582-10-15) and Unix epoch
+#[cfg(feature = "std")]
+const GREGORIAN_OFFSET: u64 = 0x01B2_1DD2_1381_4000;
+
+/// FBE UUID value
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uuid([u8; 16]);
+
+/// Invalid UUID string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UuidParseError(String);
+
+impl fmt::Display for UuidParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid UUID: {:?}", self.0)
+    }
+}
+
+impl core::error::Error for UuidParseError {}
+
+impl Uuid {
+    /// Nil UUID (all zeros)
+    #[must_use]
+    pub const fn nil() -> Self {
+        Self([0; 16])
+    }
+
+    /// Create UUID from big-endian bytes
+    #[must_use]
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+
+    /// Time-based UUID (version 1)
+    ///
+    /// Uses a random node id with the multicast bit set (RFC 4122 4.5) and a
+    /// random clock sequence chosen once per process. Timestamps are strictly
+    /// increasing within the process, so consecutive values never collide.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn v1() -> Self {
+        static LAST: AtomicU64 = AtomicU64::new(0);
+        static NODE: OnceLock<(u16, [u8; 6])> = OnceLock::new();
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| (elapsed.as_nanos() / 100) as u64)
+            + GREGORIAN_OFFSET;
+        let mut last = LAST.load(Ordering::Relaxed);
+        let ticks = loop {
+            let next = now.max(last + 1);
+            match LAST.compare_exchange_weak(last, next, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => break next,
+                Err(current) => last = current,
+            }
+        };
+
+        let (clock_seq, node) = NODE.get_or_init(|| {
+            let random = random_u64().to_be_bytes();
+            let mut node = [0u8; 6];
+            node.copy_from_slice(&random[2..]);
+            node[0] |= 0x01;
+            (u16::from_be_bytes([random[0], random[1]]), node)
+        });
+
+        let mut bytes = [0u8; 16];
+        bytes[0..4].copy_from_slice(&(ticks as u32).to_be_bytes());
+        bytes[4..6].copy_from_slice(&((ticks >> 32) as u16).to_be_bytes());
+        bytes[6..8].copy_from_slice(&(((ticks >> 48) as u16 & 0x0FFF) | 0x1000).to_be_bytes());
+        bytes[8..10].copy_from_slice(&((clock_seq & 0x3FFF) | 0x8000).to_be_bytes());
+        bytes[10..16].copy_from_slice(node);
+        Self(bytes)
+    }
+
+    /// Random UUID (version 4)
+    ///
+    /// Randomness comes from the std hasher keys, which is fine for unique
+    /// identifiers but not for secrets.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn v4() -> Self {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&random_u64().to_ne_bytes());
+        bytes[8..].copy_from_slice(&random_u64().to_ne_bytes());
+        bytes[6] = (bytes[6] & 0x0F) | 0x40;
+        bytes[8] = (bytes[8] & 0x3F) | 0x80;
+        Self(bytes)
+    }
+
+    /// Big-endian bytes
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// Convert into big-endian bytes
+    #[must_use]
+    pub const fn into_bytes(self) -> [u8; 16] {
+        self.0
+    }
+
+    /// Check if this is the nil UUID
+    #[must_use]
+    pub const fn is_nil(&self) -> bool {
+        u128::from_be_bytes(self.0) == 0
+    }
+
+    /// UUID version (1 = time-based, 4 = random, ...)
+    #[must_use]
+    pub const fn version(&self) -> u8 {
+        self.0[6] >> 4
+    }
+}
+
+/// Non-cryptographic random number from a freshly keyed std hasher
+#[cfg(feature = "std")]
+fn random_u64() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    if let Ok(elapsed) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        hasher.write_u128(elapsed.as_nanos());
+    }
+    hasher.finish()
+}
+
+impl fmt::Display for Uuid {
+    /// Lowercase hyphenated form, e.g. `123e4567-e89b-12d3-a456-426655440000`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                f.write_str("-")?;
+            }
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Uuid({})", self)
+    }
+}
+
+impl FromStr for Uuid {
+    type Err = UuidParseError;
+
+    /// Parse hyphenated, simple (32 hex digits) or braced UUID strings
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let error = || UuidParseError(text.to_string());
+        let value = text
+            .strip_prefix('{')
+            .and_then(|value| value.strip_suffix('}'))
+            .unwrap_or(text);
+        let hyphenated = value.len() == 36
+            && value
+                .bytes()
+                .enumerate()
+                .all(|(i, c)| matches!(i, 8 | 13 | 18 | 23) == (c == b'-'));
+        if !hyphenated && value.len() != 32 {
+            return Err(error());
+        }
+
+        let mut bytes = [0u8; 16];
+        let mut digits = value.bytes().filter(|&c| c != b'-');
+        for byte in &mut bytes {
+            let high = digits.next().and_then(|c| (c as char).to_digit(16));
+            let low = digits.next().and_then(|c| (c as char).to_digit(16));
+            match (high, low) {
+                (Some(high), Some(low)) => *byte = (high << 4 | low) as u8,
+                _ => return Err(error()),
+            }
+        }
+        Ok(Self(bytes))
+    }
+}
+
+// ============================================================================
+// Conversions
+// ============================================================================
+
+impl From<[u8; 16]> for Uuid {
+    fn from(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Uuid> for [u8; 16] {
+    fn from(value: Uuid) -> Self {
+        value.0
+    }
+}
+
+impl From<u128> for Uuid {
+    fn from(value: u128) -> Self {
+        Self(value.to_be_bytes())
+    }
+}
+
+impl From<Uuid> for u128 {
+    fn from(value: Uuid) -> Self {
+        u128::from_be_bytes(value.0)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<::uuid::Uuid> for Uuid {
+    fn from(value: ::uuid::Uuid) -> Self {
+        Self(value.into_bytes())
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<Uuid> for ::uuid::Uuid {
+    fn from(value: Uuid) -> Self {
+        ::uuid::Uuid::from_bytes(value.0)
+    }
+}
+
+// ============================================================================
+// serde (FBE JSON: hyphenated string, binary formats: 16 bytes inline)
+// ============================================================================
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Uuid {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serde::Serialize::serialize(&self.0, serializer)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Uuid {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let text = <alloc::borrow::Cow<'de, str> as serde::Deserialize>::deserialize(deserializer)?;
+            text.parse().map_err(serde::de::Error::custom)
+        } else {
+            <[u8; 16] as serde::Deserialize>::deserialize(deserializer).map(Self)
+        }
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for Uuid {
+    type Parameters = ();
+    type Strategy = proptest::strategy::Map<proptest::num::u128::Any, fn(u128) -> Self>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        proptest::strategy::Strategy::prop_map(proptest::num::u128::ANY, Self::from)
+    }
+}
@@ -0,0 +1,154 @@
+//! Fast Binary Encoding vectored (scatter-gather) output
+//!
+//! [`VectoredWriter`] serializes values in the FinalModel layout like
+//! [`FinalValue::write_final`], but `bytes` and `string` payloads at or above
+//! a threshold are referenced instead of copied. The result is a list of
+//! [`IoSlice`]s alternating between small inline chunks and the caller's
+//! payloads, ready for `Write::write_vectored`.
+
+use crate::buffer::WriteBuffer;
+use crate::final_model::FinalValue;
+#[cfg(feature = "std")]
+use std::io::{self, IoSlice, Write};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+/// Scatter-gather writer referencing large payloads
+#[derive(Debug)]
+pub struct VectoredWriter<'a> {
+    inline: WriteBuffer,
+    /// Payloads spliced in at the given inline position
+    payloads: Vec<(usize, &'a [u8])>,
+    threshold: usize,
+}
+
+impl Default for VectoredWriter<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> VectoredWriter<'a> {
+    /// Default payload size referenced instead of copied
+    pub const DEFAULT_THRESHOLD: usize = 4096;
+
+    /// Create a new vectored writer with the default threshold
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_threshold(Self::DEFAULT_THRESHOLD)
+    }
+
+    /// Create a new vectored writer referencing payloads of at least `threshold` bytes
+    #[must_use]
+    pub fn with_threshold(threshold: usize) -> Self {
+        Self {
+            inline: WriteBuffer::growable(),
+            payloads: Vec::new(),
+            threshold,
+        }
+    }
+
+    /// Get the payload size referenced instead of copied
+    #[must_use]
+    pub const fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Get total serialized size
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.inline.size() + self.payloads.iter().map(|(_, data)| data.len()).sum::<usize>()
+    }
+
+    /// Get number of referenced payloads
+    #[must_use]
+    pub fn payloads(&self) -> usize {
+        self.payloads.len()
+    }
+
+    /// Append a value, referencing its large payloads
+    pub fn append<T: FinalValue>(&mut self, value: &'a T) {
+        value.write_vectored(self);
+    }
+
+    /// Append a value by copying it inline
+    pub fn append_inline<T: FinalValue>(&mut self, value: &T) {
+        let offset = self.inline.size();
+        value.write_final(&mut self.inline, offset);
+    }
+
+    /// Append size-prefixed bytes, referencing them when at or above the threshold
+    pub fn append_bytes(&mut self, data: &'a [u8]) {
+        let offset = self.inline.size();
+        self.inline.write_u32(offset, data.len() as u32);
+        if data.len() >= self.threshold {
+            self.payloads.push((offset + 4, data));
+        } else {
+            self.inline.write_array(offset + 4, data);
+        }
+    }
+
+    /// Visit inline chunks and referenced payloads in order
+    fn for_each_chunk<'s>(&'s self, mut f: impl FnMut(&'s [u8])) {
+        let inline = self.inline.data();
+        let mut start = 0;
+        for &(position, data) in &self.payloads {
+            if position > start {
+                f(&inline[start..position]);
+            }
+            f(data);
+            start = position;
+        }
+        if inline.len() > start {
+            f(&inline[start..]);
+        }
+    }
+
+    /// Get the serialized data as I/O slices
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn io_slices(&self) -> Vec<IoSlice<'_>> {
+        let mut slices = Vec::with_capacity(2 * self.payloads.len() + 1);
+        self.for_each_chunk(|chunk| slices.push(IoSlice::new(chunk)));
+        slices
+    }
+
+    /// Copy the serialized data into a contiguous vector
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(self.size());
+        self.for_each_chunk(|chunk| data.extend_from_slice(chunk));
+        data
+    }
+
+    /// Write the serialized data with vectored writes
+    #[cfg(feature = "std")]
+    pub fn write_to<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<usize> {
+        let mut slices = self.io_slices();
+        write_all_vectored(writer, &mut slices)?;
+        Ok(self.size())
+    }
+}
+
+/// Write all slices, retrying partial vectored writes
+#[cfg(feature = "std")]
+pub fn write_all_vectored<W: Write + ?Sized>(
+    writer: &mut W,
+    mut slices: &mut [IoSlice<'_>],
+) -> io::Result<()> {
+    IoSlice::advance_slices(&mut slices, 0);
+    while !slices.is_empty() {
+        match writer.write_vectored(slices) {
+            Ok(0) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                ));
+            }
+            Ok(written) => IoSlice::advance_slices(&mut slices, written),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
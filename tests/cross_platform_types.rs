@@ -1,4 +1,5 @@
 use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::{Decimal, Timestamp, Uuid};
 use std::fs;
 
 #[test]
@@ -7,17 +8,17 @@ fn test_cross_platform_types() {
     let mut writer = WriteBuffer::new();
 
     // Timestamp
-    let timestamp: u64 = 1729526400000000000;
+    let timestamp = Timestamp::new(1729526400000000000);
     writer.allocate(8);
     writer.write_timestamp(0, timestamp);
 
     // UUID
-    let uuid: [u8; 16] = [
+    let uuid = Uuid::from_bytes([
         0x12, 0x3e, 0x45, 0x67, 0xe8, 0x9b, 0x12, 0xd3, 0xa4, 0x56, 0x42, 0x66, 0x55, 0x44, 0x00,
         0x00,
-    ];
+    ]);
     writer.allocate(16);
-    writer.write_uuid(8, &uuid);
+    writer.write_uuid(8, uuid);
 
     // Bytes
     let bytes_data = b"Binary\x00\xFF";
@@ -25,16 +26,9 @@ fn test_cross_platform_types() {
     writer.write_bytes(24, bytes_data);
 
     // Decimal
-    let decimal_value: i128 = 123456123456;
-    let decimal_scale: u8 = 6;
-    let decimal_negative = false;
+    let decimal = Decimal::try_new(123456123456, 6).unwrap();
     writer.allocate(16);
-    writer.write_decimal(
-        24 + 4 + bytes_data.len(),
-        decimal_value,
-        decimal_scale,
-        decimal_negative,
-    );
+    writer.write_decimal(24 + 4 + bytes_data.len(), decimal);
 
     // Save to file
     fs::write("/tmp/rust_types.bin", writer.data()).unwrap();
@@ -48,10 +42,9 @@ fn test_cross_platform_types() {
     assert_eq!(reader.read_uuid(8), uuid);
     assert_eq!(reader.read_bytes(24), bytes_data.to_vec());
 
-    let (val, scale, neg) = reader.read_decimal(24 + 4 + bytes_data.len());
-    assert_eq!(val, decimal_value);
-    assert_eq!(scale, decimal_scale);
-    assert_eq!(neg, decimal_negative);
+    let read_decimal = reader.read_decimal(24 + 4 + bytes_data.len());
+    assert_eq!(read_decimal, decimal);
+    assert_eq!(read_decimal.scale(), decimal.scale());
 
     // Try reading PHP binary if exists
     if std::path::Path::new("/tmp/php_types.bin").exists() {
@@ -63,22 +56,18 @@ fn test_cross_platform_types() {
         let php_timestamp = php_reader.read_timestamp(0);
         let php_uuid = php_reader.read_uuid(8);
         let php_bytes = php_reader.read_bytes(24);
-        let (php_val, php_scale, php_neg) = php_reader.read_decimal(24 + 4 + php_bytes.len());
+        let php_decimal = php_reader.read_decimal(24 + 4 + php_bytes.len());
 
         println!("PHP→Rust timestamp: {}", php_timestamp);
         println!("PHP→Rust UUID: {:02x?}", php_uuid);
         println!("PHP→Rust bytes length: {}", php_bytes.len());
-        println!(
-            "PHP→Rust decimal: value={}, scale={}, negative={}",
-            php_val, php_scale, php_neg
-        );
+        println!("PHP→Rust decimal: {}", php_decimal);
 
         assert_eq!(php_timestamp, timestamp);
         assert_eq!(php_uuid, uuid);
         assert_eq!(php_bytes, bytes_data.to_vec());
-        assert_eq!(php_val, decimal_value);
-        assert_eq!(php_scale, decimal_scale);
-        assert_eq!(php_neg, decimal_negative);
+        assert_eq!(php_decimal, decimal);
+        assert_eq!(php_decimal.scale(), decimal.scale());
 
         println!("✓ Cross-platform types test passed!");
     }
@@ -0,0 +1,17 @@
+#!/bin/sh
+# Regenerate the upstream golden files with the upstream C++ FBE generator
+#
+# Needs `fbec` from https://github.com/chronoxor/FastBinaryEncoding on PATH,
+# a C++17 compiler (CXX, default c++), RapidJSON headers (RAPIDJSON_INCLUDE,
+# default /usr/include) and libuuid (LDLIBS, default -luuid). This script is
+# the only writer of tests/golden/upstream/<struct>/*.bin.
+set -eu
+
+dir=$(cd "$(dirname "$0")" && pwd)
+work=$(mktemp -d)
+trap 'rm -rf "$work"' EXIT
+
+fbec --cpp --final --json --input="$dir/../golden.fbe" --output="$work"
+${CXX:-c++} -std=c++17 -O1 -I"$work" -I"${RAPIDJSON_INCLUDE:-/usr/include}" \
+    "$dir/golden.cpp" "$work"/*.cpp -o "$work/golden" ${LDLIBS:--luuid}
+"$work/golden" "$dir/../cases" "$dir"
@@ -0,0 +1,86 @@
+// Write upstream FBE Model and FinalModel binaries for the golden JSON cases
+//
+// Built by generate.sh against the C++ code of the upstream fbec:
+// golden <cases dir> <output dir> reads <cases dir>/<struct>/<case>.json and
+// writes <output dir>/<struct>/<case>.model.bin and <case>.final.bin.
+
+#include "golden_final_models.h"
+#include "golden_json.h"
+#include "golden_models.h"
+
+#include <filesystem>
+#include <fstream>
+#include <iostream>
+#include <sstream>
+#include <stdexcept>
+#include <string>
+
+namespace fs = std::filesystem;
+
+static void write_file(const fs::path& path, const FBE::FBEBuffer& buffer)
+{
+    std::ofstream output(path, std::ios::binary);
+    output.write(reinterpret_cast<const char*>(buffer.data()), buffer.size());
+    if (!output)
+        throw std::runtime_error("cannot write " + path.string());
+}
+
+template <class TValue, class TModel, class TFinalModel>
+static void convert(const fs::path& cases, const fs::path& output)
+{
+    fs::create_directories(output);
+    for (const auto& entry : fs::directory_iterator(cases))
+    {
+        if (entry.path().extension() != ".json")
+            continue;
+
+        std::ifstream input(entry.path());
+        std::stringstream text;
+        text << input.rdbuf();
+        rapidjson::Document json;
+        json.Parse(text.str().c_str());
+        TValue value;
+        if (json.HasParseError() || !FBE::JSON::from_json(json, value))
+            throw std::runtime_error("cannot read " + entry.path().string());
+
+        const std::string name = entry.path().stem().string();
+
+        TModel model;
+        model.serialize(value);
+        if (!model.verify())
+            throw std::runtime_error("invalid Model for " + entry.path().string());
+        write_file(output / (name + ".model.bin"), model.buffer());
+
+        TFinalModel final_model;
+        final_model.serialize(value);
+        if (!final_model.verify())
+            throw std::runtime_error("invalid FinalModel for " + entry.path().string());
+        write_file(output / (name + ".final.bin"), final_model.buffer());
+    }
+}
+
+int main(int argc, char** argv)
+{
+    if (argc != 3)
+    {
+        std::cerr << "usage: golden <cases dir> <output dir>" << std::endl;
+        return 1;
+    }
+
+    const fs::path cases = argv[1];
+    const fs::path output = argv[2];
+    try
+    {
+        convert<golden::Line, FBE::golden::LineModel, FBE::golden::LineFinalModel>(cases / "line", output / "line");
+        convert<golden::Order, FBE::golden::OrderModel, FBE::golden::OrderFinalModel>(cases / "order", output / "order");
+        convert<golden::Scalars, FBE::golden::ScalarsModel, FBE::golden::ScalarsFinalModel>(cases / "scalars", output / "scalars");
+        convert<golden::Optionals, FBE::golden::OptionalsModel, FBE::golden::OptionalsFinalModel>(cases / "optionals", output / "optionals");
+        convert<golden::Collections, FBE::golden::CollectionsModel, FBE::golden::CollectionsFinalModel>(cases / "collections", output / "collections");
+    }
+    catch (const std::exception& ex)
+    {
+        std::cerr << ex.what() << std::endl;
+        return 1;
+    }
+    return 0;
+}
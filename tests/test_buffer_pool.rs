@@ -0,0 +1,105 @@
+//! FBE write buffer pool tests
+#![cfg(feature = "std")]
+
+use fbe::buffer::WriteBuffer;
+use fbe::pool::BufferPool;
+use fbe::sender::Serialize;
+
+struct Message {
+    id: i32,
+    text: String,
+}
+
+impl Serialize for Message {
+    fn serialize(&self, buffer: &mut WriteBuffer) -> usize {
+        buffer.write_i32(0, self.id);
+        buffer.write_string(4, &self.text);
+        buffer.size()
+    }
+
+    fn size_hint(&self) -> usize {
+        8 + self.text.len()
+    }
+}
+
+#[test]
+fn test_pool_reuses_capacity() {
+    let pool = BufferPool::new();
+    let mut buffer = pool.acquire();
+    assert!(buffer.is_growable());
+    buffer.write_bytes(0, &[0xAB; 300]);
+    let capacity = buffer.capacity();
+    let data = buffer.data().as_ptr();
+    drop(buffer);
+
+    let buffer = pool.acquire();
+    assert_eq!(buffer.size(), 0);
+    assert_eq!(buffer.offset(), 0);
+    assert_eq!(buffer.capacity(), capacity);
+    assert_eq!(buffer.data().as_ptr(), data);
+}
+
+#[test]
+fn test_pool_serialize() {
+    let pool = BufferPool::new();
+    let message = Message {
+        id: 42,
+        text: "hello".to_string(),
+    };
+    for _ in 0..3 {
+        let buffer = pool.serialize(&message);
+        assert_eq!(buffer.size(), 13);
+        assert_eq!(&buffer.data()[..4], &42i32.to_le_bytes());
+        assert_eq!(&buffer.data()[8..13], b"hello");
+    }
+}
+
+#[test]
+fn test_pool_shared_free_list() {
+    let pool = BufferPool::with_limits(4, 1024);
+
+    // Releases beyond the per-thread cache spill into the shared free list
+    let buffers: Vec<_> = (0..32).map(|_| pool.acquire()).collect();
+    drop(buffers);
+    assert_eq!(pool.available(), 4);
+
+    // Oversized buffers are freed instead of being returned
+    let before = pool.available();
+    let mut buffers: Vec<_> = (0..16).map(|_| pool.acquire()).collect();
+    buffers[0].write_bytes(0, &[0; 4096]);
+    drop(buffers);
+    assert_eq!(pool.available(), before);
+}
+
+#[test]
+fn test_pool_into_inner() {
+    let pool = BufferPool::with_limits(4, 1024);
+    let mut buffer = pool.acquire().into_inner();
+    buffer.write_i32(0, 7);
+    assert_eq!(buffer.size(), 4);
+    assert_eq!(pool.available(), 0);
+}
+
+#[test]
+fn test_pool_threads() {
+    let pool = BufferPool::new();
+    let handles: Vec<_> = (0..4)
+        .map(|id| {
+            let pool = pool.clone();
+            std::thread::spawn(move || {
+                for index in 0..1000 {
+                    let message = Message {
+                        id: id * 1000 + index,
+                        text: format!("message {}", index),
+                    };
+                    let buffer = pool.serialize(&message);
+                    assert_eq!(buffer.size(), message.size_hint());
+                    assert_eq!(&buffer.data()[..4], &message.id.to_le_bytes());
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
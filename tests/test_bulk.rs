@@ -0,0 +1,81 @@
+//! FBE bulk primitive encoding tests
+
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::bulk::{self, Primitive};
+use std::fmt::Debug;
+
+fn round_trip<T: Primitive + PartialEq + Debug>(values: &[T]) {
+    let mut writer = WriteBuffer::new();
+    writer.allocate(4);
+    let size = writer.write_vector(0, values);
+    assert_eq!(size, 4 + values.len() * T::SIZE);
+    writer.allocate(values.len() * T::SIZE);
+    let array_offset = 8 + values.len() * T::SIZE;
+    assert_eq!(writer.write_array(array_offset, values), values.len() * T::SIZE);
+
+    let reader = ReadBuffer::borrowed(writer.data());
+    assert_eq!(reader.read_vector::<T>(0), values);
+    assert_eq!(reader.read_array::<T>(array_offset, values.len()), values);
+}
+
+#[test]
+fn test_bulk_round_trip() {
+    round_trip(&[i8::MIN, -1, 0, 1, i8::MAX]);
+    round_trip(&[0u8, 0x7F, 0xFF]);
+    round_trip(&[i16::MIN, -2, 0, i16::MAX]);
+    round_trip(&[0u16, 0xBEEF, u16::MAX]);
+    round_trip(&[i32::MIN, -3, 0, i32::MAX]);
+    round_trip(&[0u32, 0xDEAD_BEEF, u32::MAX]);
+    round_trip(&[i64::MIN, -4, 0, i64::MAX]);
+    round_trip(&[0u64, 0x0123_4567_89AB_CDEF, u64::MAX]);
+    round_trip(&[f32::MIN, -1.5, 0.0, f32::MAX]);
+    round_trip(&[f64::MIN, -2.25, 0.0, f64::MAX]);
+    round_trip::<i32>(&[]);
+}
+
+#[test]
+fn test_bulk_little_endian_layout() {
+    let values = [0x0102_0304i32, -2];
+    let mut bytes = [0u8; 8];
+    bulk::encode(&values, &mut bytes);
+    assert_eq!(bytes, [4, 3, 2, 1, 0xFE, 0xFF, 0xFF, 0xFF]);
+    assert_eq!(bulk::decode::<i32>(&bytes), values);
+
+    // Bulk and element-by-element writes produce the same bytes
+    let doubles = [1.5f64, -0.25, 1e300];
+    let mut scalar = WriteBuffer::new();
+    scalar.allocate(24);
+    for (i, &value) in doubles.iter().enumerate() {
+        scalar.write_f64(i * 8, value);
+    }
+    let mut bulk = WriteBuffer::new();
+    bulk.allocate(24);
+    bulk.write_array_f64(0, &doubles);
+    assert_eq!(bulk.data(), scalar.data());
+}
+
+#[test]
+fn test_bulk_view() {
+    let values = [1u32, 2, 3, 4];
+    let mut writer = WriteBuffer::new();
+    writer.allocate(24);
+
+    // Place one copy on a u32 boundary in memory and one just past it
+    let skew = writer.data().as_ptr() as usize % align_of::<u32>();
+    let aligned = (align_of::<u32>() - skew) % align_of::<u32>();
+    writer.write_array(aligned, &values);
+    writer.write_array(aligned + 17, &values[..1]);
+
+    let reader = ReadBuffer::borrowed(writer.data());
+    if cfg!(target_endian = "little") {
+        assert_eq!(reader.view_array::<u32>(aligned, 4), Some(&values[..]));
+    }
+    assert_eq!(reader.view_array::<u32>(aligned + 17, 1), None);
+    assert_eq!(reader.read_array::<u32>(aligned + 17, 1), [1]);
+}
+
+#[test]
+#[should_panic(expected = "Invalid bulk output size!")]
+fn test_bulk_encode_size_mismatch() {
+    bulk::encode(&[1u16, 2], &mut [0u8; 3]);
+}
@@ -0,0 +1,112 @@
+//! FBE `bytes` crate integration tests
+#![cfg(feature = "bytes")]
+
+use bytes::{Bytes, BytesMut};
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::final_model::FinalValue;
+
+fn payload() -> Vec<u8> {
+    (0..4096).map(|i| i as u8).collect()
+}
+
+#[test]
+fn test_write_buffer_bytes_mut() {
+    let mut bytes = BytesMut::with_capacity(64);
+    bytes.extend_from_slice(b"head");
+    let ptr = bytes.as_ptr();
+
+    let mut buffer = WriteBuffer::from_bytes_mut(bytes);
+    assert_eq!(buffer.size(), 4);
+    let offset = buffer.allocate(8);
+    buffer.write_i64(offset, 0x0FBE);
+    assert_eq!(buffer.data().as_ptr(), ptr);
+
+    let bytes = buffer.into_bytes_mut();
+    assert_eq!(bytes.as_ptr(), ptr);
+    assert_eq!(&bytes[..4], b"head");
+    assert_eq!(&bytes[4..], 0x0FBEi64.to_le_bytes());
+
+    // Written size equal to the capacity keeps the allocation as well
+    let mut buffer = WriteBuffer::with_capacity(16);
+    buffer.allocate(16);
+    let ptr = buffer.data().as_ptr();
+    assert_eq!(buffer.into_bytes_mut().as_ptr(), ptr);
+}
+
+#[test]
+fn test_write_buffer_bytes_mut_shared() {
+    let mut head = BytesMut::with_capacity(64);
+    head.extend_from_slice(b"headtail");
+    let tail = head.split_off(4);
+    let ptr = tail.as_ptr();
+
+    // Storage shared with `head` is copied
+    let buffer = WriteBuffer::from_bytes_mut(tail);
+    assert_eq!(buffer.data(), b"tail");
+    assert_ne!(buffer.data().as_ptr(), ptr);
+    assert_eq!(&head[..], b"head");
+}
+
+#[test]
+fn test_write_buffer_freeze() {
+    let mut buffer = WriteBuffer::growable();
+    payload().write_final(&mut buffer, 0);
+    let ptr = buffer.data().as_ptr();
+    let size = buffer.size();
+
+    let bytes = buffer.freeze();
+    assert_eq!(bytes.as_ptr(), ptr);
+    assert_eq!(bytes.len(), size);
+    assert_eq!(Vec::<u8>::read_final(&ReadBuffer::from(bytes), 0).0, payload());
+}
+
+#[test]
+fn test_read_buffer_bytes() {
+    let mut buffer = WriteBuffer::growable();
+    buffer.write_i32(0, 7);
+    buffer.write_bytes(4, &payload());
+    let data = buffer.freeze();
+
+    let reader = ReadBuffer::from_bytes(data.clone());
+    assert_eq!(reader.data().as_ptr(), data.as_ptr());
+    let field = reader.read_bytes_shared(4);
+    assert_eq!(field, payload());
+    assert_eq!(field.as_ptr(), data[8..].as_ptr());
+
+    // Sub-slices keep the allocation alive after the reader is gone
+    drop(reader);
+    drop(data);
+    assert_eq!(field, payload());
+
+    // Other storage copies
+    let copy = payload();
+    let mut buffer = WriteBuffer::growable();
+    buffer.write_bytes(0, &copy);
+    let reader = ReadBuffer::borrowed(buffer.data());
+    let field = reader.read_bytes_shared(0);
+    assert_eq!(field, copy);
+    assert_ne!(field.as_ptr(), buffer.data()[4..].as_ptr());
+}
+
+#[test]
+fn test_bytes_final_value() {
+    let value = (Bytes::from(payload()), Bytes::new());
+    let mut buffer = WriteBuffer::growable();
+    let size = value.0.write_final(&mut buffer, 0);
+    assert_eq!(value.1.write_final(&mut buffer, size), 4);
+    assert_eq!(buffer.size(), value.0.final_size() + value.1.final_size());
+
+    // Identical layout to Vec<u8>
+    let mut expected = WriteBuffer::growable();
+    payload().write_final(&mut expected, 0);
+    Vec::<u8>::new().write_final(&mut expected, size);
+    assert_eq!(buffer.data(), expected.data());
+
+    let data = buffer.freeze();
+    let reader = ReadBuffer::from(data.clone());
+    let (first, first_size) = Bytes::read_final(&reader, 0);
+    let (second, second_size) = Bytes::read_final(&reader, first_size);
+    assert_eq!((first_size, second_size), (4 + 4096, 4));
+    assert_eq!(first.as_ptr(), data[4..].as_ptr());
+    assert_eq!((first, second), value);
+}
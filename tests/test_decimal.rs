@@ -0,0 +1,213 @@
+//! FBE Decimal type tests
+
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::decimal::{DecimalError, RoundingMode};
+use fbe::Decimal;
+
+fn dec(text: &str) -> Decimal {
+    text.parse().unwrap()
+}
+
+#[test]
+fn test_decimal_text() {
+    assert_eq!(dec("123.456").into_parts(), (123456, 3, false));
+    assert_eq!(dec("-0.005").into_parts(), (5, 3, true));
+    assert_eq!(dec("+.5").into_parts(), (5, 1, false));
+    assert_eq!(dec("-0.00").into_parts(), (0, 2, false));
+    assert_eq!(dec("79228162514264337593543950335"), Decimal::MAX);
+
+    assert_eq!(dec("-0.005").to_string(), "-0.005");
+    assert_eq!(dec("42").to_string(), "42");
+    assert_eq!(format!("{:.2}", dec("1.005")), "1.00");
+    assert_eq!(format!("{:.2}", dec("1.015")), "1.02");
+    assert_eq!(format!("{:.3}", dec("7")), "7.000");
+    assert_eq!(format!("{:>8}", dec("-1.5")), "    -1.5");
+
+    // Excess fractional digits are rounded half to even
+    assert_eq!(
+        dec("0.12345678901234567890123456785").to_string(),
+        "0.1234567890123456789012345678"
+    );
+
+    assert_eq!(
+        "79228162514264337593543950336".parse::<Decimal>(),
+        Err(DecimalError::Overflow)
+    );
+    for text in ["", "-", ".", "1e5", "1.2.3", "--1", " 1"] {
+        assert!(
+            matches!(text.parse::<Decimal>(), Err(DecimalError::Parse(_))),
+            "{:?}",
+            text
+        );
+    }
+}
+
+#[test]
+fn test_decimal_compare() {
+    assert_eq!(dec("1.0"), dec("1.00"));
+    assert!(dec("-2") < dec("-1.5"));
+    assert!(dec("0.1") > dec("0.09"));
+    assert!(Decimal::MIN < Decimal::ZERO && Decimal::ZERO < Decimal::MAX);
+    assert_eq!(dec("1.2300").normalize().into_parts(), (123, 2, false));
+
+    let set: std::collections::HashSet<_> = [dec("1.5"), dec("1.50"), dec("1.500")].into();
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn test_decimal_arithmetic() {
+    assert_eq!(dec("0.1") + dec("0.2"), dec("0.3"));
+    assert_eq!((dec("1.10") + dec("2.205")).to_string(), "3.305");
+    assert_eq!(dec("1") - dec("1.5"), dec("-0.5"));
+    assert_eq!((dec("1.5") * dec("-2.25")).to_string(), "-3.375");
+    assert_eq!((dec("1") / dec("4")).to_string(), "0.25");
+    assert_eq!((dec("100") / dec("0.5")).to_string(), "200");
+    assert_eq!(
+        (dec("1") / dec("3")).to_string(),
+        "0.3333333333333333333333333333"
+    );
+    assert_eq!(
+        (dec("2") / dec("3")).to_string(),
+        "0.6666666666666666666666666667"
+    );
+    assert_eq!(dec("-7") / dec("2"), dec("-3.5"));
+
+    let mut value = dec("10");
+    value += dec("5");
+    value -= dec("3");
+    value *= dec("2");
+    value /= dec("8");
+    assert_eq!(value, dec("3"));
+    assert_eq!(-value, dec("-3"));
+
+    // Product digits beyond scale 28 are rounded away
+    let tiny = dec("0.000000000000001");
+    assert_eq!(tiny * tiny, Decimal::ZERO);
+    assert_eq!(
+        (Decimal::MAX * dec("0.1")).to_string(),
+        "7922816251426433759354395033.5"
+    );
+    assert_eq!(
+        (Decimal::MAX * dec("0.15")).to_string(),
+        "11884224377139650639031592550"
+    );
+
+    assert_eq!(Decimal::MAX.checked_add(Decimal::ONE), None);
+    assert_eq!(Decimal::MAX.checked_mul(dec("2")), None);
+    assert_eq!(Decimal::ONE.checked_div(Decimal::ZERO), None);
+    assert_eq!(Decimal::MAX.checked_sub(Decimal::MAX), Some(Decimal::ZERO));
+    assert_eq!(
+        Decimal::ONE.div_round(Decimal::ZERO, RoundingMode::HalfEven),
+        Err(DecimalError::DivisionByZero)
+    );
+}
+
+#[test]
+#[should_panic(expected = "attempt to add decimals: Decimal overflow")]
+fn test_decimal_overflow_panics() {
+    let _ = Decimal::MAX + Decimal::ONE;
+}
+
+#[test]
+fn test_decimal_rounding_modes() {
+    let price = dec("-2.345");
+    let expected = [
+        (RoundingMode::HalfEven, "-2.34"),
+        (RoundingMode::HalfUp, "-2.35"),
+        (RoundingMode::HalfDown, "-2.34"),
+        (RoundingMode::Up, "-2.35"),
+        (RoundingMode::Down, "-2.34"),
+        (RoundingMode::Ceiling, "-2.34"),
+        (RoundingMode::Floor, "-2.35"),
+    ];
+    for (mode, text) in expected {
+        assert_eq!(price.round_dp(2, mode).to_string(), text, "{:?}", mode);
+    }
+
+    assert_eq!(
+        dec("1").div_round(dec("3"), RoundingMode::Up).unwrap().to_string(),
+        "0.3333333333333333333333333334"
+    );
+    assert_eq!(
+        dec("2").div_round(dec("3"), RoundingMode::Down).unwrap().to_string(),
+        "0.6666666666666666666666666666"
+    );
+}
+
+#[test]
+fn test_decimal_range() {
+    assert_eq!(
+        Decimal::from_parts(1 << 96, 0, false),
+        Err(DecimalError::Overflow)
+    );
+    assert_eq!(
+        Decimal::from_parts(1, 29, false),
+        Err(DecimalError::ScaleOutOfRange(29))
+    );
+    assert_eq!(Decimal::try_new(-12345, 2).unwrap(), dec("-123.45"));
+    assert!(Decimal::try_from(i128::MAX).is_err());
+    assert_eq!(Decimal::from(-5i64), dec("-5"));
+    assert_eq!(Decimal::try_from(0.1f64).unwrap(), dec("0.1"));
+    assert!(Decimal::try_from(f64::NAN).is_err());
+    assert_eq!(dec("-1.25").to_f64(), -1.25);
+}
+
+#[test]
+fn test_decimal_buffer() {
+    let price = dec("-123.45");
+    assert_eq!(
+        price.to_bytes(),
+        [0x39, 0x30, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0x80]
+    );
+    assert_eq!(Decimal::from_bytes(price.to_bytes()), Ok(price));
+
+    let mut writer = WriteBuffer::new();
+    writer.allocate(32);
+    writer.write_decimal(0, price);
+    writer.write_decimal(16, Decimal::MAX);
+
+    let mut reader = ReadBuffer::new();
+    reader.attach_buffer(writer.data(), 0, writer.size());
+    assert_eq!(reader.read_decimal(0).into_parts(), price.into_parts());
+    assert_eq!(reader.read_decimal(16), Decimal::MAX);
+
+    // Non-conforming peers may send a scale above 28
+    let mut bytes = dec("1.5").to_bytes();
+    bytes[0] = 15;
+    bytes[14] = 29;
+    assert_eq!(
+        Decimal::from_bytes(bytes),
+        Err(DecimalError::ScaleOutOfRange(29))
+    );
+    reader.attach_buffer(&bytes, 0, bytes.len());
+    assert_eq!(reader.read_decimal(0), dec("0.0000000000000000000000000002"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_decimal_serde() {
+    let price = dec("-0.50");
+    #[cfg(feature = "json")]
+    {
+        assert_eq!(fbe::json::to_json(&price).unwrap(), r#""-0.50""#);
+        assert_eq!(fbe::json::from_json::<Decimal>(r#""-0.50""#).unwrap(), price);
+    }
+
+    let bytes = fbe::serde::to_vec(&price).unwrap();
+    assert_eq!(bytes, price.to_bytes());
+    let decoded: Decimal = fbe::serde::from_slice(&bytes).unwrap();
+    assert_eq!(decoded.into_parts(), price.into_parts());
+}
+
+#[cfg(feature = "rust_decimal")]
+#[test]
+fn test_decimal_rust_decimal() {
+    let value = rust_decimal::Decimal::new(-12345, 3);
+    let converted = Decimal::from(value);
+    assert_eq!(converted, dec("-12.345"));
+    assert_eq!(rust_decimal::Decimal::from(converted), value);
+    assert_eq!(
+        rust_decimal::Decimal::from(Decimal::MAX),
+        rust_decimal::Decimal::MAX
+    );
+}
@@ -0,0 +1,478 @@
+//! FBE decode limits tests
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::field_model::{FieldModelString, FieldModelVector, FieldValue};
+use fbe::field_model_collections::{
+    FieldModelArrayI32, FieldModelArrayString, FieldModelVectorI32, FieldModelVectorString,
+};
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+use fbe::field_model_collections::{FieldModelMapI32, FieldModelSetI32};
+use fbe::final_model::{FinalModelVector, FinalValue};
+use fbe::limits::{DecodeBudget, DecodeError, DecodeLimits};
+
+/// Pointer-based vector whose count claims `count` items but holds none
+fn crafted_vector(count: u32) -> Vec<u8> {
+    let mut buffer = WriteBuffer::new();
+    buffer.allocate(8);
+    buffer.write_u32(0, 4);
+    buffer.write_u32(4, count);
+    buffer.data().to_vec()
+}
+
+#[test]
+fn test_read_vector_crafted_count() {
+    let data = crafted_vector(u32::MAX);
+    let reader = ReadBuffer::from(data);
+
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    assert_eq!(
+        reader.try_read_vector_i32(0, &mut budget),
+        Err(DecodeError::CollectionTooLong { len: u32::MAX as usize, limit: 1024 * 1024 })
+    );
+    assert_eq!(budget.allocated(), 0);
+
+    let mut budget = DecodeBudget::new(DecodeLimits::unlimited());
+    assert_eq!(
+        reader.try_read_vector_i32(0, &mut budget),
+        Err(DecodeError::OutOfBounds { offset: 8, size: u32::MAX as usize * 4, len: 8 })
+    );
+
+    let mut budget = DecodeBudget::new(DecodeLimits::unlimited());
+    assert!(matches!(
+        reader.try_read_vector_string(0, &mut budget),
+        Err(DecodeError::OutOfBounds { .. })
+    ));
+}
+
+#[test]
+fn test_read_vector_within_limits() {
+    let mut buffer = WriteBuffer::new();
+    buffer.allocate(4);
+    buffer.write_vector_i32(0, &[1, 2, 3]);
+    let strings_offset = buffer.allocate(4);
+    buffer.write_vector_string(strings_offset, &["a".to_string(), "bc".to_string()]);
+    let reader = ReadBuffer::from(buffer.data().to_vec());
+
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    assert_eq!(reader.try_read_vector_i32(0, &mut budget).unwrap(), vec![1, 2, 3]);
+    assert_eq!(reader.try_read_vector_string(strings_offset, &mut budget).unwrap(), vec!["a", "bc"]);
+    assert_eq!(budget.depth(), 0);
+
+    let limits = DecodeLimits { max_collection_len: 2, ..DecodeLimits::default() };
+    let mut budget = DecodeBudget::new(limits);
+    assert_eq!(
+        reader.try_read_vector_i32(0, &mut budget),
+        Err(DecodeError::CollectionTooLong { len: 3, limit: 2 })
+    );
+}
+
+#[test]
+fn test_read_string_limits() {
+    let mut buffer = WriteBuffer::new();
+    buffer.allocate(4 + 5);
+    buffer.write_string(0, "hello");
+    let data = buffer.data().to_vec();
+
+    let limits = DecodeLimits { max_string_len: 4, ..DecodeLimits::default() };
+    let mut budget = DecodeBudget::new(limits);
+    assert_eq!(
+        FieldModelString::new(&data, 0).try_get(&mut budget),
+        Err(DecodeError::StringTooLong { len: 5, limit: 4 })
+    );
+
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    assert_eq!(FieldModelString::new(&data, 0).try_get(&mut budget).unwrap(), "hello");
+    assert_eq!(budget.allocated(), 5);
+
+    let reader = ReadBuffer::from(data[..6].to_vec());
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    assert_eq!(
+        reader.try_read_string(0, &mut budget),
+        Err(DecodeError::OutOfBounds { offset: 4, size: 5, len: 6 })
+    );
+}
+
+#[test]
+fn test_allocation_limit() {
+    let mut buffer = WriteBuffer::new();
+    buffer.allocate(4);
+    buffer.write_vector_i32(0, &[0; 16]);
+    let reader = ReadBuffer::from(buffer.data().to_vec());
+
+    let limits = DecodeLimits { max_allocation: 100, ..DecodeLimits::default() };
+    let mut budget = DecodeBudget::new(limits);
+    assert_eq!(reader.try_read_vector_i32(0, &mut budget).unwrap().len(), 16);
+    assert_eq!(budget.allocated(), 64);
+    assert_eq!(
+        reader.try_read_vector_i32(0, &mut budget),
+        Err(DecodeError::AllocationExceeded { limit: 100 })
+    );
+}
+
+#[test]
+fn test_collection_models_crafted_count() {
+    let data = crafted_vector(0x4000_0000);
+    let mut budget = DecodeBudget::new(DecodeLimits::unlimited());
+    let model = FieldModelVector::new(&data, 0, |buffer, offset| ReadBuffer::borrowed(buffer).read_i32(offset));
+    assert!(matches!(model.try_get(&mut budget), Err(DecodeError::OutOfBounds { .. })));
+
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    let model = FinalModelVector::new(&data[4..], 0, |buffer, offset| ReadBuffer::borrowed(buffer).read_i32(offset));
+    assert_eq!(
+        model.try_get(&mut budget),
+        Err(DecodeError::CollectionTooLong { len: 0x4000_0000, limit: 1024 * 1024 })
+    );
+}
+
+#[test]
+fn test_i32_collection_models_crafted_count() {
+    let data = crafted_vector(u32::MAX);
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    assert!(matches!(
+        FieldModelVectorI32::new(&data, 0).try_get(&mut budget),
+        Err(DecodeError::CollectionTooLong { .. })
+    ));
+    assert!(matches!(
+        FieldModelVectorString::new(&data, 0).try_get(&mut budget),
+        Err(DecodeError::CollectionTooLong { .. })
+    ));
+    assert_eq!(budget.allocated(), 0);
+}
+
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+#[test]
+fn test_hash_collection_models_crafted_count() {
+    let data = crafted_vector(u32::MAX);
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    assert!(matches!(
+        FieldModelMapI32::new(&data, 0).try_get(&mut budget),
+        Err(DecodeError::CollectionTooLong { .. })
+    ));
+    assert!(matches!(
+        FieldModelSetI32::new(&data, 0).try_get(&mut budget),
+        Err(DecodeError::CollectionTooLong { .. })
+    ));
+    assert_eq!(budget.allocated(), 0);
+}
+
+#[test]
+fn test_collection_size_overflow() {
+    let reader = ReadBuffer::from(vec![0; 8]);
+    assert!(matches!(
+        reader.check_items(4, usize::MAX, 8),
+        Err(DecodeError::OutOfBounds { offset: 4, size: usize::MAX, len: 8 })
+    ));
+
+    let data = vec![0; 8];
+    assert!(matches!(
+        FieldModelArrayI32::new(&data, 0, usize::MAX / 2).try_get(),
+        Err(DecodeError::OutOfBounds { size: usize::MAX, .. })
+    ));
+    assert!(matches!(
+        FieldModelArrayI32::new(&data, 4, 2).try_get(),
+        Err(DecodeError::OutOfBounds { offset: 4, size: 8, len: 8 })
+    ));
+    assert_eq!(FieldModelArrayI32::new(&data, 0, 2).try_get().unwrap(), vec![0, 0]);
+
+    let mut budget = DecodeBudget::new(DecodeLimits::unlimited());
+    assert!(matches!(
+        FieldModelArrayString::new(&data, 0, usize::MAX / 2).try_get(&mut budget),
+        Err(DecodeError::OutOfBounds { size: usize::MAX, .. })
+    ));
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    assert!(matches!(
+        FieldModelArrayString::new(&data, 0, usize::MAX / 2).try_get(&mut budget),
+        Err(DecodeError::CollectionTooLong { .. })
+    ));
+
+    let mut buffer = WriteBuffer::new();
+    buffer.allocate(4 + 3 + 4);
+    buffer.write_string(0, "fbe");
+    buffer.write_u32(7, 100);
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    assert!(matches!(
+        FieldModelArrayString::new(buffer.data(), 0, 2).try_get(&mut budget),
+        Err(DecodeError::OutOfBounds { offset: 11, size: 100, .. })
+    ));
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    assert_eq!(FieldModelArrayString::new(buffer.data(), 0, 1).try_get(&mut budget).unwrap(), vec!["fbe"]);
+}
+
+#[test]
+fn test_final_value_crafted_count() {
+    let reader = ReadBuffer::from(u32::MAX.to_le_bytes().to_vec());
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    assert!(matches!(
+        Vec::<String>::try_read_final(&reader, 0, &mut budget),
+        Err(DecodeError::CollectionTooLong { .. })
+    ));
+
+    let mut budget = DecodeBudget::new(DecodeLimits::unlimited());
+    assert!(matches!(
+        Vec::<u64>::try_read_final(&reader, 0, &mut budget),
+        Err(DecodeError::OutOfBounds { offset: 4, size: 8, len: 4 })
+    ));
+}
+
+#[test]
+fn test_final_value_roundtrip_and_truncation() {
+    let value: Vec<Option<String>> = vec![Some("fbe".to_string()), None];
+    let mut buffer = WriteBuffer::growable();
+    let size = value.write_final(&mut buffer, 0);
+    let data = buffer.data().to_vec();
+
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    let reader = ReadBuffer::from(data.clone());
+    assert_eq!(Vec::<Option<String>>::try_read_final(&reader, 0, &mut budget).unwrap(), (value, size));
+
+    for len in 0..size {
+        let reader = ReadBuffer::from(data[..len].to_vec());
+        let mut budget = DecodeBudget::new(DecodeLimits::default());
+        assert!(matches!(
+            Vec::<Option<String>>::try_read_final(&reader, 0, &mut budget),
+            Err(DecodeError::OutOfBounds { .. })
+        ));
+    }
+}
+
+#[test]
+fn test_final_value_invalid_wchar() {
+    let reader = ReadBuffer::from([1, 0, 0, 0, 0x00, 0xD8, 0, 0].to_vec());
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    assert_eq!(
+        Vec::<char>::try_read_final(&reader, 0, &mut budget),
+        Err(DecodeError::InvalidChar(fbe::chars::CharError::InvalidWChar(0xD800)))
+    );
+    assert_eq!(Vec::<char>::read_final(&reader, 0), (vec![char::REPLACEMENT_CHARACTER], 8));
+}
+
+#[test]
+fn test_field_value_roundtrip_and_truncation() {
+    let value: Vec<Option<String>> = vec![Some("fbe".to_string()), None];
+    let mut buffer = WriteBuffer::growable();
+    buffer.allocate(4);
+    value.write_field(&mut buffer, 0, 0);
+    let data = buffer.data().to_vec();
+    assert_eq!(data.len(), 4 + value.fbe_extra());
+
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    let reader = ReadBuffer::from(data.clone());
+    assert_eq!(Vec::<Option<String>>::try_read_field(&reader, 0, 0, &mut budget).unwrap(), value);
+    assert_eq!(Vec::<Option<String>>::read_field(&reader, 0, 0), value);
+
+    for len in 0..data.len() {
+        let reader = ReadBuffer::from(data[..len].to_vec());
+        let mut budget = DecodeBudget::new(DecodeLimits::default());
+        assert!(matches!(
+            Vec::<Option<String>>::try_read_field(&reader, 0, 0, &mut budget),
+            Err(DecodeError::OutOfBounds { .. })
+        ));
+    }
+}
+
+#[test]
+fn test_field_value_crafted_pointers() {
+    // Optional with has_value set and a null pointer
+    let reader = ReadBuffer::from(vec![1, 0, 0, 0, 0]);
+    let mut budget = DecodeBudget::new(DecodeLimits::default());
+    assert_eq!(
+        Option::<u32>::try_read_field(&reader, 0, 0, &mut budget),
+        Err(DecodeError::InvalidModel { offset: 0 })
+    );
+
+    // Null vector pointer is an empty vector, a crafted count is rejected
+    let reader = ReadBuffer::from(vec![0, 0, 0, 0]);
+    assert_eq!(Vec::<u64>::try_read_field(&reader, 0, 0, &mut budget).unwrap(), Vec::<u64>::new());
+    let reader = ReadBuffer::from(crafted_vector(u32::MAX));
+    assert!(matches!(
+        Vec::<String>::try_read_field(&reader, 0, 0, &mut budget),
+        Err(DecodeError::CollectionTooLong { .. })
+    ));
+
+    // Pointer past the end of the buffer
+    let reader = ReadBuffer::from(u32::MAX.to_le_bytes().to_vec());
+    assert!(matches!(
+        String::try_read_field(&reader, 8, 0, &mut budget),
+        Err(DecodeError::OutOfBounds { .. })
+    ));
+}
+
+#[test]
+fn test_depth_limit() {
+    let value = vec![vec![vec![1u8]]];
+    let mut buffer = WriteBuffer::growable();
+    value.write_final(&mut buffer, 0);
+    let reader = ReadBuffer::from(buffer.data().to_vec());
+
+    let limits = DecodeLimits { max_depth: 2, ..DecodeLimits::default() };
+    let mut budget = DecodeBudget::new(limits);
+    assert_eq!(
+        Vec::<Vec<Vec<u8>>>::try_read_final(&reader, 0, &mut budget),
+        Err(DecodeError::TooDeep { limit: 2 })
+    );
+
+    let limits = DecodeLimits { max_depth: 3, ..DecodeLimits::default() };
+    let mut budget = DecodeBudget::new(limits);
+    assert_eq!(Vec::<Vec<Vec<u8>>>::try_read_final(&reader, 0, &mut budget).unwrap().0, value);
+    assert_eq!(budget.depth(), 0);
+}
+
+#[test]
+fn test_message_size_limit() {
+    let limits = DecodeLimits { max_message_size: 16, ..DecodeLimits::default() };
+    assert!(DecodeBudget::for_message(limits, 16).is_ok());
+    assert_eq!(
+        DecodeBudget::for_message(limits, 17).unwrap_err(),
+        DecodeError::MessageTooLarge { size: 17, limit: 16 }
+    );
+    assert_eq!(
+        DecodeError::MessageTooLarge { size: 17, limit: 16 }.to_string(),
+        "Message size 17 exceeds limit 16"
+    );
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use fbe::FbeStruct;
+    use fbe::buffer::{ReadBuffer, WriteBuffer};
+    use fbe::limits::{DecodeError, DecodeLimits};
+    use fbe::final_model::FinalModel;
+    use fbe::receiver::Receiver;
+
+    #[derive(FbeStruct, Debug, Clone, PartialEq, Default)]
+    struct Inner {
+        tags: Vec<String>,
+    }
+
+    #[derive(FbeStruct, Debug, Clone, PartialEq, Default)]
+    struct Outer {
+        id: u32,
+        inner: Inner,
+        note: Option<String>,
+    }
+
+    fn outer() -> Outer {
+        Outer {
+            id: 7,
+            inner: Inner { tags: vec!["a".to_string(), "b".to_string()] },
+            note: Some("note".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_derive_try_deserialize() {
+        let mut buffer = WriteBuffer::growable();
+        outer().serialize(&mut buffer);
+        let data = buffer.data().to_vec();
+
+        let reader = ReadBuffer::from(data.clone());
+        assert_eq!(Outer::try_deserialize(&reader, &DecodeLimits::default()).unwrap(), outer());
+        assert_eq!(OuterFinalModel::new(&data, 0).try_get(&DecodeLimits::default()).unwrap(), outer());
+
+        let limits = DecodeLimits { max_depth: 1, ..DecodeLimits::default() };
+        assert_eq!(Outer::try_deserialize(&reader, &limits), Err(DecodeError::TooDeep { limit: 1 }));
+
+        let limits = DecodeLimits { max_string_len: 3, ..DecodeLimits::default() };
+        assert_eq!(
+            Outer::try_deserialize(&reader, &limits),
+            Err(DecodeError::StringTooLong { len: 4, limit: 3 })
+        );
+
+        let reader = ReadBuffer::from(data[..data.len() - 1].to_vec());
+        assert!(matches!(
+            Outer::try_deserialize(&reader, &DecodeLimits::default()),
+            Err(DecodeError::OutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn test_derive_model_try_get() {
+        let mut buffer = WriteBuffer::growable();
+        let size = OuterSizedModelMut::new(&mut buffer, 0).set(&outer());
+        let mut data = buffer.data().to_vec();
+        assert_eq!(OuterSizedModel::new(&data, 0).try_get(&DecodeLimits::default()).unwrap(), outer());
+
+        // Size header pointing past the end of the buffer
+        data[..4].copy_from_slice(&(size as u32 + 1).to_le_bytes());
+        assert!(matches!(
+            OuterSizedModel::new(&data, 0).try_get(&DecodeLimits::default()),
+            Err(DecodeError::OutOfBounds { .. })
+        ));
+
+        // Truncated size header
+        assert_eq!(OuterSizedModel::new(&data[..3], 0).size(), 0);
+        assert!(!OuterSizedModel::new(&data[..3], 0).verify());
+    }
+
+    #[test]
+    fn test_derive_pointer_model_try_get() {
+        let mut buffer = WriteBuffer::growable();
+        let size = OuterModelMut::new(&mut buffer, 0).set(&outer());
+        let data = buffer.data().to_vec();
+        assert_eq!(size, data.len());
+        assert_eq!(OuterModel::new(&data, 0).try_get(&DecodeLimits::default()).unwrap(), outer());
+
+        let limits = DecodeLimits { max_depth: 1, ..DecodeLimits::default() };
+        assert_eq!(OuterModel::new(&data, 0).try_get(&limits), Err(DecodeError::TooDeep { limit: 1 }));
+
+        // Size header pointing past the end of the buffer
+        let mut crafted = data.clone();
+        crafted[..4].copy_from_slice(&(size as u32 + 1).to_le_bytes());
+        assert!(matches!(
+            OuterModel::new(&crafted, 0).try_get(&DecodeLimits::default()),
+            Err(DecodeError::OutOfBounds { .. })
+        ));
+
+        // Null struct pointer
+        let mut crafted = data.clone();
+        crafted[4..8].copy_from_slice(&0u32.to_le_bytes());
+        assert_eq!(
+            OuterModel::new(&crafted, 0).try_get(&DecodeLimits::default()),
+            Err(DecodeError::InvalidModel { offset: 4 })
+        );
+
+        // Struct body smaller than its fields
+        let mut crafted = data.clone();
+        crafted[8..12].copy_from_slice(&8u32.to_le_bytes());
+        assert_eq!(
+            OuterModel::new(&crafted, 0).try_get(&DecodeLimits::default()),
+            Err(DecodeError::InvalidModel { offset: 8 })
+        );
+
+        // Pointers past the size header
+        let mut crafted = data.clone();
+        crafted[..4].copy_from_slice(&(size as u32 - 1).to_le_bytes());
+        assert!(matches!(
+            OuterModel::new(&crafted, 0).try_get(&DecodeLimits::default()),
+            Err(DecodeError::OutOfBounds { .. })
+        ));
+    }
+
+    struct Inbox;
+
+    impl Receiver for Inbox {
+        fn is_logging(&self) -> bool {
+            false
+        }
+
+        fn set_logging(&mut self, _enable: bool) {}
+
+        fn on_receive(&mut self, _data: &[u8]) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_receiver_try_receive_value() {
+        let mut buffer = WriteBuffer::growable();
+        outer().serialize(&mut buffer);
+
+        let value: Outer = Inbox.try_receive_value(buffer.data(), &DecodeLimits::default()).unwrap();
+        assert_eq!(value, outer());
+
+        let limits = DecodeLimits { max_message_size: 8, ..DecodeLimits::default() };
+        assert!(matches!(
+            Inbox.try_receive_value::<Outer>(buffer.data(), &limits),
+            Err(DecodeError::MessageTooLarge { limit: 8, .. })
+        ));
+    }
+}
@@ -0,0 +1,393 @@
+//! Derive macro tests against the hand-written FBE structs
+#![cfg(feature = "derive")]
+
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::chars::CharError;
+use fbe::collections::HashSet;
+use fbe::field_model::FieldModel;
+use fbe::final_model::{FinalModel, FinalValue};
+use fbe::{FbeEnum, FbeFlags, FbeStruct};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, FbeStruct)]
+#[fbe(id = 10)]
+struct Order {
+    #[fbe(key)]
+    id: i32,
+    symbol: String,
+    price: f64,
+}
+
+#[derive(Debug, Clone, FbeStruct)]
+struct UserSession {
+    #[fbe(key)]
+    user_id: i32,
+    #[fbe(key)]
+    session_id: String,
+    timestamp: i64,
+    ip_address: String,
+}
+
+#[derive(Debug, Clone, PartialEq, FbeStruct)]
+struct Config {
+    #[fbe(default = 30)]
+    timeout: i32,
+    #[fbe(default = 3)]
+    retries: i32,
+    #[fbe(default = 0.95)]
+    threshold: f64,
+    #[fbe(default = 1.5)]
+    ratio: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, FbeStruct)]
+struct Settings {
+    #[fbe(default = true)]
+    enabled: bool,
+    debug: bool,
+    #[fbe(default = "DefaultName")]
+    name: String,
+    #[fbe(default = "/var/log")]
+    path: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, FbeStruct)]
+struct Person {
+    name: String,
+    age: i32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, FbeStruct)]
+#[fbe(id = 2)]
+struct Employee {
+    #[fbe(base)]
+    person: Person,
+    company: String,
+    salary: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, FbeEnum)]
+#[repr(u8)]
+enum Side {
+    Buy = 1,
+    Sell = 2,
+    #[fbe(default)]
+    Unknown = 255,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FbeEnum)]
+enum Status {
+    Pending,
+    Active,
+    Completed = 10,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FbeFlags)]
+#[fbe(flags(READ = 0x01, WRITE = 0x02, EXECUTE = 0x04))]
+struct Permissions(u32);
+
+#[derive(Debug, Clone, PartialEq, FbeStruct)]
+struct Account {
+    side: Side,
+    status: Status,
+    permissions: Permissions,
+    owner: Option<Person>,
+    note: Option<String>,
+    levels: Vec<f64>,
+    tags: HashSet<String>,
+    limits: BTreeMap<i32, String>,
+    history: [u16; 3],
+}
+
+#[derive(Debug, Clone, PartialEq, FbeStruct)]
+struct Point(i32, i32);
+
+#[derive(Debug, Clone, PartialEq, FbeStruct)]
+struct Letters {
+    #[fbe(char)]
+    initial: char,
+    letter: char,
+}
+
+#[derive(Debug, Clone, PartialEq, FbeStruct)]
+struct Paragraph {
+    first: Option<Letters>,
+    lines: Vec<Letters>,
+}
+
+fn roundtrip<T: FinalValue>(value: &T) -> (T, usize) {
+    let mut buffer = WriteBuffer::new();
+    let offset = buffer.allocate(value.final_size());
+    let size = value.write_final(&mut buffer, offset);
+    assert_eq!(size, buffer.size());
+    let buffer = ReadBuffer::from(buffer.data().to_vec());
+    let (result, read) = T::read_final(&buffer, 0);
+    assert_eq!(read, size);
+    assert_eq!(T::read_final_size(&buffer, 0), size);
+    (result, size)
+}
+
+#[test]
+fn test_derive_matches_hand_written_keys() {
+    let order = Order {
+        id: 123,
+        symbol: "AAPL".to_string(),
+        price: 150.50,
+    };
+    let mut buffer = WriteBuffer::new();
+    order.serialize(&mut buffer);
+
+    let mut expected = WriteBuffer::new();
+    expected.reserve(100);
+    fbe::keys::Order::new(123, "AAPL".to_string(), 150.50).serialize(&mut expected);
+    assert_eq!(buffer.data(), expected.data());
+
+    let decoded = Order::deserialize(&ReadBuffer::from(buffer.data().to_vec()));
+    assert_eq!(decoded.symbol, "AAPL");
+    assert_eq!(*order.key(), 123);
+    assert_eq!(Order::FBE_TYPE, 10);
+    assert_eq!(<Order as fbe::Serialize>::FBE_TYPE, 10);
+
+    let other = Order {
+        price: 1.0,
+        ..order.clone()
+    };
+    assert_eq!(order, other);
+
+    let session = UserSession {
+        user_id: 7,
+        session_id: "abc".to_string(),
+        timestamp: 1,
+        ip_address: "127.0.0.1".to_string(),
+    };
+    assert_eq!(session.key(), (&7, &"abc".to_string()));
+    let mut sessions = HashSet::new();
+    sessions.insert(session.clone());
+    assert!(sessions.contains(&UserSession {
+        timestamp: 2,
+        ..session
+    }));
+}
+
+#[test]
+fn test_derive_defaults() {
+    let config = Config::default();
+    assert_eq!(config.timeout, 30);
+    assert_eq!(config.retries, 3);
+    assert_eq!(config.threshold, 0.95);
+    assert_eq!(config.ratio, 1.5);
+
+    let settings = Settings::default();
+    assert!(settings.enabled);
+    assert!(!settings.debug);
+    assert_eq!(settings.name, "DefaultName");
+    assert_eq!(settings.path, "/var/log");
+
+    let mut buffer = WriteBuffer::new();
+    settings.serialize(&mut buffer);
+    let mut expected = WriteBuffer::new();
+    expected.reserve(100);
+    fbe::defaults::Settings::default().serialize(&mut expected);
+    assert_eq!(buffer.data(), expected.data());
+}
+
+#[test]
+fn test_derive_base_struct() {
+    let employee = Employee {
+        person: Person {
+            name: "Alice".to_string(),
+            age: 30,
+        },
+        company: "Acme".to_string(),
+        salary: 100000.0,
+    };
+    assert_eq!(employee.as_ref().name, "Alice");
+
+    let mut buffer = WriteBuffer::new();
+    employee.serialize(&mut buffer);
+    let mut expected = WriteBuffer::new();
+    expected.reserve(100);
+    fbe::inheritance::Employee::new("Alice".to_string(), 30, "Acme".to_string(), 100000.0)
+        .serialize(&mut expected);
+    assert_eq!(buffer.data(), expected.data());
+
+    // Base struct prefix decodes as the base type
+    assert_eq!(Person::deserialize(&ReadBuffer::from(buffer.data().to_vec())), employee.person);
+}
+
+#[test]
+fn test_derive_enum_and_flags() {
+    assert_eq!(u8::from(Side::Sell), 2);
+    assert_eq!(Side::try_from(1u8), Ok(Side::Buy));
+    assert_eq!(Side::try_from(3u8), Err(3));
+    assert_eq!(i32::from(Status::Active), 1);
+    assert_eq!(i32::from(Status::Completed), 10);
+
+    assert_eq!(roundtrip(&Side::Sell), (Side::Sell, 1));
+    assert_eq!(roundtrip(&Status::Completed), (Status::Completed, 4));
+    let (side, _) = Side::read_final(&ReadBuffer::from(vec![9]), 0);
+    assert_eq!(side, Side::Unknown);
+
+    let mut permissions = Permissions::READ | Permissions::WRITE;
+    assert!(permissions.contains(Permissions::READ));
+    assert!(!permissions.contains(Permissions::EXECUTE));
+    permissions.remove(Permissions::WRITE);
+    permissions |= Permissions::EXECUTE;
+    assert_eq!(permissions.bits(), 0x05);
+    assert_eq!(roundtrip(&permissions), (permissions, 4));
+}
+
+#[test]
+fn test_derive_collections_roundtrip() {
+    let account = Account {
+        side: Side::Buy,
+        status: Status::Active,
+        permissions: Permissions::READ,
+        owner: Some(Person {
+            name: "Bob".to_string(),
+            age: 40,
+        }),
+        note: None,
+        levels: vec![1.5, 2.5],
+        tags: HashSet::from(["vip".to_string()]),
+        limits: BTreeMap::from([(1, "one".to_string()), (2, "two".to_string())]),
+        history: [1, 2, 3],
+    };
+    let (decoded, size) = roundtrip(&account);
+    assert_eq!(decoded, account);
+    assert_eq!(size, account.final_size());
+
+    assert_eq!(roundtrip(&Point(3, -4)), (Point(3, -4), 8));
+}
+
+#[test]
+fn test_derive_models() {
+    let person = Person {
+        name: "Carol".to_string(),
+        age: 25,
+    };
+
+    let mut buffer = WriteBuffer::new();
+    let size = PersonSizedModelMut::new(&mut buffer, 0).set(&person);
+    assert_eq!(size, 4 + 4 + 5 + 4);
+    assert_eq!(&buffer.data()[..4], &(size as u32).to_le_bytes());
+
+    let model = PersonSizedModel::new(buffer.data(), 0);
+    assert!(model.verify());
+    assert_eq!(model.size(), size);
+    assert_eq!(model.get(), person);
+
+    let mut buffer = WriteBuffer::new();
+    let size = PersonFinalModelMut::new(&mut buffer, 0).set(&person);
+    let model = PersonFinalModel::new(buffer.data(), 0);
+    assert_eq!(model.size(), size);
+    assert_eq!(model.get(), person);
+}
+
+#[test]
+fn test_derive_pointer_model_layout() {
+    let employee = Employee {
+        person: Person {
+            name: "Alice".to_string(),
+            age: 30,
+        },
+        company: "Acme".to_string(),
+        salary: 100000.0,
+    };
+
+    let mut buffer = WriteBuffer::new();
+    let size = EmployeeModelMut::new(&mut buffer, 0).set(&employee);
+    let mut expected = Vec::new();
+    expected.extend_from_slice(&53u32.to_le_bytes());
+    expected.extend_from_slice(&8u32.to_le_bytes());
+    // Struct body: size, type, base fields inline, then own fields
+    expected.extend_from_slice(&28u32.to_le_bytes());
+    expected.extend_from_slice(&2u32.to_le_bytes());
+    expected.extend_from_slice(&28u32.to_le_bytes());
+    expected.extend_from_slice(&30i32.to_le_bytes());
+    expected.extend_from_slice(&37u32.to_le_bytes());
+    expected.extend_from_slice(&100000.0f64.to_le_bytes());
+    // Strings, pointed to relative to the struct body
+    expected.extend_from_slice(&5u32.to_le_bytes());
+    expected.extend_from_slice(b"Alice");
+    expected.extend_from_slice(&4u32.to_le_bytes());
+    expected.extend_from_slice(b"Acme");
+    assert_eq!(buffer.data(), expected.as_slice());
+    assert_eq!(size, 53);
+
+    let model = EmployeeModel::new(buffer.data(), 0);
+    assert!(model.verify());
+    assert_eq!(model.size(), size);
+    assert_eq!(model.get(), employee);
+
+    // Struct type id is checked by verify
+    assert!(!PersonModel::new(buffer.data(), 0).verify());
+
+    let account = Account {
+        side: Side::Sell,
+        status: Status::Completed,
+        permissions: Permissions::WRITE,
+        owner: Some(employee.person.clone()),
+        note: Some("note".to_string()),
+        levels: vec![1.5],
+        tags: HashSet::from(["a".to_string(), "b".to_string()]),
+        limits: BTreeMap::from([(1, "one".to_string())]),
+        history: [4, 5, 6],
+    };
+    let mut buffer = WriteBuffer::new();
+    AccountModelMut::new(&mut buffer, 0).set(&account);
+    assert_eq!(AccountModel::new(buffer.data(), 0).get(), account);
+}
+
+#[test]
+fn test_derive_char_fields() {
+    let letters = Letters {
+        initial: 'é',
+        letter: 'Ω',
+    };
+    let (decoded, size) = roundtrip(&letters);
+    assert_eq!(decoded, letters);
+    assert_eq!(size, 1 + 4);
+
+    let invalid = Letters {
+        initial: 'Ω',
+        letter: 'Ω',
+    };
+    let mut buffer = WriteBuffer::new();
+    assert_eq!(invalid.try_serialize(&mut buffer), Err(CharError::NotLatin1('Ω')));
+    assert_eq!(buffer.size(), 0);
+    invalid.serialize(&mut buffer);
+    assert_eq!(buffer.data()[0], b'?');
+
+    // Nested structs are checked through their containers
+    let mut buffer = WriteBuffer::new();
+    assert_eq!(letters.try_serialize(&mut buffer), Ok(5));
+    let nested = Paragraph {
+        first: None,
+        lines: vec![letters.clone(), invalid.clone()],
+    };
+    assert_eq!(nested.try_serialize(&mut buffer), Err(CharError::NotLatin1('Ω')));
+    let nested = Paragraph {
+        first: Some(invalid),
+        lines: vec![letters.clone()],
+    };
+    assert_eq!(nested.try_serialize(&mut buffer), Err(CharError::NotLatin1('Ω')));
+
+    let mut buffer = WriteBuffer::new();
+    let size = LettersModelMut::new(&mut buffer, 0).set(&letters);
+    assert_eq!(size, 8 + 8 + 1 + 4);
+    assert_eq!(LettersModel::new(buffer.data(), 0).get(), letters);
+}
+
+#[test]
+fn test_derive_sender_receiver_traits() {
+    use fbe::{Deserialize, Serialize};
+
+    let employee = Employee::default();
+    let mut buffer = WriteBuffer::new();
+    let size = Serialize::serialize(&employee, &mut buffer);
+    assert_eq!(size, 4 + 4 + 4 + 8);
+    let decoded = <Employee as Deserialize>::deserialize(&ReadBuffer::from(buffer.data().to_vec()));
+    assert_eq!(decoded, employee);
+    assert_eq!(<Employee as Deserialize>::FBE_TYPE, 2);
+}
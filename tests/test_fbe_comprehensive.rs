@@ -11,6 +11,7 @@
 //! - Binary compatibility verification
 
 use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::{Decimal, Timestamp, Uuid};
 
 // ============================================================================
 // PRIMITIVE TYPES TESTS (14 types)
@@ -267,16 +268,16 @@ fn test_complex_uuid() {
     buffer.allocate(48);
 
     // Test UUIDs
-    let uuid1 = [0u8; 16]; // Nil UUID
-    let uuid2 = [
+    let uuid1 = Uuid::nil(); // Nil UUID
+    let uuid2 = Uuid::from_bytes([
         0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0,
         0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88,
-    ];
-    let uuid3 = [0xff; 16]; // Max UUID
+    ]);
+    let uuid3 = Uuid::from_bytes([0xff; 16]); // Max UUID
 
-    buffer.write_uuid(0, &uuid1);
-    buffer.write_uuid(16, &uuid2);
-    buffer.write_uuid(32, &uuid3);
+    buffer.write_uuid(0, uuid1);
+    buffer.write_uuid(16, uuid2);
+    buffer.write_uuid(32, uuid3);
 
     let mut read_buf = ReadBuffer::new();
     read_buf.attach_buffer(buffer.data(), 0, buffer.size());
@@ -294,9 +295,9 @@ fn test_complex_timestamp() {
     buffer.allocate(24);
 
     // Timestamps in nanoseconds since epoch
-    let ts1 = 0u64;
-    let ts2 = 1609459200000000000u64; // 2021-01-01 00:00:00 UTC
-    let ts3 = 1735689600000000000u64; // 2025-01-01 00:00:00 UTC
+    let ts1 = Timestamp::new(0);
+    let ts2 = Timestamp::new(1609459200000000000); // 2021-01-01 00:00:00 UTC
+    let ts3 = Timestamp::new(1735689600000000000); // 2025-01-01 00:00:00 UTC
 
     buffer.write_timestamp(0, ts1);
     buffer.write_timestamp(8, ts2);
@@ -354,24 +355,24 @@ fn test_complex_decimal() {
     // 123.45 = 12345 with scale 2
     // -0.001 = 1 with scale 3, negative
 
-    buffer.write_decimal(0, 12345, 2, false);  // 123.45
-    buffer.write_decimal(16, 1, 3, true);       // -0.001
-    buffer.write_decimal(32, 0, 0, false);      // 0
+    buffer.write_decimal(0, Decimal::from_parts(12345, 2, false).unwrap());  // 123.45
+    buffer.write_decimal(16, Decimal::from_parts(1, 3, true).unwrap());       // -0.001
+    buffer.write_decimal(32, Decimal::ZERO);                                  // 0
 
     let mut read_buf = ReadBuffer::new();
     read_buf.attach_buffer(buffer.data(), 0, buffer.size());
 
-    let (val1, scale1, neg1) = read_buf.read_decimal(0);
+    let (val1, scale1, neg1) = read_buf.read_decimal(0).into_parts();
     assert_eq!(val1, 12345);
     assert_eq!(scale1, 2);
     assert_eq!(neg1, false);
 
-    let (val2, scale2, neg2) = read_buf.read_decimal(16);
+    let (val2, scale2, neg2) = read_buf.read_decimal(16).into_parts();
     assert_eq!(val2, 1);
     assert_eq!(scale2, 3);
     assert_eq!(neg2, true);
 
-    let (val3, scale3, neg3) = read_buf.read_decimal(32);
+    let (val3, scale3, neg3) = read_buf.read_decimal(32).into_parts();
     assert_eq!(val3, 0);
     assert_eq!(scale3, 0);
     assert_eq!(neg3, false);
@@ -1,7 +1,8 @@
 use fbe::buffer::{ReadBuffer, WriteBuffer};
 use fbe::field_model::FieldModel;
 use fbe::field_model_collections::*;
-use std::collections::{HashMap, HashSet};
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+use fbe::collections::{HashMap, HashSet};
 
 #[test]
 fn test_field_model_vector_i32() {
@@ -38,6 +39,7 @@ fn test_field_model_array_i32() {
     assert_eq!(field2.size(), 12); // 3 × 4
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 #[test]
 fn test_field_model_map_i32() {
     let mut buffer = WriteBuffer::new();
@@ -62,6 +64,7 @@ fn test_field_model_map_i32() {
     assert_eq!(field2.extra(), 20); // size + 2 pairs
 }
 
+#[cfg(any(feature = "std", feature = "hashbrown"))]
 #[test]
 fn test_field_model_set_i32() {
     let mut buffer = WriteBuffer::new();
@@ -0,0 +1,447 @@
+//! Golden corpus tests
+//!
+//! Every case of the schema in `tests/golden/golden.fbe` is a JSON value in
+//! `tests/golden/cases/<struct>/<case>.json`. Its binary encodings live in
+//! two places:
+//!
+//! - `tests/golden/upstream/<struct>/<case>.{model,final}.bin` are written
+//!   by the upstream C++ FBE generator (`tests/golden/upstream/generate.sh`)
+//!   and never by this crate;
+//! - `tests/golden/snapshot/<struct>/<case>.{model,final,sized}.bin` are
+//!   snapshots of this crate's encoders, including the Rust-only
+//!   `SizedModel` layout.
+//!
+//! Each binary must decode to the JSON value, render back to the same JSON
+//! and re-encode byte for byte. `FBE_GOLDEN_UPDATE=1` rewrites the JSON
+//! cases and the snapshots from the values below; upstream files are only
+//! read.
+#![cfg(all(feature = "derive", feature = "json"))]
+
+use fbe::buffer::WriteBuffer;
+use fbe::field_model::FieldModel;
+use fbe::limits::{DecodeError, DecodeLimits};
+use fbe::{Decimal, FbeEnum, FbeFlags, FbeStruct, Timestamp, Uuid};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(FbeEnum, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(into = "u8", try_from = "u8")]
+#[repr(u8)]
+enum Side {
+    #[default]
+    Buy = 0,
+    Sell = 1,
+}
+
+#[derive(FbeFlags, Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[fbe(flags(HIDDEN = 0x01, LOCKED = 0x02))]
+struct State(u8);
+
+#[derive(FbeStruct, Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+struct Line {
+    quantity: i64,
+    note: Option<String>,
+}
+
+#[derive(FbeStruct, Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[fbe(id = 1)]
+struct Order {
+    id: i32,
+    symbol: String,
+    side: Side,
+    state: State,
+    price: f64,
+    total: Decimal,
+    owner: Uuid,
+    created: Timestamp,
+    #[serde(with = "fbe::json::bytes")]
+    signature: Vec<u8>,
+    parent: Option<i32>,
+    lines: Vec<Line>,
+    fills: BTreeMap<String, i64>,
+}
+
+#[derive(FbeStruct, Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[fbe(id = 2)]
+struct Scalars {
+    flag: bool,
+    raw: u8,
+    #[fbe(char)]
+    #[serde(with = "fbe::json::char")]
+    letter: char,
+    #[serde(with = "fbe::json::wchar")]
+    symbol: char,
+    i8: i8,
+    u8: u8,
+    i16: i16,
+    u16: u16,
+    i32: i32,
+    u32: u32,
+    i64: i64,
+    u64: u64,
+    f32: f32,
+    f64: f64,
+    amount: Decimal,
+    text: String,
+    #[serde(with = "fbe::json::bytes")]
+    data: Vec<u8>,
+    time: Timestamp,
+    id: Uuid,
+    side: Side,
+    state: State,
+}
+
+#[derive(FbeStruct, Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[fbe(id = 3)]
+struct Optionals {
+    flag: Option<bool>,
+    raw: Option<u8>,
+    #[serde(with = "fbe::json::wchar::option")]
+    symbol: Option<char>,
+    i8: Option<i8>,
+    u16: Option<u16>,
+    i32: Option<i32>,
+    u64: Option<u64>,
+    f32: Option<f32>,
+    f64: Option<f64>,
+    amount: Option<Decimal>,
+    text: Option<String>,
+    #[serde(with = "fbe::json::bytes::option")]
+    data: Option<Vec<u8>>,
+    time: Option<Timestamp>,
+    id: Option<Uuid>,
+    side: Option<Side>,
+    state: Option<State>,
+    line: Option<Line>,
+}
+
+#[derive(FbeStruct, Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[fbe(id = 4)]
+struct Collections {
+    array: [i16; 3],
+    lines: [Line; 2],
+    slots: [Option<i32>; 2],
+    vector: Vec<f64>,
+    #[serde(with = "fbe::json::bytes::vec")]
+    chunks: Vec<Vec<u8>>,
+    list: Vec<String>,
+    sides: BTreeSet<Side>,
+    owners: BTreeSet<Uuid>,
+    names: BTreeMap<i32, String>,
+    book: BTreeMap<String, Line>,
+    prices: HashMap<String, Decimal>,
+    index: HashMap<i32, Line>,
+}
+
+/// Layout name with its decoder and encoder
+type Layout<T> = (&'static str, fn(&[u8]) -> Result<T, DecodeError>, fn(&T) -> Vec<u8>);
+
+/// Struct with golden cases
+trait Golden: Sized + PartialEq + Debug + Serialize + DeserializeOwned {
+    /// Directory of the struct's cases and binaries
+    const DIR: &'static str;
+
+    /// Values written by `FBE_GOLDEN_UPDATE=1`
+    fn cases() -> Vec<(&'static str, Self)>;
+
+    fn decode_model(data: &[u8]) -> Result<Self, DecodeError>;
+    fn decode_final(data: &[u8]) -> Result<Self, DecodeError>;
+    fn decode_sized(data: &[u8]) -> Result<Self, DecodeError>;
+    fn encode_model(&self) -> Vec<u8>;
+    fn encode_final(&self) -> Vec<u8>;
+    fn encode_sized(&self) -> Vec<u8>;
+
+    /// Upstream layouts first, then the Rust-only `SizedModel`
+    fn layouts() -> [Layout<Self>; 3] {
+        [
+            ("model", Self::decode_model, Self::encode_model),
+            ("final", Self::decode_final, Self::encode_final),
+            ("sized", Self::decode_sized, Self::encode_sized),
+        ]
+    }
+}
+
+macro_rules! impl_golden {
+    ($type:ty, $dir:expr, $model:ident, $model_mut:ident, $sized_model:ident, $sized_model_mut:ident, $final_model:ident, $final_model_mut:ident, $cases:expr) => {
+        impl Golden for $type {
+            const DIR: &'static str = $dir;
+
+            fn cases() -> Vec<(&'static str, Self)> {
+                $cases
+            }
+
+            fn decode_model(data: &[u8]) -> Result<Self, DecodeError> {
+                let model = $model::new(data, 0);
+                assert!(model.verify(), "Model verify");
+                model.try_get(&DecodeLimits::default())
+            }
+
+            fn decode_final(data: &[u8]) -> Result<Self, DecodeError> {
+                $final_model::new(data, 0).try_get(&DecodeLimits::default())
+            }
+
+            fn decode_sized(data: &[u8]) -> Result<Self, DecodeError> {
+                $sized_model::new(data, 0).try_get(&DecodeLimits::default())
+            }
+
+            fn encode_model(&self) -> Vec<u8> {
+                let mut buffer = WriteBuffer::new();
+                let size = $model_mut::new(&mut buffer, 0).set(self);
+                buffer.data()[..size].to_vec()
+            }
+
+            fn encode_final(&self) -> Vec<u8> {
+                let mut buffer = WriteBuffer::new();
+                let size = $final_model_mut::new(&mut buffer, 0).set(self);
+                buffer.data()[..size].to_vec()
+            }
+
+            fn encode_sized(&self) -> Vec<u8> {
+                let mut buffer = WriteBuffer::new();
+                let size = $sized_model_mut::new(&mut buffer, 0).set(self);
+                buffer.data()[..size].to_vec()
+            }
+        }
+    };
+}
+
+fn owner() -> Uuid {
+    "123e4567-e89b-12d3-a456-426655440000".parse().unwrap()
+}
+
+fn line_cases() -> Vec<(&'static str, Line)> {
+    vec![
+        ("note", Line { quantity: 250, note: Some("partial fill".to_string()) }),
+        ("no_note", Line { quantity: -1, note: None }),
+    ]
+}
+
+fn order_cases() -> Vec<(&'static str, Order)> {
+    let full = Order {
+        id: 12345,
+        symbol: "EUR/USD €".to_string(),
+        side: Side::Sell,
+        state: State::HIDDEN | State::LOCKED,
+        price: 1.0875,
+        total: "-123456.789".parse().unwrap(),
+        owner: owner(),
+        created: Timestamp::new(1729526400000000000),
+        signature: b"Binary\x00\xFF".to_vec(),
+        parent: Some(42),
+        lines: vec![
+            Line { quantity: 100, note: None },
+            Line { quantity: 250, note: Some("partial fill".to_string()) },
+        ],
+        fills: BTreeMap::from([("ecn".to_string(), 100), ("lit".to_string(), 250)]),
+    };
+    vec![("empty", Order::default()), ("full", full)]
+}
+
+fn scalars_cases() -> Vec<(&'static str, Scalars)> {
+    let extremes = Scalars {
+        flag: true,
+        raw: 0xFE,
+        letter: 'é',
+        symbol: 'Ω',
+        i8: i8::MIN,
+        u8: u8::MAX,
+        i16: i16::MIN,
+        u16: u16::MAX,
+        i32: i32::MIN,
+        u32: u32::MAX,
+        i64: i64::MIN,
+        u64: u64::MAX,
+        f32: -1.5,
+        f64: 1.0875,
+        amount: "79228162514264337593543950335".parse().unwrap(),
+        text: "FBE ✓".to_string(),
+        data: vec![0x00, 0x01, 0xFF],
+        time: Timestamp::new(1729526400123456789),
+        id: owner(),
+        side: Side::Sell,
+        state: State::LOCKED,
+    };
+    vec![("default", Scalars::default()), ("extremes", extremes)]
+}
+
+fn optionals_cases() -> Vec<(&'static str, Optionals)> {
+    let some = Optionals {
+        flag: Some(false),
+        raw: Some(0),
+        symbol: Some('€'),
+        i8: Some(-1),
+        u16: Some(443),
+        i32: Some(0),
+        u64: Some(1 << 40),
+        f32: Some(0.25),
+        f64: Some(-0.0),
+        amount: Some("0.001".parse().unwrap()),
+        text: Some(String::new()),
+        data: Some(Vec::new()),
+        time: Some(Timestamp::new(1)),
+        id: Some(Uuid::nil()),
+        side: Some(Side::Buy),
+        state: Some(State::HIDDEN),
+        line: Some(Line { quantity: 7, note: Some("nested".to_string()) }),
+    };
+    vec![("none", Optionals::default()), ("some", some)]
+}
+
+fn collections_cases() -> Vec<(&'static str, Collections)> {
+    let full = Collections {
+        array: [1, -2, 3],
+        lines: [Line { quantity: 1, note: None }, Line { quantity: 2, note: Some("two".to_string()) }],
+        slots: [Some(7), None],
+        vector: vec![0.5, -2.25],
+        chunks: vec![b"FBE".to_vec(), Vec::new()],
+        list: vec!["first".to_string(), "second".to_string()],
+        sides: BTreeSet::from([Side::Buy, Side::Sell]),
+        owners: BTreeSet::from([Uuid::nil(), owner()]),
+        names: BTreeMap::from([(-2, "minus two".to_string()), (1, "one".to_string())]),
+        book: BTreeMap::from([("ecn".to_string(), Line { quantity: 100, note: None })]),
+        // Hash collections hold one entry so their encoding does not depend on iteration order
+        prices: HashMap::from([("EUR/USD".to_string(), "1.0875".parse().unwrap())]),
+        index: HashMap::from([(7, Line { quantity: -7, note: Some("seven".to_string()) })]),
+    };
+    vec![("empty", Collections::default()), ("full", full)]
+}
+
+impl_golden!(Line, "line", LineModel, LineModelMut, LineSizedModel, LineSizedModelMut, LineFinalModel, LineFinalModelMut, line_cases());
+impl_golden!(Order, "order", OrderModel, OrderModelMut, OrderSizedModel, OrderSizedModelMut, OrderFinalModel, OrderFinalModelMut, order_cases());
+impl_golden!(Scalars, "scalars", ScalarsModel, ScalarsModelMut, ScalarsSizedModel, ScalarsSizedModelMut, ScalarsFinalModel, ScalarsFinalModelMut, scalars_cases());
+impl_golden!(Optionals, "optionals", OptionalsModel, OptionalsModelMut, OptionalsSizedModel, OptionalsSizedModelMut, OptionalsFinalModel, OptionalsFinalModelMut, optionals_cases());
+impl_golden!(Collections, "collections", CollectionsModel, CollectionsModelMut, CollectionsSizedModel, CollectionsSizedModelMut, CollectionsFinalModel, CollectionsFinalModelMut, collections_cases());
+
+/// `tests/golden/<kind>/<struct>`
+fn golden_dir<T: Golden>(kind: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden").join(kind).join(T::DIR)
+}
+
+/// Rewrite the JSON cases and snapshots of `T`
+fn update<T: Golden>() {
+    let cases = golden_dir::<T>("cases");
+    let snapshot = golden_dir::<T>("snapshot");
+    fs::create_dir_all(&cases).unwrap();
+    fs::create_dir_all(&snapshot).unwrap();
+    for (name, value) in T::cases() {
+        let json = serde_json::to_string_pretty(&value).unwrap();
+        fs::write(cases.join(format!("{}.json", name)), json + "\n").unwrap();
+        for (layout, _, encode) in T::layouts() {
+            fs::write(snapshot.join(format!("{}.{}.bin", name, layout)), encode(&value)).unwrap();
+        }
+    }
+}
+
+/// Check the `layouts` binaries in `tests/golden/<kind>` of every JSON case, return the number of cases
+fn check<T: Golden>(kind: &str, layouts: &[Layout<T>]) -> usize {
+    let dir = golden_dir::<T>(kind);
+    let mut cases = 0;
+    for entry in fs::read_dir(golden_dir::<T>("cases")).unwrap() {
+        let path = entry.unwrap().path();
+        let Some(name) = path.file_name().unwrap().to_str().unwrap().strip_suffix(".json") else {
+            continue;
+        };
+
+        let text = fs::read_to_string(&path).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+        let expected: T = fbe::json::from_json(&text).unwrap();
+
+        for (layout, decode, encode) in layouts {
+            let file = dir.join(format!("{}.{}.bin", name, layout));
+            let data = fs::read(&file).unwrap_or_else(|err| panic!("{}: {}", file.display(), err));
+
+            let decoded = decode(&data).unwrap_or_else(|err| panic!("{}: {}", file.display(), err));
+            assert_eq!(decoded, expected, "{} does not decode to {}.json", file.display(), name);
+            assert_eq!(serde_json::to_value(&decoded).unwrap(), json, "{} renders different JSON", file.display());
+            assert_eq!(encode(&decoded), data, "{} does not re-encode byte for byte", file.display());
+        }
+        cases += 1;
+    }
+    cases
+}
+
+/// Check the snapshots of `T`, rewriting them first with `FBE_GOLDEN_UPDATE=1`
+fn check_snapshot<T: Golden>() {
+    if std::env::var_os("FBE_GOLDEN_UPDATE").is_some() {
+        update::<T>();
+    }
+    assert!(check::<T>("snapshot", &T::layouts()) >= 2, "missing golden {} cases", T::DIR);
+}
+
+/// Check the upstream Model and FinalModel files of `T`
+fn check_upstream<T: Golden>() {
+    assert!(check::<T>("upstream", &T::layouts()[..2]) >= 2, "missing golden {} cases", T::DIR);
+}
+
+#[test]
+fn test_golden_snapshot_line() {
+    check_snapshot::<Line>();
+}
+
+#[test]
+fn test_golden_snapshot_order() {
+    check_snapshot::<Order>();
+}
+
+#[test]
+fn test_golden_snapshot_scalars() {
+    check_snapshot::<Scalars>();
+}
+
+#[test]
+fn test_golden_snapshot_optionals() {
+    check_snapshot::<Optionals>();
+}
+
+#[test]
+fn test_golden_snapshot_collections() {
+    check_snapshot::<Collections>();
+}
+
+#[test]
+#[ignore = "needs tests/golden/upstream generated by upstream fbec (tests/golden/upstream/generate.sh)"]
+fn test_golden_upstream() {
+    check_upstream::<Line>();
+    check_upstream::<Order>();
+    check_upstream::<Scalars>();
+    check_upstream::<Optionals>();
+    check_upstream::<Collections>();
+}
+
+#[test]
+fn test_golden_sized_header() {
+    // Sized files are the FinalModel data behind a 4-byte size header (header included)
+    let dir = golden_dir::<Order>("snapshot");
+    let sized = fs::read(dir.join("full.sized.bin")).unwrap();
+    let final_data = fs::read(dir.join("full.final.bin")).unwrap();
+    assert_eq!(sized[..4], (sized.len() as u32).to_le_bytes());
+    assert_eq!(sized[4..], final_data[..]);
+
+    // FinalModel fields are inline in schema order
+    assert_eq!(final_data[..4], 12345i32.to_le_bytes(), "id");
+    assert_eq!(final_data[4..8], 11u32.to_le_bytes(), "symbol size");
+    assert_eq!(&final_data[8..19], "EUR/USD €".as_bytes(), "symbol");
+    assert_eq!(final_data[19..21], [1, 0x03], "side, state");
+}
+
+#[test]
+fn test_golden_model_header() {
+    // Model files are the full size and a pointer to the struct body
+    let model = fs::read(golden_dir::<Order>("snapshot").join("full.model.bin")).unwrap();
+    assert_eq!(model[..4], (model.len() as u32).to_le_bytes());
+    assert_eq!(model[4..8], 8u32.to_le_bytes(), "struct pointer");
+
+    // Struct body: size, type id, then fixed-size field slots with pointers relative to the body
+    let fields_size = 4 + 4 + 1 + 1 + 8 + 16 + 16 + 8 + 4 + 5 + 4 + 4;
+    assert_eq!(model[8..12], ((8 + fields_size) as u32).to_le_bytes(), "body size");
+    assert_eq!(model[12..16], 1u32.to_le_bytes(), "type id");
+    assert_eq!(model[16..20], 12345i32.to_le_bytes(), "id");
+    let symbol = 8 + u32::from_le_bytes(model[20..24].try_into().unwrap()) as usize;
+    assert_eq!(model[symbol..symbol + 4], 11u32.to_le_bytes(), "symbol size");
+    assert_eq!(&model[symbol + 4..symbol + 15], "EUR/USD €".as_bytes(), "symbol");
+    assert_eq!(model[24..26], [1, 0x03], "side, state");
+}
@@ -0,0 +1,163 @@
+//! FBE JSON convention tests
+#![cfg(feature = "json")]
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Enum with the serde impls fbec generates (underlying value as number)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+enum Side {
+    Buy = 1,
+    Sell = 2,
+}
+
+impl Serialize for Side {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Serialize::serialize(&(*self as u8), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Side {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <u8 as Deserialize>::deserialize(deserializer)?;
+        match value {
+            v if v == Self::Buy as u8 => Ok(Self::Buy),
+            v if v == Self::Sell as u8 => Ok(Self::Sell),
+            _ => Err(serde::de::Error::custom(format!("invalid Side value: {}", value))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Order {
+    id: i32,
+    side: Side,
+    #[serde(with = "fbe::json::wchar")]
+    letter: char,
+    #[serde(with = "fbe::json::bytes")]
+    data: Vec<u8>,
+    price: fbe::Decimal,
+    created: u64,
+    #[serde(with = "fbe::json::uuid")]
+    uid: [u8; 16],
+    #[serde(with = "fbe::json::uuid::option")]
+    parent: Option<[u8; 16]>,
+    #[serde(with = "fbe::json::bytes::vec")]
+    chunks: Vec<Vec<u8>>,
+    sides: BTreeSet<Side>,
+    levels: BTreeMap<i32, f64>,
+    tags: BTreeMap<Side, String>,
+    note: Option<String>,
+}
+
+fn order() -> Order {
+    Order {
+        id: 7,
+        side: Side::Sell,
+        letter: 'Z',
+        data: vec![0xFB, 0xE0, 0x01],
+        price: fbe::Decimal::try_new(-123456, 3).unwrap(),
+        created: 1_700_000_000_123_456_789,
+        uid: [
+            0x12, 0x3e, 0x45, 0x67, 0xe8, 0x9b, 0x12, 0xd3, 0xa4, 0x56, 0x42, 0x66, 0x55, 0x44, 0x00,
+            0x00,
+        ],
+        parent: None,
+        chunks: vec![b"ab".to_vec(), Vec::new()],
+        sides: [Side::Buy, Side::Sell].into_iter().collect(),
+        levels: [(1, 1.5), (-2, 0.25)].into_iter().collect(),
+        tags: [(Side::Buy, "bid".to_string())].into_iter().collect(),
+        note: Some("hi".to_string()),
+    }
+}
+
+#[test]
+fn test_json_conventions() {
+    let json = fbe::json::to_json(&order()).unwrap();
+    assert_eq!(
+        json,
+        concat!(
+            r#"{"id":7,"side":2,"letter":90,"data":"++AB","price":"-123.456","#,
+            r#""created":1700000000123456789,"uid":"123e4567-e89b-12d3-a456-426655440000","#,
+            r#""parent":null,"chunks":["YWI=",""],"sides":[1,2],"#,
+            r#""levels":{"-2":0.25,"1":1.5},"tags":{"1":"bid"},"note":"hi"}"#
+        )
+    );
+}
+
+#[test]
+fn test_json_round_trip() {
+    let mut value = order();
+    value.parent = Some([0xAB; 16]);
+    let json = fbe::json::to_json(&value).unwrap();
+    let decoded: Order = fbe::json::from_json(&json).unwrap();
+    assert_eq!(decoded, value);
+}
+
+#[test]
+fn test_json_errors() {
+    let json = fbe::json::to_json(&order()).unwrap();
+
+    let err = fbe::json::from_json::<Order>(&json.replace("\"side\":2", "\"side\":9")).unwrap_err();
+    assert!(err.to_string().contains("invalid Side value: 9"), "{}", err);
+
+    let err = fbe::json::from_json::<Order>(&json.replace("\"letter\":90", "\"letter\":55296")).unwrap_err();
+    assert!(err.to_string().contains("invalid wchar: 55296"), "{}", err);
+
+    let err = fbe::json::from_json::<Order>(&json.replace("++AB", "++A")).unwrap_err();
+    assert!(err.to_string().contains("invalid base64"), "{}", err);
+
+    let err = fbe::json::from_json::<Order>(&json.replace("-123.456", "1e3")).unwrap_err();
+    assert!(err.to_string().contains("Invalid decimal"), "{}", err);
+
+    let err = fbe::json::from_json::<Order>(&json.replace("426655440000", "42665544000")).unwrap_err();
+    assert!(err.to_string().contains("invalid uuid"), "{}", err);
+
+    let err = fbe::json::from_json::<Order>("{\"id\":").unwrap_err();
+    assert_eq!(err.line(), 1);
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Letter {
+    #[serde(with = "fbe::json::char")]
+    value: char,
+}
+
+#[test]
+fn test_json_char() {
+    for value in ['A', '\0', 'é', '\u{FF}'] {
+        let json = fbe::json::to_json(&Letter { value }).unwrap();
+        assert_eq!(json, format!("{{\"value\":{}}}", u32::from(value)));
+        assert_eq!(fbe::json::from_json::<Letter>(&json).unwrap(), Letter { value });
+    }
+
+    // Characters outside Latin-1 are rejected on both sides
+    let err = fbe::json::to_json(&Letter { value: '€' }).unwrap_err();
+    assert!(err.to_string().contains("is not Latin-1"), "{}", err);
+    let err = fbe::json::from_json::<Letter>("{\"value\":8364}").unwrap_err();
+    assert!(err.to_string().contains("invalid char: 8364"), "{}", err);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Chunks {
+    #[serde(with = "fbe::json::bytes::option_vec")]
+    chunks: Option<Vec<Vec<u8>>>,
+}
+
+#[test]
+fn test_json_option_vec() {
+    for (value, text) in [
+        (Some(vec![b"FBE".to_vec(), Vec::new()]), r#"{"chunks":["RkJF",""]}"#),
+        (Some(Vec::new()), r#"{"chunks":[]}"#),
+        (None, r#"{"chunks":null}"#),
+    ] {
+        let json = fbe::json::to_json(&Chunks { chunks: value.clone() }).unwrap();
+        assert_eq!(json, text);
+        assert_eq!(fbe::json::from_json::<Chunks>(&json).unwrap(), Chunks { chunks: value });
+    }
+
+    let err = fbe::json::from_json::<Chunks>(r#"{"chunks":[[1,2]]}"#).unwrap_err();
+    assert!(err.to_string().contains("invalid type"), "{}", err);
+}
@@ -0,0 +1,130 @@
+//! FBE runtime without `std`
+//!
+//! Run with `cargo test --no-default-features --test test_no_std` (add
+//! `hashbrown` for the hash collection tests and `json` for the serde and
+//! JSON tests) to check the
+//! `alloc`-only configuration used on embedded targets such as
+//! `thumbv7em-none-eabi`. The test crate itself is `no_std` and only uses
+//! `core` and `alloc`, so it keeps compiling only while that API is complete.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+use fbe::collections::{HashMap, HashSet};
+use fbe::field_model::{FieldModel, FieldModelString, FieldModelStringMut};
+use fbe::final_model::FinalValue;
+use fbe::{Decimal, Timestamp, Uuid};
+
+#[test]
+fn test_no_std_buffers() {
+    let mut writer = WriteBuffer::growable();
+    writer.write_i32(0, -42);
+    writer.write_string(4, "embedded");
+    writer.write_bytes(16, &[1, 2, 3]);
+
+    let reader = ReadBuffer::borrowed(writer.data());
+    assert_eq!(reader.read_i32(0), -42);
+    assert_eq!(reader.read_string(4), "embedded");
+    assert_eq!(reader.read_bytes(16), [1, 2, 3]);
+}
+
+#[test]
+fn test_no_std_field_model() {
+    let mut writer = WriteBuffer::new();
+    writer.allocate(4 + 7);
+    FieldModelStringMut::new(&mut writer, 0).set("gateway");
+    let field = FieldModelString::new(writer.data(), 0);
+    assert_eq!(field.get(), "gateway");
+    assert_eq!(field.size(), 4);
+}
+
+#[test]
+fn test_no_std_final_values() {
+    let value = (
+        vec![String::from("a"), String::from("b")],
+        Some(Decimal::from(-7)),
+    );
+
+    let mut writer = WriteBuffer::growable();
+    let size = value.0.write_final(&mut writer, 0);
+    let size = size + value.1.write_final(&mut writer, size);
+    assert_eq!(size, writer.size());
+
+    let reader = ReadBuffer::from(writer.data().to_vec());
+    let (strings, offset) = Vec::<String>::read_final(&reader, 0);
+    let (decimal, _) = Option::<Decimal>::read_final(&reader, offset);
+    assert_eq!((strings, decimal), value);
+}
+
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+#[test]
+fn test_no_std_final_collections() {
+    let mut map = HashMap::new();
+    map.insert(1u32, String::from("one"));
+    let value = (map, HashSet::from([Uuid::nil()]));
+
+    let mut writer = WriteBuffer::growable();
+    let size = value.0.write_final(&mut writer, 0);
+    let size = size + value.1.write_final(&mut writer, size);
+    assert_eq!(size, writer.size());
+
+    let reader = ReadBuffer::from(writer.data().to_vec());
+    let (map, size) = HashMap::<u32, String>::read_final(&reader, 0);
+    let (set, _) = HashSet::<Uuid>::read_final(&reader, size);
+    assert_eq!((map, set), value);
+}
+
+#[test]
+fn test_no_std_types() {
+    let price: Decimal = "-12.50".parse().unwrap();
+    assert_eq!(price.to_string(), "-12.50");
+    assert_eq!(price.to_f64(), -12.5);
+    assert_eq!(
+        Timestamp::new(1_000_000).to_string(),
+        "1970-01-01T00:00:00.001Z"
+    );
+    let uuid: Uuid = "123e4567-e89b-12d3-a456-426655440000".parse().unwrap();
+    assert_eq!(uuid.to_string(), "123e4567-e89b-12d3-a456-426655440000");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_no_std_serde() {
+    let value = (7u16, String::from("frame"), vec![1u8, 2, 3]);
+    let data = fbe::serde::to_vec(&value).unwrap();
+    assert_eq!(fbe::serde::from_slice::<(u16, String, Vec<u8>)>(&data).unwrap(), value);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_no_std_json() {
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Blob {
+        #[serde(with = "fbe::json::bytes")]
+        data: Vec<u8>,
+        #[serde(with = "fbe::json::uuid::option")]
+        owner: Option<[u8; 16]>,
+        #[serde(with = "fbe::json::char::vec")]
+        codes: Vec<char>,
+        price: Decimal,
+    }
+
+    let blob = Blob {
+        data: b"FBE".to_vec(),
+        owner: Some(Uuid::nil().into_bytes()),
+        codes: vec!['A', 'é'],
+        price: "1.5".parse().unwrap(),
+    };
+    let json = fbe::json::to_json(&blob).unwrap();
+    assert_eq!(
+        json,
+        r#"{"data":"RkJF","owner":"00000000-0000-0000-0000-000000000000","codes":[65,233],"price":"1.5"}"#
+    );
+    assert_eq!(fbe::json::from_json::<Blob>(&json).unwrap(), blob);
+    assert!(fbe::json::from_json::<Blob>(&json.replace("RkJF", "!")).unwrap_err().to_string().contains("base64"));
+}
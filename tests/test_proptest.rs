@@ -0,0 +1,235 @@
+//! Property-based round-trip tests
+#![cfg(feature = "proptest")]
+
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::field_model::FieldValue;
+use fbe::final_model::FinalValue;
+use fbe::limits::{DecodeBudget, DecodeError};
+use fbe::proptest::assert_roundtrip;
+use fbe::{Decimal, Timestamp, Uuid};
+use proptest::prelude::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+proptest! {
+    #[test]
+    fn test_primitives_roundtrip(a: bool, b: i8, c: u16, d: i32, e: u64, f: f32, g: f64) {
+        assert_roundtrip(&a);
+        assert_roundtrip(&b);
+        assert_roundtrip(&c);
+        assert_roundtrip(&d);
+        assert_roundtrip(&e);
+        assert_roundtrip(&f);
+        assert_roundtrip(&g);
+    }
+
+    #[test]
+    fn test_value_types_roundtrip(decimal: Decimal, timestamp: Timestamp, uuid: Uuid) {
+        assert_roundtrip(&decimal);
+        assert_roundtrip(&timestamp);
+        assert_roundtrip(&uuid);
+        prop_assert!(decimal.scale() <= fbe::decimal::MAX_SCALE);
+        prop_assert_eq!(Decimal::from_bytes(decimal.to_bytes()).unwrap(), decimal);
+    }
+
+    #[test]
+    fn test_collections_roundtrip(
+        strings: Vec<String>,
+        bytes: Vec<u8>,
+        optionals: Vec<Option<i64>>,
+        array: [Option<String>; 3],
+        map: HashMap<u32, String>,
+        tree: BTreeMap<String, Vec<Uuid>>,
+        set: BTreeSet<i16>,
+    ) {
+        assert_roundtrip(&strings);
+        assert_roundtrip(&bytes);
+        assert_roundtrip(&optionals);
+        assert_roundtrip(&array);
+        assert_roundtrip(&map);
+        assert_roundtrip(&tree);
+        assert_roundtrip(&set);
+    }
+}
+
+/// Value whose `final_size` under-reports its written size
+#[derive(Debug, PartialEq)]
+struct ShortSize(u32);
+
+impl FinalValue for ShortSize {
+    fn final_size(&self) -> usize {
+        2
+    }
+
+    fn write_final(&self, buffer: &mut WriteBuffer, offset: usize) -> usize {
+        buffer.write_u32(offset, self.0);
+        4
+    }
+
+    fn read_final(buffer: &ReadBuffer, offset: usize) -> (Self, usize) {
+        (Self(buffer.read_u32(offset)), 4)
+    }
+}
+
+impl FieldValue for ShortSize {
+    const FBE_SIZE: usize = 4;
+
+    fn fbe_extra(&self) -> usize {
+        0
+    }
+
+    fn write_field(&self, buffer: &mut WriteBuffer, _base: usize, offset: usize) {
+        buffer.write_u32(offset, self.0);
+    }
+
+    fn read_field(buffer: &ReadBuffer, _base: usize, offset: usize) -> Self {
+        Self(buffer.read_u32(offset))
+    }
+
+    fn try_read_field(buffer: &ReadBuffer, _base: usize, offset: usize, _budget: &mut DecodeBudget) -> Result<Self, DecodeError> {
+        buffer.check_bounds(offset, 4)?;
+        Ok(Self(buffer.read_u32(offset)))
+    }
+}
+
+#[test]
+#[should_panic(expected = "FinalModel size")]
+fn test_roundtrip_detects_size_mismatch() {
+    assert_roundtrip(&ShortSize(7));
+}
+
+/// Value whose `fbe_extra` under-reports its pointed-to data
+#[derive(Debug, PartialEq)]
+struct ShortExtra(String);
+
+impl FinalValue for ShortExtra {
+    fn final_size(&self) -> usize {
+        self.0.final_size()
+    }
+
+    fn write_final(&self, buffer: &mut WriteBuffer, offset: usize) -> usize {
+        self.0.write_final(buffer, offset)
+    }
+
+    fn read_final(buffer: &ReadBuffer, offset: usize) -> (Self, usize) {
+        let (value, size) = String::read_final(buffer, offset);
+        (Self(value), size)
+    }
+
+    fn read_final_size(buffer: &ReadBuffer, offset: usize) -> usize {
+        String::read_final_size(buffer, offset)
+    }
+
+    fn try_read_final(buffer: &ReadBuffer, offset: usize, budget: &mut DecodeBudget) -> Result<(Self, usize), DecodeError> {
+        let (value, size) = String::try_read_final(buffer, offset, budget)?;
+        Ok((Self(value), size))
+    }
+}
+
+impl FieldValue for ShortExtra {
+    const FBE_SIZE: usize = 4;
+
+    fn fbe_extra(&self) -> usize {
+        0
+    }
+
+    fn write_field(&self, buffer: &mut WriteBuffer, base: usize, offset: usize) {
+        self.0.write_field(buffer, base, offset);
+    }
+
+    fn read_field(buffer: &ReadBuffer, base: usize, offset: usize) -> Self {
+        Self(String::read_field(buffer, base, offset))
+    }
+
+    fn try_read_field(buffer: &ReadBuffer, base: usize, offset: usize, budget: &mut DecodeBudget) -> Result<Self, DecodeError> {
+        String::try_read_field(buffer, base, offset, budget).map(Self)
+    }
+}
+
+#[test]
+#[should_panic(expected = "Model field size")]
+fn test_roundtrip_detects_model_size_mismatch() {
+    assert_roundtrip(&ShortExtra("pointed-to".to_string()));
+}
+
+#[cfg(feature = "json")]
+mod json {
+    use fbe::proptest::assert_json_roundtrip;
+    use fbe::{Decimal, Timestamp, Uuid};
+    use proptest::prelude::*;
+    use std::collections::BTreeMap;
+
+    proptest! {
+        #[test]
+        fn test_json_roundtrip(
+            decimal: Decimal,
+            timestamp: Timestamp,
+            uuid: Uuid,
+            value: f64,
+            map: BTreeMap<i32, Option<String>>,
+        ) {
+            assert_json_roundtrip(&decimal);
+            assert_json_roundtrip(&timestamp);
+            assert_json_roundtrip(&uuid);
+            assert_json_roundtrip(&value);
+            assert_json_roundtrip(&map);
+        }
+    }
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use fbe::proptest::{assert_message_roundtrip, assert_roundtrip};
+    use fbe::{Decimal, FbeEnum, FbeFlags, FbeStruct, Uuid};
+    use proptest::prelude::*;
+    use std::collections::HashMap;
+
+    #[derive(FbeEnum, Debug, Clone, Copy, PartialEq)]
+    #[repr(u8)]
+    enum Side {
+        Buy = 1,
+        Sell = 2,
+    }
+
+    #[derive(FbeFlags, Debug, Clone, Copy, PartialEq)]
+    #[fbe(flags(HIDDEN = 0x01, LOCKED = 0x02))]
+    struct State(u32);
+
+    #[derive(FbeStruct, Debug, Clone, PartialEq)]
+    struct Line {
+        quantity: i64,
+        note: Option<String>,
+    }
+
+    #[derive(FbeStruct, Debug, Clone, PartialEq)]
+    #[fbe(id = 5)]
+    struct Order {
+        id: u32,
+        owner: Uuid,
+        side: Side,
+        state: State,
+        price: Decimal,
+        fills: HashMap<u32, i64>,
+        lines: Vec<Line>,
+    }
+
+    #[derive(FbeStruct, Debug, Clone, PartialEq)]
+    struct Pair(i32, String);
+
+    #[derive(FbeStruct, Debug, Clone, PartialEq)]
+    struct Empty;
+
+    proptest! {
+        #[test]
+        fn test_derive_roundtrip(order: Order, pair: Pair, empty: Empty) {
+            assert_roundtrip(&order);
+            assert_roundtrip(&pair);
+            assert_roundtrip(&empty);
+            assert_message_roundtrip(&order);
+        }
+
+        #[test]
+        fn test_derive_enum_variants(sides in proptest::collection::vec(any::<Side>(), 64)) {
+            prop_assert!(sides.iter().all(|side| matches!(side, Side::Buy | Side::Sell)));
+        }
+    }
+}
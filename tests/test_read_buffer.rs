@@ -0,0 +1,71 @@
+//! FBE read buffer storage tests
+
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::final_model::{FinalModelI64, FinalValue};
+use std::sync::Arc;
+
+fn records() -> Vec<String> {
+    (0..1000).map(|index| format!("record {}", index)).collect()
+}
+
+fn write_records() -> WriteBuffer {
+    let records = records();
+    let mut writer = WriteBuffer::new();
+    writer.allocate(8 + records.final_size());
+    writer.write_i64(0, 0x0FBE);
+    records.write_final(&mut writer, 8);
+    writer
+}
+
+fn check_records(reader: &ReadBuffer) {
+    assert_eq!(FinalModelI64::new(reader.data(), 0).get(), 0x0FBE);
+    let (decoded, size) = Vec::<String>::read_final(reader, 8);
+    assert_eq!(decoded, records());
+    assert_eq!(8 + size, reader.size());
+}
+
+#[test]
+fn test_read_buffer_borrowed() {
+    let writer = write_records();
+    let reader = ReadBuffer::borrowed(writer.data());
+    assert_eq!(reader.data().as_ptr(), writer.data().as_ptr());
+    check_records(&reader);
+
+    let mut reader = ReadBuffer::new();
+    reader.attach_buffer(writer.data(), 0, writer.size());
+    assert_eq!(reader.data().as_ptr(), writer.data().as_ptr());
+    check_records(&reader);
+
+    // Owned copy outlives the input
+    let owned = {
+        let writer = write_records();
+        ReadBuffer::from(writer.data()).into_owned()
+    };
+    check_records(&owned);
+}
+
+#[test]
+fn test_read_buffer_shared() {
+    let data = Arc::new(write_records().data().to_vec());
+    let reader = ReadBuffer::from_shared(Arc::clone(&data));
+    assert_eq!(reader.data().as_ptr(), data.as_ptr());
+    check_records(&reader);
+
+    let clone = reader.clone();
+    assert_eq!(clone.data().as_ptr(), data.as_ptr());
+    assert_eq!(Arc::strong_count(&data), 3);
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_read_buffer_map_file() {
+    let path = std::env::temp_dir().join(format!("fbe_map_file_{}.bin", std::process::id()));
+    std::fs::write(&path, write_records().data()).unwrap();
+
+    let reader = ReadBuffer::map_file(&path).unwrap();
+    check_records(&reader);
+    drop(reader);
+
+    std::fs::remove_file(&path).unwrap();
+    assert!(ReadBuffer::map_file(&path).is_err());
+}
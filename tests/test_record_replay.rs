@@ -0,0 +1,267 @@
+//! Record/replay tests
+#![cfg(feature = "std")]
+
+use fbe::buffer::WriteBuffer;
+use fbe::limits::DecodeLimits;
+use fbe::record::{
+    Record, RecordReader, RecordWriter, Recorder, ReplaySpeed, Replayer, RECORD_VERSION,
+};
+use fbe::{Direction, Receiver, Sender, Serialize};
+use std::io::Cursor;
+use std::time::{Duration, Instant};
+
+struct Level(u32);
+
+impl Serialize for Level {
+    const FBE_TYPE: usize = 5;
+
+    fn serialize(&self, buffer: &mut WriteBuffer) -> usize {
+        let offset = buffer.allocate(4);
+        buffer.write_u32(offset, self.0);
+        4
+    }
+}
+
+#[derive(Default)]
+struct NullSender {
+    buffer: WriteBuffer,
+    sent: usize,
+}
+
+impl Sender for NullSender {
+    fn is_logging(&self) -> bool {
+        false
+    }
+
+    fn set_logging(&mut self, _enable: bool) {}
+
+    fn send_buffer(&mut self) -> &mut WriteBuffer {
+        &mut self.buffer
+    }
+
+    fn on_send(&mut self, data: &[u8]) -> usize {
+        self.sent += 1;
+        data.len()
+    }
+}
+
+#[derive(Default)]
+struct Collector {
+    messages: Vec<Vec<u8>>,
+}
+
+impl Receiver for Collector {
+    fn is_logging(&self) -> bool {
+        false
+    }
+
+    fn set_logging(&mut self, _enable: bool) {}
+
+    fn on_receive(&mut self, data: &[u8]) -> bool {
+        self.messages.push(data.to_vec());
+        true
+    }
+}
+
+fn recording(gap: Duration, count: u32) -> Vec<u8> {
+    let mut writer = RecordWriter::with_start(Vec::new(), 1_000).unwrap();
+    for i in 0..count {
+        let timestamp = 1_000 + i as u64 * gap.as_nanos() as u64;
+        writer
+            .write_at(timestamp, Direction::Receive, 5, &i.to_le_bytes())
+            .unwrap();
+    }
+    writer.into_inner()
+}
+
+#[test]
+fn test_record_file_format() {
+    let mut writer = RecordWriter::with_start(Vec::new(), 42).unwrap();
+    writer.write_at(43, Direction::Send, 7, &[0xAA, 0xBB]).unwrap();
+    let data = writer.into_inner();
+
+    assert_eq!(hex::encode(&data[..16]), "46424552454301002a00000000000000");
+    assert_eq!(hex::encode(&data[16..]), "2b00000000000000000700000002000000aabb");
+
+    let mut reader = RecordReader::new(Cursor::new(data)).unwrap();
+    assert_eq!(reader.version(), RECORD_VERSION);
+    assert_eq!(reader.start(), 42);
+    assert_eq!(
+        reader.read_record().unwrap(),
+        Some(Record {
+            timestamp: 43,
+            direction: Direction::Send,
+            fbe_type: 7,
+            payload: vec![0xAA, 0xBB],
+        })
+    );
+    assert_eq!(reader.read_record().unwrap(), None);
+}
+
+#[test]
+fn test_record_file_invalid_header() {
+    let err = RecordReader::new(Cursor::new(b"NOTREC\x01\x00\0\0\0\0\0\0\0\0".to_vec()))
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    let err = RecordReader::new(Cursor::new(b"FBEREC\x02\x00\0\0\0\0\0\0\0\0".to_vec()))
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_recorder_wraps_sender_and_receiver() {
+    let writer = RecordWriter::new(Vec::new()).unwrap();
+    let mut sender = Recorder::new(NullSender::default(), writer);
+    sender.send(&Level(1));
+    sender.send(&Level(2));
+    assert_eq!(sender.get_ref().sent, 2);
+    let (_, writer) = sender.into_parts().unwrap();
+
+    let mut receiver = Recorder::new(Collector::default(), writer);
+    receiver.receive(&[1, 2, 3]);
+    assert_eq!(receiver.get_ref().messages.len(), 1);
+    let (_, writer) = receiver.into_parts().unwrap();
+
+    let records: Vec<Record> = RecordReader::new(Cursor::new(writer.into_inner()))
+        .unwrap()
+        .collect::<std::io::Result<_>>()
+        .unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].direction, Direction::Send);
+    assert_eq!(records[0].fbe_type, 5);
+    assert_eq!(records[1].payload, 2u32.to_le_bytes());
+    assert_eq!(records[2].direction, Direction::Receive);
+    assert_eq!(records[2].payload, vec![1, 2, 3]);
+    assert!(records[0].timestamp <= records[2].timestamp);
+}
+
+#[test]
+fn test_recorder_records_receive_type() {
+    let data = recording(Duration::ZERO, 2);
+    let writer = RecordWriter::new(Vec::new()).unwrap();
+    let mut receiver = Recorder::new(Collector::default(), writer);
+    let mut replayer = Replayer::new(RecordReader::new(Cursor::new(data)).unwrap())
+        .speed(ReplaySpeed::Max);
+    assert_eq!(replayer.replay(&mut receiver).unwrap(), 2);
+    assert!(receiver.receive_frame(9, &[1]));
+    assert_eq!(receiver.get_ref().messages.len(), 3);
+    let (_, writer) = receiver.into_parts().unwrap();
+
+    let types: Vec<u32> = RecordReader::new(Cursor::new(writer.into_inner()))
+        .unwrap()
+        .map(|record| record.unwrap().fbe_type)
+        .collect();
+    assert_eq!(types, vec![5, 5, 9]);
+}
+
+#[test]
+fn test_record_payload_limit() {
+    let data = recording(Duration::ZERO, 1);
+    let mut reader = RecordReader::new(Cursor::new(data)).unwrap();
+    reader.set_decode_limits(DecodeLimits { max_message_size: 3, ..DecodeLimits::default() });
+    let err = reader.read_record().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    // A corrupted size is rejected before the payload is allocated
+    let mut data = recording(Duration::ZERO, 1);
+    data[16 + 13..16 + 17].copy_from_slice(&u32::MAX.to_le_bytes());
+    let mut reader = RecordReader::new(Cursor::new(data)).unwrap();
+    assert_eq!(reader.decode_limits().max_message_size, DecodeLimits::default().max_message_size);
+    let err = reader.read_record().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_replay_speeds() {
+    let data = recording(Duration::from_millis(20), 4);
+
+    let mut collector = Collector::default();
+    let started = Instant::now();
+    let mut replayer = Replayer::new(RecordReader::new(Cursor::new(data.clone())).unwrap());
+    assert_eq!(replayer.replay(&mut collector).unwrap(), 4);
+    assert!(started.elapsed() >= Duration::from_millis(60));
+    assert_eq!(collector.messages[3], 3u32.to_le_bytes());
+
+    let mut collector = Collector::default();
+    let started = Instant::now();
+    let mut replayer = Replayer::new(RecordReader::new(Cursor::new(data.clone())).unwrap())
+        .speed(ReplaySpeed::Accelerated(4.0));
+    assert_eq!(replayer.replay(&mut collector).unwrap(), 4);
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(15));
+
+    let mut collector = Collector::default();
+    let mut replayer = Replayer::new(RecordReader::new(Cursor::new(data.clone())).unwrap())
+        .speed(ReplaySpeed::Max)
+        .direction(Direction::Send);
+    assert_eq!(replayer.replay(&mut collector).unwrap(), 0);
+    assert!(collector.messages.is_empty());
+
+    // Factors that are not positive replay without delays instead of panicking
+    for factor in [0.0, -1.0, f64::NAN] {
+        let mut collector = Collector::default();
+        let started = Instant::now();
+        let mut replayer = Replayer::new(RecordReader::new(Cursor::new(data.clone())).unwrap())
+            .speed(ReplaySpeed::Accelerated(factor));
+        assert_eq!(replayer.replay(&mut collector).unwrap(), 4);
+        assert!(started.elapsed() < Duration::from_millis(60));
+    }
+}
+
+/// Receiver rejecting every message after the first
+#[derive(Default)]
+struct RejectAfterFirst {
+    accepted: usize,
+}
+
+impl Receiver for RejectAfterFirst {
+    fn is_logging(&self) -> bool {
+        false
+    }
+
+    fn set_logging(&mut self, _enable: bool) {}
+
+    fn on_receive(&mut self, _data: &[u8]) -> bool {
+        if self.accepted == 1 {
+            return false;
+        }
+        self.accepted += 1;
+        true
+    }
+}
+
+#[test]
+fn test_replay_rejected() {
+    let data = recording(Duration::ZERO, 3);
+    let mut receiver = RejectAfterFirst::default();
+    let mut replayer = Replayer::new(RecordReader::new(Cursor::new(data)).unwrap())
+        .speed(ReplaySpeed::Max);
+    let err = replayer.replay(&mut receiver).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(receiver.accepted, 1);
+}
+
+#[test]
+fn test_record_replay_file() {
+    let path = std::env::temp_dir().join(format!("fbe_record_{}.fbr", std::process::id()));
+
+    let mut writer = RecordWriter::create(&path).unwrap();
+    writer.write(Direction::Receive, 1, b"hello").unwrap();
+    writer.write(Direction::Receive, 1, b"world").unwrap();
+    writer.flush().unwrap();
+    drop(writer);
+
+    let mut collector = Collector::default();
+    let count = Replayer::open(&path)
+        .unwrap()
+        .speed(ReplaySpeed::Max)
+        .replay(&mut collector)
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(count, 2);
+    assert_eq!(collector.messages, vec![b"hello".to_vec(), b"world".to_vec()]);
+}
@@ -0,0 +1,184 @@
+//! Sender/Receiver tests: buffer reuse and structured logging
+
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::{Deserialize, Direction, MessageLog, Receiver, Sender, Serialize};
+use std::cell::RefCell;
+
+// No Display implementation on purpose
+#[derive(Debug, Clone, PartialEq)]
+struct Tick {
+    id: i32,
+    price: f64,
+}
+
+impl Serialize for Tick {
+    const FBE_TYPE: usize = 7;
+
+    fn serialize(&self, buffer: &mut WriteBuffer) -> usize {
+        let offset = buffer.allocate(12);
+        buffer.write_i32(offset, self.id);
+        buffer.write_f64(offset + 4, self.price);
+        12
+    }
+
+    fn log_json(&self) -> Option<String> {
+        Some(format!("{{\"id\":{},\"price\":{}}}", self.id, self.price))
+    }
+}
+
+impl Deserialize for Tick {
+    const FBE_TYPE: usize = 7;
+
+    fn deserialize(buffer: &ReadBuffer) -> Self {
+        Self {
+            id: buffer.read_i32(0),
+            price: buffer.read_f64(4),
+        }
+    }
+}
+
+#[derive(Default)]
+struct MockSender {
+    logging: bool,
+    buffer: WriteBuffer,
+    sent: Vec<Vec<u8>>,
+    records: RefCell<Vec<MessageLog>>,
+    logs: RefCell<Vec<String>>,
+}
+
+impl Sender for MockSender {
+    fn is_logging(&self) -> bool {
+        self.logging
+    }
+
+    fn set_logging(&mut self, enable: bool) {
+        self.logging = enable;
+    }
+
+    fn send_buffer(&mut self) -> &mut WriteBuffer {
+        &mut self.buffer
+    }
+
+    fn on_send(&mut self, data: &[u8]) -> usize {
+        self.sent.push(data.to_vec());
+        data.len()
+    }
+
+    fn on_send_log(&self, message: &str) {
+        self.logs.borrow_mut().push(message.to_string());
+    }
+
+    fn on_send_message(&self, record: &MessageLog) {
+        self.records.borrow_mut().push(record.clone());
+        self.on_send_log(&record.to_string());
+    }
+}
+
+#[derive(Default)]
+struct MockReceiver {
+    logging: bool,
+    received: Vec<Tick>,
+    records: RefCell<Vec<MessageLog>>,
+}
+
+impl Receiver for MockReceiver {
+    fn is_logging(&self) -> bool {
+        self.logging
+    }
+
+    fn set_logging(&mut self, enable: bool) {
+        self.logging = enable;
+    }
+
+    fn on_receive(&mut self, data: &[u8]) -> bool {
+        let tick: Tick = self.receive_value(data);
+        self.received.push(tick);
+        true
+    }
+
+    fn on_receive_message(&self, record: &MessageLog) {
+        self.records.borrow_mut().push(record.clone());
+    }
+}
+
+#[test]
+fn test_send_without_display() {
+    let mut sender = MockSender::default();
+    let tick = Tick { id: 1, price: 1.5 };
+
+    assert_eq!(sender.send(&tick), 12);
+    assert_eq!(sender.sent.len(), 1);
+    assert_eq!(sender.sent[0].len(), 12);
+    assert!(sender.logs.borrow().is_empty());
+    assert!(sender.records.borrow().is_empty());
+}
+
+#[test]
+fn test_send_reuses_buffer() {
+    let mut sender = MockSender::default();
+
+    sender.send(&Tick { id: 1, price: 1.5 });
+    let capacity = sender.buffer.capacity();
+    assert!(capacity >= 12);
+
+    for i in 0..100 {
+        sender.send(&Tick { id: i, price: 2.5 });
+    }
+
+    // Buffer is reset between messages and keeps its capacity
+    assert_eq!(sender.buffer.capacity(), capacity);
+    assert_eq!(sender.buffer.size(), 12);
+    assert_eq!(sender.sent.len(), 101);
+    assert!(sender.sent.iter().all(|data| data.len() == 12));
+}
+
+#[test]
+fn test_send_keeps_buffer_mode() {
+    let mut sender = MockSender::default();
+    assert!(!sender.buffer.is_growable());
+
+    sender.send(&Tick { id: 1, price: 1.5 });
+    assert!(!sender.buffer.is_growable());
+
+    sender.buffer.set_growable(true);
+    sender.send(&Tick { id: 2, price: 2.5 });
+    assert!(sender.buffer.is_growable());
+}
+
+#[test]
+fn test_send_log_record() {
+    let mut sender = MockSender::default();
+    sender.set_logging(true);
+    sender.send(&Tick { id: 3, price: 0.5 });
+
+    let records = sender.records.borrow();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].direction, Direction::Send);
+    assert!(records[0].type_name.ends_with("Tick"));
+    assert_eq!(records[0].type_id, 7);
+    assert_eq!(records[0].size, 12);
+    assert_eq!(records[0].json.as_deref(), Some("{\"id\":3,\"price\":0.5}"));
+
+    let logs = sender.logs.borrow();
+    assert_eq!(logs.len(), 1);
+    assert!(logs[0].contains("(type 7, 12 bytes)"));
+}
+
+#[test]
+fn test_receive_log_record() {
+    let mut sender = MockSender::default();
+    sender.send(&Tick { id: 9, price: 4.25 });
+
+    let mut receiver = MockReceiver::default();
+    receiver.set_logging(true);
+    assert!(receiver.receive(&sender.sent[0]));
+
+    assert_eq!(receiver.received, vec![Tick { id: 9, price: 4.25 }]);
+
+    let records = receiver.records.borrow();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].direction, Direction::Receive);
+    assert_eq!(records[0].type_id, 7);
+    assert_eq!(records[0].size, 12);
+    assert_eq!(records[0].json, None);
+}
@@ -0,0 +1,190 @@
+//! serde data format tests
+#![cfg(feature = "serde")]
+
+use fbe::buffer::WriteBuffer;
+use fbe::model_final::Product;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Item {
+    id: i32,
+    name: String,
+    price: f64,
+    quantity: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Event {
+    Empty,
+    Price(f64),
+    Fill { id: u64, qty: i32 },
+    Pair(u8, u16),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Order {
+    id: u64,
+    side: Side,
+    flag: bool,
+    code: char,
+    note: Option<String>,
+    limit: Option<f64>,
+    tags: Vec<String>,
+    levels: [i16; 3],
+    pair: (u8, i64),
+    attrs: BTreeMap<String, i32>,
+    events: Vec<Event>,
+    wide: i128,
+}
+
+fn order() -> Order {
+    Order {
+        id: 42,
+        side: Side::Sell,
+        flag: true,
+        code: 'ж',
+        note: Some("rush".to_string()),
+        limit: None,
+        tags: vec!["a".to_string(), "bc".to_string()],
+        levels: [-1, 0, 1],
+        pair: (7, -7),
+        attrs: BTreeMap::from([("x".to_string(), 1), ("y".to_string(), 2)]),
+        events: vec![
+            Event::Empty,
+            Event::Price(1.5),
+            Event::Fill { id: 9, qty: -3 },
+            Event::Pair(1, 2),
+        ],
+        wide: -170141183460469231731687303715884105728,
+    }
+}
+
+#[test]
+fn test_serde_matches_final_model() {
+    let product = Product::new(123, "Laptop".to_string(), 999.99, 5);
+    let item = Item {
+        id: 123,
+        name: "Laptop".to_string(),
+        price: 999.99,
+        quantity: 5,
+    };
+
+    let mut buffer = WriteBuffer::new();
+    product.serialize_final(&mut buffer);
+    assert_eq!(fbe::serde::to_vec(&item).unwrap(), buffer.data());
+
+    let mut buffer = WriteBuffer::new();
+    product.serialize_model(&mut buffer);
+    assert_eq!(fbe::serde::to_vec_size_prefixed(&item).unwrap(), buffer.data());
+
+    let decoded: Item = fbe::serde::from_slice_size_prefixed(buffer.data()).unwrap();
+    assert_eq!(decoded, item);
+}
+
+#[test]
+fn test_serde_primitive_layout() {
+    assert_eq!(fbe::serde::to_vec(&true).unwrap(), [1]);
+    assert_eq!(fbe::serde::to_vec(&0x1234u16).unwrap(), [0x34, 0x12]);
+    assert_eq!(fbe::serde::to_vec(&'A').unwrap(), [0x41, 0, 0, 0]);
+    assert_eq!(fbe::serde::to_vec("hi").unwrap(), [2, 0, 0, 0, b'h', b'i']);
+    assert_eq!(fbe::serde::to_vec(&Some(5u8)).unwrap(), [1, 5]);
+    assert_eq!(fbe::serde::to_vec(&None::<u8>).unwrap(), [0]);
+    assert_eq!(fbe::serde::to_vec(&vec![1u8, 2]).unwrap(), [2, 0, 0, 0, 1, 2]);
+    assert_eq!(fbe::serde::to_vec(&[1u8, 2]).unwrap(), [1, 2]);
+    assert_eq!(fbe::serde::to_vec(&Side::Sell).unwrap(), [1, 0, 0, 0]);
+}
+
+#[test]
+fn test_serde_roundtrip() {
+    let order = order();
+    let data = fbe::serde::to_vec(&order).unwrap();
+    assert_eq!(fbe::serde::from_slice::<Order>(&data).unwrap(), order);
+
+    #[cfg(feature = "std")]
+    {
+        let mut output = Vec::new();
+        fbe::serde::to_writer(&mut output, &order).unwrap();
+        assert_eq!(output, data);
+        let decoded: Order = fbe::serde::from_reader(output.as_slice()).unwrap();
+        assert_eq!(decoded, order);
+    }
+
+    let data = fbe::serde::to_vec_size_prefixed(&order).unwrap();
+    assert_eq!(fbe::serde::from_slice_size_prefixed::<Order>(&data).unwrap(), order);
+}
+
+#[test]
+fn test_serde_borrowed_str() {
+    #[derive(Serialize, Deserialize)]
+    struct Borrowed<'a> {
+        name: &'a str,
+        #[serde(with = "serde_bytes_compat")]
+        data: &'a [u8],
+    }
+
+    mod serde_bytes_compat {
+        pub fn serialize<S: serde::Serializer>(data: &[u8], s: S) -> Result<S::Ok, S::Error> {
+            s.serialize_bytes(data)
+        }
+
+        pub fn deserialize<'de, D: serde::Deserializer<'de>>(d: D) -> Result<&'de [u8], D::Error> {
+            serde::Deserialize::deserialize(d)
+        }
+    }
+
+    let data = fbe::serde::to_vec(&Borrowed {
+        name: "zero",
+        data: &[1, 2, 3],
+    })
+    .unwrap();
+    let decoded: Borrowed = fbe::serde::from_slice(&data).unwrap();
+    assert_eq!(decoded.name, "zero");
+    assert_eq!(decoded.data, [1, 2, 3]);
+}
+
+#[test]
+fn test_serde_invalid_input() {
+    use fbe::serde::Error;
+
+    let data = fbe::serde::to_vec(&order()).unwrap();
+    for size in 0..data.len() {
+        assert!(fbe::serde::from_slice::<Order>(&data[..size]).is_err());
+    }
+
+    assert!(matches!(fbe::serde::from_slice::<bool>(&[2]), Err(Error::InvalidBool(2))));
+    assert!(matches!(
+        fbe::serde::from_slice::<Option<u8>>(&[3, 0]),
+        Err(Error::InvalidOptional(3))
+    ));
+    assert!(matches!(
+        fbe::serde::from_slice::<char>(&[0, 0xD8, 0, 0]),
+        Err(Error::InvalidChar(0xD800))
+    ));
+    assert!(matches!(
+        fbe::serde::from_slice::<String>(&[1, 0, 0, 0, 0xFF]),
+        Err(Error::InvalidUtf8)
+    ));
+    assert!(matches!(
+        fbe::serde::from_slice::<u8>(&[1, 2]),
+        Err(Error::TrailingBytes(1))
+    ));
+    assert!(matches!(
+        fbe::serde::from_slice::<Vec<u8>>(&[0xFF, 0xFF, 0xFF, 0xFF]),
+        Err(Error::Eof)
+    ));
+    assert!(matches!(
+        fbe::serde::from_slice_size_prefixed::<u8>(&[9, 0, 0, 0, 1]),
+        Err(Error::InvalidSize(9))
+    ));
+    assert!(matches!(
+        fbe::serde::from_slice::<serde_json::Value>(&[0]),
+        Err(Error::AnyUnsupported)
+    ));
+}
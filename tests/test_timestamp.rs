@@ -0,0 +1,105 @@
+//! Timestamp type tests
+
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::field_model::{FieldModelTimestamp, FieldModelTimestampMut};
+use fbe::timestamp::TimestampRangeError;
+use fbe::Timestamp;
+use std::time::Duration;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[test]
+fn test_timestamp_conversions() {
+    let timestamp = Timestamp::new(1_729_512_000_123_456_789);
+    assert_eq!(timestamp.nanos(), 1_729_512_000_123_456_789);
+    assert_eq!(timestamp.secs(), 1_729_512_000);
+    assert_eq!(timestamp.subsec_nanos(), 123_456_789);
+    assert_eq!(Timestamp::from_secs_nanos(1_729_512_000, 123_456_789), Some(timestamp));
+    assert_eq!(Timestamp::from_secs_nanos(u64::MAX, 0), None);
+
+    let duration = Duration::from(timestamp);
+    assert_eq!(duration, Duration::new(1_729_512_000, 123_456_789));
+    assert_eq!(Timestamp::try_from(duration), Ok(timestamp));
+    assert_eq!(Timestamp::try_from(Duration::MAX), Err(TimestampRangeError));
+
+    assert_eq!(u64::from(timestamp), timestamp.nanos());
+    assert_eq!(Timestamp::from(42u64), Timestamp::new(42));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_timestamp_system_time() {
+    let timestamp = Timestamp::new(1_729_512_000_123_456_789);
+    let time = SystemTime::from(timestamp);
+    assert_eq!(time.duration_since(UNIX_EPOCH).unwrap(), Duration::from(timestamp));
+    assert_eq!(Timestamp::try_from(time), Ok(timestamp));
+    assert_eq!(
+        Timestamp::try_from(UNIX_EPOCH - Duration::from_secs(1)),
+        Err(TimestampRangeError)
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_timestamp_utc() {
+    let before = SystemTime::now();
+    let now = Timestamp::utc();
+    let after = SystemTime::now();
+    assert!(Timestamp::try_from(before).unwrap() <= now);
+    assert!(now <= Timestamp::try_from(after).unwrap());
+    assert!(now.to_rfc3339().starts_with("20"));
+}
+
+#[test]
+fn test_timestamp_display() {
+    let timestamp = Timestamp::new(1_729_512_000_123_456_789);
+    assert_eq!(timestamp.to_string(), "2024-10-21T12:00:00.123456789Z");
+    assert_eq!(format!("{}", Timestamp::new(1_000_000)), "1970-01-01T00:00:00.001Z");
+}
+
+#[test]
+fn test_timestamp_field_model_and_json() {
+    let timestamp = Timestamp::new(1_729_512_000_000_000_001);
+    let mut writer = WriteBuffer::new();
+    writer.allocate(8);
+    FieldModelTimestampMut::new(&mut writer, 0).set(timestamp);
+    assert_eq!(FieldModelTimestamp::new(writer.data(), 0).get(), timestamp);
+
+    let reader = ReadBuffer::from(writer.data().to_vec());
+    assert_eq!(reader.read_timestamp(0), timestamp);
+    assert_eq!(reader.read_u64(0), timestamp.nanos());
+
+    #[cfg(feature = "json")]
+    {
+        assert_eq!(fbe::json::to_json(&timestamp).unwrap(), "1729512000000000001");
+        assert_eq!(
+            fbe::json::from_json::<Timestamp>("1729512000000000001").unwrap(),
+            timestamp
+        );
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_timestamp_chrono() {
+    let timestamp = Timestamp::new(1_729_512_000_123_456_789);
+    let date_time = chrono::DateTime::<chrono::Utc>::from(timestamp);
+    assert_eq!(date_time.to_rfc3339(), "2024-10-21T12:00:00.123456789+00:00");
+    assert_eq!(Timestamp::try_from(date_time), Ok(timestamp));
+
+    let before_epoch = chrono::DateTime::from_timestamp(-1, 0).unwrap();
+    assert_eq!(Timestamp::try_from(before_epoch), Err(TimestampRangeError));
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn test_timestamp_time() {
+    let timestamp = Timestamp::new(1_729_512_000_123_456_789);
+    let date_time = time::OffsetDateTime::from(timestamp);
+    assert_eq!(date_time.unix_timestamp_nanos(), 1_729_512_000_123_456_789);
+    assert_eq!(Timestamp::try_from(date_time), Ok(timestamp));
+    assert_eq!(
+        Timestamp::try_from(time::OffsetDateTime::UNIX_EPOCH - time::Duration::NANOSECOND),
+        Err(TimestampRangeError)
+    );
+}
@@ -0,0 +1,164 @@
+//! Tokio transport tests over in-process duplex streams
+#![cfg(feature = "tokio")]
+
+use bytes::BytesMut;
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::frame::{Frame, FrameRef};
+use fbe::tokio::{AsyncReceiver, AsyncSender, FbeCodec};
+use fbe::{Deserialize, Receiver, Serialize};
+use tokio_util::codec::{Decoder, Encoder};
+
+#[derive(Debug, Clone, PartialEq)]
+struct Quote {
+    symbol: String,
+    bid: f64,
+}
+
+impl Serialize for Quote {
+    const FBE_TYPE: usize = 11;
+
+    fn serialize(&self, buffer: &mut WriteBuffer) -> usize {
+        let size = 4 + self.symbol.len() + 8;
+        let offset = buffer.allocate(size);
+        buffer.write_string(offset, &self.symbol);
+        buffer.write_f64(offset + 4 + self.symbol.len(), self.bid);
+        size
+    }
+}
+
+impl Deserialize for Quote {
+    const FBE_TYPE: usize = 11;
+
+    fn deserialize(buffer: &ReadBuffer) -> Self {
+        let symbol = buffer.read_string(0);
+        let bid = buffer.read_f64(4 + symbol.len());
+        Self { symbol, bid }
+    }
+}
+
+#[derive(Default)]
+struct Collector {
+    messages: Vec<Vec<u8>>,
+    capacity: Option<usize>,
+}
+
+impl Receiver for Collector {
+    fn is_logging(&self) -> bool {
+        false
+    }
+
+    fn set_logging(&mut self, _enable: bool) {}
+
+    fn on_receive(&mut self, data: &[u8]) -> bool {
+        if self.capacity == Some(self.messages.len()) {
+            return false;
+        }
+        self.messages.push(data.to_vec());
+        true
+    }
+}
+
+#[test]
+fn test_codec_roundtrip_partial_input() {
+    let mut codec = FbeCodec::new();
+    let mut encoded = BytesMut::new();
+    codec.encode(FrameRef::new(3, b"abc"), &mut encoded).unwrap();
+    codec.encode(Frame::new(4, b"defg".to_vec()), &mut encoded).unwrap();
+    assert_eq!(encoded.len(), 8 + 3 + 8 + 4);
+
+    let mut input = BytesMut::new();
+    input.extend_from_slice(&encoded[..10]);
+    assert_eq!(codec.decode(&mut input).unwrap(), None);
+
+    input.extend_from_slice(&encoded[10..]);
+    assert_eq!(codec.decode(&mut input).unwrap(), Some(Frame::new(3, b"abc".to_vec())));
+    assert_eq!(codec.decode(&mut input).unwrap(), Some(Frame::new(4, b"defg".to_vec())));
+    assert_eq!(codec.decode(&mut input).unwrap(), None);
+}
+
+#[test]
+fn test_codec_frame_limit() {
+    let mut codec = FbeCodec::with_max_frame_size(16);
+    let mut encoded = BytesMut::new();
+    assert!(codec.encode(FrameRef::new(1, &[0; 9]), &mut encoded).is_err());
+
+    let mut input = BytesMut::from(&[32u8, 0, 0, 0, 1, 0, 0, 0][..]);
+    assert!(codec.decode(&mut input).is_err());
+}
+
+#[tokio::test]
+async fn test_async_send_receive_duplex() {
+    let (client, server) = tokio::io::duplex(64);
+
+    let writer = tokio::spawn(async move {
+        let mut sender = AsyncSender::new(client);
+        sender.set_logging(true);
+        for i in 0..10 {
+            let quote = Quote {
+                symbol: format!("SYM{}", i),
+                bid: i as f64 * 1.5,
+            };
+            let size = sender.send(&quote).await.unwrap();
+            assert_eq!(size, 8 + 4 + quote.symbol.len() + 8);
+        }
+    });
+
+    let mut receiver = AsyncReceiver::new(server);
+    receiver.set_logging(true);
+    for i in 0..10 {
+        let quote: Quote = receiver.read_value().await.unwrap().unwrap();
+        assert_eq!(quote.symbol, format!("SYM{}", i));
+        assert_eq!(quote.bid, i as f64 * 1.5);
+    }
+
+    writer.await.unwrap();
+    assert_eq!(receiver.read_value::<Quote>().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_async_forward_to_receiver() {
+    let (client, server) = tokio::io::duplex(16);
+
+    let writer = tokio::spawn(async move {
+        let mut sender = AsyncSender::new(client);
+        sender.send_serialized(1, b"first").await.unwrap();
+        sender.send_serialized(2, b"second").await.unwrap();
+    });
+
+    let mut receiver = AsyncReceiver::new(server);
+    let mut collector = Collector::default();
+    let count = receiver.forward(&mut collector).await.unwrap();
+    writer.await.unwrap();
+
+    assert_eq!(count, 2);
+    assert_eq!(collector.messages, vec![b"first".to_vec(), b"second".to_vec()]);
+}
+
+#[tokio::test]
+async fn test_async_forward_rejected() {
+    let (client, server) = tokio::io::duplex(64);
+
+    let mut sender = AsyncSender::new(client);
+    sender.send_serialized(1, b"first").await.unwrap();
+    sender.send_serialized(2, b"second").await.unwrap();
+    drop(sender);
+
+    let mut receiver = AsyncReceiver::new(server);
+    let mut collector = Collector { capacity: Some(1), ..Collector::default() };
+    let err = receiver.forward(&mut collector).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(collector.messages, vec![b"first".to_vec()]);
+}
+
+#[tokio::test]
+async fn test_async_receive_unexpected_type() {
+    let (client, server) = tokio::io::duplex(64);
+
+    let mut sender = AsyncSender::new(client);
+    sender.send_serialized(99, &[0; 12]).await.unwrap();
+    drop(sender);
+
+    let mut receiver = AsyncReceiver::new(server);
+    let err = receiver.read_value::<Quote>().await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
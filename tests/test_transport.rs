@@ -0,0 +1,230 @@
+//! Blocking transport tests: std::io streams, TCP and Unix domain sockets
+#![cfg(feature = "std")]
+
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::frame::Frame;
+use fbe::transport::{FrameReceiver, StreamReceiver, StreamSender, TcpSender, TcpServer};
+use fbe::{Deserialize, Receiver, Sender, Serialize};
+use std::io::{self, Cursor, Write};
+use std::sync::mpsc;
+use std::thread;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Ping {
+    seq: u32,
+}
+
+impl Serialize for Ping {
+    const FBE_TYPE: usize = 21;
+
+    fn serialize(&self, buffer: &mut WriteBuffer) -> usize {
+        let offset = buffer.allocate(4);
+        buffer.write_u32(offset, self.seq);
+        4
+    }
+}
+
+impl Deserialize for Ping {
+    const FBE_TYPE: usize = 21;
+
+    fn deserialize(buffer: &ReadBuffer) -> Self {
+        Self {
+            seq: buffer.read_u32(0),
+        }
+    }
+}
+
+#[test]
+fn test_stream_sender_receiver() {
+    let mut sender = StreamSender::new(Vec::new());
+    for seq in 0..5 {
+        assert_eq!(sender.send(&Ping { seq }).unwrap(), 8 + 4);
+    }
+    assert_eq!(sender.send_serialized(b"raw").unwrap(), 8 + 3);
+    let data = sender.into_inner();
+    assert_eq!(&data[..8], &[12, 0, 0, 0, 21, 0, 0, 0]);
+
+    let mut receiver = StreamReceiver::new(Cursor::new(data));
+    for seq in 0..5 {
+        assert_eq!(receiver.read_value::<Ping>().unwrap(), Some(Ping { seq }));
+    }
+    assert_eq!(receiver.read_frame().unwrap(), Some(Frame::new(0, b"raw".to_vec())));
+    assert_eq!(receiver.read_frame().unwrap(), None);
+}
+
+#[test]
+fn test_stream_receiver_truncated_frame() {
+    let mut sender = StreamSender::new(Vec::new());
+    sender.send(&Ping { seq: 1 }).unwrap();
+    let mut data = sender.into_inner();
+    data.pop();
+
+    let mut receiver = StreamReceiver::new(Cursor::new(data));
+    let err = receiver.read_frame().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+/// Writer failing every write
+struct BrokenPipe;
+
+impl Write for BrokenPipe {
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_stream_sender_errors() {
+    let mut sender = StreamSender::new(BrokenPipe);
+    assert_eq!(sender.send(&Ping { seq: 1 }).unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+    assert!(sender.send_frame(1, b"raw").is_err());
+    assert!(sender.take_error().is_none());
+
+    // Generic `Sender` callers get 0 and the stashed error
+    assert_eq!(Sender::send(&mut sender, &Ping { seq: 2 }), 0);
+    assert_eq!(sender.take_error().unwrap().kind(), io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn test_stream_sender_frame_size_limit() {
+    let mut sender = StreamSender::with_max_frame_size(Vec::new(), 16);
+    assert_eq!(sender.send_frame(1, &[0; 8]).unwrap(), 16);
+    let err = sender.send_frame(1, &[0; 9]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(sender.send(&Ping { seq: 1 }).unwrap(), 12);
+
+    // Rejected frames are not written
+    let data = sender.into_inner();
+    assert_eq!(data.len(), 16 + 12);
+}
+
+/// Receiver accepting a fixed number of payloads
+struct Limited {
+    accepted: usize,
+    capacity: usize,
+}
+
+impl Receiver for Limited {
+    fn is_logging(&self) -> bool {
+        false
+    }
+
+    fn set_logging(&mut self, _enable: bool) {}
+
+    fn on_receive(&mut self, _data: &[u8]) -> bool {
+        if self.accepted == self.capacity {
+            return false;
+        }
+        self.accepted += 1;
+        true
+    }
+}
+
+#[test]
+fn test_stream_receiver_forward_rejected() {
+    let mut sender = StreamSender::new(Vec::new());
+    for seq in 0..3 {
+        sender.send(&Ping { seq }).unwrap();
+    }
+    let data = sender.into_inner();
+
+    let mut target = Limited { accepted: 0, capacity: 3 };
+    let mut receiver = StreamReceiver::new(Cursor::new(data.clone()));
+    assert_eq!(receiver.forward(&mut target).unwrap(), 3);
+
+    let mut target = Limited { accepted: 0, capacity: 1 };
+    let mut receiver = StreamReceiver::new(Cursor::new(data));
+    let err = receiver.forward(&mut target).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(target.accepted, 1);
+}
+
+#[test]
+fn test_frame_receiver_chunks() {
+    let mut sender = StreamSender::new(Vec::new());
+    for seq in 0..3 {
+        sender.send(&Ping { seq }).unwrap();
+    }
+    let data = sender.into_inner();
+
+    let mut frames = Vec::new();
+    let mut receiver = FrameReceiver::new(|frame: &Frame| {
+        frames.push(frame.clone());
+        true
+    });
+    for chunk in data.chunks(5) {
+        assert!(receiver.receive(chunk));
+    }
+    drop(receiver);
+
+    assert_eq!(frames.len(), 3);
+    assert!(frames.iter().all(|frame| frame.fbe_type == 21));
+    assert_eq!(frames[2].payload, 2u32.to_le_bytes());
+}
+
+#[test]
+fn test_frame_receiver_invalid_frame() {
+    let mut receiver = FrameReceiver::new(|_: &Frame| true);
+    assert!(!receiver.receive(&[1, 0, 0, 0, 0, 0, 0, 0]));
+    assert!(receiver.take_error().is_some());
+}
+
+#[test]
+fn test_tcp_server_echo_and_shutdown() {
+    let server = TcpServer::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    let shutdown = server.shutdown_handle();
+    let (tx, rx) = mpsc::channel();
+
+    let server_thread = thread::spawn(move || {
+        server.run(move |connection, frame| {
+            tx.send(frame.clone()).unwrap();
+            connection.sender().on_send_frame(frame.fbe_type as usize, &frame.payload);
+        })
+    });
+
+    let mut sender = TcpSender::connect(addr).unwrap();
+    let mut receiver = StreamReceiver::new(sender.get_ref().try_clone().unwrap());
+    for seq in 0..10 {
+        assert_eq!(sender.send(&Ping { seq }).unwrap(), 12);
+        assert_eq!(receiver.read_value::<Ping>().unwrap(), Some(Ping { seq }));
+    }
+
+    for seq in 0..10u32 {
+        let frame = rx.recv().unwrap();
+        assert_eq!(frame.fbe_type, 21);
+        assert_eq!(frame.payload, seq.to_le_bytes());
+    }
+
+    shutdown.shutdown();
+    server_thread.join().unwrap().unwrap();
+    assert!(shutdown.is_shutdown());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unix_stream_pair() {
+    use fbe::transport::{UnixReceiver, UnixSender};
+    use std::os::unix::net::UnixStream;
+
+    let (left, right) = UnixStream::pair().unwrap();
+    let writer = thread::spawn(move || {
+        let mut sender = UnixSender::new(left);
+        for seq in 0..3 {
+            sender.send(&Ping { seq }).unwrap();
+        }
+    });
+
+    let mut receiver = UnixReceiver::new(right);
+    let mut count = 0;
+    while let Some(ping) = receiver.read_value::<Ping>().unwrap() {
+        assert_eq!(ping.seq, count);
+        count += 1;
+    }
+    writer.join().unwrap();
+    assert_eq!(count, 3);
+}
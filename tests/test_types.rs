@@ -1,11 +1,13 @@
 use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::chars::CharError;
+use fbe::{Decimal, Timestamp, Uuid};
 
 #[test]
 fn test_timestamp() {
     let mut writer = WriteBuffer::with_capacity(100);
     writer.allocate(8);
 
-    let timestamp: u64 = 1729526400000000000; // 2024-10-21 12:00:00 UTC in nanoseconds
+    let timestamp = Timestamp::new(1729526400000000000); // 2024-10-21 12:00:00 UTC in nanoseconds
     writer.write_timestamp(0, timestamp);
 
     let mut reader = ReadBuffer::new();
@@ -20,11 +22,11 @@ fn test_uuid() {
     let mut writer = WriteBuffer::with_capacity(100);
     writer.allocate(16);
 
-    let uuid: [u8; 16] = [
+    let uuid = Uuid::from_bytes([
         0x12, 0x3e, 0x45, 0x67, 0xe8, 0x9b, 0x12, 0xd3, 0xa4, 0x56, 0x42, 0x66, 0x55, 0x44, 0x00,
         0x00,
-    ];
-    writer.write_uuid(0, &uuid);
+    ]);
+    writer.write_uuid(0, uuid);
 
     let mut reader = ReadBuffer::new();
     reader.attach_buffer(writer.data(), 0, writer.size());
@@ -54,16 +56,16 @@ fn test_decimal() {
     writer.allocate(16);
 
     // Test: 123456.123456 with scale 6
-    let value: i128 = 123456123456;
+    let value: u128 = 123456123456;
     let scale: u8 = 6;
     let negative = false;
 
-    writer.write_decimal(0, value, scale, negative);
+    writer.write_decimal(0, Decimal::from_parts(value, scale, negative).unwrap());
 
     let mut reader = ReadBuffer::new();
     reader.attach_buffer(writer.data(), 0, writer.size());
 
-    let (read_value, read_scale, read_negative) = reader.read_decimal(0);
+    let (read_value, read_scale, read_negative) = reader.read_decimal(0).into_parts();
     assert_eq!(value, read_value);
     assert_eq!(scale, read_scale);
     assert_eq!(negative, read_negative);
@@ -75,17 +77,51 @@ fn test_decimal_negative() {
     writer.allocate(16);
 
     // Test: -999.99 with scale 2
-    let value: i128 = 99999;
+    let value: u128 = 99999;
     let scale: u8 = 2;
     let negative = true;
 
-    writer.write_decimal(0, value, scale, negative);
+    writer.write_decimal(0, Decimal::from_parts(value, scale, negative).unwrap());
 
     let mut reader = ReadBuffer::new();
     reader.attach_buffer(writer.data(), 0, writer.size());
 
-    let (read_value, read_scale, read_negative) = reader.read_decimal(0);
+    let (read_value, read_scale, read_negative) = reader.read_decimal(0).into_parts();
     assert_eq!(value, read_value);
     assert_eq!(scale, read_scale);
     assert_eq!(negative, read_negative);
 }
+
+#[test]
+fn test_char() {
+    let mut writer = WriteBuffer::with_capacity(100);
+    writer.allocate(3);
+
+    writer.write_char(0, 'A').unwrap();
+    writer.write_char(1, 'é').unwrap();
+    assert_eq!(writer.write_char(2, 'Ω'), Err(CharError::NotLatin1('Ω')));
+
+    let mut reader = ReadBuffer::new();
+    reader.attach_buffer(writer.data(), 0, writer.size());
+
+    assert_eq!(reader.read_char(0), 'A');
+    assert_eq!(reader.read_char(1), 'é');
+    assert_eq!(writer.data()[1], 0xE9);
+}
+
+#[test]
+fn test_wchar() {
+    let mut writer = WriteBuffer::with_capacity(100);
+    writer.allocate(12);
+
+    writer.write_wchar(0, 'Ω');
+    writer.write_wchar(4, '🦀');
+    writer.write_u32(8, 0xD800);
+
+    let mut reader = ReadBuffer::new();
+    reader.attach_buffer(writer.data(), 0, writer.size());
+
+    assert_eq!(reader.read_wchar(0), Ok('Ω'));
+    assert_eq!(reader.read_wchar(4), Ok('🦀'));
+    assert_eq!(reader.read_wchar(8), Err(CharError::InvalidWChar(0xD800)));
+}
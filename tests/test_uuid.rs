@@ -0,0 +1,97 @@
+//! UUID type tests
+
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::field_model::{FieldModelUuid, FieldModelUuidMut};
+use fbe::final_model::{FinalModelUuid, FinalModelUuidMut, FinalValue};
+use fbe::Uuid;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+
+const TEXT: &str = "123e4567-e89b-12d3-a456-426655440000";
+const BYTES: [u8; 16] = [
+    0x12, 0x3e, 0x45, 0x67, 0xe8, 0x9b, 0x12, 0xd3, 0xa4, 0x56, 0x42, 0x66, 0x55, 0x44, 0x00, 0x00,
+];
+
+#[test]
+fn test_uuid_parse_format() {
+    let uuid: Uuid = TEXT.parse().unwrap();
+    assert_eq!(uuid, Uuid::from_bytes(BYTES));
+    assert_eq!(uuid.to_string(), TEXT);
+    assert_eq!(format!("{:?}", uuid), format!("Uuid({})", TEXT));
+    assert_eq!("123E4567E89B12D3A456426655440000".parse::<Uuid>(), Ok(uuid));
+    assert_eq!(format!("{{{}}}", TEXT).parse::<Uuid>(), Ok(uuid));
+    assert_eq!(uuid.version(), 1);
+
+    assert!("123e4567-e89b-12d3-a456-42665544000".parse::<Uuid>().is_err());
+    assert!("123e4567e-89b-12d3-a456-426655440000".parse::<Uuid>().is_err());
+    assert!("123e4567-e89b-12d3-a456-42665544000g".parse::<Uuid>().is_err());
+    assert!("".parse::<Uuid>().is_err());
+
+    assert_eq!(u128::from(uuid), 0x123e4567_e89b_12d3_a456_426655440000);
+    assert_eq!(Uuid::from(0x123e4567_e89b_12d3_a456_426655440000u128), uuid);
+    assert_eq!(<[u8; 16]>::from(uuid), BYTES);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_uuid_generation() {
+    assert!(Uuid::nil().is_nil());
+    assert_eq!(Uuid::nil().to_string(), "00000000-0000-0000-0000-000000000000");
+    assert_eq!(Uuid::default(), Uuid::nil());
+
+    let mut seen = HashSet::new();
+    for _ in 0..1000 {
+        let v1 = Uuid::v1();
+        assert_eq!(v1.version(), 1);
+        assert_eq!(v1.as_bytes()[8] & 0xC0, 0x80);
+        assert_eq!(v1.as_bytes()[10] & 0x01, 0x01);
+        assert!(seen.insert(v1));
+
+        let v4 = Uuid::v4();
+        assert_eq!(v4.version(), 4);
+        assert_eq!(v4.as_bytes()[8] & 0xC0, 0x80);
+        assert!(seen.insert(v4));
+    }
+}
+
+#[test]
+fn test_uuid_models() {
+    let uuid = Uuid::from_bytes(BYTES);
+    let mut writer = WriteBuffer::new();
+    writer.allocate(32);
+    FieldModelUuidMut::new(&mut writer, 0).set(uuid);
+    FinalModelUuidMut::new(&mut writer, 16).set(Uuid::nil());
+    assert_eq!(&writer.data()[..16], &BYTES);
+    assert_eq!(FieldModelUuid::new(writer.data(), 0).get(), uuid);
+    assert_eq!(FinalModelUuid::new(writer.data(), 16).get(), Uuid::nil());
+
+    let reader = ReadBuffer::from(writer.data().to_vec());
+    assert_eq!(reader.read_uuid(0), uuid);
+    assert_eq!(Uuid::read_final(&reader, 0), (uuid, 16));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_uuid_serde() {
+    let uuid = Uuid::from_bytes(BYTES);
+    #[cfg(feature = "json")]
+    {
+        assert_eq!(fbe::json::to_json(&uuid).unwrap(), format!("\"{}\"", TEXT));
+        assert_eq!(fbe::json::from_json::<Uuid>(&format!("\"{}\"", TEXT)).unwrap(), uuid);
+        assert!(fbe::json::from_json::<Uuid>("\"nope\"").is_err());
+    }
+
+    // Binary serde format keeps the 16-byte FBE layout
+    let bytes = fbe::serde::to_vec(&uuid).unwrap();
+    assert_eq!(bytes, BYTES);
+    assert_eq!(fbe::serde::from_slice::<Uuid>(&bytes).unwrap(), uuid);
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn test_uuid_crate() {
+    let uuid = Uuid::from_bytes(BYTES);
+    let other = uuid::Uuid::from(uuid);
+    assert_eq!(other.to_string(), TEXT);
+    assert_eq!(Uuid::from(other), uuid);
+}
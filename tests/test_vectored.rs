@@ -0,0 +1,166 @@
+//! FBE vectored output tests
+#![cfg(all(feature = "derive", feature = "std"))]
+
+use fbe::FbeStruct;
+use fbe::buffer::{ReadBuffer, WriteBuffer};
+use fbe::sender::Sender;
+use fbe::transport::StreamSender;
+use fbe::vectored::VectoredWriter;
+use std::io::{self, IoSlice, Write};
+
+#[derive(Debug, Clone, PartialEq, FbeStruct)]
+#[fbe(id = 7)]
+struct Mail {
+    id: i32,
+    subject: String,
+    body: String,
+    attachment: Vec<u8>,
+    extra: Option<Vec<u8>>,
+    parts: Vec<Vec<u8>>,
+}
+
+fn mail() -> Mail {
+    Mail {
+        id: 42,
+        subject: "report".to_string(),
+        body: "x".repeat(5000),
+        attachment: (0..1 << 20).map(|i| i as u8).collect(),
+        extra: Some(vec![0xAB; 8192]),
+        parts: vec![vec![1, 2, 3], vec![0xCD; 4096]],
+    }
+}
+
+fn serialized(value: &Mail) -> Vec<u8> {
+    let mut buffer = WriteBuffer::new();
+    let size = value.serialize(&mut buffer);
+    buffer.data()[..size].to_vec()
+}
+
+#[test]
+fn test_vectored_layout() {
+    let value = mail();
+    let mut writer = VectoredWriter::new();
+    writer.append(&value);
+
+    // Body, attachment, extra and the large part are referenced, not copied
+    assert_eq!(writer.payloads(), 4);
+    assert_eq!(writer.size(), serialized(&value).len());
+    assert_eq!(writer.to_vec(), serialized(&value));
+
+    let slices = writer.io_slices();
+    assert!(slices.iter().any(|slice| slice.as_ptr() == value.attachment.as_ptr()));
+    assert!(slices.iter().any(|slice| slice.as_ptr() == value.body.as_ptr()));
+    assert!(slices.iter().all(|slice| slice.len() < 64 || slice.len() >= 4096));
+
+    let decoded = Mail::deserialize(&ReadBuffer::from(writer.to_vec()));
+    assert_eq!(decoded, value);
+}
+
+#[test]
+fn test_vectored_threshold() {
+    let value = mail();
+    let mut writer = VectoredWriter::with_threshold(usize::MAX);
+    writer.append(&value);
+    assert_eq!(writer.payloads(), 0);
+    assert_eq!(writer.io_slices().len(), 1);
+    assert_eq!(writer.to_vec(), serialized(&value));
+
+    let mut writer = VectoredWriter::with_threshold(0);
+    writer.append(&value);
+    assert_eq!(writer.payloads(), 6);
+    assert_eq!(writer.to_vec(), serialized(&value));
+}
+
+/// Writer accepting at most `limit` bytes per call
+struct Trickle {
+    data: Vec<u8>,
+    limit: usize,
+    calls: usize,
+}
+
+impl Write for Trickle {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_vectored(&[IoSlice::new(buf)])
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.calls += 1;
+        let mut written = 0;
+        for buf in bufs {
+            let take = buf.len().min(self.limit - written);
+            self.data.extend_from_slice(&buf[..take]);
+            written += take;
+            if written == self.limit {
+                break;
+            }
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_vectored_partial_writes() {
+    let value = mail();
+    let mut writer = VectoredWriter::new();
+    writer.append(&value);
+
+    let mut out = Trickle {
+        data: Vec::new(),
+        limit: 100_000,
+        calls: 0,
+    };
+    assert_eq!(writer.write_to(&mut out).unwrap(), writer.size());
+    assert_eq!(out.data, serialized(&value));
+    assert_eq!(out.calls, writer.size().div_ceil(100_000));
+}
+
+#[test]
+fn test_send_vectored() {
+    let value = mail();
+
+    let mut copied = StreamSender::new(Vec::new());
+    let size = copied.send(&value).unwrap();
+    let mut vectored = StreamSender::new(Vec::new());
+    assert_eq!(vectored.send_vectored(&value).unwrap(), size);
+    assert_eq!(vectored.get_ref(), copied.get_ref());
+}
+
+/// Sender without a vectored override
+#[derive(Default)]
+struct Collector {
+    buffer: WriteBuffer,
+    frames: Vec<(usize, Vec<u8>)>,
+}
+
+impl Sender for Collector {
+    fn is_logging(&self) -> bool {
+        false
+    }
+
+    fn set_logging(&mut self, _enable: bool) {}
+
+    fn send_buffer(&mut self) -> &mut WriteBuffer {
+        &mut self.buffer
+    }
+
+    fn on_send(&mut self, data: &[u8]) -> usize {
+        self.on_send_frame(0, data)
+    }
+
+    fn on_send_frame(&mut self, fbe_type: usize, data: &[u8]) -> usize {
+        self.frames.push((fbe_type, data.to_vec()));
+        data.len()
+    }
+}
+
+#[test]
+fn test_send_vectored_fallback() {
+    let value = mail();
+    let mut sender = Collector::default();
+    assert_eq!(sender.send_vectored(&value), serialized(&value).len());
+    assert_eq!(sender.frames, [(7, serialized(&value))]);
+}